@@ -12,20 +12,9 @@
 
 use std::fs::File;
 use std::io::Read;
-#[cfg(feature = "_hack")]
-use std::net::SocketAddr;
 
-#[cfg(feature = "_hack")]
-use axum::Router;
-#[cfg(feature = "_hack")]
-use axum::routing::get_service;
 use perspective::client::{TableInitOptions, UpdateData};
 use perspective::server::Server;
-#[cfg(feature = "_hack")]
-use tower_http::{
-    services::{ServeDir, ServeFile},
-    trace::TraceLayer,
-};
 use tracing_subscriber::filter::LevelFilter;
 use tracing_subscriber::fmt::layer;
 use tracing_subscriber::prelude::*;
@@ -58,21 +47,20 @@ async fn load_server_arrow(server: &Server) -> Result<(), AppError> {
 /// Host a combination HTTP file server + WebSocket server, which serves a
 /// simple Perspective application. The app's HTML, etc., assets are served
 /// from the root, while the app's embedded WebAssembly [`perspective::Client`]
-/// will connect to this server over a WebSocket via the path `/ws`.
+/// will connect to this server over a WebSocket via the path `/ws`. Hosted
+/// tables are listed at `/status`, and Ctrl+C closes every session before
+/// exiting.
 async fn start_web_server_and_block(_server: Server) -> Result<(), AppError> {
     #[cfg(feature = "_hack")]
     {
-        let app = Router::new()
-            .route("/", get_service(ServeFile::new("src/index.html")))
-            .route("/ws", perspective::axum::websocket_handler())
-            .fallback_service(ServeDir::new(ROOT_PATH))
-            .with_state(_server)
-            .layer(TraceLayer::new_for_http());
-
-        let service = app.into_make_service_with_connect_info::<SocketAddr>();
         let listener = tokio::net::TcpListener::bind(SERVER_ADDRESS).await?;
-        tracing::info!("listening on {}", listener.local_addr()?);
-        axum::serve(listener, service).await?;
+        perspective::axum::PerspectiveApp::new(_server)
+            .with_index("src/index.html")
+            .with_assets(ROOT_PATH)
+            .serve(listener, async {
+                let _ = tokio::signal::ctrl_c().await;
+            })
+            .await?;
     }
 
     Ok(())
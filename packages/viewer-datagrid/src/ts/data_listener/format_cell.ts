@@ -13,6 +13,7 @@
 import { FormatterCache, Formatter } from "./formatter_cache.js";
 import type { DatagridModel, ColumnsConfig, ColumnConfig } from "../types.js";
 import type { ColumnType } from "@perspective-dev/client";
+import { is_numeric_type } from "@perspective-dev/viewer/src/ts/column-format.js";
 
 const FORMAT_CACHE = new FormatterCache();
 const MAX_BAR_WIDTH_PCT = 1;
@@ -42,7 +43,7 @@ export function format_cell(
         this._schema[title] ||
         "string") as ColumnType;
     const plugin: ColumnConfig = plugins[title] || {};
-    const is_numeric = type === "integer" || type === "float";
+    const is_numeric = is_numeric_type(type);
 
     if (
         is_numeric &&
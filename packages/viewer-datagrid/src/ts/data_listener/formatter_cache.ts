@@ -15,6 +15,7 @@ import {
     createDateFormatter,
     createDatetimeFormatter,
     createNumberFormatter,
+    is_numeric_type,
 } from "@perspective-dev/viewer/src/ts/column-format.js";
 import type { ColumnConfig } from "../types.js";
 
@@ -84,7 +85,7 @@ export class FormatterCache {
                     formatter_key,
                     this.create_datetime_formatter(type, plugin),
                 );
-            } else if (type === "integer" || type === "float") {
+            } else if (is_numeric_type(type)) {
                 this._formatters.set(
                     formatter_key,
                     this.create_number_formatter(type, plugin),
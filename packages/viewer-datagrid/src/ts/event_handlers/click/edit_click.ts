@@ -18,6 +18,7 @@ import {
 } from "../../types.js";
 
 import type { HTMLPerspectiveViewerElement } from "@perspective-dev/viewer";
+import { is_numeric_type } from "@perspective-dev/viewer/src/ts/column-format.js";
 
 export function write_cell(
     table: RegularTable,
@@ -32,7 +33,7 @@ export function write_cell(
     const type = model._schema[model._column_paths[meta.x!]];
     let text: string | number | boolean | null = active_cell.textContent || "";
     const id = model._ids[meta.y! - meta.y0][0];
-    if (type === "float" || type === "integer") {
+    if (is_numeric_type(type)) {
         const parsed = parseFloat(text.replace(/,/g, ""));
         if (isNaN(parsed)) {
             return false;
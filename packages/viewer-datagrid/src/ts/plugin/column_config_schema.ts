@@ -12,6 +12,7 @@
 
 import type { ColumnType } from "@perspective-dev/client";
 import type { DatagridPluginElement } from "../types.js";
+import { is_numeric_type } from "@perspective-dev/viewer/src/ts/column-format.js";
 
 interface ViewerConfigLike {
     group_by?: string[];
@@ -53,7 +54,7 @@ export default function column_config_schema(
 ): ColumnConfigSchema {
     const fields: ControlSpec[] = [];
 
-    if (type === "integer" || type === "float") {
+    if (is_numeric_type(type)) {
         const pos_fg = this.model!._pos_fg_color[0];
         const neg_fg = this.model!._neg_fg_color[0];
         const pos_bg = this.model!._pos_bg_color[0];
@@ -24,6 +24,7 @@ import { cell_style_datetime } from "./table_cell/datetime.js";
 import { cell_style_boolean } from "./table_cell/boolean.js";
 import { cell_style_row_header } from "./table_cell/row_header.js";
 import { CollectedCell } from "./types.js";
+import { is_numeric_type } from "@perspective-dev/viewer/src/ts/column-format.js";
 
 /**
  * Apply styles to all body cells in a single pass.
@@ -51,7 +52,7 @@ export function applyBodyCellStyles(
         const plugin = column_name
             ? plugins[column_name.toString()]
             : undefined;
-        const is_numeric = type === "integer" || type === "float";
+        const is_numeric = is_numeric_type(type);
 
         // Calculate aggregate depth visibility
         // @ts-ignore
@@ -14,6 +14,7 @@ import { RegularTableElement } from "regular-table";
 import { get_psp_type, type DatagridModel } from "../types.js";
 import { CollectedHeaderRow } from "./types.js";
 import type { HTMLPerspectiveViewerElement } from "@perspective-dev/viewer";
+import { is_numeric_type } from "@perspective-dev/viewer/src/ts/column-format.js";
 
 /**
  * Apply selected column styling in response to column settings toggle events.
@@ -161,7 +162,7 @@ export function styleColumnHeaderRow(
         );
 
         const type = get_psp_type(model, metadata);
-        const is_numeric = type === "integer" || type === "float";
+        const is_numeric = is_numeric_type(type);
         const is_string = type === "string";
         const is_date = type === "date";
         const is_datetime = type === "datetime";
@@ -32,8 +32,8 @@ use std::fs;
 
 use perspective_client::config::*;
 use perspective_client::{
-    ColumnWindow, DeleteOptions, JoinOptions, OnUpdateData, OnUpdateOptions, SystemInfo,
-    TableInitOptions, UpdateOptions, ViewWindow,
+    ColumnWindow, DeleteOptions, JoinOptions, OnUpdateData, OnUpdateOptions, ParquetWriteOptions,
+    SystemInfo, TableInitOptions, UnionOptions, UnnestOptions, UpdateOptions, ViewWindow,
 };
 use perspective_js::TypedArrayWindow;
 use perspective_viewer::config::{
@@ -84,9 +84,12 @@ pub fn generate_type_bindings_js() -> Result<(), Box<dyn Error>> {
     JoinOptions::export_all_to(&path)?;
     OnUpdateData::export_all_to(&path)?;
     OnUpdateOptions::export_all_to(&path)?;
+    ParquetWriteOptions::export_all_to(&path)?;
     SystemInfo::<f64>::export_all_to(&path)?;
     TableInitOptions::export_all_to(&path)?;
     TypedArrayWindow::export_all_to(&path)?;
+    UnionOptions::export_all_to(&path)?;
+    UnnestOptions::export_all_to(&path)?;
     UpdateOptions::export_all_to(&path)?;
     ViewConfig::export_all_to(&path)?;
     ViewConfigUpdate::export_all_to(&path)?;
@@ -16,20 +16,31 @@ use std::ops::Deref;
 use std::sync::Arc;
 
 use async_lock::{Mutex, RwLock};
-use futures::Future;
 use futures::future::{BoxFuture, LocalBoxFuture, join_all};
+use futures::stream::BoxStream;
+use futures::{Future, SinkExt, StreamExt};
 use prost::Message;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
+use crate::infer::{
+    InferSchemaOptions, InferredSchema, apply_column_types, apply_date_formats, diagnose,
+};
+use crate::parquet::read_parquet;
+use crate::proto::make_table_req::MakeTableOptions;
 use crate::proto::request::ClientReq;
 use crate::proto::response::ClientResp;
 use crate::proto::{
     ColumnType, GetFeaturesReq, GetFeaturesResp, GetHostedTablesReq, GetHostedTablesResp,
-    HostedTable, JoinType, MakeJoinTableReq, MakeTableReq, RemoveHostedTablesUpdateReq, Request,
-    Response, ServerError, ServerSystemInfoReq,
+    HostedTable, JoinType, MakeJoinTableReq, MakeTableReq, MakeTableResp, MakeUnionTableReq,
+    MakeUnnestTableReq, RemoveHostedTablesUpdateReq, Request, Response, ServerError,
+    ServerSystemInfoReq, ViewStreamNextReq,
+};
+use crate::protocol::{Capabilities, PROTOCOL_VERSION};
+use crate::table::{
+    JoinKeys, JoinOptions, RetentionOptions, Table, TableInitOptions, TableOptions, UnionOptions,
+    UnnestOptions,
 };
-use crate::table::{JoinOptions, Table, TableInitOptions, TableOptions};
 use crate::table_data::{TableData, UpdateData};
 use crate::table_ref::TableRef;
 use crate::utils::*;
@@ -66,6 +77,99 @@ pub struct SystemInfo<T = u64> {
     /// Bytes allocated for use on the [`Client`].  This is only
     /// available if `trace-allocator` is enabled.
     pub client_used: Option<T>,
+
+    /// Resource usage of this [`Client`]'s session on the [`Server`]. This is
+    /// only available if the [`Server`] has session limits configured.
+    pub session_usage: Option<SessionUsage<T>>,
+
+    /// Memory use and row count of each [`Table`] hosted on the [`Server`].
+    pub tables: Vec<TableMemoryUsage<T>>,
+
+    /// Memory use and row count of each [`crate::View`] open on the
+    /// [`Server`].
+    pub views: Vec<ViewMemoryUsage<T>>,
+
+    /// Memory use of each session's [`crate::View`]s on the [`Server`].
+    pub sessions: Vec<SessionMemoryUsage<T>>,
+}
+
+/// Approximate memory use of a [`Table`] hosted on a [`Server`].
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct TableMemoryUsage<T = u64> {
+    pub table_id: String,
+
+    /// Rows in the [`Table`], per [`Table::size`].
+    pub num_rows: T,
+
+    /// [`crate::View`]s open on the [`Table`].
+    pub num_views: u32,
+
+    /// Bytes allocated for the [`Table`]'s rows, including removed rows
+    /// which have not been reclaimed.
+    pub bytes: T,
+}
+
+/// Approximate memory use of a [`crate::View`] open on a [`Server`].
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct ViewMemoryUsage<T = u64> {
+    pub view_id: String,
+    pub table_id: String,
+
+    /// The session which created the [`crate::View`], per
+    /// [`SessionMemoryUsage::session_id`].
+    pub session_id: u32,
+
+    /// Rows in the [`crate::View`], per [`crate::View::num_rows`].
+    pub num_rows: u32,
+
+    /// Columns in the [`crate::View`], per [`crate::View::num_columns`].
+    pub num_columns: u32,
+
+    /// Bytes allocated for the [`crate::View`]'s aggregate trees, sort order
+    /// and expression columns. This excludes its [`Table`], which is shared
+    /// by all of its [`crate::View`]s.
+    pub bytes: T,
+}
+
+/// Approximate memory use of a session's [`crate::View`]s on a [`Server`].
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct SessionMemoryUsage<T = u64> {
+    /// An id for this session, unique on the [`Server`].
+    pub session_id: u32,
+
+    /// Whether this is this [`Client`]'s own session.
+    pub is_current: bool,
+
+    /// [`crate::View`]s this session has open.
+    pub num_views: u32,
+
+    /// Total [`ViewMemoryUsage::bytes`] of this session's [`crate::View`]s.
+    pub view_bytes: T,
+}
+
+/// Resource usage of a single session on a [`Server`], counted against its
+/// session limits.
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct SessionUsage<T = u64> {
+    /// [`crate::View`]s this session has open.
+    pub num_views: u32,
+
+    /// [`Table`]s this session has created and not deleted.
+    pub num_tables: u32,
+
+    /// `on_update` callbacks this session has registered.
+    pub num_on_update: u32,
+
+    /// Total rows this session has sent via [`Client::table`] and
+    /// [`Table::update`].
+    pub rows_ingested: T,
+
+    /// [`crate::View`]s the [`Server`] has deleted because this session left
+    /// them idle.
+    pub evicted_views: T,
+
+    /// Requests the [`Server`] rejected for exceeding a session limit.
+    pub rejected_requests: T,
 }
 
 impl<U: Copy + 'static> SystemInfo<U> {
@@ -83,6 +187,46 @@ impl<U: Copy + 'static> SystemInfo<U> {
             timestamp: self.timestamp.map(|x| x.as_()),
             client_heap: self.client_heap.map(|x| x.as_()),
             client_used: self.client_used.map(|x| x.as_()),
+            session_usage: self.session_usage.as_ref().map(|x| SessionUsage {
+                num_views: x.num_views,
+                num_tables: x.num_tables,
+                num_on_update: x.num_on_update,
+                rows_ingested: x.rows_ingested.as_(),
+                evicted_views: x.evicted_views.as_(),
+                rejected_requests: x.rejected_requests.as_(),
+            }),
+            tables: self
+                .tables
+                .iter()
+                .map(|x| TableMemoryUsage {
+                    table_id: x.table_id.clone(),
+                    num_rows: x.num_rows.as_(),
+                    num_views: x.num_views,
+                    bytes: x.bytes.as_(),
+                })
+                .collect(),
+            views: self
+                .views
+                .iter()
+                .map(|x| ViewMemoryUsage {
+                    view_id: x.view_id.clone(),
+                    table_id: x.table_id.clone(),
+                    session_id: x.session_id,
+                    num_rows: x.num_rows,
+                    num_columns: x.num_columns,
+                    bytes: x.bytes.as_(),
+                })
+                .collect(),
+            sessions: self
+                .sessions
+                .iter()
+                .map(|x| SessionMemoryUsage {
+                    session_id: x.session_id,
+                    is_current: x.is_current,
+                    num_views: x.num_views,
+                    view_bytes: x.view_bytes.as_(),
+                })
+                .collect(),
         }
     }
 }
@@ -127,6 +271,39 @@ type BoxFn<I, O> = Box<dyn Fn(I) -> O + Send + Sync + 'static>;
 type Box2Fn<I, J, O> = Box<dyn Fn(I, J) -> O + Send + Sync + 'static>;
 
 type Subscriptions<C> = Arc<RwLock<HashMap<u32, C>>>;
+
+/// Removes a [`Client::stream`] subscription if its stream is dropped
+/// before the last frame arrives, and cancels the server's export.
+struct StreamGuard {
+    client: Client,
+    msg_id: u32,
+    entity_id: String,
+    done: bool,
+}
+
+impl Drop for StreamGuard {
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+
+        // Best effort, as this can't wait for the lock; a subscription which
+        // is missed only forwards frames to a closed channel.
+        if let Some(mut subscriptions) = self.client.subscriptions.try_write() {
+            subscriptions.remove(&self.msg_id);
+        }
+
+        // This can't `await` the send either, so the cancellation is sent
+        // before the `Client`'s next request.
+        self.client.cancelled_streams.lock().unwrap().push(Request {
+            msg_id: self.msg_id,
+            entity_id: std::mem::take(&mut self.entity_id),
+            client_req: Some(ClientReq::ViewStreamNextReq(ViewStreamNextReq {
+                cancel: true,
+            })),
+        });
+    }
+}
 type OnErrorCallback =
     Box2Fn<ClientError, Option<ReconnectCallback>, BoxFuture<'static, Result<(), ClientError>>>;
 
@@ -215,6 +392,7 @@ pub struct Client {
     subscriptions_errors: Subscriptions<OnErrorCallback>,
     subscriptions_once: Subscriptions<OnceCallback>,
     subscriptions: Subscriptions<BoxFn<Response, BoxFuture<'static, Result<(), ClientError>>>>,
+    cancelled_streams: Arc<std::sync::Mutex<Vec<Request>>>,
 }
 
 impl PartialEq for Client {
@@ -254,6 +432,7 @@ impl Client {
             subscriptions: Subscriptions::default(),
             subscriptions_errors: Arc::default(),
             subscriptions_once: Arc::default(),
+            cancelled_streams: Arc::default(),
         })
     }
 
@@ -377,6 +556,18 @@ impl Client {
         Ok(id)
     }
 
+    /// Send `msg`, after the cancellations of any [`Client::stream`]s which
+    /// were dropped before their last frame.
+    async fn send_request(&self, msg: &Request) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let cancelled = std::mem::take(&mut *self.cancelled_streams.lock().unwrap());
+        for req in cancelled {
+            tracing::debug!("SEND {}", req);
+            (self.send)(&req).await?;
+        }
+
+        (self.send)(msg).await
+    }
+
     /// Generate a message ID unique to this client.
     pub(crate) fn gen_id(&self) -> u32 {
         self.id_gen.next()
@@ -406,7 +597,7 @@ impl Client {
             .insert(msg.msg_id, on_update);
 
         tracing::debug!("SEND {}", msg);
-        if let Err(e) = (self.send)(msg).await {
+        if let Err(e) = self.send_request(msg).await {
             self.subscriptions_once.write().await.remove(&msg.msg_id);
             Err(ClientError::Unknown(e.to_string()))
         } else {
@@ -425,7 +616,7 @@ impl Client {
             .insert(msg.msg_id, Box::new(move |x| Box::pin(on_update(x))));
 
         tracing::debug!("SEND {}", msg);
-        if let Err(e) = (self.send)(msg).await {
+        if let Err(e) = self.send_request(msg).await {
             self.subscriptions.write().await.remove(&msg.msg_id);
             Err(ClientError::Unknown(e.to_string()))
         } else {
@@ -433,6 +624,87 @@ impl Client {
         }
     }
 
+    /// Send a `ClientReq` which is answered by a sequence of `ClientResp`
+    /// frames sharing its `msg_id`, ending with the frame for which `is_done`
+    /// is `true` (or a `ServerError`). Each frame after the first is
+    /// requested with a `ViewStreamNextReq` when the returned stream is
+    /// polled for it, so at most one unread frame is ever buffered. The
+    /// subscription is removed when the sequence ends, or when the returned
+    /// stream is dropped, in which case the export is cancelled with a
+    /// `ViewStreamNextReq` before this [`Client`]'s next request.
+    pub(crate) async fn stream<T>(
+        &self,
+        req: &Request,
+        is_done: T,
+    ) -> ClientResult<BoxStream<'static, ClientResult<ClientResp>>>
+    where
+        T: Fn(&ClientResp) -> bool + Send + 'static,
+    {
+        let (sender, receiver) = futures::channel::mpsc::channel::<ClientResp>(1);
+        self.subscribe(req, move |res: Response| {
+            let mut sender = sender.clone();
+            async move {
+                if let Some(resp) = res.client_resp {
+                    // The receiver is gone if the `Stream` was dropped early.
+                    let _ = sender.send(resp).await;
+                }
+
+                Ok(())
+            }
+        })
+        .await?;
+
+        let next_req = Request {
+            msg_id: req.msg_id,
+            entity_id: req.entity_id.clone(),
+            client_req: Some(ClientReq::ViewStreamNextReq(ViewStreamNextReq {
+                cancel: false,
+            })),
+        };
+
+        let guard = StreamGuard {
+            client: self.clone(),
+            msg_id: req.msg_id,
+            entity_id: req.entity_id.clone(),
+            done: false,
+        };
+
+        // The last field is whether the next frame must be requested first.
+        let state = Some((receiver, guard, next_req, is_done, false));
+        Ok(futures::stream::unfold(state, |state| async move {
+            let (mut receiver, mut guard, next_req, is_done, pull) = state?;
+            if pull {
+                tracing::debug!("SEND {}", next_req);
+                if let Err(e) = guard.client.send_request(&next_req).await {
+                    return Some((Err(ClientError::Unknown(e.to_string())), None));
+                }
+            }
+
+            let (item, done) = match receiver.next().await {
+                None => (Err(ClientError::ResponseAborted), true),
+                Some(resp @ ClientResp::ServerError(_)) => (Err(resp.into()), true),
+                Some(resp) => {
+                    let done = is_done(&resp);
+                    (Ok(resp), done)
+                },
+            };
+
+            if done {
+                guard.done = true;
+                guard
+                    .client
+                    .subscriptions
+                    .write()
+                    .await
+                    .remove(&guard.msg_id);
+                Some((item, None))
+            } else {
+                Some((item, Some((receiver, guard, next_req, is_done, true))))
+            }
+        })
+        .boxed())
+    }
+
     /// Send a `ClientReq` and await both the successful completion of the
     /// `send`, _and_ the `ClientResp` which is returned.
     pub(crate) async fn oneshot(&self, req: &Request) -> ClientResult<ClientResp> {
@@ -455,7 +727,10 @@ impl Client {
             let msg = Request {
                 msg_id: self.gen_id(),
                 entity_id: "".to_owned(),
-                client_req: Some(ClientReq::GetFeaturesReq(GetFeaturesReq {})),
+                client_req: Some(ClientReq::GetFeaturesReq(GetFeaturesReq {
+                    protocol_version: PROTOCOL_VERSION,
+                    capabilities: Capabilities::ALL.bits(),
+                })),
             };
 
             let features = Features(Arc::new(match self.oneshot(&msg).await? {
@@ -463,6 +738,7 @@ impl Client {
                 resp => Err(resp),
             }?));
 
+            features.check_protocol()?;
             *guard = Some(features.clone());
             features
         };
@@ -470,6 +746,20 @@ impl Client {
         Ok(features)
     }
 
+    /// Exchange protocol versions and capabilities with the
+    /// `perspective_server::Server`, returning the negotiated
+    /// [`Capabilities`].
+    ///
+    /// The handshake is otherwise performed lazily by the first request which
+    /// needs it, so calling this immediately after connecting is only
+    /// necessary to surface a [`ClientError::IncompatibleServer`] early.
+    /// Servers which predate the handshake are accepted with no
+    /// [`Capabilities`], in which case optional features degrade to their
+    /// legacy encoding.
+    pub async fn handshake(&self) -> ClientResult<Capabilities> {
+        Ok(self.get_features().await?.negotiated_capabilities())
+    }
+
     /// Creates a new [`Table`] from either a _schema_ or _data_.
     ///
     /// The [`Client::table`] factory function can be initialized with either a
@@ -509,9 +799,14 @@ impl Client {
     ///     - `name` - The name of the table. This will be generated if it is
     ///       not provided.
     ///     - `format` - The explicit format of the input data, can be one of
-    ///       `"json"`, `"columns"`, `"csv"` or `"arrow"`. This overrides
-    ///       language-specific type dispatch behavior, which allows stringified
-    ///       and byte array alternative inputs.
+    ///       `"json"`, `"columns"`, `"csv"`, `"arrow"` or `"parquet"`. This
+    ///       overrides language-specific type dispatch behavior, which allows
+    ///       stringified and byte array alternative inputs.
+    ///     - `column_types` - Explicit types for some columns, which override
+    ///       the inferred types of those columns (see
+    ///       [`Client::infer_schema`]).
+    ///     - `date_formats` - `strftime`-style formats (e.g. `"%d/%m/%Y"`) for
+    ///       columns whose dates are not in a format the engine recognizes.
     ///
     /// # Examples
     ///
@@ -552,12 +847,167 @@ impl Client {
             let on_update_token = view.on_update(callback, options).await?;
             table.view_update_token = Some(on_update_token);
             Ok(table)
+        } else if let Some(column_types) = options.column_types.clone().filter(|x| !x.is_empty())
+            && let TableData::Update(data) = input
+        {
+            // Create the `Table` from the inferred schema with `column_types`
+            // applied, so the data is read as the pinned types.
+            let date_formats = options.date_formats.clone().unwrap_or_default();
+            let mut schema = self.engine_schema(data.clone(), &date_formats).await?;
+            apply_column_types(&mut schema, &column_types)?;
+            let table = self
+                .crate_table_inner(TableData::Schema(schema), options.into(), entity_id)
+                .await?;
+
+            table.update(data, crate::UpdateOptions::default()).await?;
+            Ok(table)
+        } else if let TableData::Update(UpdateData::Parquet { data, columns }) = input {
+            // The first batch creates the `Table` (so empty files still yield
+            // a schema), the remainder are streamed in as updates.
+            let mut batches = read_parquet(data, columns.as_deref())?;
+            let first = batches.next().ok_or(ClientError::Option)??;
+            let table = self
+                .crate_table_inner(UpdateData::Arrow(first).into(), options.into(), entity_id)
+                .await?;
+
+            for batch in batches {
+                let options = crate::UpdateOptions::default();
+                table.update(UpdateData::Arrow(batch?), options).await?;
+            }
+
+            Ok(table)
+        } else if let TableData::Update(data) = input {
+            let date_formats = options.date_formats.clone().unwrap_or_default();
+            let data = apply_date_formats(data, &date_formats)?;
+            self.crate_table_inner(data.into(), options.into(), entity_id)
+                .await
         } else {
             self.crate_table_inner(input, options.into(), entity_id)
                 .await
         }
     }
 
+    /// Infer the [`Schema`](crate::Schema) the engine would create for
+    /// `input`, and check how well a sample of each column's values fits its
+    /// type. This catches columns which are mistyped by a handful of values
+    /// (e.g. a date column with one malformed date, which becomes a
+    /// `"string"`) or whose values would be altered (e.g. numeric IDs with
+    /// leading zeros) before they are loaded, so they can be pinned with
+    /// [`TableInitOptions::column_types`].
+    ///
+    /// The schema is inferred by the engine itself, which parses all of
+    /// `input` as [`Client::table`] would (though it does not host a
+    /// [`Table`] for it), so this costs about as much as loading `input`.
+    /// Servers without [`Capabilities::SCHEMA_ONLY`] instead host a
+    /// temporary [`Table`], which is briefly visible to
+    /// [`Client::on_hosted_tables_update`] subscribers.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use perspective_client::*;
+    /// # async fn run(client: Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let data = UpdateData::Csv("id,date\n007,2024-01-01\n008,N/A".into());
+    /// let inferred = client
+    ///     .infer_schema(data, InferSchemaOptions::default())
+    ///     .await?;
+    /// for (name, column) in &inferred.columns {
+    ///     if column.candidate != column.r#type {
+    ///         println!(
+    ///             "{name}: {:?} {:?}",
+    ///             column.candidate, column.offending_values
+    ///         );
+    ///     }
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub async fn infer_schema(
+        &self,
+        input: UpdateData,
+        options: InferSchemaOptions,
+    ) -> ClientResult<InferredSchema> {
+        let date_formats = options.date_formats.clone().unwrap_or_default();
+        let mut schema = self.engine_schema(input.clone(), &date_formats).await?;
+        if let Some(column_types) = &options.column_types {
+            apply_column_types(&mut schema, column_types)?;
+        }
+
+        let columns = diagnose(&input, &schema, &options)?;
+        Ok(InferredSchema { schema, columns })
+    }
+
+    /// The schema, in column order, the engine creates for `input`.
+    async fn engine_schema(
+        &self,
+        input: UpdateData,
+        date_formats: &HashMap<String, String>,
+    ) -> ClientResult<Vec<(String, ColumnType)>> {
+        let input = match input {
+            // Parquet is typed, so the first batch has the full schema.
+            UpdateData::Parquet { data, columns } => {
+                let mut batches = read_parquet(data, columns.as_deref())?;
+                UpdateData::Arrow(batches.next().ok_or(ClientError::Option)??)
+            },
+            input => apply_date_formats(input, date_formats)?,
+        };
+
+        if !self
+            .get_features()
+            .await?
+            .negotiated_capabilities()
+            .contains(Capabilities::SCHEMA_ONLY)
+        {
+            return self.temp_table_schema(input).await;
+        }
+
+        let options = TableOptions::from(TableInitOptions::default());
+        let msg = Request {
+            msg_id: self.gen_id(),
+            entity_id: randid(),
+            client_req: Some(ClientReq::MakeTableReq(MakeTableReq {
+                data: Some(TableData::from(input).try_into()?),
+                options: Some(MakeTableOptions {
+                    schema_only: Some(true),
+                    ..MakeTableOptions::try_from(options)?
+                }),
+            })),
+        };
+
+        match self.oneshot(&msg).await? {
+            ClientResp::MakeTableResp(MakeTableResp {
+                schema: Some(schema),
+            }) => Ok(schema
+                .schema
+                .into_iter()
+                .filter_map(|x| Some((x.name, ColumnType::try_from(x.r#type).ok()?)))
+                .collect()),
+            resp => Err(resp.into()),
+        }
+    }
+
+    /// The schema, in column order, of a temporary [`Table`] created from
+    /// `input`, for servers without [`Capabilities::SCHEMA_ONLY`].
+    async fn temp_table_schema(
+        &self,
+        input: UpdateData,
+    ) -> ClientResult<Vec<(String, ColumnType)>> {
+        let options = TableInitOptions::default().into();
+        let table = self
+            .crate_table_inner(input.into(), options, randid())
+            .await?;
+        let columns = table.columns().await;
+        let schema = table.schema().await;
+        table.delete(crate::DeleteOptions::default()).await?;
+        let schema = schema?;
+        Ok(columns?
+            .into_iter()
+            .filter_map(|name| {
+                let column_type = *schema.get(&name)?;
+                Some((name, column_type))
+            })
+            .collect())
+    }
+
     async fn crate_table_inner(
         &self,
         input: TableData,
@@ -568,7 +1018,7 @@ impl Client {
             msg_id: self.gen_id(),
             entity_id: entity_id.clone(),
             client_req: Some(ClientReq::MakeTableReq(MakeTableReq {
-                data: Some(input.into()),
+                data: Some(input.try_into()?),
                 options: Some(options.clone().try_into()?),
             })),
         };
@@ -588,37 +1038,207 @@ impl Client {
     ///
     /// * `left` - The left source table (as a [`Table`] or name string).
     /// * `right` - The right source table (as a [`Table`] or name string).
-    /// * `on` - The column name to join on. Must exist in both tables with the
-    ///   same type.
-    /// * `options` - Join configuration (join type, table name).
+    /// * `on` - The column name (or names, for a composite key) to join on.
+    ///   Must exist in both tables with the same type, unless
+    ///   [`JoinOptions::right_on`] (or [`JoinOptions::right_on_columns`]) names
+    ///   the right table's columns.
+    /// * `options` - Join configuration (join type, table name, column
+    ///   suffixes, as-of column).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use perspective_client::*;
+    /// # async fn run(client: Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let options = JoinOptions {
+    ///     join_type: Some(JoinType::Left),
+    ///     as_of: Some("timestamp".into()),
+    ///     ..JoinOptions::default()
+    /// };
+    ///
+    /// let table = client
+    ///     .join("trades".into(), "quotes".into(), "ticker", options)
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
     pub async fn join(
         &self,
         left: TableRef,
         right: TableRef,
-        on: &str,
+        on: impl Into<JoinKeys>,
         options: JoinOptions,
     ) -> ClientResult<Table> {
-        let entity_id = options.name.unwrap_or_else(randid);
+        let on = on.into();
+        let right_on = options.right_keys().unwrap_or_else(|| on.clone());
+        if options.is_extended(&on)
+            && !self
+                .get_features()
+                .await?
+                .negotiated_capabilities()
+                .contains(Capabilities::JOIN_OPTIONS)
+        {
+            return Err(ClientError::NotImplemented("join options"));
+        }
+
+        let entity_id = options.name.clone().unwrap_or_else(randid);
         let join_type: JoinType = options.join_type.unwrap_or_default();
-        let right_on_column = options.right_on.unwrap_or_default();
+        let (on_column, on_columns, right_on_column, right_on_columns) =
+            match (on.columns(), right_on.columns()) {
+                ([left], [right]) => (left.clone(), vec![], right.clone(), vec![]),
+                (left, right) => (String::new(), left.to_vec(), String::new(), right.to_vec()),
+            };
+
         let msg = Request {
             msg_id: self.gen_id(),
             entity_id: entity_id.clone(),
             client_req: Some(ClientReq::MakeJoinTableReq(MakeJoinTableReq {
                 left_table_id: left.table_name().to_owned(),
                 right_table_id: right.table_name().to_owned(),
-                on_column: on.to_owned(),
+                on_column,
                 join_type: join_type.into(),
                 right_on_column,
+                on_columns,
+                right_on_columns,
+                left_suffix: options.left_suffix.unwrap_or_default(),
+                right_suffix: options.right_suffix.unwrap_or_default(),
+                as_of_column: options.as_of.unwrap_or_default(),
+                right_as_of_column: options.right_as_of.unwrap_or_default(),
             })),
         };
 
         let client = self.clone();
+        let index = match on.columns() {
+            [column] => Some(column.clone()),
+            _ => None,
+        };
+
         match self.oneshot(&msg).await? {
             ClientResp::MakeJoinTableResp(_) => Ok(Table::new(entity_id, client, TableOptions {
-                index: Some(on.to_owned()),
+                index,
+                limit: None,
+                page_to_disk: None,
+                date_formats: HashMap::default(),
+                retention: None,
+            })),
+            resp => Err(resp.into()),
+        }
+    }
+
+    /// Create a new read-only [`Table`] by concatenating the rows of several
+    /// source tables (a UNION ALL). The resulting table is reactive: when any
+    /// source table is updated, the union is automatically recomputed.
+    ///
+    /// # Arguments
+    ///
+    /// * `tables` - The source tables (as [`Table`]s or name strings), which
+    ///   must have identical schemas. Rows appear in the order of `tables`.
+    /// * `options` - Union configuration (table name, source column).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use perspective_client::*;
+    /// # async fn run(client: Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let options = UnionOptions {
+    ///     source_column: Some("venue".into()),
+    ///     ..UnionOptions::default()
+    /// };
+    ///
+    /// let table = client
+    ///     .union(vec!["xnys".into(), "xnas".into()], options)
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn union(&self, tables: Vec<TableRef>, options: UnionOptions) -> ClientResult<Table> {
+        if !self
+            .get_features()
+            .await?
+            .negotiated_capabilities()
+            .contains(Capabilities::UNION)
+        {
+            return Err(ClientError::NotImplemented("union"));
+        }
+
+        let entity_id = options.name.unwrap_or_else(randid);
+        let msg = Request {
+            msg_id: self.gen_id(),
+            entity_id: entity_id.clone(),
+            client_req: Some(ClientReq::MakeUnionTableReq(MakeUnionTableReq {
+                table_ids: tables.iter().map(|x| x.table_name().to_owned()).collect(),
+                source_column: options.source_column.unwrap_or_default(),
+            })),
+        };
+
+        let client = self.clone();
+        match self.oneshot(&msg).await? {
+            ClientResp::MakeUnionTableResp(_) => Ok(Table::new(entity_id, client, TableOptions {
+                index: None,
+                limit: None,
+                page_to_disk: None,
+                date_formats: HashMap::default(),
+                retention: None,
+            })),
+            resp => Err(resp.into()),
+        }
+    }
+
+    /// Create a new read-only [`Table`] with one row for each element of a
+    /// `list_string` or `list_float` column of a source table, repeating the
+    /// source row's other columns. The unnested column has the list's
+    /// element type, so the result can be grouped or filtered by element.
+    /// A null or empty list yields a single row with a null element. The
+    /// resulting table is reactive: when the source table is updated, it is
+    /// automatically recomputed.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The source table (as a [`Table`] or name string).
+    /// * `column` - The list column to unnest.
+    /// * `options` - Unnest configuration (table name).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use perspective_client::*;
+    /// # async fn run(client: Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let table = client
+    ///     .unnest("orders".into(), "tags", UnnestOptions::default())
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn unnest(
+        &self,
+        table: TableRef,
+        column: &str,
+        options: UnnestOptions,
+    ) -> ClientResult<Table> {
+        if !self
+            .get_features()
+            .await?
+            .negotiated_capabilities()
+            .contains(Capabilities::UNNEST)
+        {
+            return Err(ClientError::NotImplemented("unnest"));
+        }
+
+        let entity_id = options.name.unwrap_or_else(randid);
+        let msg = Request {
+            msg_id: self.gen_id(),
+            entity_id: entity_id.clone(),
+            client_req: Some(ClientReq::MakeUnnestTableReq(MakeUnnestTableReq {
+                table_id: table.table_name().to_owned(),
+                column: column.to_owned(),
+            })),
+        };
+
+        let client = self.clone();
+        match self.oneshot(&msg).await? {
+            ClientResp::MakeUnnestTableResp(_) => Ok(Table::new(entity_id, client, TableOptions {
+                index: None,
                 limit: None,
                 page_to_disk: None,
+                date_formats: HashMap::default(),
+                retention: None,
             })),
             resp => Err(resp.into()),
         }
@@ -666,6 +1286,8 @@ impl Client {
                 // `page_to_disk` is a server-side property not surfaced in table
                 // info; it does not affect client-side behavior.
                 page_to_disk: None,
+                date_formats: HashMap::default(),
+                retention: info.retention.map(RetentionOptions::from),
             };
 
             let client = self.clone();
@@ -799,6 +1421,46 @@ impl Client {
                     timestamp,
                     client_heap,
                     client_used,
+                    session_usage: resp.session_usage.map(|x| SessionUsage {
+                        num_views: x.num_views,
+                        num_tables: x.num_tables,
+                        num_on_update: x.num_on_update,
+                        rows_ingested: x.rows_ingested,
+                        evicted_views: x.evicted_views,
+                        rejected_requests: x.rejected_requests,
+                    }),
+                    tables: resp
+                        .tables
+                        .into_iter()
+                        .map(|x| TableMemoryUsage {
+                            table_id: x.table_id,
+                            num_rows: x.num_rows,
+                            num_views: x.num_views,
+                            bytes: x.bytes,
+                        })
+                        .collect(),
+                    views: resp
+                        .views
+                        .into_iter()
+                        .map(|x| ViewMemoryUsage {
+                            view_id: x.view_id,
+                            table_id: x.table_id,
+                            session_id: x.session_id,
+                            num_rows: x.num_rows,
+                            num_columns: x.num_columns,
+                            bytes: x.bytes,
+                        })
+                        .collect(),
+                    sessions: resp
+                        .sessions
+                        .into_iter()
+                        .map(|x| SessionMemoryUsage {
+                            session_id: x.session_id,
+                            is_current: x.is_current,
+                            num_views: x.num_views,
+                            view_bytes: x.view_bytes,
+                        })
+                        .collect(),
                 };
 
                 Ok(info)
@@ -25,6 +25,10 @@ impl Display for ColumnType {
             Self::Boolean => "boolean",
             Self::Date => "date",
             Self::Datetime => "datetime",
+            Self::Int64 => "int64",
+            Self::Duration => "duration",
+            Self::ListString => "list_string",
+            Self::ListFloat => "list_float",
         })
     }
 }
@@ -53,6 +57,14 @@ impl TryFrom<&str> for ColumnType {
             Ok(Self::Date)
         } else if val == "datetime" {
             Ok(Self::Datetime)
+        } else if val == "int64" {
+            Ok(Self::Int64)
+        } else if val == "duration" {
+            Ok(Self::Duration)
+        } else if val == "list_string" || val == "list<string>" {
+            Ok(Self::ListString)
+        } else if val == "list_float" || val == "list<float>" {
+            Ok(Self::ListFloat)
         } else {
             Err(ClientError::Internal(format!("Unknown type {val}")))
         }
@@ -68,7 +80,28 @@ impl ColumnType {
             ColumnType::Integer => "Integer",
             ColumnType::Float => "Float",
             ColumnType::Boolean => "Boolean",
+            ColumnType::Int64 => "Int64",
+            ColumnType::Duration => "Duration",
+            ColumnType::ListString => "List String",
+            ColumnType::ListFloat => "List Float",
         }
         .into()
     }
+
+    /// Whether this type's values are lists, stored as JSON array text.
+    pub fn is_list(&self) -> bool {
+        matches!(self, ColumnType::ListString | ColumnType::ListFloat)
+    }
+
+    /// Whether this type's values are numbers, which can be formatted,
+    /// summed, etc.
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            ColumnType::Integer
+                | ColumnType::Float
+                | ColumnType::Int64
+                | ColumnType::Duration
+        )
+    }
 }
@@ -16,8 +16,10 @@ use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
+use super::temporal::{Date, Datetime};
 use crate::proto;
 use crate::proto::scalar;
+use crate::proto::view_config::filter_tree;
 
 /// This type represents the ViewConfig serializable type, which must be JSON
 /// safe.
@@ -27,8 +29,8 @@ pub enum Scalar {
     Float(f64),
     String(String),
     Bool(bool),
-    // DateTime(i64),
-    // Date(String),
+    Date(#[ts(type = "{ date: string }")] Date),
+    Datetime(#[ts(type = "{ datetime: string }")] Datetime),
     // Int(i32),
     #[default]
     Null,
@@ -40,12 +42,27 @@ impl From<&str> for Scalar {
     }
 }
 
+impl Scalar {
+    /// The encoding of this [`Scalar`] for servers which lack
+    /// [`crate::protocol::Capabilities::TEMPORAL_SCALARS`]: dates as
+    /// `"YYYY-MM-DD"` strings and datetimes as POSIX milliseconds.
+    pub(crate) fn to_legacy_temporal(&self) -> Self {
+        match self {
+            Self::Date(x) => Self::String(x.to_string()),
+            Self::Datetime(x) => Self::Float(x.timestamp_millis() as f64),
+            x => x.clone(),
+        }
+    }
+}
+
 impl Display for Scalar {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match self {
             Self::Float(x) => write!(fmt, "{x}"),
             Self::String(x) => write!(fmt, "{x}"),
             Self::Bool(x) => write!(fmt, "{x}"),
+            Self::Date(x) => write!(fmt, "{x}"),
+            Self::Datetime(x) => write!(fmt, "{x}"),
             Self::Null => write!(fmt, ""),
         }
     }
@@ -95,6 +112,13 @@ impl FilterTerm {
     pub fn is_null(&self) -> bool {
         matches!(self, FilterTerm::Scalar(Scalar::Null))
     }
+
+    pub(crate) fn to_legacy_temporal(&self) -> Self {
+        match self {
+            Self::Scalar(x) => Self::Scalar(x.to_legacy_temporal()),
+            Self::Array(xs) => Self::Array(xs.iter().map(Scalar::to_legacy_temporal).collect()),
+        }
+    }
 }
 
 #[derive(Clone, Deserialize, Debug, PartialEq, Serialize, TS)]
@@ -143,6 +167,117 @@ pub enum FilterReducer {
     Or,
 }
 
+/// A node in a nested filter expression, either a single [`Filter`] leaf or
+/// a [`FilterGroup`] of further nodes. Leaves serialize as the same 3-tuple
+/// used by the flat `filter` list, so a flat filter list is also a valid list
+/// of `FilterTree` nodes.
+#[derive(Clone, Deserialize, Debug, PartialEq, Serialize, TS)]
+#[serde(untagged)]
+pub enum FilterTree {
+    Filter(Filter),
+    Group(FilterGroup),
+}
+
+impl From<Filter> for FilterTree {
+    fn from(value: Filter) -> Self {
+        Self::Filter(value)
+    }
+}
+
+impl From<FilterGroup> for FilterTree {
+    fn from(value: FilterGroup) -> Self {
+        Self::Group(value)
+    }
+}
+
+impl FilterTree {
+    /// Visit every [`Filter`] leaf in this tree, depth first.
+    pub fn leaves(&self) -> Box<dyn Iterator<Item = &Filter> + '_> {
+        match self {
+            Self::Filter(x) => Box::new(std::iter::once(x)),
+            Self::Group(x) => x.leaves(),
+        }
+    }
+
+    /// Visit every [`Filter`] leaf in this tree mutably, depth first.
+    pub fn leaves_mut(&mut self) -> Box<dyn Iterator<Item = &mut Filter> + '_> {
+        match self {
+            Self::Filter(x) => Box::new(std::iter::once(x)),
+            Self::Group(x) => x.leaves_mut(),
+        }
+    }
+}
+
+/// A group of [`FilterTree`] nodes combined with a [`FilterReducer`], and
+/// optionally negated, e.g. `NOT (a OR (b AND c))`.
+#[derive(Clone, Deserialize, Debug, Default, PartialEq, Serialize, TS)]
+#[serde(deny_unknown_fields)]
+pub struct FilterGroup {
+    #[serde(default)]
+    pub op: FilterReducer,
+
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    #[serde(default)]
+    #[ts(optional)]
+    pub negated: bool,
+
+    #[serde(default)]
+    pub filters: Vec<FilterTree>,
+}
+
+impl FilterGroup {
+    pub fn new(op: FilterReducer, filters: Vec<FilterTree>) -> Self {
+        FilterGroup {
+            op,
+            negated: false,
+            filters,
+        }
+    }
+
+    /// Build the group equivalent to a legacy flat `filter` list and its
+    /// `filter_op`.
+    pub fn from_flat(filter: Vec<Filter>, op: FilterReducer) -> Self {
+        Self::new(op, filter.into_iter().map(FilterTree::Filter).collect())
+    }
+
+    /// Negate this group.
+    pub fn not(self) -> Self {
+        FilterGroup {
+            negated: !self.negated,
+            ..self
+        }
+    }
+
+    /// Visit every [`Filter`] leaf in this group, depth first.
+    pub fn leaves(&self) -> Box<dyn Iterator<Item = &Filter> + '_> {
+        Box::new(self.filters.iter().flat_map(|x| x.leaves()))
+    }
+
+    /// Visit every [`Filter`] leaf in this group mutably, depth first.
+    pub fn leaves_mut(&mut self) -> Box<dyn Iterator<Item = &mut Filter> + '_> {
+        Box::new(self.filters.iter_mut().flat_map(|x| x.leaves_mut()))
+    }
+
+    /// Whether this group can be expressed as a legacy flat `filter` list,
+    /// i.e. it is not negated and contains no nested groups.
+    pub fn is_flat(&self) -> bool {
+        !self.negated
+            && self
+                .filters
+                .iter()
+                .all(|x| matches!(x, FilterTree::Filter(_)))
+    }
+
+    /// Convert this group to a legacy flat `filter` list and `filter_op`, if
+    /// it [`FilterGroup::is_flat`].
+    pub fn to_flat(&self) -> Option<(Vec<Filter>, FilterReducer)> {
+        self.is_flat().then(|| {
+            let filters = self.leaves().cloned().collect();
+            (filters, self.op.clone())
+        })
+    }
+}
+
 impl From<Scalar> for proto::Scalar {
     fn from(value: Scalar) -> Self {
         match value {
@@ -155,6 +290,12 @@ impl From<Scalar> for proto::Scalar {
             Scalar::Bool(x) => proto::Scalar {
                 scalar: Some(scalar::Scalar::Bool(x)),
             },
+            Scalar::Date(x) => proto::Scalar {
+                scalar: Some(scalar::Scalar::Date(x.days_since_epoch())),
+            },
+            Scalar::Datetime(x) => proto::Scalar {
+                scalar: Some(scalar::Scalar::Datetime(x.timestamp_millis())),
+            },
             Scalar::Null => proto::Scalar {
                 scalar: Some(scalar::Scalar::Null(0)),
             },
@@ -168,6 +309,10 @@ impl From<proto::Scalar> for Scalar {
             Some(scalar::Scalar::Bool(x)) => Scalar::Bool(x),
             Some(scalar::Scalar::String(x)) => Scalar::String(x),
             Some(scalar::Scalar::Float(x)) => Scalar::Float(x),
+            Some(scalar::Scalar::Date(x)) => Scalar::Date(Date::from_days_since_epoch(x)),
+            Some(scalar::Scalar::Datetime(x)) => {
+                Scalar::Datetime(Datetime::from_timestamp_millis(x))
+            },
             Some(scalar::Scalar::Null(_)) => Scalar::Null,
             None => Scalar::Null,
         }
@@ -200,3 +345,64 @@ impl From<proto::view_config::Filter> for Filter {
         )
     }
 }
+
+impl From<FilterReducer> for proto::view_config::FilterReducer {
+    fn from(value: FilterReducer) -> Self {
+        match value {
+            FilterReducer::And => proto::view_config::FilterReducer::And,
+            FilterReducer::Or => proto::view_config::FilterReducer::Or,
+        }
+    }
+}
+
+impl From<proto::view_config::FilterReducer> for FilterReducer {
+    fn from(value: proto::view_config::FilterReducer) -> Self {
+        match value {
+            proto::view_config::FilterReducer::And => FilterReducer::And,
+            proto::view_config::FilterReducer::Or => FilterReducer::Or,
+        }
+    }
+}
+
+impl From<FilterTree> for proto::view_config::FilterTree {
+    fn from(value: FilterTree) -> Self {
+        proto::view_config::FilterTree {
+            node: Some(match value {
+                FilterTree::Filter(x) => filter_tree::Node::Filter(x.into()),
+                FilterTree::Group(x) => filter_tree::Node::Group(x.into()),
+            }),
+        }
+    }
+}
+
+impl From<proto::view_config::FilterTree> for FilterTree {
+    fn from(value: proto::view_config::FilterTree) -> Self {
+        match value.node {
+            Some(filter_tree::Node::Filter(x)) => FilterTree::Filter(x.into()),
+            Some(filter_tree::Node::Group(x)) => FilterTree::Group(x.into()),
+            None => FilterTree::Group(FilterGroup::default()),
+        }
+    }
+}
+
+impl From<FilterGroup> for proto::view_config::FilterGroup {
+    fn from(value: FilterGroup) -> Self {
+        proto::view_config::FilterGroup {
+            op: proto::view_config::FilterReducer::from(value.op) as i32,
+            negated: value.negated,
+            filters: value.filters.into_iter().map(|x| x.into()).collect(),
+        }
+    }
+}
+
+impl From<proto::view_config::FilterGroup> for FilterGroup {
+    fn from(value: proto::view_config::FilterGroup) -> Self {
+        FilterGroup {
+            op: proto::view_config::FilterReducer::try_from(value.op)
+                .unwrap_or_default()
+                .into(),
+            negated: value.negated,
+            filters: value.filters.into_iter().map(|x| x.into()).collect(),
+        }
+    }
+}
@@ -25,6 +25,7 @@ pub mod expressions;
 mod filters;
 mod plugin;
 mod sort;
+mod temporal;
 mod view_config;
 
 pub use aggregates::*;
@@ -32,6 +33,7 @@ pub use expressions::*;
 pub use filters::*;
 pub use plugin::*;
 pub use sort::*;
+pub use temporal::*;
 pub use view_config::*;
 
 pub use crate::proto::{ColumnType, SortOp};
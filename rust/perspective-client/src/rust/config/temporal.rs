@@ -0,0 +1,288 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+const MS_PER_DAY: i64 = 86_400_000;
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum TemporalParseError {
+    #[error("Invalid date {0:?}, expected `YYYY-MM-DD`")]
+    Date(String),
+
+    #[error("Invalid datetime {0:?}, expected RFC 3339 with a UTC offset")]
+    Datetime(String),
+}
+
+/// A calendar date filter operand, which has no time zone. Serializes as
+/// `{"date": "YYYY-MM-DD"}`, and travels over the protocol as days since
+/// `1970-01-01`.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(try_from = "DateRepr", into = "DateRepr")]
+pub struct Date(i32);
+
+/// An instant filter operand. Serializes as `{"datetime": "..."}` in RFC 3339
+/// form with an explicit offset (which is normalized to UTC), e.g.
+/// `{"datetime": "2026-01-01T09:30:00.000Z"}`, and travels over the protocol
+/// as milliseconds since the Unix epoch.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(try_from = "DatetimeRepr", into = "DatetimeRepr")]
+pub struct Datetime(i64);
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct DateRepr {
+    date: String,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct DatetimeRepr {
+    datetime: String,
+}
+
+impl Date {
+    /// Create a [`Date`] from a calendar year, month (1-12) and day (1-31),
+    /// or `None` if no such date exists.
+    pub fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+
+        i32::try_from(days_from_civil(year, month, day))
+            .ok()
+            .map(Date)
+    }
+
+    pub fn from_days_since_epoch(days: i32) -> Self {
+        Date(days)
+    }
+
+    pub fn days_since_epoch(&self) -> i32 {
+        self.0
+    }
+
+    /// The calendar year, month (1-12) and day (1-31) of this date.
+    pub fn ymd(&self) -> (i32, u32, u32) {
+        // Any `i32` days since the epoch is within a few million years of it.
+        let (year, month, day) = civil_from_days(self.0 as i64);
+        (year as i32, month, day)
+    }
+
+    /// Midnight UTC at the start of this date.
+    pub fn to_datetime(&self) -> Datetime {
+        Datetime(self.0 as i64 * MS_PER_DAY)
+    }
+}
+
+impl Datetime {
+    pub fn from_timestamp_millis(millis: i64) -> Self {
+        Datetime(millis)
+    }
+
+    pub fn timestamp_millis(&self) -> i64 {
+        self.0
+    }
+
+    /// The UTC calendar date of this instant, or `None` if it is too far
+    /// from the epoch for a [`Date`].
+    pub fn to_date(&self) -> Option<Date> {
+        i32::try_from(self.0.div_euclid(MS_PER_DAY)).ok().map(Date)
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (y, m, d) = self.ymd();
+        write!(fmt, "{y:04}-{m:02}-{d:02}")
+    }
+}
+
+impl Display for Datetime {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ms = self.0.rem_euclid(MS_PER_DAY);
+        let (hh, mm, ss, fff) = (
+            ms / 3_600_000,
+            (ms / 60_000) % 60,
+            (ms / 1000) % 60,
+            ms % 1000,
+        );
+
+        let (y, m, d) = civil_from_days(self.0.div_euclid(MS_PER_DAY));
+        write!(
+            fmt,
+            "{y:04}-{m:02}-{d:02}T{hh:02}:{mm:02}:{ss:02}.{fff:03}Z"
+        )
+    }
+}
+
+impl FromStr for Date {
+    type Err = TemporalParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_ymd(s).ok_or_else(|| TemporalParseError::Date(s.to_owned()))
+    }
+}
+
+impl FromStr for Datetime {
+    type Err = TemporalParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_rfc3339(s).ok_or_else(|| TemporalParseError::Datetime(s.to_owned()))
+    }
+}
+
+impl TryFrom<DateRepr> for Date {
+    type Error = TemporalParseError;
+
+    fn try_from(value: DateRepr) -> Result<Self, Self::Error> {
+        value.date.parse()
+    }
+}
+
+impl From<Date> for DateRepr {
+    fn from(value: Date) -> Self {
+        DateRepr {
+            date: value.to_string(),
+        }
+    }
+}
+
+impl TryFrom<DatetimeRepr> for Datetime {
+    type Error = TemporalParseError;
+
+    fn try_from(value: DatetimeRepr) -> Result<Self, Self::Error> {
+        value.datetime.parse()
+    }
+}
+
+impl From<Datetime> for DatetimeRepr {
+    fn from(value: Datetime) -> Self {
+        DatetimeRepr {
+            datetime: value.to_string(),
+        }
+    }
+}
+
+pub(crate) fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub(crate) fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Howard Hinnant's `days_from_civil`/`civil_from_days`, see
+// http://howardhinnant.github.io/date_algorithms.html. These use `i64`, as
+// extreme `i32` years or days overflow `i32` arithmetic.
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = year as i64;
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn parse_digits(s: &str) -> Option<u32> {
+    if s.is_empty() || !s.bytes().all(|x| x.is_ascii_digit()) {
+        return None;
+    }
+
+    s.parse().ok()
+}
+
+fn parse_ymd(s: &str) -> Option<Date> {
+    let (year, rest) = s.split_at_checked(4)?;
+    let (month, day) = rest.strip_prefix('-')?.split_once('-')?;
+    if month.len() != 2 || day.len() != 2 {
+        return None;
+    }
+
+    Date::from_ymd(
+        parse_digits(year)? as i32,
+        parse_digits(month)?,
+        parse_digits(day)?,
+    )
+}
+
+/// Parses `YYYY-MM-DD(T| )HH:MM:SS[.fff](Z|+HH:MM|-HH:MM)`. Fractional
+/// seconds beyond millisecond precision are truncated.
+fn parse_rfc3339(s: &str) -> Option<Datetime> {
+    let date = parse_ymd(s.get(..10)?)?;
+    let rest = s.get(10..)?.strip_prefix(['T', 't', ' '])?;
+    let (hh, rest) = rest.split_at_checked(2)?;
+    let (mm, rest) = rest.strip_prefix(':')?.split_at_checked(2)?;
+    let (ss, mut rest) = rest.strip_prefix(':')?.split_at_checked(2)?;
+    let (hh, mm, ss) = (parse_digits(hh)?, parse_digits(mm)?, parse_digits(ss)?);
+    if hh > 23 || mm > 59 || ss > 60 {
+        return None;
+    }
+
+    let mut millis = 0;
+    if let Some(frac) = rest.strip_prefix('.') {
+        let len = frac.bytes().take_while(|x| x.is_ascii_digit()).count();
+        if len == 0 {
+            return None;
+        }
+
+        let digits = &frac[..len.min(3)];
+        millis = parse_digits(digits)? * 10_u32.pow(3 - digits.len() as u32);
+        rest = &frac[len..];
+    }
+
+    let offset_minutes = match rest {
+        "Z" | "z" => 0,
+        _ => {
+            let sign = match rest.get(..1)? {
+                "+" => 1,
+                "-" => -1,
+                _ => return None,
+            };
+
+            let (oh, om) = rest.get(1..)?.split_once(':')?;
+            if oh.len() != 2 || om.len() != 2 {
+                return None;
+            }
+
+            sign * (parse_digits(oh)? * 60 + parse_digits(om)?) as i64
+        },
+    };
+
+    let seconds = hh as i64 * 3600 + mm as i64 * 60 + ss as i64 - offset_minutes * 60;
+    Some(Datetime(
+        date.0 as i64 * MS_PER_DAY + seconds * 1000 + millis as i64,
+    ))
+}
@@ -89,6 +89,10 @@ pub struct ViewConfig {
     #[serde(default)]
     pub filter_op: FilterReducer,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub filter_tree: Option<FilterGroup>,
+
     #[serde(default)]
     pub expressions: Expressions,
 
@@ -213,6 +217,19 @@ pub struct ViewConfigUpdate {
     #[ts(optional)]
     pub filter_op: Option<FilterReducer>,
 
+    /// The `filter_tree` property is a nested alternative to `filter` and
+    /// `filter_op`, which can express arbitrarily nested `AND`, `OR` and
+    /// `NOT` groups, e.g. `(region = 'EU' OR region = 'UK') AND sales > 100`.
+    /// When set, `filter` and `filter_op` are ignored. Leaves use the same
+    /// `[column, op, term]` form as `filter`; groups are objects of the form
+    /// `{"op": "or", "negated": false, "filters": [...]}`.
+    ///
+    /// Not all backends support nesting, see `Features::nested_filters`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    #[ts(optional)]
+    pub filter_tree: Option<FilterGroup>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     #[ts(optional)]
@@ -220,7 +237,21 @@ pub struct ViewConfigUpdate {
 }
 
 impl From<ViewConfigUpdate> for proto::ViewConfig {
-    fn from(value: ViewConfigUpdate) -> Self {
+    fn from(mut value: ViewConfigUpdate) -> Self {
+        // Trees which have a flat equivalent are sent in the legacy form, so
+        // they are understood by servers which predate `filter_tree`.
+        let filter_tree = match value.filter_tree.take() {
+            Some(tree) => match tree.to_flat() {
+                Some((filter, filter_op)) => {
+                    value.filter = Some(filter);
+                    value.filter_op = Some(filter_op);
+                    None
+                },
+                None => Some(tree.into()),
+            },
+            None => None,
+        };
+
         proto::ViewConfig {
             group_by: value.group_by.unwrap_or_default(),
             split_by: value.split_by.unwrap_or_default(),
@@ -258,24 +289,7 @@ impl From<ViewConfigUpdate> for proto::ViewConfig {
             group_rollup_mode: value
                 .group_rollup_mode
                 .map(|x| proto::GroupRollupMode::from(x).into()),
-        }
-    }
-}
-
-impl From<FilterReducer> for proto::view_config::FilterReducer {
-    fn from(value: FilterReducer) -> Self {
-        match value {
-            FilterReducer::And => proto::view_config::FilterReducer::And,
-            FilterReducer::Or => proto::view_config::FilterReducer::Or,
-        }
-    }
-}
-
-impl From<proto::view_config::FilterReducer> for FilterReducer {
-    fn from(value: proto::view_config::FilterReducer) -> Self {
-        match value {
-            proto::view_config::FilterReducer::And => FilterReducer::And,
-            proto::view_config::FilterReducer::Or => FilterReducer::Or,
+            filter_tree,
         }
     }
 }
@@ -288,6 +302,7 @@ impl From<ViewConfig> for ViewConfigUpdate {
             columns: Some(value.columns),
             filter: Some(value.filter),
             filter_op: Some(value.filter_op),
+            filter_tree: value.filter_tree,
             sort: Some(value.sort),
             expressions: Some(value.expressions),
             aggregates: Some(value.aggregates),
@@ -314,6 +329,7 @@ impl From<proto::ViewConfig> for ViewConfig {
             filter_op: proto::view_config::FilterReducer::try_from(value.filter_op)
                 .unwrap_or_default()
                 .into(),
+            filter_tree: value.filter_tree.map(|x| x.into()),
             sort: value.sort.into_iter().map(|x| x.into()).collect(),
             expressions: Expressions(value.expressions),
             aggregates: value
@@ -340,6 +356,7 @@ impl From<ViewConfigUpdate> for ViewConfig {
             columns: value.columns.unwrap_or_default(),
             filter: value.filter.unwrap_or_default(),
             filter_op: value.filter_op.unwrap_or_default(),
+            filter_tree: value.filter_tree,
             sort: value.sort.unwrap_or_default(),
             expressions: value.expressions.unwrap_or_default(),
             aggregates: value.aggregates.unwrap_or_default(),
@@ -366,6 +383,7 @@ impl From<proto::ViewConfig> for ViewConfigUpdate {
                     .unwrap_or_default()
                     .into(),
             ),
+            filter_tree: value.filter_tree.map(|x| x.into()),
             sort: Some(value.sort.into_iter().map(|x| x.into()).collect()),
             expressions: Some(Expressions(value.expressions)),
             aggregates: Some(
@@ -384,7 +402,32 @@ impl From<proto::ViewConfig> for ViewConfigUpdate {
     }
 }
 
+impl ViewConfigUpdate {
+    /// Re-encode `Date`/`Datetime` filter terms for a server which lacks
+    /// [`crate::protocol::Capabilities::TEMPORAL_SCALARS`].
+    pub(crate) fn downgrade_temporal_scalars(&mut self) {
+        let flat = self.filter.iter_mut().flatten();
+        let tree = self.filter_tree.iter_mut().flat_map(|x| x.leaves_mut());
+        for filter in flat.chain(tree) {
+            *filter.term_mut() = filter.term().to_legacy_temporal();
+        }
+    }
+}
+
 impl ViewConfig {
+    /// Re-encode `Date`/`Datetime` filter terms as `"YYYY-MM-DD"` strings and
+    /// POSIX milliseconds respectively, the encoding
+    /// [`crate::View::get_config`] used before
+    /// [`crate::protocol::Capabilities::TEMPORAL_SCALARS`]. The
+    /// language bindings return this encoding, so existing consumers (and
+    /// saved layouts) are unaffected.
+    pub fn downgrade_temporal_scalars(&mut self) {
+        let tree = self.filter_tree.iter_mut().flat_map(|x| x.leaves_mut());
+        for filter in self.filter.iter_mut().chain(tree) {
+            *filter.term_mut() = filter.term().to_legacy_temporal();
+        }
+    }
+
     fn _apply<T>(field: &mut T, update: Option<T>) -> bool {
         match update {
             None => false,
@@ -430,6 +473,13 @@ impl ViewConfig {
         changed = Self::_apply(&mut self.group_by, update.group_by) || changed;
         changed = Self::_apply(&mut self.split_by, update.split_by) || changed;
         changed = Self::_apply(&mut self.columns, update.columns) || changed;
+        if update.filter_tree.is_some() {
+            changed = Self::_apply(&mut self.filter_tree, Some(update.filter_tree)) || changed;
+        } else if update.filter.is_some() && self.filter_tree.is_some() {
+            self.filter_tree = None;
+            changed = true;
+        }
+
         changed = Self::_apply(&mut self.filter, update.filter) || changed;
         changed = Self::_apply(&mut self.sort, update.sort) || changed;
         changed = Self::_apply(&mut self.aggregates, update.aggregates) || changed;
@@ -444,6 +494,16 @@ impl ViewConfig {
         changed
     }
 
+    /// The effective filter of this config as a [`FilterGroup`], which is
+    /// `filter_tree` if set, or the flat `filter` list combined by
+    /// `filter_op` otherwise.
+    pub fn filter_group(&self) -> FilterGroup {
+        match &self.filter_tree {
+            Some(tree) => tree.clone(),
+            None => FilterGroup::from_flat(self.filter.clone(), self.filter_op.clone()),
+        }
+    }
+
     pub fn is_aggregated(&self) -> bool {
         !self.group_by.is_empty() || self.group_rollup_mode == GroupRollupMode::Total
     }
@@ -454,6 +514,11 @@ impl ViewConfig {
             || self.split_by.contains(&name)
             || self.sort.iter().any(|x| x.0 == name)
             || self.filter.iter().any(|x| x.column() == name)
+            || self
+                .filter_tree
+                .iter()
+                .flat_map(|x| x.leaves())
+                .any(|x| x.column() == name)
             || self.columns.contains(&Some(name))
     }
 
@@ -0,0 +1,396 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Client-side diagnostics for the engine's type inference, and the
+//! per-column `column_types` and `date_formats` overrides of
+//! [`TableInitOptions`].
+
+mod csv;
+mod dates;
+
+use std::collections::{HashMap, HashSet};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use ts_rs::TS;
+
+use self::csv::parse_csv;
+use self::dates::{parse_date, parse_date_with_format};
+use crate::proto::ColumnType;
+use crate::table_data::UpdateData;
+use crate::utils::*;
+#[cfg(doc)]
+use crate::{Client, TableInitOptions};
+
+/// Options for [`Client::infer_schema`].
+#[derive(Clone, Debug, Default, Deserialize, Serialize, TS)]
+pub struct InferSchemaOptions {
+    /// The number of rows to check for [`ColumnInference`], defaults to
+    /// `1000`. The [`InferredSchema::schema`] always reflects all of the
+    /// input.
+    #[serde(default)]
+    #[ts(optional)]
+    pub sample_rows: Option<u32>,
+
+    /// The maximum [`ColumnInference::offending_values`] to report per
+    /// column, defaults to `5`.
+    #[serde(default)]
+    #[ts(optional)]
+    pub max_offending_values: Option<u32>,
+
+    /// As [`TableInitOptions::column_types`].
+    #[serde(default)]
+    #[ts(optional)]
+    pub column_types: Option<HashMap<String, ColumnType>>,
+
+    /// As [`TableInitOptions::date_formats`].
+    #[serde(default)]
+    #[ts(optional)]
+    pub date_formats: Option<HashMap<String, String>>,
+}
+
+/// The result of [`Client::infer_schema`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct InferredSchema {
+    /// The schema [`Client::table`] would create for this input, in column
+    /// order, with any `column_types` overrides applied.
+    pub schema: Vec<(String, ColumnType)>,
+
+    /// How well each column's sampled values fit, by column name.
+    pub columns: IndexMap<String, ColumnInference>,
+}
+
+/// How well the sampled values of one column fit their inferred type.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ColumnInference {
+    /// The type of this column in [`InferredSchema::schema`].
+    pub r#type: ColumnType,
+
+    /// The most specific type a majority of the sampled values parse as.
+    /// When this differs from `type`, the column is likely mistyped, e.g. a
+    /// `string` column of dates with one malformed value, or an `integer`
+    /// column of IDs with leading zeros (which are `string`).
+    pub candidate: ColumnType,
+
+    /// The fraction of sampled, non-null values which parse as `candidate`.
+    pub confidence: f64,
+
+    /// Distinct sampled values which do not parse as `candidate`.
+    pub offending_values: Vec<String>,
+}
+
+/// The type a single value would be read as, absent any other values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ValueClass {
+    Boolean,
+    Integer,
+    Float,
+    Date,
+    Datetime,
+    String,
+}
+
+/// Non-`string` candidates, in order of preference when they fit equally.
+const CANDIDATES: [ColumnType; 5] = [
+    ColumnType::Boolean,
+    ColumnType::Integer,
+    ColumnType::Float,
+    ColumnType::Date,
+    ColumnType::Datetime,
+];
+
+impl ValueClass {
+    fn fits(self, column_type: ColumnType) -> bool {
+        use ValueClass::*;
+        match column_type {
+            ColumnType::String => true,
+            ColumnType::Boolean => self == Boolean,
+            ColumnType::Integer => self == Integer,
+            ColumnType::Float => matches!(self, Integer | Float),
+            ColumnType::Date => self == Date,
+            ColumnType::Datetime => matches!(self, Date | Datetime),
+            ColumnType::Int64 | ColumnType::Duration => self == Integer,
+            ColumnType::ListString | ColumnType::ListFloat => false,
+        }
+    }
+}
+
+fn classify_text(value: &str, date_format: Option<&str>) -> ValueClass {
+    if let Some(format) = date_format {
+        return match parse_date_with_format(value, format) {
+            Some(date) if date.has_time() => ValueClass::Datetime,
+            Some(_) => ValueClass::Date,
+            None => ValueClass::String,
+        };
+    }
+
+    let digits = value.strip_prefix(['-', '+']).unwrap_or(value);
+    if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") {
+        ValueClass::Boolean
+    } else if !digits.is_empty() && digits.bytes().all(|x| x.is_ascii_digit()) {
+        // Leading zeros would be lost by a numeric type.
+        if digits.len() > 1 && digits.starts_with('0') {
+            ValueClass::String
+        } else if value.parse::<i64>().is_ok() {
+            ValueClass::Integer
+        } else {
+            ValueClass::Float
+        }
+    } else if digits.starts_with(|x: char| x.is_ascii_digit() || x == '.')
+        && value.parse::<f64>().is_ok_and(f64::is_finite)
+    {
+        ValueClass::Float
+    } else {
+        match parse_date(value) {
+            Some(date) if date.has_time() => ValueClass::Datetime,
+            Some(_) => ValueClass::Date,
+            None => ValueClass::String,
+        }
+    }
+}
+
+/// The class of a JSON cell, or `None` for `null`.
+fn classify_json(value: &Value, date_format: Option<&str>) -> Option<(ValueClass, String)> {
+    match value {
+        Value::Null => None,
+        Value::Bool(x) => Some((ValueClass::Boolean, x.to_string())),
+        Value::Number(x) if x.is_f64() => Some((ValueClass::Float, x.to_string())),
+        Value::Number(x) => Some((ValueClass::Integer, x.to_string())),
+        Value::String(x) => Some((classify_text(x, date_format), x.clone())),
+        x => Some((ValueClass::String, x.to_string())),
+    }
+}
+
+/// Sample up to `max_rows` rows of `input` as JSON cells by column name, or
+/// `None` for formats which carry their own types (Arrow and Parquet).
+fn sample_columns(
+    input: &UpdateData,
+    max_rows: usize,
+) -> ClientResult<Option<IndexMap<String, Vec<Value>>>> {
+    let mut columns = IndexMap::<String, Vec<Value>>::new();
+    let mut push_row = |row: serde_json::Map<String, Value>| {
+        for (name, value) in row {
+            columns.entry(name).or_default().push(value);
+        }
+    };
+
+    match input {
+        UpdateData::Arrow(_) | UpdateData::Parquet { .. } => return Ok(None),
+        UpdateData::Csv(text) => {
+            let csv = parse_csv(text, Some(max_rows));
+            for row in csv.rows {
+                push_row(
+                    csv.header
+                        .iter()
+                        .cloned()
+                        .zip(
+                            row.into_iter()
+                                .map(|x| x.map_or(Value::Null, Value::String)),
+                        )
+                        .collect(),
+                );
+            }
+        },
+        UpdateData::JsonRows(text) => {
+            let rows: Vec<serde_json::Map<String, Value>> =
+                serde_json::from_str(text).map_err(ClientError::external)?;
+
+            rows.into_iter().take(max_rows).for_each(push_row);
+        },
+        UpdateData::Ndjson(text) => {
+            for line in text.lines().filter(|x| !x.trim().is_empty()).take(max_rows) {
+                push_row(serde_json::from_str(line).map_err(ClientError::external)?);
+            }
+        },
+        UpdateData::JsonColumns(text) => {
+            let cols: IndexMap<String, Vec<Value>> =
+                serde_json::from_str(text).map_err(ClientError::external)?;
+
+            for (name, mut values) in cols {
+                values.truncate(max_rows);
+                columns.insert(name, values);
+            }
+        },
+    }
+
+    Ok(Some(columns))
+}
+
+/// Diagnose how well the sampled `input` fits `schema` (the engine's types).
+pub(crate) fn diagnose(
+    input: &UpdateData,
+    schema: &[(String, ColumnType)],
+    options: &InferSchemaOptions,
+) -> ClientResult<IndexMap<String, ColumnInference>> {
+    let sample_rows = options.sample_rows.unwrap_or(1000) as usize;
+    let max_offending = options.max_offending_values.unwrap_or(5) as usize;
+    let date_formats = options.date_formats.clone().unwrap_or_default();
+    let mut samples = sample_columns(input, sample_rows)?;
+    let mut columns = IndexMap::new();
+    for (name, r#type) in schema {
+        let values = samples
+            .as_mut()
+            .and_then(|x| x.swap_remove(name))
+            .unwrap_or_default();
+
+        let date_format = date_formats.get(name).map(String::as_str);
+        let classes = values
+            .iter()
+            .filter_map(|x| classify_json(x, date_format))
+            .collect::<Vec<_>>();
+
+        let count = |column_type| {
+            classes
+                .iter()
+                .filter(|(class, _)| class.fits(column_type))
+                .count()
+        };
+
+        // Prefer the first of the best fitting candidates, if it is a
+        // majority, otherwise `string` (which everything fits).
+        let (candidate, fit) = CANDIDATES
+            .iter()
+            .map(|x| (*x, count(*x)))
+            .rev()
+            .max_by_key(|(_, fit)| *fit)
+            .filter(|(_, fit)| *fit * 2 > classes.len())
+            .unwrap_or((ColumnType::String, classes.len()));
+
+        let mut seen = HashSet::new();
+        let offending_values = classes
+            .iter()
+            .filter(|(class, _)| !class.fits(candidate))
+            .map(|(_, value)| value)
+            .filter(|value| seen.insert(value.as_str()))
+            .take(max_offending)
+            .cloned()
+            .collect();
+
+        let confidence = if classes.is_empty() {
+            1.0
+        } else {
+            fit as f64 / classes.len() as f64
+        };
+
+        columns.insert(name.clone(), ColumnInference {
+            r#type: *r#type,
+            candidate: if classes.is_empty() {
+                *r#type
+            } else {
+                candidate
+            },
+            confidence,
+            offending_values,
+        });
+    }
+
+    Ok(columns)
+}
+
+/// Rewrite the `date_formats` columns of text `input` as ISO 8601, which the
+/// engine reads. Values which don't match their format are left as-is.
+pub(crate) fn apply_date_formats(
+    input: UpdateData,
+    date_formats: &HashMap<String, String>,
+) -> ClientResult<UpdateData> {
+    if date_formats.is_empty() {
+        return Ok(input);
+    }
+
+    let rewrite = |name: &str, value: &mut Value| {
+        if let (Some(format), Value::String(text)) = (date_formats.get(name), &*value)
+            && let Some(date) = parse_date_with_format(text, format)
+        {
+            *value = Value::String(date.to_iso());
+        }
+    };
+
+    let rewrite_row = |row: &mut serde_json::Map<String, Value>| {
+        for (name, value) in row.iter_mut() {
+            rewrite(name, value);
+        }
+    };
+
+    Ok(match input {
+        UpdateData::Csv(text) => {
+            let mut csv = parse_csv(&text, None);
+            for (idx, name) in csv.header.iter().enumerate() {
+                let Some(format) = date_formats.get(name) else {
+                    continue;
+                };
+
+                for cell in csv.rows.iter_mut().filter_map(|x| x.get_mut(idx)?.as_mut()) {
+                    if let Some(date) = parse_date_with_format(cell, format) {
+                        *cell = date.to_iso();
+                    }
+                }
+            }
+
+            UpdateData::Csv(csv.to_csv_string())
+        },
+        UpdateData::JsonRows(text) => {
+            let mut rows: Vec<serde_json::Map<String, Value>> =
+                serde_json::from_str(&text).map_err(ClientError::external)?;
+
+            rows.iter_mut().for_each(rewrite_row);
+            UpdateData::JsonRows(serde_json::to_string(&rows).map_err(ClientError::external)?)
+        },
+        UpdateData::Ndjson(text) => {
+            let mut out = String::with_capacity(text.len());
+            for line in text.lines().filter(|x| !x.trim().is_empty()) {
+                let mut row: serde_json::Map<String, Value> =
+                    serde_json::from_str(line).map_err(ClientError::external)?;
+
+                rewrite_row(&mut row);
+                out.push_str(&serde_json::to_string(&row).map_err(ClientError::external)?);
+                out.push('\n');
+            }
+
+            UpdateData::Ndjson(out)
+        },
+        UpdateData::JsonColumns(text) => {
+            let mut cols: IndexMap<String, Vec<Value>> =
+                serde_json::from_str(&text).map_err(ClientError::external)?;
+
+            for (name, values) in cols.iter_mut() {
+                values.iter_mut().for_each(|x| rewrite(name, x));
+            }
+
+            UpdateData::JsonColumns(serde_json::to_string(&cols).map_err(ClientError::external)?)
+        },
+        x @ (UpdateData::Arrow(_) | UpdateData::Parquet { .. }) => x,
+    })
+}
+
+/// Replace the types in `schema` with their `column_types` overrides.
+pub(crate) fn apply_column_types(
+    schema: &mut [(String, ColumnType)],
+    column_types: &HashMap<String, ColumnType>,
+) -> ClientResult<()> {
+    if let Some(name) = column_types
+        .keys()
+        .find(|name| !schema.iter().any(|(x, _)| x == *name))
+    {
+        return Err(ClientError::Unknown(format!(
+            "Column \"{name}\" in `column_types` not found"
+        )));
+    }
+
+    for (name, column_type) in schema.iter_mut() {
+        if let Some(x) = column_types.get(name) {
+            *column_type = *x;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! A minimal RFC 4180 CSV reader and writer, sufficient to sample and rewrite
+//! CSV input before it is sent to the engine (which has its own reader).
+
+/// A parsed CSV document. Empty unquoted cells are `None`, as the engine
+/// reads them as `null`.
+pub(crate) struct Csv {
+    pub header: Vec<String>,
+    pub rows: Vec<Vec<Option<String>>>,
+}
+
+/// Parse `text`, reading at most `max_rows` rows after the header.
+pub(crate) fn parse_csv(text: &str, max_rows: Option<usize>) -> Csv {
+    let mut records = Records {
+        chars: text.chars().peekable(),
+    };
+
+    let header = records
+        .next()
+        .unwrap_or_default()
+        .into_iter()
+        .map(Option::unwrap_or_default)
+        .collect();
+
+    let rows = match max_rows {
+        Some(max_rows) => records.take(max_rows).collect(),
+        None => records.collect(),
+    };
+
+    Csv { header, rows }
+}
+
+impl Csv {
+    /// Serialize back to CSV, quoting only where necessary.
+    pub(crate) fn to_csv_string(&self) -> String {
+        let mut out = String::new();
+        let header = self.header.iter().map(|x| Some(x.as_str()));
+        write_record(&mut out, header);
+        for row in &self.rows {
+            write_record(&mut out, row.iter().map(Option::as_deref));
+        }
+
+        out
+    }
+}
+
+fn write_record<'a>(out: &mut String, cells: impl Iterator<Item = Option<&'a str>>) {
+    for (idx, cell) in cells.enumerate() {
+        if idx > 0 {
+            out.push(',');
+        }
+
+        match cell {
+            None => {},
+            Some(cell) if cell.is_empty() || cell.contains([',', '"', '\n', '\r']) => {
+                out.push('"');
+                out.push_str(&cell.replace('"', "\"\""));
+                out.push('"');
+            },
+            Some(cell) => out.push_str(cell),
+        }
+    }
+
+    out.push('\n');
+}
+
+struct Records<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl Iterator for Records<'_> {
+    type Item = Vec<Option<String>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Skip blank lines between records.
+        while self.chars.next_if(|x| *x == '\n' || *x == '\r').is_some() {}
+        self.chars.peek()?;
+        let mut record = vec![];
+        let mut cell = String::new();
+        let mut quoted = false;
+        loop {
+            match self.chars.next() {
+                Some('"') if cell.is_empty() && !quoted => {
+                    quoted = true;
+                    while let Some(c) = self.chars.next() {
+                        match c {
+                            '"' if self.chars.next_if_eq(&'"').is_some() => cell.push('"'),
+                            '"' => break,
+                            c => cell.push(c),
+                        }
+                    }
+                },
+                Some(',') => {
+                    record.push((quoted || !cell.is_empty()).then(|| std::mem::take(&mut cell)));
+                    quoted = false;
+                },
+                Some('\r') if self.chars.peek() == Some(&'\n') => {},
+                Some('\n') | None => {
+                    record.push((quoted || !cell.is_empty()).then_some(cell));
+                    return Some(record);
+                },
+                Some(c) => cell.push(c),
+            }
+        }
+    }
+}
@@ -0,0 +1,262 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Date and datetime recognition, approximating the formats the engine's CSV
+//! and JSON readers accept, plus a `strptime`-style parser for explicit
+//! `date_formats`.
+
+use crate::config::days_in_month;
+
+/// A parsed date, with an optional time of day.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct DateTime {
+    year: i32,
+    month: u32,
+    day: u32,
+    time: Option<(u32, u32, u32, u32)>,
+}
+
+impl DateTime {
+    /// Whether this value has a (non-midnight) time of day, which the engine
+    /// reads as a `datetime` rather than a `date`.
+    pub(crate) fn has_time(&self) -> bool {
+        self.time.is_some_and(|x| x != (0, 0, 0, 0))
+    }
+
+    /// Format as ISO 8601, which every engine reader accepts.
+    pub(crate) fn to_iso(self) -> String {
+        let date = format!("{:04}-{:02}-{:02}", self.year, self.month, self.day);
+        match self.time {
+            Some((h, m, s, ms)) => format!("{date} {h:02}:{m:02}:{s:02}.{ms:03}"),
+            None => date,
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        (1..=12).contains(&self.month)
+            && (1..=days_in_month(self.year, self.month)).contains(&self.day)
+            && self
+                .time
+                .is_none_or(|(h, m, s, _)| h < 24 && m < 60 && s < 61)
+    }
+}
+
+/// A cursor over the bytes of a value being parsed.
+struct Scanner<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            input: input.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.pos == self.input.len()
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        let matched = self.peek() == Some(c);
+        self.pos += matched as usize;
+        matched
+    }
+
+    fn eat_any(&mut self, cs: &[u8]) -> Option<u8> {
+        let c = self.peek().filter(|x| cs.contains(x))?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    /// Between `min` and `max` ASCII digits.
+    fn digits(&mut self, min: usize, max: usize) -> Option<u32> {
+        let start = self.pos;
+        while self.pos - start < max && self.peek().is_some_and(|x| x.is_ascii_digit()) {
+            self.pos += 1;
+        }
+
+        if self.pos - start < min {
+            self.pos = start;
+            return None;
+        }
+
+        std::str::from_utf8(&self.input[start..self.pos])
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    /// A case-insensitive keyword, e.g. `AM`.
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let end = self.pos + keyword.len();
+        let matched = self
+            .input
+            .get(self.pos..end)
+            .is_some_and(|x| x.eq_ignore_ascii_case(keyword.as_bytes()));
+
+        if matched {
+            self.pos = end;
+        }
+
+        matched
+    }
+
+    /// Fractional seconds, as milliseconds.
+    fn millis(&mut self) -> Option<u32> {
+        let start = self.pos;
+        let digits = self.digits(1, 9)?;
+        let len = (self.pos - start) as u32;
+        Some(match len {
+            0..=3 => digits * 10u32.pow(3 - len),
+            _ => digits / 10u32.pow(len - 3),
+        })
+    }
+}
+
+/// Parse `value` as one of the date formats the engine infers: ISO 8601
+/// (`2024-01-31`, `2024-01-31T12:30:00.000Z`), `2024/01/31`, US
+/// (`01/31/2024`, `01-31-2024`), each with an optional time, or a time with
+/// `AM`/`PM`.
+pub(crate) fn parse_date(value: &str) -> Option<DateTime> {
+    let mut scanner = Scanner::new(value.trim());
+    let mut parsed = DateTime::default();
+    let first = scanner.digits(1, 4)?;
+    let sep = scanner.eat_any(b"-/")?;
+    if first > 31 {
+        parsed.year = first as i32;
+        parsed.month = scanner.digits(1, 2)?;
+        scanner.eat(sep).then_some(())?;
+        parsed.day = scanner.digits(1, 2)?;
+    } else {
+        parsed.month = first;
+        parsed.day = scanner.digits(1, 2)?;
+        scanner.eat(sep).then_some(())?;
+        parsed.year = scanner.digits(4, 4)? as i32;
+    }
+
+    if !scanner.is_done() {
+        scanner.eat_any(b"T ")?;
+        scanner.eat(b' ');
+        parsed.time = Some(parse_time(&mut scanner)?);
+    }
+
+    (scanner.is_done() && parsed.is_valid()).then_some(parsed)
+}
+
+fn parse_time(scanner: &mut Scanner) -> Option<(u32, u32, u32, u32)> {
+    let mut hour = scanner.digits(1, 2)?;
+    scanner.eat(b':').then_some(())?;
+    let minute = scanner.digits(2, 2)?;
+    let mut second = 0;
+    let mut millis = 0;
+    if scanner.eat(b':') {
+        second = scanner.digits(2, 2)?;
+        if scanner.eat(b'.') {
+            millis = scanner.millis()?;
+        }
+    }
+
+    scanner.eat(b' ');
+    if scanner.keyword("AM") {
+        hour %= 12;
+    } else if scanner.keyword("PM") {
+        hour = hour % 12 + 12;
+    } else if !scanner.eat(b'Z') && scanner.eat_any(b"+-").is_some() {
+        // Offsets are accepted, but (as in the engine) not applied.
+        scanner.digits(2, 2)?;
+        scanner.eat(b':');
+        scanner.digits(2, 2)?;
+    }
+
+    Some((hour, minute, second, millis))
+}
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// Parse `value` with a `strptime`-style `format`. Supports `%Y`, `%y`, `%m`,
+/// `%b` (month name), `%d`, `%H`, `%I`, `%M`, `%S`, `%f` (fractional
+/// seconds), `%p` (`AM`/`PM`), `%z` (an offset, which is not applied) and
+/// `%%`. Any other character must match literally.
+pub(crate) fn parse_date_with_format(value: &str, format: &str) -> Option<DateTime> {
+    let mut scanner = Scanner::new(value.trim());
+    let mut parsed = DateTime {
+        month: 1,
+        day: 1,
+        ..DateTime::default()
+    };
+
+    let mut time = (0, 0, 0, 0);
+    let mut has_time = false;
+    let mut pm = None;
+    let mut format = format.chars();
+    while let Some(c) = format.next() {
+        if c != '%' {
+            let mut buf = [0; 4];
+            for b in c.encode_utf8(&mut buf).bytes() {
+                scanner.eat(b).then_some(())?;
+            }
+
+            continue;
+        }
+
+        let specifier = format.next()?;
+        has_time = has_time || matches!(specifier, 'H' | 'I' | 'M' | 'S' | 'f' | 'p');
+        match specifier {
+            'Y' => parsed.year = scanner.digits(4, 4)? as i32,
+            'y' => parsed.year = 2000 + scanner.digits(2, 2)? as i32,
+            'm' => parsed.month = scanner.digits(1, 2)?,
+            'd' => parsed.day = scanner.digits(1, 2)?,
+            'b' => {
+                parsed.month = MONTHS
+                    .iter()
+                    .position(|x| scanner.keyword(x))
+                    .map(|x| x as u32 + 1)?;
+
+                // Allow full month names, e.g. `%b` matching `January`.
+                while scanner.peek().is_some_and(|x| x.is_ascii_alphabetic()) {
+                    scanner.pos += 1;
+                }
+            },
+            'H' | 'I' => time.0 = scanner.digits(1, 2)?,
+            'M' => time.1 = scanner.digits(1, 2)?,
+            'S' => time.2 = scanner.digits(1, 2)?,
+            'f' => time.3 = scanner.millis()?,
+            'p' if scanner.keyword("AM") => pm = Some(false),
+            'p' if scanner.keyword("PM") => pm = Some(true),
+            'z' if scanner.eat(b'Z') => {},
+            'z' => {
+                scanner.eat_any(b"+-")?;
+                scanner.digits(2, 2)?;
+                scanner.eat(b':');
+                scanner.digits(2, 2)?;
+            },
+            '%' => scanner.eat(b'%').then_some(())?,
+            _ => return None,
+        }
+    }
+
+    if let Some(pm) = pm {
+        time.0 = time.0 % 12 + if pm { 12 } else { 0 };
+    }
+
+    parsed.time = has_time.then_some(time);
+    (scanner.is_done() && parsed.is_valid()).then_some(parsed)
+}
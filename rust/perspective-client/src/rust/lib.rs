@@ -35,10 +35,15 @@
 )]
 
 mod client;
+mod infer;
+mod parquet;
+#[cfg(not(target_family = "wasm"))]
+mod recording;
 mod session;
 mod table;
 mod table_data;
 mod table_ref;
+mod table_writer;
 mod view;
 pub mod virtual_server;
 
@@ -48,18 +53,29 @@ pub mod config;
 #[allow(clippy::all)]
 pub mod proto;
 
+pub mod protocol;
 pub mod utils;
 
-pub use crate::client::{Client, ClientHandler, Features, ReconnectCallback, SystemInfo};
+pub use crate::client::{
+    Client, ClientHandler, Features, ReconnectCallback, SessionMemoryUsage, SessionUsage,
+    SystemInfo, TableMemoryUsage, ViewMemoryUsage,
+};
+pub use crate::infer::{ColumnInference, InferSchemaOptions, InferredSchema};
+pub use crate::parquet::{ParquetCompression, ParquetWriteOptions};
 use crate::proto::HostedTable;
 pub use crate::proto::JoinType;
+#[cfg(not(target_family = "wasm"))]
+pub use crate::recording::{RecordingSession, SessionRecorder, read_recording};
 pub use crate::session::{ProxySession, Session};
 pub use crate::table::{
-    DeleteOptions, ExprValidationResult, JoinOptions, Table, TableInitOptions, TableReadFormat,
-    UpdateOptions,
+    DeleteOptions, ExprValidationResult, JoinKeys, JoinOptions, RetentionOptions, Table,
+    TableInitOptions, TableReadFormat, UnionOptions, UnnestOptions, UpdateOptions,
 };
 pub use crate::table_data::{TableData, UpdateData};
 pub use crate::table_ref::TableRef;
+pub use crate::table_writer::{
+    TableWriter, TableWriterInput, TableWriterOptions, TableWriterStats, TableWriterTask,
+};
 pub use crate::view::{
     ColumnWindow, OnUpdateData, OnUpdateMode, OnUpdateOptions, View, ViewWindow,
 };
@@ -78,6 +94,8 @@ impl From<&str> for HostedTable {
             entity_id: entity_id.to_string(),
             index: None,
             limit: None,
+            derived: false,
+            retention: None,
         }
     }
 }
@@ -139,6 +157,7 @@ macro_rules! assert_view_api {
                     &$x::remove_delete,
                     &$x::schema,
                     &$x::to_arrow,
+                    &$x::to_parquet,
                     &$x::to_columns_string,
                     &$x::to_json_string,
                     &$x::to_csv,
@@ -0,0 +1,243 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Apache Parquet support. Parquet is never sent to the engine directly;
+//! instead it is decoded client-side into Arrow IPC batches on the way in, and
+//! encoded from [`View::to_arrow`] output on the way out.
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+#[cfg(doc)]
+use crate::View;
+use crate::utils::ClientResult;
+
+/// Compression codecs available to [`View::to_parquet`].
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum ParquetCompression {
+    Uncompressed,
+    #[default]
+    Snappy,
+    Gzip,
+    Lz4,
+    Zstd,
+    Brotli,
+}
+
+/// Options for [`View::to_parquet`].
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, TS)]
+pub struct ParquetWriteOptions {
+    /// The column chunk compression codec, defaults to `"snappy"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub compression: Option<ParquetCompression>,
+
+    /// The maximum number of rows written to a single row group.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub row_group_size: Option<usize>,
+}
+
+/// A sequence of Arrow IPC streams, one per decoded record batch.
+pub(crate) type ArrowBatches = Box<dyn Iterator<Item = ClientResult<prost::bytes::Bytes>> + Send>;
+
+#[cfg(feature = "parquet")]
+mod imp {
+    use std::io::Cursor;
+
+    use ::parquet::arrow::arrow_reader::{
+        ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder,
+    };
+    use ::parquet::arrow::{ArrowWriter, ProjectionMask};
+    use ::parquet::basic::{BrotliLevel, Compression, GzipLevel, ZstdLevel};
+    use ::parquet::file::properties::WriterProperties;
+    use arrow_array::RecordBatch;
+    use arrow_ipc::reader::StreamReader;
+    use arrow_ipc::writer::StreamWriter;
+    use arrow_schema::SchemaRef;
+    use prost::bytes::Bytes;
+
+    use super::*;
+    use crate::utils::ClientError;
+
+    /// Rows per decoded batch. Large files are streamed to the engine in
+    /// batches of this size rather than materialized as a single message.
+    const PARQUET_BATCH_ROWS: usize = 65_536;
+
+    fn write_ipc(schema: &SchemaRef, batch: Option<&RecordBatch>) -> ClientResult<Bytes> {
+        let mut buf = Vec::new();
+        let mut writer = StreamWriter::try_new(&mut buf, schema).map_err(ClientError::external)?;
+        if let Some(batch) = batch {
+            writer.write(batch).map_err(ClientError::external)?;
+        }
+
+        writer.finish().map_err(ClientError::external)?;
+        drop(writer);
+        Ok(buf.into())
+    }
+
+    struct ParquetBatches {
+        reader: ParquetRecordBatchReader,
+        schema: SchemaRef,
+        started: bool,
+    }
+
+    impl Iterator for ParquetBatches {
+        type Item = ClientResult<Bytes>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let batch = self.reader.next();
+            let started = std::mem::replace(&mut self.started, true);
+            match batch {
+                Some(batch) => Some(
+                    batch
+                        .map_err(ClientError::external)
+                        .and_then(|batch| write_ipc(&self.schema, Some(&batch))),
+                ),
+
+                // An empty file still yields its schema, so the resulting
+                // `Table` has the right columns.
+                None if !started => Some(write_ipc(&self.schema, None)),
+                None => None,
+            }
+        }
+    }
+
+    fn open_parquet(
+        data: Bytes,
+        columns: Option<&[String]>,
+    ) -> ClientResult<ParquetRecordBatchReader> {
+        let mut builder =
+            ParquetRecordBatchReaderBuilder::try_new(data).map_err(ClientError::external)?;
+        if let Some(columns) = columns {
+            if let Some(missing) = columns
+                .iter()
+                .find(|x| builder.schema().field_with_name(x).is_err())
+            {
+                return Err(ClientError::Internal(format!(
+                    "Column \"{missing}\" not found in Parquet file"
+                )));
+            }
+
+            let mask = ProjectionMask::columns(
+                builder.parquet_schema(),
+                columns.iter().map(String::as_str),
+            );
+
+            builder = builder.with_projection(mask);
+        }
+
+        builder
+            .with_batch_size(PARQUET_BATCH_ROWS)
+            .build()
+            .map_err(ClientError::external)
+    }
+
+    pub(crate) fn read_parquet(
+        data: Bytes,
+        columns: Option<&[String]>,
+    ) -> ClientResult<ArrowBatches> {
+        let reader = open_parquet(data, columns)?;
+        let schema = reader.schema();
+        Ok(Box::new(ParquetBatches {
+            reader,
+            schema,
+            started: false,
+        }))
+    }
+
+    pub(crate) fn read_parquet_to_arrow(
+        data: Bytes,
+        columns: Option<&[String]>,
+    ) -> ClientResult<Bytes> {
+        let reader = open_parquet(data, columns)?;
+        let mut buf = Vec::new();
+        let mut writer =
+            StreamWriter::try_new(&mut buf, &reader.schema()).map_err(ClientError::external)?;
+
+        for batch in reader {
+            writer
+                .write(&batch.map_err(ClientError::external)?)
+                .map_err(ClientError::external)?;
+        }
+
+        writer.finish().map_err(ClientError::external)?;
+        drop(writer);
+        Ok(buf.into())
+    }
+
+    pub(crate) fn write_parquet(
+        arrow: &[u8],
+        options: &ParquetWriteOptions,
+    ) -> ClientResult<Bytes> {
+        let reader =
+            StreamReader::try_new(Cursor::new(arrow), None).map_err(ClientError::external)?;
+        let compression = match options.compression.unwrap_or_default() {
+            ParquetCompression::Uncompressed => Compression::UNCOMPRESSED,
+            ParquetCompression::Snappy => Compression::SNAPPY,
+            ParquetCompression::Gzip => Compression::GZIP(GzipLevel::default()),
+            ParquetCompression::Lz4 => Compression::LZ4_RAW,
+            ParquetCompression::Zstd => Compression::ZSTD(ZstdLevel::default()),
+            ParquetCompression::Brotli => Compression::BROTLI(BrotliLevel::default()),
+        };
+
+        let mut props = WriterProperties::builder().set_compression(compression);
+        if let Some(row_group_size) = options.row_group_size {
+            props = props.set_max_row_group_size(row_group_size.max(1));
+        }
+
+        let mut buf = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut buf, reader.schema(), Some(props.build()))
+            .map_err(ClientError::external)?;
+
+        for batch in reader {
+            writer
+                .write(&batch.map_err(ClientError::external)?)
+                .map_err(ClientError::external)?;
+        }
+
+        writer.close().map_err(ClientError::external)?;
+        Ok(buf.into())
+    }
+}
+
+#[cfg(not(feature = "parquet"))]
+mod imp {
+    use prost::bytes::Bytes;
+
+    use super::*;
+    use crate::utils::ClientError;
+
+    pub(crate) fn read_parquet(
+        _data: Bytes,
+        _columns: Option<&[String]>,
+    ) -> ClientResult<ArrowBatches> {
+        Err(ClientError::NotImplemented("parquet"))
+    }
+
+    pub(crate) fn read_parquet_to_arrow(
+        _data: Bytes,
+        _columns: Option<&[String]>,
+    ) -> ClientResult<Bytes> {
+        Err(ClientError::NotImplemented("parquet"))
+    }
+
+    pub(crate) fn write_parquet(
+        _arrow: &[u8],
+        _options: &ParquetWriteOptions,
+    ) -> ClientResult<Bytes> {
+        Err(ClientError::NotImplemented("parquet"))
+    }
+}
+
+pub(crate) use imp::{read_parquet, read_parquet_to_arrow, write_parquet};
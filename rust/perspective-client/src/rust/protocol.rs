@@ -0,0 +1,171 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Protocol versioning and capability negotiation.
+//!
+//! A [`crate::Client`] and a `perspective_server::Server` (or
+//! [`crate::virtual_server::VirtualServer`]) exchange their protocol versions
+//! and [`Capabilities`] in the `GetFeaturesReq`/`GetFeaturesResp` handshake.
+//! [`PROTOCOL_VERSION`] must be incremented whenever a change to
+//! `perspective.proto` is not backwards compatible, and
+//! [`MIN_PROTOCOL_VERSION`] raised when support for an older version is
+//! dropped. Additive, optional changes should instead be described by a new
+//! [`Capabilities`] bit, so that a peer which lacks it can degrade gracefully.
+
+use std::fmt::Display;
+use std::ops::{BitAnd, BitOr};
+
+use crate::proto::GetFeaturesResp;
+use crate::utils::{ClientError, ClientResult};
+
+/// The protocol version implemented by this crate.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// The oldest protocol version this crate can interoperate with. Peers which
+/// predate the handshake report version `0`, which is always accepted (with
+/// no [`Capabilities`]).
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// A set of optional protocol features, negotiated as the intersection of
+/// the client's and server's sets.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Capabilities(u64);
+
+impl Capabilities {
+    /// Every capability this crate implements.
+    pub const ALL: Self = Self(
+        Self::TEMPORAL_SCALARS.0
+            | Self::JOIN_OPTIONS.0
+            | Self::UNION.0
+            | Self::STREAMING_EXPORT.0
+            | Self::UNNEST.0
+            | Self::SCHEMA_ONLY.0,
+    );
+    /// `MakeJoinTableReq` composite keys, suffixes, as-of joins and the
+    /// `RIGHT`, `SEMI` and `ANTI` join types.
+    pub const JOIN_OPTIONS: Self = Self(1 << 1);
+    /// `MakeTableOptions.schema_only`, for [`crate::Client::infer_schema`].
+    /// Without this, the schema is read from a temporary hosted `Table`.
+    pub const SCHEMA_ONLY: Self = Self(1 << 5);
+    /// `ViewToArrowStreamReq`, `ViewToCSVStreamReq` and `ViewStreamNextReq`,
+    /// for [`crate::View::to_arrow_stream`] and
+    /// [`crate::View::to_csv_stream`].
+    /// Without this, the export is sent as one chunk.
+    pub const STREAMING_EXPORT: Self = Self(1 << 3);
+    /// `Scalar.date` and `Scalar.datetime` can be encoded. Without this,
+    /// dates are sent as `"YYYY-MM-DD"` strings and datetimes as POSIX
+    /// milliseconds.
+    pub const TEMPORAL_SCALARS: Self = Self(1 << 0);
+    /// `MakeUnionTableReq`, for [`crate::Client::union`].
+    pub const UNION: Self = Self(1 << 2);
+    /// `MakeUnnestTableReq`, for [`crate::Client::unnest`].
+    pub const UNNEST: Self = Self(1 << 4);
+
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Create a [`Capabilities`] from its wire representation, ignoring bits
+    /// this crate does not know about.
+    pub const fn from_bits_truncate(bits: u64) -> Self {
+        Self(bits & Self::ALL.0)
+    }
+
+    pub const fn bits(&self) -> u64 {
+        self.0
+    }
+
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for Capabilities {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitAnd for Capabilities {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+/// The versions a peer reported in the handshake.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ProtocolVersion {
+    /// The version the peer implements, or `0` if it predates the handshake.
+    pub version: u32,
+
+    /// The oldest version the peer can interoperate with.
+    pub min_version: u32,
+}
+
+impl Display for ProtocolVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.version == 0 {
+            write!(f, "unversioned")
+        } else {
+            write!(f, "{} (min {})", self.version, self.min_version)
+        }
+    }
+}
+
+impl ProtocolVersion {
+    /// This crate's [`PROTOCOL_VERSION`] and [`MIN_PROTOCOL_VERSION`].
+    pub const fn current() -> Self {
+        Self {
+            version: PROTOCOL_VERSION,
+            min_version: MIN_PROTOCOL_VERSION,
+        }
+    }
+
+    /// Whether a peer reporting `self` can interoperate with one reporting
+    /// `other`. Unversioned peers are always compatible.
+    pub fn is_compatible(&self, other: &Self) -> bool {
+        self.version == 0
+            || other.version == 0
+            || (self.version >= other.min_version && other.version >= self.min_version)
+    }
+}
+
+impl GetFeaturesResp {
+    /// The server's protocol version from the handshake.
+    pub fn protocol(&self) -> ProtocolVersion {
+        ProtocolVersion {
+            version: self.protocol_version,
+            min_version: self.min_protocol_version,
+        }
+    }
+
+    /// The capabilities supported by both the server and this crate.
+    pub fn negotiated_capabilities(&self) -> Capabilities {
+        Capabilities::from_bits_truncate(self.capabilities)
+    }
+
+    /// Validate the server's protocol version against this crate's, returning
+    /// [`ClientError::IncompatibleServer`] if they cannot interoperate.
+    pub(crate) fn check_protocol(&self) -> ClientResult<()> {
+        let client = ProtocolVersion::current();
+        let server = self.protocol();
+        if client.is_compatible(&server) {
+            Ok(())
+        } else {
+            Err(ClientError::IncompatibleServer { client, server })
+        }
+    }
+}
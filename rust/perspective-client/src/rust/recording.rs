@@ -0,0 +1,159 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::io::{self, Read, Write};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use futures::Future;
+use futures::future::BoxFuture;
+use prost::Message;
+
+use crate::Session;
+use crate::proto::RecordedMessage;
+use crate::proto::recorded_message::Message as RecordedKind;
+#[cfg(doc)]
+use crate::{Client, ProxySession};
+
+struct SessionRecorderState {
+    writer: Mutex<Box<dyn Write + Send>>,
+    start: Instant,
+}
+
+/// Records the requests and responses of a [`Session`] to a [`Write`], as a
+/// log of length-delimited, timestamped [`RecordedMessage`]s which can be
+/// read back via [`read_recording`], e.g. to replay against a fresh
+/// `perspective_server::Server` via `perspective_server::Server::replay`.
+///
+/// Requests are recorded by wrapping the [`Session`] via
+/// [`SessionRecorder::wrap`], and responses by wrapping the [`Session`]'s
+/// response callback via [`SessionRecorder::wrap_callback`].
+///
+/// ```rust,ignore
+/// let recorder = SessionRecorder::new(File::create("session.log")?);
+/// let session = server
+///     .new_session_with_callback(recorder.wrap_callback(send_to_client))
+///     .await;
+///
+/// let session = recorder.wrap(session);
+/// ```
+#[derive(Clone)]
+pub struct SessionRecorder(Arc<SessionRecorderState>);
+
+impl SessionRecorder {
+    /// Create a new [`SessionRecorder`] which writes to `writer`. Writes
+    /// are unbuffered, so `writer` should generally be buffered, e.g. via
+    /// [`std::io::BufWriter`].
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self(Arc::new(SessionRecorderState {
+            writer: Mutex::new(Box::new(writer)),
+            start: Instant::now(),
+        }))
+    }
+
+    /// Wrap `session` such that its requests are recorded.
+    pub fn wrap<S>(&self, session: S) -> RecordingSession<S> {
+        RecordingSession {
+            session,
+            recorder: self.clone(),
+        }
+    }
+
+    /// Record a request sent to the [`Session`].
+    pub fn record_request(&self, request: &[u8]) {
+        self.record(RecordedKind::Request(request.to_vec()));
+    }
+
+    /// Wrap `send_response`, a [`Session`]'s response callback (e.g. for
+    /// `perspective_server::Server::new_session_with_callback`), such that
+    /// each response is recorded before it is sent.
+    pub fn wrap_callback<E, F>(
+        &self,
+        send_response: F,
+    ) -> impl for<'a> Fn(&'a [u8]) -> BoxFuture<'a, Result<(), E>> + Send + Sync + 'static
+    where
+        E: 'static,
+        F: for<'a> Fn(&'a [u8]) -> BoxFuture<'a, Result<(), E>> + Send + Sync + 'static,
+    {
+        let recorder = self.clone();
+        move |response| {
+            recorder.record(RecordedKind::Response(response.to_vec()));
+            send_response(response)
+        }
+    }
+
+    /// Flush the underlying writer.
+    pub fn flush(&self) -> io::Result<()> {
+        self.0.writer.lock().unwrap().flush()
+    }
+
+    fn record(&self, message: RecordedKind) {
+        let msg = RecordedMessage {
+            timestamp: self.0.start.elapsed().as_micros() as u64,
+            message: Some(message),
+        };
+
+        let mut writer = self.0.writer.lock().unwrap();
+        if let Err(e) = writer.write_all(&msg.encode_length_delimited_to_vec()) {
+            tracing::error!("Failed to record message: {}", e);
+        }
+    }
+}
+
+/// A [`Session`] which records its requests to a [`SessionRecorder`], see
+/// [`SessionRecorder::wrap`]. This can wrap any [`Session`], including a
+/// [`ProxySession`].
+pub struct RecordingSession<S> {
+    session: S,
+    recorder: SessionRecorder,
+}
+
+impl<S> RecordingSession<S> {
+    /// The wrapped [`Session`].
+    pub fn inner(&self) -> &S {
+        &self.session
+    }
+}
+
+impl<E, S: Session<E>> Session<E> for RecordingSession<S> {
+    fn handle_request(&self, request: &[u8]) -> impl Future<Output = Result<(), E>> {
+        self.recorder.record_request(request);
+        self.session.handle_request(request)
+    }
+
+    async fn close(self) {
+        self.session.close().await;
+        if let Err(e) = self.recorder.flush() {
+            tracing::error!("Failed to flush recording: {}", e);
+        }
+    }
+}
+
+/// Read a recording written by a [`SessionRecorder`]. A truncated final
+/// message, e.g. from a process which exited mid-write, is ignored.
+pub fn read_recording(mut reader: impl Read) -> io::Result<Vec<RecordedMessage>> {
+    let mut bytes = vec![];
+    reader.read_to_end(&mut bytes)?;
+    let mut buf = bytes.as_slice();
+    let mut messages = vec![];
+    while !buf.is_empty() {
+        match RecordedMessage::decode_length_delimited(&mut buf) {
+            Ok(msg) => messages.push(msg),
+            Err(e) => {
+                tracing::warn!("Ignoring truncated recording: {}", e);
+                break;
+            },
+        }
+    }
+
+    Ok(messages)
+}
@@ -19,11 +19,14 @@ use ts_rs::TS;
 use crate::assert_table_api;
 use crate::client::{Client, Features};
 use crate::config::{Expressions, ViewConfigUpdate};
+use crate::infer::apply_date_formats;
+use crate::parquet::read_parquet;
 use crate::proto::make_table_req::MakeTableOptions;
 use crate::proto::make_table_req::make_table_options::MakeTableType;
 use crate::proto::request::ClientReq;
 use crate::proto::response::ClientResp;
 use crate::proto::*;
+use crate::protocol::Capabilities;
 use crate::table_data::UpdateData;
 use crate::utils::*;
 use crate::view::View;
@@ -50,6 +53,9 @@ pub enum TableReadFormat {
 
     #[serde(rename = "ndjson")]
     Ndjson,
+
+    #[serde(rename = "parquet")]
+    Parquet,
 }
 
 impl TableReadFormat {
@@ -60,6 +66,7 @@ impl TableReadFormat {
             Some("columns") => Some(TableReadFormat::ColumnsString),
             Some("arrow") => Some(TableReadFormat::Arrow),
             Some("ndjson") => Some(TableReadFormat::Ndjson),
+            Some("parquet") => Some(TableReadFormat::Parquet),
             None => None,
             Some(x) => return Err(format!("Unknown format \"{x}\"")),
         })
@@ -99,6 +106,27 @@ pub struct TableInitOptions {
     #[serde(default)]
     #[ts(optional)]
     pub page_to_disk: Option<bool>,
+
+    /// Pin the types of these columns, rather than inferring them from the
+    /// input data. Columns not listed are inferred as usual. See
+    /// [`Client::infer_schema`] to find columns which need pinning.
+    #[serde(default)]
+    #[ts(optional)]
+    pub column_types: Option<HashMap<String, ColumnType>>,
+
+    /// Parse the CSV or JSON string values of these columns as dates with a
+    /// `strptime`-style format (e.g. `"%d/%m/%Y %H:%M"`), for formats the
+    /// engine would not otherwise recognize. Also applies to subsequent
+    /// [`Table::update`] calls from this [`Client`].
+    #[serde(default)]
+    #[ts(optional)]
+    pub date_formats: Option<HashMap<String, String>>,
+
+    /// Evict this [`Table`], or trim its rows, on the server, see
+    /// [`RetentionOptions`].
+    #[serde(default)]
+    #[ts(optional)]
+    pub retention: Option<RetentionOptions>,
 }
 
 impl TableInitOptions {
@@ -107,13 +135,72 @@ impl TableInitOptions {
     }
 }
 
+/// How long the server keeps a [`Table`] and its rows, for
+/// [`TableInitOptions::retention`]. Every policy is optional, and they may be
+/// combined.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, TS)]
+pub struct RetentionOptions {
+    /// Delete this [`Table`] once no [`Client`] has made a request on it, or
+    /// on any of its [`View`]s, for this many seconds. Deleting the [`Table`]
+    /// also deletes its [`View`]s, and fires their `on_delete` callbacks.
+    /// A [`Table`] with a [`View`] which has an `on_update` callback, or which
+    /// is the source of a join, union or unnest [`Table`], is never evicted.
+    #[serde(default)]
+    #[ts(optional)]
+    pub idle_timeout: Option<f64>,
+
+    /// Remove the _earliest_ inserted rows in excess of `max_rows`. Unlike
+    /// [`TableInitOptions::limit`], this may be combined with an `index`.
+    #[serde(default)]
+    #[ts(optional)]
+    pub max_rows: Option<u32>,
+
+    /// Remove rows whose `"datetime"` column `age_column` is more than
+    /// `max_age` seconds in the past. Both or neither must be set.
+    #[serde(default)]
+    #[ts(optional)]
+    pub age_column: Option<String>,
+
+    /// The age, in seconds, past which rows are removed for `age_column`.
+    #[serde(default)]
+    #[ts(optional)]
+    pub max_age: Option<f64>,
+}
+
+impl From<RetentionOptions> for TableRetention {
+    fn from(value: RetentionOptions) -> Self {
+        let millis = |secs: f64| (secs * 1000.0) as u64;
+        TableRetention {
+            idle_timeout_ms: value.idle_timeout.map(millis),
+            max_rows: value.max_rows,
+            age_column: value.age_column,
+            max_age_ms: value.max_age.map(millis),
+        }
+    }
+}
+
+impl From<TableRetention> for RetentionOptions {
+    fn from(value: TableRetention) -> Self {
+        let secs = |millis: u64| millis as f64 / 1000.0;
+        RetentionOptions {
+            idle_timeout: value.idle_timeout_ms.map(secs),
+            max_rows: value.max_rows,
+            age_column: value.age_column,
+            max_age: value.max_age_ms.map(secs),
+        }
+    }
+}
+
 impl TryFrom<TableOptions> for MakeTableOptions {
     type Error = ClientError;
 
     fn try_from(value: TableOptions) -> Result<Self, Self::Error> {
         let page_to_disk = value.page_to_disk;
+        let retention = value.retention.clone().map(TableRetention::from);
         Ok(MakeTableOptions {
             page_to_disk,
+            retention,
+            schema_only: None,
             make_table_type: match value {
                 TableOptions {
                     index: Some(_),
@@ -137,6 +224,8 @@ pub(crate) struct TableOptions {
     pub index: Option<String>,
     pub limit: Option<u32>,
     pub page_to_disk: Option<bool>,
+    pub date_formats: HashMap<String, String>,
+    pub retention: Option<RetentionOptions>,
 }
 
 impl From<TableInitOptions> for TableOptions {
@@ -145,10 +234,60 @@ impl From<TableInitOptions> for TableOptions {
             index: value.index,
             limit: value.limit,
             page_to_disk: value.page_to_disk,
+            date_formats: value.date_formats.unwrap_or_default(),
+            retention: value.retention,
         }
     }
 }
 
+/// The key column(s) of a [`Client::join`], either a single column name or a
+/// list of column names for a composite key.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS)]
+#[serde(untagged)]
+pub enum JoinKeys {
+    Column(String),
+    Columns(Vec<String>),
+}
+
+impl JoinKeys {
+    pub fn columns(&self) -> &[String] {
+        match self {
+            Self::Column(x) => std::slice::from_ref(x),
+            Self::Columns(x) => x.as_slice(),
+        }
+    }
+}
+
+impl From<&str> for JoinKeys {
+    fn from(value: &str) -> Self {
+        Self::Column(value.to_owned())
+    }
+}
+
+impl From<String> for JoinKeys {
+    fn from(value: String) -> Self {
+        Self::Column(value)
+    }
+}
+
+impl From<Vec<String>> for JoinKeys {
+    fn from(value: Vec<String>) -> Self {
+        Self::Columns(value)
+    }
+}
+
+impl From<&[&str]> for JoinKeys {
+    fn from(value: &[&str]) -> Self {
+        Self::Columns(value.iter().map(|x| (*x).to_owned()).collect())
+    }
+}
+
+impl<const N: usize> From<[&str; N]> for JoinKeys {
+    fn from(value: [&str; N]) -> Self {
+        value.as_slice().into()
+    }
+}
+
 /// Options for [`Client::join`].
 #[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
 pub struct JoinOptions {
@@ -160,9 +299,102 @@ pub struct JoinOptions {
     #[ts(optional)]
     pub name: Option<String>,
 
+    /// The right table's key column, if it is named differently than the
+    /// left table's.
     #[serde(default)]
     #[ts(optional)]
     pub right_on: Option<String>,
+
+    /// The right table's key columns for a composite key, if they are named
+    /// differently than the left table's. When set, `right_on` is ignored.
+    #[serde(default)]
+    #[ts(optional)]
+    pub right_on_columns: Option<Vec<String>>,
+
+    /// Appended to the names of left table columns which conflict with a
+    /// right table column. Without either suffix, conflicts are an error.
+    #[serde(default)]
+    #[ts(optional)]
+    pub left_suffix: Option<String>,
+
+    /// Appended to the names of right table columns which conflict with a
+    /// left table column.
+    #[serde(default)]
+    #[ts(optional)]
+    pub right_suffix: Option<String>,
+
+    /// Make this an as-of join on this sorted (typically `datetime`) column
+    /// of the left table, matching each left row to the right row with the
+    /// latest `right_as_of` value less than or equal to its own (among rows
+    /// with equal keys). Only `inner` and `left` join types are supported.
+    #[serde(default)]
+    #[ts(optional)]
+    pub as_of: Option<String>,
+
+    /// The right table's as-of column, if it is named differently than
+    /// `as_of`.
+    #[serde(default)]
+    #[ts(optional)]
+    pub right_as_of: Option<String>,
+}
+
+/// Options for [`Client::union`].
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+pub struct UnionOptions {
+    #[serde(default)]
+    #[ts(optional)]
+    pub name: Option<String>,
+
+    /// Add a `string` column with this name, holding the name of each row's
+    /// source table.
+    #[serde(default)]
+    #[ts(optional)]
+    pub source_column: Option<String>,
+}
+
+/// Options for [`Client::unnest`].
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+pub struct UnnestOptions {
+    #[serde(default)]
+    #[ts(optional)]
+    pub name: Option<String>,
+}
+
+impl JoinOptions {
+    /// Set [`JoinOptions::right_on`] for a single column key, or
+    /// [`JoinOptions::right_on_columns`] for a composite key.
+    pub fn with_right_on(mut self, right_on: JoinKeys) -> Self {
+        match right_on {
+            JoinKeys::Column(x) => self.right_on = Some(x),
+            JoinKeys::Columns(x) => self.right_on_columns = Some(x),
+        }
+
+        self
+    }
+
+    /// The right table's key columns, if they are named differently than
+    /// the left table's.
+    pub(crate) fn right_keys(&self) -> Option<JoinKeys> {
+        match (&self.right_on, &self.right_on_columns) {
+            (_, Some(x)) => Some(JoinKeys::Columns(x.clone())),
+            (Some(x), None) => Some(JoinKeys::Column(x.clone())),
+            (None, None) => None,
+        }
+    }
+
+    /// Whether these options require [`Capabilities::JOIN_OPTIONS`].
+    pub(crate) fn is_extended(&self, on: &JoinKeys) -> bool {
+        on.columns().len() != 1
+            || self.right_on_columns.as_ref().is_some_and(|x| x.len() != 1)
+            || !matches!(
+                self.join_type,
+                None | Some(JoinType::Inner | JoinType::Left | JoinType::Outer)
+            )
+            || self.left_suffix.is_some()
+            || self.right_suffix.is_some()
+            || self.as_of.is_some()
+            || self.right_as_of.is_some()
+    }
 }
 
 /// Options for [`Table::delete`].
@@ -276,6 +508,11 @@ impl Table {
         self.options.limit.as_ref().map(|limit| *limit)
     }
 
+    /// Returns the user-specified [`RetentionOptions`] for this table.
+    pub fn get_retention(&self) -> Option<&RetentionOptions> {
+        self.options.retention.as_ref()
+    }
+
     /// Returns the user-specified name for this table, or the auto-generated
     /// name if a name was not specified when the table was created.
     pub fn get_name(&self) -> &str {
@@ -381,6 +618,8 @@ impl Table {
     ///   JavaScript)
     /// - `"string"` - A [`String`] data type (encoded internally as a
     ///   _dictionary_)
+    /// - `"int64"` - A signed 64 bit integer
+    /// - `"duration"` - A millisecond-precision duration
     ///
     /// Note that all [`Table`] columns are _nullable_, regardless of the data
     /// type.
@@ -466,7 +705,7 @@ impl Table {
     /// ```
     pub async fn remove(&self, input: UpdateData) -> ClientResult<()> {
         let msg = self.client_message(ClientReq::TableRemoveReq(TableRemoveReq {
-            data: Some(input.into()),
+            data: Some(input.try_into()?),
         }));
 
         match self.client.oneshot(&msg).await? {
@@ -497,8 +736,24 @@ impl Table {
     /// # Ok(()) }
     /// ```
     pub async fn replace(&self, input: UpdateData) -> ClientResult<()> {
+        let input = apply_date_formats(input, &self.options.date_formats)?;
+        if let UpdateData::Parquet { data, columns } = input {
+            let mut batches = read_parquet(data, columns.as_deref())?;
+            let first = batches.next().ok_or(ClientError::Option)??;
+            self.replace_inner(UpdateData::Arrow(first)).await?;
+            for batch in batches {
+                self.update_inner(UpdateData::Arrow(batch?), 0).await?;
+            }
+
+            return Ok(());
+        }
+
+        self.replace_inner(input).await
+    }
+
+    async fn replace_inner(&self, input: UpdateData) -> ClientResult<()> {
         let msg = self.client_message(ClientReq::TableReplaceReq(TableReplaceReq {
-            data: Some(input.into()),
+            data: Some(input.try_into()?),
         }));
 
         match self.client.oneshot(&msg).await? {
@@ -535,9 +790,24 @@ impl Table {
     /// # Ok(()) }
     /// ```
     pub async fn update(&self, input: UpdateData, options: UpdateOptions) -> ClientResult<()> {
+        let port_id = options.port_id.unwrap_or(0);
+        let input = apply_date_formats(input, &self.options.date_formats)?;
+        if let UpdateData::Parquet { data, columns } = input {
+            for batch in read_parquet(data, columns.as_deref())? {
+                self.update_inner(UpdateData::Arrow(batch?), port_id)
+                    .await?;
+            }
+
+            return Ok(());
+        }
+
+        self.update_inner(input, port_id).await
+    }
+
+    async fn update_inner(&self, input: UpdateData, port_id: u32) -> ClientResult<()> {
         let msg = self.client_message(ClientReq::TableUpdateReq(TableUpdateReq {
-            data: Some(input.into()),
-            port_id: options.port_id.unwrap_or(0),
+            data: Some(input.try_into()?),
+            port_id,
         }));
 
         match self.client.oneshot(&msg).await? {
@@ -596,7 +866,26 @@ impl Table {
     ///     .await?;
     /// # Ok(()) }
     /// ```
-    pub async fn view(&self, config: Option<ViewConfigUpdate>) -> ClientResult<View> {
+    pub async fn view(&self, mut config: Option<ViewConfigUpdate>) -> ClientResult<View> {
+        let features = self.client.get_features().await?;
+        let is_nested = config
+            .as_ref()
+            .and_then(|x| x.filter_tree.as_ref())
+            .is_some_and(|x| !x.is_flat());
+
+        if is_nested && !features.nested_filters {
+            return Err(ClientError::NotImplemented("nested filters"));
+        }
+
+        if !features
+            .negotiated_capabilities()
+            .contains(Capabilities::TEMPORAL_SCALARS)
+        {
+            config
+                .iter_mut()
+                .for_each(|x| x.downgrade_temporal_scalars());
+        }
+
         let view_name = randid();
         let msg = Request {
             msg_id: self.client.gen_id(),
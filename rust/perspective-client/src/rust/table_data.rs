@@ -12,8 +12,10 @@
 
 use prost::bytes::Bytes;
 
+use crate::parquet::read_parquet_to_arrow;
 use crate::proto;
 use crate::proto::*;
+use crate::utils::ClientError;
 use crate::view::View;
 #[cfg(doc)]
 use crate::{Client, Table};
@@ -29,13 +31,21 @@ pub enum TableData {
 
 /// The possible formats of input data which [`Table::update`] may take as an
 /// argument.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum UpdateData {
     Csv(String),
     Arrow(Bytes),
     JsonRows(String),
     JsonColumns(String),
     Ndjson(String),
+
+    /// Apache Parquet file contents, decoded to Arrow record batches before
+    /// reaching the engine. `columns` optionally projects a subset of the
+    /// file's columns. Requires the `parquet` feature.
+    Parquet {
+        data: Bytes,
+        columns: Option<Vec<String>>,
+    },
 }
 
 impl From<UpdateData> for TableData {
@@ -44,10 +54,12 @@ impl From<UpdateData> for TableData {
     }
 }
 
-impl From<TableData> for proto::MakeTableData {
-    fn from(value: TableData) -> Self {
+impl TryFrom<TableData> for proto::MakeTableData {
+    type Error = ClientError;
+
+    fn try_from(value: TableData) -> Result<Self, Self::Error> {
         let data = match value {
-            TableData::Update(x) => return x.into(),
+            TableData::Update(x) => return x.try_into(),
             TableData::View(view) => make_table_data::Data::FromView(view.name),
             TableData::Schema(x) => make_table_data::Data::FromSchema(proto::Schema {
                 schema: x
@@ -60,20 +72,29 @@ impl From<TableData> for proto::MakeTableData {
             }),
         };
 
-        MakeTableData { data: Some(data) }
+        Ok(MakeTableData { data: Some(data) })
     }
 }
 
-impl From<UpdateData> for proto::MakeTableData {
-    fn from(value: UpdateData) -> Self {
+impl TryFrom<UpdateData> for proto::MakeTableData {
+    type Error = ClientError;
+
+    fn try_from(value: UpdateData) -> Result<Self, Self::Error> {
         let data = match value {
             UpdateData::Csv(x) => make_table_data::Data::FromCsv(x),
             UpdateData::Arrow(x) => make_table_data::Data::FromArrow(x.into()),
             UpdateData::JsonRows(x) => make_table_data::Data::FromRows(x),
             UpdateData::JsonColumns(x) => make_table_data::Data::FromCols(x),
             UpdateData::Ndjson(x) => make_table_data::Data::FromNdjson(x),
+
+            // [`Client::table`] and [`Table::update`] stream Parquet to the
+            // engine in batches instead, but otherwise the whole file is
+            // decoded to a single Arrow message.
+            UpdateData::Parquet { data, columns } => make_table_data::Data::FromArrow(
+                read_parquet_to_arrow(data, columns.as_deref())?.into(),
+            ),
         };
 
-        MakeTableData { data: Some(data) }
+        Ok(MakeTableData { data: Some(data) })
     }
 }
@@ -0,0 +1,569 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::io::Cursor;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use arrow_array::cast::AsArray;
+use arrow_array::{Array, RecordBatch, UInt32Array};
+use arrow_ipc::reader::StreamReader;
+use arrow_ipc::writer::StreamWriter;
+use arrow_schema::{DataType, SchemaRef};
+use async_lock::Mutex;
+use futures::channel::{mpsc, oneshot};
+use futures::future::{Either, select};
+use futures::{Future, SinkExt, Stream, StreamExt};
+use indexmap::IndexMap;
+use prost::bytes::Bytes;
+use serde::Serialize;
+
+use crate::table::{Table, UpdateOptions};
+use crate::table_data::UpdateData;
+use crate::utils::*;
+
+/// The number of [`TableWriterInput`] which may be queued for a
+/// [`TableWriterTask`] before [`TableWriter::write`] waits.
+const WRITER_QUEUE_LEN: usize = 1024;
+
+/// A single input to a [`TableWriter`].
+#[derive(Clone, Debug)]
+pub enum TableWriterInput {
+    /// One row, as a JSON object of column name to value.
+    Row(serde_json::Map<String, serde_json::Value>),
+
+    /// An Apache Arrow IPC stream of any number of rows.
+    Arrow(Bytes),
+}
+
+/// Options for [`TableWriter::new`].
+#[derive(Clone, Debug)]
+pub struct TableWriterOptions {
+    /// Flush once this many rows are buffered.
+    pub max_rows: usize,
+
+    /// Flush rows which have been buffered for this long, even if `max_rows`
+    /// has not been reached.
+    pub max_delay: Duration,
+
+    /// When the [`Table`] has an `index`, merge buffered rows with the same
+    /// index value into one row. Later values overwrite earlier ones, and
+    /// columns a later row omits keep their earlier value, just as
+    /// successive partial updates would. Arrow rows are merged by keeping the
+    /// last row for each index value. Rows with a missing or `null` index
+    /// value are never merged.
+    pub coalesce: bool,
+
+    /// The `port_id` to update, see [`UpdateOptions`].
+    pub port_id: Option<u32>,
+}
+
+impl Default for TableWriterOptions {
+    fn default() -> Self {
+        Self {
+            max_rows: 10_000,
+            max_delay: Duration::from_millis(50),
+            coalesce: true,
+            port_id: None,
+        }
+    }
+}
+
+/// Counters for a [`TableWriter`]. These are cumulative, so ingest rates can
+/// be derived by sampling [`TableWriter::stats`] periodically.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct TableWriterStats {
+    /// Rows passed to [`TableWriter::write`].
+    pub rows_received: u64,
+
+    /// Rows sent to the [`Table`], after coalescing.
+    pub rows_written: u64,
+
+    /// Rows merged into an earlier buffered row with the same index value.
+    pub rows_coalesced: u64,
+
+    /// [`Table::update`] calls made.
+    pub flushes: u64,
+
+    /// Rows currently buffered.
+    pub pending_rows: u64,
+}
+
+#[derive(Default)]
+struct WriterMetrics {
+    rows_received: AtomicU64,
+    rows_written: AtomicU64,
+    rows_coalesced: AtomicU64,
+    flushes: AtomicU64,
+    pending_rows: AtomicU64,
+}
+
+impl WriterMetrics {
+    fn stats(&self) -> TableWriterStats {
+        TableWriterStats {
+            rows_received: self.rows_received.load(Ordering::Relaxed),
+            rows_written: self.rows_written.load(Ordering::Relaxed),
+            rows_coalesced: self.rows_coalesced.load(Ordering::Relaxed),
+            flushes: self.flushes.load(Ordering::Relaxed),
+            pending_rows: self.pending_rows.load(Ordering::Relaxed),
+        }
+    }
+}
+
+type Row = serde_json::Map<String, serde_json::Value>;
+
+enum Message {
+    Row(Row),
+    Arrow(SchemaRef, Vec<RecordBatch>),
+    Flush(oneshot::Sender<ClientResult<()>>),
+}
+
+/// A handle for buffered writes to a [`Table`].
+///
+/// Rather than calling [`Table::update`] once per message, a [`TableWriter`]
+/// buffers rows and Arrow batches until `max_rows` or `max_delay` (see
+/// [`TableWriterOptions`]) is reached, then applies them as a single update.
+/// The buffering happens on a [`TableWriterTask`], which must be run for
+/// writes to make progress.
+///
+/// [`TableWriter`] is cheap to [`Clone`]. When every clone has been dropped,
+/// the [`TableWriterTask`] flushes whatever remains and completes.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use perspective_client::*;
+/// # async fn sleep(_: std::time::Duration) {}
+/// # async fn run(table: Table) -> Result<(), Box<dyn std::error::Error>> {
+/// let (writer, task) = TableWriter::new(table, TableWriterOptions::default());
+/// let write = async move {
+///     let row = serde_json::json!({"symbol": "AAPL", "price": 180.1});
+///     let row = row.as_object().unwrap().clone();
+///     writer.write(TableWriterInput::Row(row)).await
+/// };
+///
+/// // `sleep` is the runtime's timer, e.g. `tokio::time::sleep`. Dropping
+/// // `writer` at the end of `write` flushes and completes `task`.
+/// let (write, task) = futures::join!(write, task.run(sleep));
+/// write?;
+/// task?;
+/// # Ok(()) }
+/// ```
+#[derive(Clone)]
+pub struct TableWriter {
+    // Shared rather than cloned, as each `mpsc::Sender` clone is given its
+    // own slot in the queue, which would defeat `WRITER_QUEUE_LEN`.
+    sender: Arc<Mutex<mpsc::Sender<Message>>>,
+    metrics: Arc<WriterMetrics>,
+}
+
+/// The buffering half of a [`TableWriter`], created by [`TableWriter::new`].
+pub struct TableWriterTask {
+    table: Table,
+    options: TableWriterOptions,
+
+    /// The index column to coalesce rows by, if any.
+    index: Option<String>,
+    receiver: mpsc::Receiver<Message>,
+    metrics: Arc<WriterMetrics>,
+}
+
+impl TableWriter {
+    /// Create a [`TableWriter`] for `table`, and the [`TableWriterTask`]
+    /// which applies its writes.
+    pub fn new(table: Table, options: TableWriterOptions) -> (Self, TableWriterTask) {
+        let (sender, receiver) = mpsc::channel(WRITER_QUEUE_LEN);
+        let metrics = Arc::new(WriterMetrics::default());
+        let writer = TableWriter {
+            sender: Arc::new(Mutex::new(sender)),
+            metrics: metrics.clone(),
+        };
+
+        let index = table.get_index().filter(|_| options.coalesce);
+        let task = TableWriterTask {
+            table,
+            options,
+            index,
+            receiver,
+            metrics,
+        };
+
+        (writer, task)
+    }
+
+    /// Buffer `input` for the next flush. Waits only if the
+    /// [`TableWriterTask`] has fallen behind.
+    pub async fn write(&self, input: TableWriterInput) -> ClientResult<()> {
+        let message = match input {
+            TableWriterInput::Row(row) => {
+                self.metrics.rows_received.fetch_add(1, Ordering::Relaxed);
+                Message::Row(row)
+            },
+            TableWriterInput::Arrow(arrow) => {
+                let reader = StreamReader::try_new(Cursor::new(arrow), None)
+                    .map_err(ClientError::external)?;
+
+                let schema = reader.schema();
+                let batches = reader
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(ClientError::external)?;
+
+                let rows = batches.iter().map(|x| x.num_rows() as u64).sum();
+                self.metrics
+                    .rows_received
+                    .fetch_add(rows, Ordering::Relaxed);
+                Message::Arrow(schema, batches)
+            },
+        };
+
+        self.send(message).await
+    }
+
+    /// [`TableWriter::write`] every input from `input`, in order.
+    pub async fn write_all<S>(&self, input: S) -> ClientResult<()>
+    where
+        S: Stream<Item = TableWriterInput>,
+    {
+        futures::pin_mut!(input);
+        while let Some(input) = input.next().await {
+            self.write(input).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Apply everything buffered so far, resolving when the [`Table`] has
+    /// been updated.
+    pub async fn flush(&self) -> ClientResult<()> {
+        let (sender, receiver) = oneshot::channel();
+        self.send(Message::Flush(sender)).await?;
+        receiver.await.map_err(|_| Self::closed())?
+    }
+
+    /// Ingest counters for this writer, see [`TableWriterStats`].
+    pub fn stats(&self) -> TableWriterStats {
+        self.metrics.stats()
+    }
+
+    async fn send(&self, message: Message) -> ClientResult<()> {
+        self.sender
+            .lock()
+            .await
+            .send(message)
+            .await
+            .map_err(|_| Self::closed())
+    }
+
+    fn closed() -> ClientError {
+        ClientError::Unknown("TableWriterTask is not running".to_owned())
+    }
+}
+
+impl TableWriterTask {
+    /// Apply writes until every [`TableWriter`] has been dropped, then flush
+    /// and return. `sleep` is the async runtime's timer, e.g.
+    /// `tokio::time::sleep`, and is used to enforce
+    /// [`TableWriterOptions::max_delay`].
+    ///
+    /// If an update fails, the error is returned (and reported to any pending
+    /// [`TableWriter::flush`]) and subsequent writes fail.
+    pub async fn run<F, U>(mut self, sleep: F) -> ClientResult<()>
+    where
+        F: Fn(Duration) -> U,
+        U: Future<Output = ()>,
+    {
+        let mut pending = Pending::default();
+        let mut timer: Option<Pin<Box<U>>> = None;
+        loop {
+            if pending.is_empty() {
+                timer = None;
+            } else if timer.is_none() {
+                timer = Some(Box::pin(sleep(self.options.max_delay)));
+            }
+
+            // `None` when `max_delay` has elapsed.
+            let next = match timer.as_mut() {
+                Some(timer) => match select(self.receiver.next(), timer.as_mut()).await {
+                    Either::Left((next, _)) => Some(next),
+                    Either::Right(_) => None,
+                },
+                None => Some(self.receiver.next().await),
+            };
+
+            let Some(next) = next else {
+                self.flush(&mut pending).await?;
+                continue;
+            };
+
+            match next {
+                Some(Message::Row(row)) => {
+                    if !matches!(pending, Pending::Empty | Pending::Rows(_)) {
+                        self.flush(&mut pending).await?;
+                    }
+
+                    let coalesced = pending.push_row(row, self.index.as_deref());
+                    if coalesced {
+                        self.metrics.rows_coalesced.fetch_add(1, Ordering::Relaxed);
+                    }
+                },
+                Some(Message::Arrow(schema, batches)) => {
+                    let compatible = match &pending {
+                        Pending::Empty => true,
+                        Pending::Arrow(pending_schema, _) => *pending_schema == schema,
+                        Pending::Rows(_) => false,
+                    };
+
+                    if !compatible {
+                        self.flush(&mut pending).await?;
+                    }
+
+                    pending.push_arrow(schema, batches);
+                },
+                Some(Message::Flush(sender)) => {
+                    let result = self.flush(&mut pending).await;
+                    let _ = sender.send(result.clone());
+                    result?;
+                    continue;
+                },
+                None => return self.flush(&mut pending).await,
+            }
+
+            let rows = pending.num_rows();
+            self.metrics
+                .pending_rows
+                .store(rows as u64, Ordering::Relaxed);
+
+            if rows >= self.options.max_rows {
+                self.flush(&mut pending).await?;
+            }
+        }
+    }
+
+    async fn flush(&self, pending: &mut Pending) -> ClientResult<()> {
+        let mut rows = pending.num_rows() as u64;
+        let data = match std::mem::take(pending) {
+            Pending::Empty => return Ok(()),
+            Pending::Rows(rows) => {
+                let rows = rows.into_values().collect::<Vec<_>>();
+                UpdateData::JsonRows(serde_json::to_string(&rows).map_err(ClientError::external)?)
+            },
+            Pending::Arrow(schema, batches) => {
+                let mut batch = arrow_select::concat::concat_batches(&schema, &batches)
+                    .map_err(ClientError::external)?;
+
+                if let Some(index) = self.index.as_deref() {
+                    let (coalesced, removed) = coalesce_arrow(batch, index)?;
+                    batch = coalesced;
+                    rows -= removed as u64;
+                    self.metrics
+                        .rows_coalesced
+                        .fetch_add(removed as u64, Ordering::Relaxed);
+                }
+
+                let mut arrow = Vec::new();
+                let mut writer =
+                    StreamWriter::try_new(&mut arrow, &schema).map_err(ClientError::external)?;
+
+                writer.write(&batch).map_err(ClientError::external)?;
+                writer.finish().map_err(ClientError::external)?;
+                drop(writer);
+                UpdateData::Arrow(arrow.into())
+            },
+        };
+
+        self.metrics.pending_rows.store(0, Ordering::Relaxed);
+        let options = UpdateOptions {
+            port_id: self.options.port_id,
+            ..UpdateOptions::default()
+        };
+
+        self.table.update(data, options).await?;
+        self.metrics.rows_written.fetch_add(rows, Ordering::Relaxed);
+        self.metrics.flushes.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+/// Buffered input. Rows and Arrow batches (or Arrow batches of different
+/// schemas) can't share an update, so switching between them flushes.
+#[derive(Default)]
+enum Pending {
+    #[default]
+    Empty,
+
+    /// Rows, keyed by their index value when coalescing and the row has one,
+    /// or by arrival order otherwise.
+    Rows(IndexMap<String, Row>),
+
+    Arrow(SchemaRef, Vec<RecordBatch>),
+}
+
+impl Pending {
+    fn is_empty(&self) -> bool {
+        matches!(self, Pending::Empty)
+    }
+
+    fn num_rows(&self) -> usize {
+        match self {
+            Pending::Empty => 0,
+            Pending::Rows(rows) => rows.len(),
+            Pending::Arrow(_, batches) => batches.iter().map(|x| x.num_rows()).sum(),
+        }
+    }
+
+    /// Buffer `row`, returning `true` if it was merged into an existing row.
+    fn push_row(&mut self, row: Row, index: Option<&str>) -> bool {
+        if self.is_empty() {
+            *self = Pending::Rows(IndexMap::new());
+        }
+
+        let Pending::Rows(rows) = self else {
+            unreachable!("flushed before push")
+        };
+
+        // Rows without an index value are kept apart, under a key which no
+        // JSON value serializes to.
+        let key = index
+            .and_then(|index| row.get(index))
+            .filter(|x| !x.is_null())
+            .map(|x| x.to_string())
+            .unwrap_or_else(|| format!("#{}", rows.len()));
+
+        match rows.get_mut(&key) {
+            Some(existing) => {
+                existing.extend(row);
+                true
+            },
+            None => {
+                rows.insert(key, row);
+                false
+            },
+        }
+    }
+
+    fn push_arrow(&mut self, schema: SchemaRef, mut batches: Vec<RecordBatch>) {
+        match self {
+            Pending::Arrow(_, pending) => pending.append(&mut batches),
+            _ => *self = Pending::Arrow(schema, batches),
+        }
+    }
+}
+
+/// A hashable value of an Arrow index column, see [`index_keys`].
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+enum IndexKey<'a> {
+    /// The bytes of a fixed width value, zero padded.
+    Fixed([u8; 16]),
+    Str(&'a str),
+}
+
+/// The index value of each row of `column`, `None` for `null`s, or `None`
+/// altogether if rows can't be compared by `column`'s type.
+fn index_keys(column: &dyn Array) -> Option<Vec<Option<IndexKey<'_>>>> {
+    let valid = |i| column.is_valid(i);
+    let keys = match column.data_type() {
+        DataType::Utf8 => {
+            let array = column.as_string::<i32>();
+            (0..array.len())
+                .map(|i| valid(i).then(|| IndexKey::Str(array.value(i))))
+                .collect()
+        },
+        DataType::LargeUtf8 => {
+            let array = column.as_string::<i64>();
+            (0..array.len())
+                .map(|i| valid(i).then(|| IndexKey::Str(array.value(i))))
+                .collect()
+        },
+        DataType::Utf8View => {
+            let array = column.as_string_view();
+            (0..array.len())
+                .map(|i| valid(i).then(|| IndexKey::Str(array.value(i))))
+                .collect()
+        },
+        DataType::Boolean => {
+            let array = column.as_boolean();
+            (0..array.len())
+                .map(|i| valid(i).then(|| IndexKey::Fixed([array.value(i) as u8; 16])))
+                .collect()
+        },
+        DataType::Dictionary(..) => {
+            let array = column.as_any_dictionary();
+            let values = index_keys(array.values().as_ref())?;
+            array
+                .normalized_keys()
+                .into_iter()
+                .enumerate()
+                .map(|(i, key)| values.get(key).copied().flatten().filter(|_| valid(i)))
+                .collect()
+        },
+        data_type => {
+            let width = data_type.primitive_width().filter(|x| *x <= 16)?;
+            let data = column.to_data();
+            let bytes = &data.buffers()[0].as_slice()[data.offset() * width..];
+            (0..column.len())
+                .map(|i| {
+                    valid(i).then(|| {
+                        let mut key = [0; 16];
+                        key[..width].copy_from_slice(&bytes[i * width..(i + 1) * width]);
+                        IndexKey::Fixed(key)
+                    })
+                })
+                .collect()
+        },
+    };
+
+    Some(keys)
+}
+
+/// Keep only the last row of `batch` for each value of its `index` column,
+/// at the position of the first, returning the number of rows removed. As
+/// with [`Pending::push_row`], rows with a `null` index are never merged.
+fn coalesce_arrow(batch: RecordBatch, index: &str) -> ClientResult<(RecordBatch, usize)> {
+    let indices = {
+        let Some(keys) = batch
+            .column_by_name(index)
+            .and_then(|x| index_keys(x.as_ref()))
+        else {
+            return Ok((batch, 0));
+        };
+
+        let mut positions = HashMap::new();
+        let mut indices = Vec::with_capacity(keys.len());
+        for (row, key) in keys.into_iter().enumerate() {
+            let row = row as u32;
+            match key.map(|key| positions.entry(key)) {
+                Some(Entry::Occupied(position)) => indices[*position.get()] = row,
+                Some(Entry::Vacant(position)) => {
+                    position.insert(indices.len());
+                    indices.push(row);
+                },
+                None => indices.push(row),
+            }
+        }
+
+        indices
+    };
+
+    let removed = batch.num_rows() - indices.len();
+    if removed == 0 {
+        return Ok((batch, 0));
+    }
+
+    let batch = arrow_select::take::take_record_batch(&batch, &UInt32Array::from(indices))
+        .map_err(ClientError::external)?;
+
+    Ok((batch, removed))
+}
@@ -17,8 +17,8 @@ use crate::proto::request::ClientReq;
 use crate::proto::response::ClientResp;
 use crate::proto::{
     MakeTableData, MakeTableReq, Request, Response, TableUpdateReq, ViewOnUpdateResp,
-    ViewToArrowResp, ViewToColumnsStringResp, ViewToCsvResp, ViewToNdjsonStringResp,
-    ViewToRowsStringResp,
+    ViewToArrowResp, ViewToArrowStreamResp, ViewToColumnsStringResp, ViewToCsvResp,
+    ViewToCsvStreamResp, ViewToNdjsonStringResp, ViewToRowsStringResp,
 };
 
 fn replace(x: Data) -> Data {
@@ -138,6 +138,27 @@ impl std::fmt::Display for Response {
                 })),
                 ..msg.clone()
             },
+            Response {
+                client_resp:
+                    Some(ClientResp::ViewToArrowStreamResp(ViewToArrowStreamResp { done, .. })),
+                ..
+            } => Response {
+                client_resp: Some(ClientResp::ViewToArrowStreamResp(ViewToArrowStreamResp {
+                    arrow: vec![],
+                    done,
+                })),
+                ..msg.clone()
+            },
+            Response {
+                client_resp: Some(ClientResp::ViewToCsvStreamResp(ViewToCsvStreamResp { done, .. })),
+                ..
+            } => Response {
+                client_resp: Some(ClientResp::ViewToCsvStreamResp(ViewToCsvStreamResp {
+                    csv: "<< redacted >>".to_owned(),
+                    done,
+                })),
+                ..msg.clone()
+            },
             Response {
                 client_resp: Some(ClientResp::ViewOnUpdateResp(ref x)),
                 ..
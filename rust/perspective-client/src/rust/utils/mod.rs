@@ -45,6 +45,12 @@ pub enum ClientError {
     #[error("Transport error: {0}")]
     TransportError(String),
 
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
+    #[error("Resource exhausted: {0}")]
+    ResourceExhausted(String),
+
     #[error("Client not yet initialized")]
     NotInitialized,
 
@@ -66,6 +72,12 @@ pub enum ClientError {
     #[error("Unexpected response {0:?}")]
     ResponseFailed(Box<proto::response::ClientResp>),
 
+    #[error("Incompatible server protocol {server}, client protocol is {client}")]
+    IncompatibleServer {
+        client: crate::protocol::ProtocolVersion,
+        server: crate::protocol::ProtocolVersion,
+    },
+
     #[error("Not yet implemented {0:?}")]
     NotImplemented(&'static str),
 
@@ -93,6 +105,14 @@ impl From<Box<dyn std::error::Error + Send + Sync>> for ClientError {
     }
 }
 
+impl ClientError {
+    /// Wrap an error from a dependency (Arrow, Parquet, `serde_json`) as a
+    /// [`ClientError::ExternalError`].
+    pub(crate) fn external<E: std::error::Error + Send + Sync + 'static>(err: E) -> Self {
+        ClientError::ExternalError(Arc::new(Box::new(err)))
+    }
+}
+
 impl<'a, A> From<std::sync::PoisonError<std::sync::MutexGuard<'a, A>>> for ClientError {
     fn from(_: std::sync::PoisonError<std::sync::MutexGuard<'a, A>>) -> Self {
         ClientError::Internal("Lock Error".to_owned())
@@ -106,6 +126,8 @@ impl From<Option<proto::response::ClientResp>> for ClientError {
                 proto::StatusCode::ServerError => ClientError::Internal(x.message),
                 proto::StatusCode::ViewNotFound => ClientError::ViewNotFound,
                 proto::StatusCode::TransportError => ClientError::TransportError(x.message),
+                proto::StatusCode::PermissionDenied => ClientError::PermissionDenied(x.message),
+                proto::StatusCode::ResourceExhausted => ClientError::ResourceExhausted(x.message),
             },
             Some(x) => ClientError::ResponseFailed(Box::new(x)),
             None => ClientError::ResponseAborted,
@@ -120,6 +142,8 @@ impl From<proto::response::ClientResp> for ClientError {
                 proto::StatusCode::ServerError => ClientError::Internal(x.message),
                 proto::StatusCode::ViewNotFound => ClientError::ViewNotFound,
                 proto::StatusCode::TransportError => ClientError::TransportError(x.message),
+                proto::StatusCode::PermissionDenied => ClientError::PermissionDenied(x.message),
+                proto::StatusCode::ResourceExhausted => ClientError::ResourceExhausted(x.message),
             },
             x => ClientError::ResponseFailed(Box::new(x)),
         }
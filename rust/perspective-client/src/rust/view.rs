@@ -14,8 +14,10 @@ use std::collections::HashMap;
 use std::ops::Deref;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use futures::Future;
+use futures::stream::BoxStream;
+use futures::{Future, StreamExt, TryStreamExt};
 use prost::bytes::Bytes;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
@@ -23,9 +25,11 @@ use ts_rs::TS;
 use self::view_on_update_req::Mode;
 use crate::assert_view_api;
 use crate::client::Client;
+use crate::parquet::{ParquetWriteOptions, write_parquet};
 use crate::proto::request::ClientReq;
 use crate::proto::response::ClientResp;
 use crate::proto::*;
+use crate::protocol::Capabilities;
 #[cfg(doc)]
 use crate::table::Table;
 pub use crate::utils::*;
@@ -70,6 +74,21 @@ pub struct ColumnWindow {
     pub end_col: Option<f32>,
 }
 
+/// The Arrow IPC body compression codecs [`ViewWindow::compression`] accepts.
+pub(crate) const ARROW_COMPRESSION: [&str; 3] = ["uncompressed", "lz4", "zstd"];
+
+fn check_arrow_compression(window: &ViewWindow) -> ClientResult<()> {
+    match &window.compression {
+        Some(compression) if !ARROW_COMPRESSION.contains(&compression.as_str()) => {
+            Err(ClientError::Unknown(format!(
+                "Unknown Arrow compression \"{compression}\", expected one of \
+                 {ARROW_COMPRESSION:?}"
+            )))
+        },
+        _ => Ok(()),
+    }
+}
+
 /// Options for serializing a window of data from a [`View`].
 ///
 /// Some fields of [`ViewWindow`] are only applicable to specific methods of
@@ -105,11 +124,19 @@ pub struct ViewWindow {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub formatted: Option<bool>,
 
-    /// Only impacts [`View::to_arrow`]
+    /// Only impacts [`View::to_arrow`], the Arrow IPC body compression codec.
+    /// One of `"lz4"` (LZ4 frame), `"zstd"` or `"uncompressed"` (the
+    /// default). Compressed Arrow can be passed to [`Table::update`] as-is.
     #[ts(optional)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub compression: Option<String>,
 
+    /// Only impacts [`View::to_arrow_stream`] and [`View::to_csv_stream`], the
+    /// maximum rows per chunk. Defaults to `65536`.
+    #[ts(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunk_rows: Option<u32>,
+
     /// When `true`, group-by columns use legacy `"colname (Group by N)"`
     /// naming. When `false`, they use `__ROW_PATH_N__` naming consistent
     /// with the SQL backend. Defaults to `true` for backwards compatibility.
@@ -118,6 +145,9 @@ pub struct ViewWindow {
     pub emit_legacy_row_path_names: Option<bool>,
 }
 
+/// `formatted` and `compression` are not properties of the viewport, and are
+/// sent on the requests they apply to (e.g. [`ViewToArrowReq::compression`])
+/// instead.
 impl From<ViewWindow> for ViewPort {
     fn from(window: ViewWindow) -> Self {
         ViewPort {
@@ -351,7 +381,11 @@ impl View {
     }
 
     /// A copy of the [`ViewConfig`] object passed to the [`Table::view`] method
-    /// which created this [`View`].
+    /// which created this [`View`]. Date and datetime filter terms are
+    /// returned as [`crate::config::Scalar::Date`] and
+    /// [`crate::config::Scalar::Datetime`], see
+    /// [`crate::config::ViewConfig::downgrade_temporal_scalars`] for the
+    /// encoding the language bindings return.
     pub async fn get_config(&self) -> ClientResult<crate::config::ViewConfig> {
         let msg = self.client_message(ClientReq::ViewGetConfigReq(ViewGetConfigReq {}));
         match self.client.oneshot(&msg).await? {
@@ -391,8 +425,10 @@ impl View {
         }
     }
 
-    /// Serializes a [`View`] to the Apache Arrow data format.
+    /// Serializes a [`View`] to the Apache Arrow data format, compressed
+    /// with the [`ViewWindow::compression`] codec if set.
     pub async fn to_arrow(&self, window: ViewWindow) -> ClientResult<Bytes> {
+        check_arrow_compression(&window)?;
         let msg = self.client_message(ClientReq::ViewToArrowReq(ViewToArrowReq {
             viewport: Some(window.clone().into()),
             compression: window.compression,
@@ -404,6 +440,64 @@ impl View {
         }
     }
 
+    /// Serializes a [`View`] to the Apache Arrow format as a stream of
+    /// chunks of at most [`ViewWindow::chunk_rows`] rows each, so a large
+    /// export is never held in one message. Concatenated, the chunks are a
+    /// single Arrow IPC stream, equivalent to [`View::to_arrow`].
+    ///
+    /// Each chunk is requested from the server when the stream is polled for
+    /// it, so neither the server nor the [`Client`] ever holds more than one
+    /// chunk of the export. The chunks are read from the [`View`] as it is
+    /// when each is requested, so if the [`View`] updates mid-export, later
+    /// chunks reflect the update (rows may shift between chunks); use
+    /// [`View::to_arrow`] for a consistent snapshot. Servers which don't
+    /// support streaming send the whole export as one chunk.
+    pub async fn to_arrow_stream(
+        &self,
+        window: ViewWindow,
+    ) -> ClientResult<BoxStream<'static, ClientResult<Bytes>>> {
+        check_arrow_compression(&window)?;
+        if !self.supports_streaming_export().await? {
+            let arrow = self.to_arrow(window).await?;
+            return Ok(futures::stream::once(async { Ok(arrow) }).boxed());
+        }
+
+        let msg = self.client_message(ClientReq::ViewToArrowStreamReq(ViewToArrowStreamReq {
+            viewport: Some(window.clone().into()),
+            compression: window.compression,
+            chunk_rows: window.chunk_rows,
+        }));
+
+        let is_done =
+            |resp: &ClientResp| !matches!(resp, ClientResp::ViewToArrowStreamResp(x) if !x.done);
+
+        let stream = self.client.stream(&msg, is_done).await?;
+        Ok(stream
+            .map(|resp| match resp? {
+                ClientResp::ViewToArrowStreamResp(ViewToArrowStreamResp { arrow, .. }) => {
+                    Ok(Bytes::from(arrow))
+                },
+                resp => Err(resp.into()),
+            })
+            .boxed())
+    }
+
+    /// Serializes a [`View`] to the Apache Parquet format, by way of
+    /// [`View::to_arrow`]. Requires the `parquet` feature.
+    pub async fn to_parquet(
+        &self,
+        window: ViewWindow,
+        options: ParquetWriteOptions,
+    ) -> ClientResult<Bytes> {
+        let window = ViewWindow {
+            compression: None,
+            ..window
+        };
+
+        let arrow = self.to_arrow(window).await?;
+        write_parquet(&arrow, &options)
+    }
+
     /// Serializes this [`View`] to a string of JSON data. Useful if you want to
     /// save additional round trip serialize/deserialize cycles.    
     pub async fn to_columns_string(&self, window: ViewWindow) -> ClientResult<String> {
@@ -471,6 +565,46 @@ impl View {
         }
     }
 
+    /// Serializes this [`View`] to CSV as a stream of chunks of at most
+    /// [`ViewWindow::chunk_rows`] rows each, as [`View::to_arrow_stream`].
+    /// Only the first chunk has the header row, so concatenated, the chunks
+    /// are equivalent to [`View::to_csv`].
+    pub async fn to_csv_stream(
+        &self,
+        window: ViewWindow,
+    ) -> ClientResult<BoxStream<'static, ClientResult<String>>> {
+        if !self.supports_streaming_export().await? {
+            let csv = self.to_csv(window).await?;
+            return Ok(futures::stream::once(async { Ok(csv) }).boxed());
+        }
+
+        let msg = self.client_message(ClientReq::ViewToCsvStreamReq(ViewToCsvStreamReq {
+            viewport: Some(window.clone().into()),
+            chunk_rows: window.chunk_rows,
+        }));
+
+        let is_done =
+            |resp: &ClientResp| !matches!(resp, ClientResp::ViewToCsvStreamResp(x) if !x.done);
+
+        let stream = self.client.stream(&msg, is_done).await?;
+        Ok(stream
+            .map(|resp| match resp? {
+                ClientResp::ViewToCsvStreamResp(ViewToCsvStreamResp { csv, .. }) => Ok(csv),
+                resp => Err(resp.into()),
+            })
+            .try_filter(|csv| futures::future::ready(!csv.is_empty()))
+            .boxed())
+    }
+
+    async fn supports_streaming_export(&self) -> ClientResult<bool> {
+        Ok(self
+            .client
+            .get_features()
+            .await?
+            .negotiated_capabilities()
+            .contains(Capabilities::STREAMING_EXPORT))
+    }
+
     /// Delete this [`View`] and clean up all resources associated with it.
     /// [`View`] objects do not stop consuming resources or processing
     /// updates when they are garbage collected - you must call this method
@@ -548,6 +682,65 @@ impl View {
         Ok(msg.msg_id)
     }
 
+    /// Register an `on_update` callback as [`View::on_update`] does, and
+    /// serialize this [`View`] to Arrow as [`View::to_arrow`] does. Every
+    /// update is reflected in exactly one of the returned Arrow or the
+    /// `on_update` callback, which makes this suitable for mirroring a
+    /// [`View`] elsewhere.
+    ///
+    /// Returns the Arrow and the callback `id` for [`View::remove_update`].
+    pub async fn to_arrow_and_on_update<T, U>(
+        &self,
+        on_update: T,
+        options: OnUpdateOptions,
+    ) -> ClientResult<(Bytes, u32)>
+    where
+        T: Fn(OnUpdateData) -> U + Send + Sync + 'static,
+        U: Future<Output = ()> + Send + 'static,
+    {
+        // Responses are handled in the order the server sends them, so
+        // updates which arrive before the Arrow are already reflected in it.
+        let has_arrow = Arc::new(AtomicBool::new(false));
+        let is_live = has_arrow.clone();
+        let callback = move |data| {
+            let update = is_live.load(Ordering::Acquire).then(|| on_update(data));
+            async move {
+                if let Some(update) = update {
+                    update.await;
+                }
+            }
+        };
+
+        let update_id = self.on_update(callback, options).await?;
+        let msg = self.client_message(ClientReq::ViewToArrowReq(ViewToArrowReq {
+            viewport: Some(ViewWindow::default().into()),
+            compression: None,
+        }));
+
+        let (sender, receiver) = futures::channel::oneshot::channel::<ClientResp>();
+        let on_arrow = Box::new(move |res: Response| {
+            has_arrow.store(true, Ordering::Release);
+            sender.send(res.client_resp.unwrap()).map_err(|x| x.into())
+        });
+
+        let result = match self.client.subscribe_once(&msg, on_arrow).await {
+            Ok(()) => receiver
+                .await
+                .map_err(|_| ClientError::Unknown(format!("Internal error for req {msg}"))),
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(ClientResp::ViewToArrowResp(ViewToArrowResp { arrow })) => {
+                Ok((arrow.into(), update_id))
+            },
+            result => {
+                self.remove_update(update_id).await?;
+                Err(result.map_or_else(|e| e, |resp| resp.into()))
+            },
+        }
+    }
+
     /// Unregister a previously registered update callback with this [`View`].
     ///
     /// # Arguments
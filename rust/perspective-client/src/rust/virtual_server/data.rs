@@ -14,21 +14,23 @@ use std::error::Error;
 use std::sync::Arc;
 
 use arrow_array::builder::{
-    BooleanBuilder, Float64Builder, Int32Builder, StringDictionaryBuilder,
-    TimestampMillisecondBuilder,
+    BooleanBuilder, DurationMillisecondBuilder, Float64Builder, Int32Builder, Int64Builder,
+    StringDictionaryBuilder, TimestampMillisecondBuilder,
 };
 use arrow_array::cast::AsArray;
 use arrow_array::types::Int32Type;
 use arrow_array::{
     Array, ArrayAccessor, ArrayRef, BooleanArray, Date32Array, Date64Array, Decimal128Array,
-    Float32Array, Float64Array, Int8Array, Int16Array, Int32Array, Int64Array, LargeStringArray,
-    RecordBatch, StringArray, Time32MillisecondArray, Time32SecondArray, Time64MicrosecondArray,
-    Time64NanosecondArray, TimestampMicrosecondArray, TimestampMillisecondArray,
-    TimestampNanosecondArray, TimestampSecondArray, UInt8Array, UInt16Array, UInt32Array,
-    UInt64Array,
+    DurationMicrosecondArray, DurationMillisecondArray, DurationNanosecondArray,
+    DurationSecondArray, Float32Array, Float64Array, Int8Array, Int16Array, Int32Array, Int64Array,
+    LargeStringArray, RecordBatch, StringArray, Time32MillisecondArray, Time32SecondArray,
+    Time64MicrosecondArray, Time64NanosecondArray, TimestampMicrosecondArray,
+    TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray, UInt8Array,
+    UInt16Array, UInt32Array, UInt64Array,
 };
+use arrow_ipc::CompressionType;
 use arrow_ipc::reader::{FileReader, StreamReader};
-use arrow_ipc::writer::StreamWriter;
+use arrow_ipc::writer::{IpcWriteOptions, StreamWriter};
 use arrow_schema::{DataType, Field, Schema, TimeUnit};
 use indexmap::IndexMap;
 use serde::Serialize;
@@ -43,12 +45,29 @@ pub enum ColumnBuilder {
     Float(Float64Builder),
     Integer(Int32Builder),
     Datetime(TimestampMillisecondBuilder),
+    Int64(Int64Builder),
+    Duration(DurationMillisecondBuilder),
 }
 
 fn dict_data_type() -> DataType {
     DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
 }
 
+/// The Arrow IPC write options for the body compression codec named by
+/// `compression` (as [`crate::ViewWindow::compression`]).
+pub(crate) fn arrow_write_options(
+    compression: Option<&str>,
+) -> Result<IpcWriteOptions, Box<dyn Error>> {
+    let compression = match compression {
+        None | Some("") | Some("uncompressed") => None,
+        Some("lz4") => Some(CompressionType::LZ4_FRAME),
+        Some("zstd") => Some(CompressionType::ZSTD),
+        Some(x) => return Err(format!("Unknown Arrow compression \"{x}\"").into()),
+    };
+
+    Ok(IpcWriteOptions::default().try_with_compression(compression)?)
+}
+
 /// A single cell value in a row-oriented data representation.
 ///
 /// Used when converting [`VirtualDataSlice`] to row format for JSON
@@ -61,6 +80,7 @@ pub enum VirtualDataCell {
     Float(Option<f64>),
     Integer(Option<i32>),
     Datetime(Option<i64>),
+    Int64(Option<i64>),
     RowPath(Vec<Scalar>),
 }
 
@@ -92,7 +112,7 @@ pub trait SetVirtualDataColumn {
 
     /// Creates a new empty column builder of the appropriate type for this
     /// value.
-    fn new_builder() -> ColumnBuilder;
+    fn new_builder(&self) -> ColumnBuilder;
 
     /// Converts this value to a [`Scalar`] representation.
     fn to_scalar(self) -> Scalar;
@@ -111,7 +131,7 @@ impl SetVirtualDataColumn for Option<String> {
         }
     }
 
-    fn new_builder() -> ColumnBuilder {
+    fn new_builder(&self) -> ColumnBuilder {
         ColumnBuilder::String(StringDictionaryBuilder::new())
     }
 
@@ -137,7 +157,7 @@ impl SetVirtualDataColumn for Option<f64> {
         }
     }
 
-    fn new_builder() -> ColumnBuilder {
+    fn new_builder(&self) -> ColumnBuilder {
         ColumnBuilder::Float(Float64Builder::new())
     }
 
@@ -163,7 +183,7 @@ impl SetVirtualDataColumn for Option<i32> {
         }
     }
 
-    fn new_builder() -> ColumnBuilder {
+    fn new_builder(&self) -> ColumnBuilder {
         ColumnBuilder::Integer(Int32Builder::new())
     }
 
@@ -189,7 +209,7 @@ impl SetVirtualDataColumn for Option<i64> {
         }
     }
 
-    fn new_builder() -> ColumnBuilder {
+    fn new_builder(&self) -> ColumnBuilder {
         ColumnBuilder::Datetime(TimestampMillisecondBuilder::new())
     }
 
@@ -215,7 +235,7 @@ impl SetVirtualDataColumn for Option<bool> {
         }
     }
 
-    fn new_builder() -> ColumnBuilder {
+    fn new_builder(&self) -> ColumnBuilder {
         ColumnBuilder::Boolean(BooleanBuilder::new())
     }
 
@@ -228,6 +248,57 @@ impl SetVirtualDataColumn for Option<bool> {
     }
 }
 
+/// A value for an `"int64"` column, written as a 64-bit integer rather
+/// than the `Option<i64>` datetime representation.
+#[derive(Clone, Copy, Debug)]
+pub struct Int64Cell(pub Option<i64>);
+
+impl SetVirtualDataColumn for Int64Cell {
+    fn write_to(self, col: &mut ColumnBuilder) -> Result<(), &'static str> {
+        if let ColumnBuilder::Int64(builder) = col {
+            builder.append_option(self.0);
+            Ok(())
+        } else {
+            Err("Bad type")
+        }
+    }
+
+    fn new_builder(&self) -> ColumnBuilder {
+        ColumnBuilder::Int64(Int64Builder::new())
+    }
+
+    fn to_scalar(self) -> Scalar {
+        self.0
+            .map(|x| Scalar::Float(x as f64))
+            .unwrap_or(Scalar::Null)
+    }
+}
+
+/// A value for a `"duration"` column, in milliseconds.
+#[derive(Clone, Copy, Debug)]
+pub struct DurationCell(pub Option<i64>);
+
+impl SetVirtualDataColumn for DurationCell {
+    fn write_to(self, col: &mut ColumnBuilder) -> Result<(), &'static str> {
+        if let ColumnBuilder::Duration(builder) = col {
+            builder.append_option(self.0);
+            Ok(())
+        } else {
+            Err("Bad type")
+        }
+    }
+
+    fn new_builder(&self) -> ColumnBuilder {
+        ColumnBuilder::Duration(DurationMillisecondBuilder::new())
+    }
+
+    fn to_scalar(self) -> Scalar {
+        self.0
+            .map(|x| Scalar::Float(x as f64))
+            .unwrap_or(Scalar::Null)
+    }
+}
+
 /// A columnar data slice returned from a virtual server view query.
 ///
 /// This struct represents a rectangular slice of data from a view, stored
@@ -249,6 +320,8 @@ impl std::fmt::Debug for ColumnBuilder {
             ColumnBuilder::Float(_) => write!(f, "ColumnBuilder::Float(..)"),
             ColumnBuilder::Integer(_) => write!(f, "ColumnBuilder::Integer(..)"),
             ColumnBuilder::Datetime(_) => write!(f, "ColumnBuilder::Datetime(..)"),
+            ColumnBuilder::Int64(_) => write!(f, "ColumnBuilder::Int64(..)"),
+            ColumnBuilder::Duration(_) => write!(f, "ColumnBuilder::Duration(..)"),
         }
     }
 }
@@ -331,6 +404,13 @@ fn extract_scalar(array: &ArrayRef, row_idx: usize) -> Scalar {
             let arr = array.as_any().downcast_ref::<Date32Array>().unwrap();
             Scalar::Float(arr.value(row_idx) as f64 * 86_400_000.0)
         },
+        DataType::Duration(TimeUnit::Millisecond) => {
+            let arr = array
+                .as_any()
+                .downcast_ref::<DurationMillisecondArray>()
+                .unwrap();
+            Scalar::Float(arr.value(row_idx) as f64)
+        },
         _ => {
             let scalar_arr = array.slice(row_idx, 1);
             Scalar::String(format!("{:?}", scalar_arr))
@@ -371,6 +451,37 @@ fn timestamp_to_millis(array: &ArrayRef, unit: &TimeUnit) -> ArrayRef {
     Arc::new(millis) as ArrayRef
 }
 
+/// Converts a duration array of any unit to milliseconds.
+fn duration_to_millis(array: &ArrayRef, unit: &TimeUnit) -> ArrayRef {
+    let millis: DurationMillisecondArray = match unit {
+        TimeUnit::Second => {
+            let arr = array
+                .as_any()
+                .downcast_ref::<DurationSecondArray>()
+                .unwrap();
+            arr.iter().map(|v| v.map(|v| v * 1_000)).collect()
+        },
+        TimeUnit::Microsecond => {
+            let arr = array
+                .as_any()
+                .downcast_ref::<DurationMicrosecondArray>()
+                .unwrap();
+            arr.iter().map(|v| v.map(|v| v / 1_000)).collect()
+        },
+        TimeUnit::Nanosecond => {
+            let arr = array
+                .as_any()
+                .downcast_ref::<DurationNanosecondArray>()
+                .unwrap();
+            arr.iter().map(|v| v.map(|v| v / 1_000_000)).collect()
+        },
+        TimeUnit::Millisecond => {
+            return array.clone();
+        },
+    };
+    Arc::new(millis) as ArrayRef
+}
+
 fn coerce_column(
     name: &str,
     field: &Field,
@@ -494,6 +605,10 @@ fn coerce_column(
                 casted,
             ))
         },
+        DataType::Duration(unit) => Ok((
+            Field::new(name, DataType::Duration(TimeUnit::Millisecond), true),
+            duration_to_millis(array, unit),
+        )),
         DataType::Time32(TimeUnit::Second) => {
             let arr = array.as_any().downcast_ref::<Time32SecondArray>().unwrap();
             let result: TimestampMillisecondArray =
@@ -744,6 +859,14 @@ impl VirtualDataSlice {
                         Field::new(name, DataType::Timestamp(TimeUnit::Millisecond, None), true),
                         Arc::new(b.finish()),
                     ),
+                    ColumnBuilder::Int64(b) => (
+                        Field::new(name, DataType::Int64, true),
+                        Arc::new(b.finish()),
+                    ),
+                    ColumnBuilder::Duration(b) => (
+                        Field::new(name, DataType::Duration(TimeUnit::Millisecond), true),
+                        Arc::new(b.finish()),
+                    ),
                 };
                 fields.push(field);
                 arrays.push(array);
@@ -758,19 +881,47 @@ impl VirtualDataSlice {
         self.frozen.as_ref().unwrap()
     }
 
-    /// Serializes the data to Arrow IPC streaming format.
-    pub(crate) fn render_to_arrow_ipc(&mut self) -> Result<Vec<u8>, Box<dyn Error>> {
+    /// Serializes the data to Arrow IPC streaming format, with the body
+    /// compression codec named by `compression` (as
+    /// [`crate::ViewWindow::compression`]).
+    pub(crate) fn render_to_arrow_ipc(
+        &mut self,
+        compression: Option<&str>,
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        let options = arrow_write_options(compression)?;
         let batch = self.freeze().clone();
         let schema = batch.schema();
         let mut buf = Vec::new();
         {
-            let mut writer = StreamWriter::try_new(&mut buf, &schema)?;
+            let mut writer = StreamWriter::try_new_with_options(&mut buf, &schema, options)?;
             writer.write(&batch)?;
             writer.finish()?;
         }
         Ok(buf)
     }
 
+    /// Serializes the data to CSV, with a header row if `header`.
+    pub(crate) fn render_to_csv(&mut self, header: bool) -> String {
+        let rows = self.render_to_rows(RowPathStyle::PerLevel);
+        let mut csv = String::new();
+        if header && let Some(first_row) = rows.first() {
+            let headers: Vec<&str> = first_row.keys().map(|k| k.as_str()).collect();
+            csv.push_str(&headers.join(","));
+            csv.push('\n');
+        }
+
+        for row in &rows {
+            let values: Vec<String> = row
+                .values()
+                .map(|cell| serde_json::to_string(cell).unwrap_or_default())
+                .collect();
+            csv.push_str(&values.join(","));
+            csv.push('\n');
+        }
+
+        csv
+    }
+
     /// Converts the columnar data to a row-oriented representation for JSON
     /// serialization.
     ///
@@ -816,6 +967,9 @@ impl VirtualDataSlice {
                             DataType::Timestamp(TimeUnit::Millisecond, _) => {
                                 VirtualDataCell::Datetime(None)
                             },
+                            DataType::Int64 | DataType::Duration(TimeUnit::Millisecond) => {
+                                VirtualDataCell::Int64(None)
+                            },
                             _ => continue,
                         }
                     } else {
@@ -842,9 +996,15 @@ impl VirtualDataSlice {
                                 VirtualDataCell::Integer(Some(arr.value(row_idx)))
                             },
                             DataType::Int64 => {
-                                // TODO ????
                                 let arr = col.as_any().downcast_ref::<Int64Array>().unwrap();
-                                VirtualDataCell::Float(Some(arr.value(row_idx) as f64))
+                                VirtualDataCell::Int64(Some(arr.value(row_idx)))
+                            },
+                            DataType::Duration(TimeUnit::Millisecond) => {
+                                let arr = col
+                                    .as_any()
+                                    .downcast_ref::<DurationMillisecondArray>()
+                                    .unwrap();
+                                VirtualDataCell::Int64(Some(arr.value(row_idx)))
                             },
                             DataType::Time64(TimeUnit::Microsecond) => {
                                 let arr = col
@@ -995,7 +1155,24 @@ impl VirtualDataSlice {
                                 if arr.is_null(i) {
                                     None
                                 } else {
-                                    Some(arr.value(i) as f64)
+                                    Some(arr.value(i))
+                                }
+                            })
+                            .collect::<Vec<_>>(),
+                    )?
+                },
+                DataType::Duration(TimeUnit::Millisecond) => {
+                    let arr = col
+                        .as_any()
+                        .downcast_ref::<DurationMillisecondArray>()
+                        .unwrap();
+                    serde_json::to_value(
+                        (0..num_rows)
+                            .map(|i| {
+                                if arr.is_null(i) {
+                                    None
+                                } else {
+                                    Some(arr.value(i))
                                 }
                             })
                             .collect::<Vec<_>>(),
@@ -1101,7 +1278,7 @@ impl VirtualDataSlice {
             Ok(())
         } else {
             if !self.builders.contains_key(name) {
-                self.builders.insert(name.to_owned(), T::new_builder());
+                self.builders.insert(name.to_owned(), value.new_builder());
             }
 
             let col = self
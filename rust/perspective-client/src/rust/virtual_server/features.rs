@@ -18,6 +18,14 @@ use serde::{Deserialize, Serialize};
 use crate::config::GroupRollupMode;
 use crate::proto::get_features_resp::{AggregateArgs, AggregateOptions, ColumnTypeOptions};
 use crate::proto::{ColumnType, GetFeaturesResp};
+use crate::protocol::{Capabilities, MIN_PROTOCOL_VERSION, PROTOCOL_VERSION};
+
+/// The [`Capabilities`] a [`super::VirtualServer`] implements. Joins, unions
+/// and unnest tables require the engine's own table storage, so a
+/// [`super::VirtualServer`] does not support them.
+pub const VIRTUAL_SERVER_CAPABILITIES: Capabilities = Capabilities::from_bits_truncate(
+    Capabilities::TEMPORAL_SCALARS.bits() | Capabilities::STREAMING_EXPORT.bits(),
+);
 
 /// Describes the capabilities supported by a virtual server handler.
 ///
@@ -57,6 +65,11 @@ pub struct Features<'a> {
     /// Whether update callbacks are supported.
     #[serde(default)]
     pub on_update: bool,
+
+    /// Whether nested `AND`/`OR`/`NOT` filter groups (`filter_tree`) are
+    /// supported.
+    #[serde(default)]
+    pub nested_filters: bool,
 }
 
 /// Specification for an aggregate function.
@@ -85,6 +98,10 @@ impl<'a> From<Features<'a>> for GetFeaturesResp {
             expressions: value.expressions,
             on_update: value.on_update,
             sort: value.sort,
+            nested_filters: value.nested_filters,
+            protocol_version: PROTOCOL_VERSION,
+            min_protocol_version: MIN_PROTOCOL_VERSION,
+            capabilities: VIRTUAL_SERVER_CAPABILITIES.bits(),
             aggregates: value
                 .aggregates
                 .iter()
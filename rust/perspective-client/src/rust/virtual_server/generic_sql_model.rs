@@ -389,6 +389,12 @@ impl GenericSQLVirtualServerModel {
             Scalar::Bool(b) => Some(if *b { "TRUE" } else { "FALSE" }.to_string()),
             Scalar::Float(f) => Some(f.to_string()),
             Scalar::String(s) => Some(format!("'{}'", s.replace('\'', "''"))),
+            Scalar::Date(d) => Some(format!("DATE '{}'", d)),
+            Scalar::Datetime(dt) => {
+                let utc = dt.to_string();
+                let utc = utc.trim_end_matches('Z').replacen('T', " ", 1);
+                Some(format!("TIMESTAMP WITH TIME ZONE '{}+00:00'", utc))
+            },
         }
     }
 }
@@ -10,7 +10,10 @@
 // ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
-use crate::config::{Aggregate, GroupRollupMode, Sort, SortDir, ViewConfig};
+use crate::config::{
+    Aggregate, Filter, FilterGroup, FilterReducer, FilterTree, GroupRollupMode, Sort, SortDir,
+    ViewConfig,
+};
 
 fn aggregate_to_string(agg: &Aggregate) -> String {
     match agg {
@@ -475,22 +478,45 @@ impl<'a> ViewQueryContext<'a> {
     }
 
     fn where_sql(&self) -> String {
-        let clauses: Vec<String> = self
-            .config
-            .filter
+        match self.filter_group_sql(&self.config.filter_group()) {
+            Some(clause) => format!(" WHERE {}", clause),
+            None => String::new(),
+        }
+    }
+
+    fn filter_sql(&self, flt: &Filter) -> Option<String> {
+        super::GenericSQLVirtualServerModel::filter_term_to_sql(flt.term())
+            .map(|term_lit| format!("{} {} {}", self.col_name(flt.column()), flt.op(), term_lit))
+    }
+
+    /// Renders a (possibly nested) filter group as a boolean SQL expression,
+    /// or `None` if the group has no applicable terms.
+    fn filter_group_sql(&self, group: &FilterGroup) -> Option<String> {
+        let clauses: Vec<String> = group
+            .filters
             .iter()
-            .filter_map(|flt| {
-                super::GenericSQLVirtualServerModel::filter_term_to_sql(flt.term()).map(
-                    |term_lit| format!("{} {} {}", self.col_name(flt.column()), flt.op(), term_lit),
-                )
+            .filter_map(|node| match node {
+                FilterTree::Filter(flt) => self.filter_sql(flt),
+                FilterTree::Group(group) => {
+                    self.filter_group_sql(group).map(|x| format!("({})", x))
+                },
             })
             .collect();
 
         if clauses.is_empty() {
-            String::new()
-        } else {
-            format!(" WHERE {}", clauses.join(" AND "))
+            return None;
         }
+
+        let sql = clauses.join(match group.op {
+            FilterReducer::And => " AND ",
+            FilterReducer::Or => " OR ",
+        });
+
+        Some(if group.negated {
+            format!("NOT ({})", sql)
+        } else {
+            sql
+        })
     }
 
     /// Builds the `ORDER BY` expression for the `ROW_NUMBER()` window
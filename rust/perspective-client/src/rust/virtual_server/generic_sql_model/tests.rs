@@ -13,7 +13,9 @@
 use std::collections::HashMap;
 
 use super::*;
-use crate::config::{Aggregate, GroupRollupMode};
+use crate::config::{
+    Aggregate, Date, Datetime, Filter, FilterGroup, FilterReducer, FilterTree, GroupRollupMode,
+};
 
 #[test]
 fn test_get_hosted_tables() {
@@ -860,3 +862,180 @@ fn test_table_make_view_total_with_split_by() {
         sql
     );
 }
+
+#[test]
+fn test_table_make_view_filter_op_or() {
+    let builder = GenericSQLVirtualServerModel::new(GenericSQLVirtualServerModelArgs::default());
+    let mut config = ViewConfig::default();
+    config.columns = vec![Some("region".to_string())];
+    config.filter =
+        serde_json::from_str(r#"[["region", "==", "EU"], ["region", "==", "UK"]]"#).unwrap();
+    config.filter_op = FilterReducer::Or;
+    let sql = builder
+        .table_make_view("source_table", "dest_view", &config)
+        .unwrap();
+
+    assert!(
+        sql.contains(" WHERE \"region\" == 'EU' OR \"region\" == 'UK'"),
+        "expected OR filter: {}",
+        sql
+    );
+}
+
+#[test]
+fn test_table_make_view_nested_filter_tree() {
+    let builder = GenericSQLVirtualServerModel::new(GenericSQLVirtualServerModelArgs::default());
+    let mut config = ViewConfig::default();
+    config.columns = vec![Some("region".to_string()), Some("sales".to_string())];
+    config.filter_tree = Some(
+        serde_json::from_str(
+            r#"{
+                "op": "and",
+                "filters": [
+                    {"op": "or", "filters": [["region", "==", "EU"], ["region", "==", "UK"]]},
+                    ["sales", ">", 100]
+                ]
+            }"#,
+        )
+        .unwrap(),
+    );
+
+    let sql = builder
+        .table_make_view("source_table", "dest_view", &config)
+        .unwrap();
+
+    assert!(
+        sql.contains(" WHERE (\"region\" == 'EU' OR \"region\" == 'UK') AND \"sales\" > 100"),
+        "expected nested filter: {}",
+        sql
+    );
+}
+
+#[test]
+fn test_table_make_view_negated_filter_group() {
+    let builder = GenericSQLVirtualServerModel::new(GenericSQLVirtualServerModelArgs::default());
+    let mut config = ViewConfig::default();
+    config.columns = vec![Some("region".to_string())];
+    config.filter = vec![serde_json::from_str(r#"["ignored", "==", 1]"#).unwrap()];
+    config.filter_tree = Some(FilterGroup::new(FilterReducer::And, vec![
+        FilterTree::Group(
+            FilterGroup::new(FilterReducer::Or, vec![
+                serde_json::from_str::<Filter>(r#"["region", "==", "EU"]"#)
+                    .unwrap()
+                    .into(),
+                serde_json::from_str::<Filter>(r#"["region", "==", "UK"]"#)
+                    .unwrap()
+                    .into(),
+            ])
+            .not(),
+        ),
+    ]));
+
+    let sql = builder
+        .table_make_view("source_table", "dest_view", &config)
+        .unwrap();
+
+    assert!(
+        sql.contains(" WHERE (NOT (\"region\" == 'EU' OR \"region\" == 'UK'))"),
+        "expected negated group: {}",
+        sql
+    );
+    assert!(
+        !sql.contains("ignored"),
+        "flat filter should be ignored: {}",
+        sql
+    );
+}
+
+#[test]
+fn test_filter_tree_flat_serde_compat() {
+    let config: ViewConfig = serde_json::from_str(
+        r#"{"filter": [["a", "==", 1], ["b", "==", "x"]], "filter_op": "or"}"#,
+    )
+    .unwrap();
+
+    assert!(config.filter_tree.is_none());
+    let group = config.filter_group();
+    assert!(group.is_flat());
+    assert_eq!(
+        group.to_flat(),
+        Some((config.filter.clone(), FilterReducer::Or))
+    );
+
+    let nested: FilterGroup =
+        serde_json::from_str(r#"{"op": "or", "filters": [["a", "==", 1], {"filters": []}]}"#)
+            .unwrap();
+
+    assert!(!nested.is_flat());
+    assert_eq!(nested.to_flat(), None);
+    assert_eq!(nested.leaves().count(), 1);
+}
+
+#[test]
+fn test_table_make_view_temporal_filter_literals() {
+    let builder = GenericSQLVirtualServerModel::new(GenericSQLVirtualServerModelArgs::default());
+    let mut config = ViewConfig::default();
+    config.columns = vec![Some("d".to_string()), Some("ts".to_string())];
+    config.filter = serde_json::from_str(
+        r#"[
+            ["d", ">=", {"date": "2026-01-01"}],
+            ["ts", "<", {"datetime": "2026-01-01T09:30:00.5+05:00"}]
+        ]"#,
+    )
+    .unwrap();
+
+    let sql = builder
+        .table_make_view("source_table", "dest_view", &config)
+        .unwrap();
+
+    assert!(
+        sql.contains("\"d\" >= DATE '2026-01-01'"),
+        "expected date literal: {}",
+        sql
+    );
+    assert!(
+        sql.contains("\"ts\" < TIMESTAMP WITH TIME ZONE '2026-01-01 04:30:00.500+00:00'"),
+        "expected UTC timestamp literal: {}",
+        sql
+    );
+}
+
+#[test]
+fn test_temporal_scalar_serde() {
+    let date: Scalar = serde_json::from_str(r#"{"date": "1969-12-31"}"#).unwrap();
+    assert_eq!(date, Scalar::Date(Date::from_days_since_epoch(-1)));
+    assert_eq!(
+        serde_json::to_string(&date).unwrap(),
+        r#"{"date":"1969-12-31"}"#
+    );
+
+    let datetime: Scalar = serde_json::from_str(r#"{"datetime": "2000-02-29T00:00:00Z"}"#).unwrap();
+    assert_eq!(
+        datetime,
+        Scalar::Datetime(Datetime::from_timestamp_millis(951_782_400_000))
+    );
+    assert_eq!(
+        serde_json::to_string(&datetime).unwrap(),
+        r#"{"datetime":"2000-02-29T00:00:00.000Z"}"#
+    );
+
+    // Datetimes must carry an explicit offset.
+    assert!(serde_json::from_str::<Datetime>(r#"{"datetime": "2026-01-01T00:00:00"}"#).is_err());
+    assert!(serde_json::from_str::<Date>(r#"{"date": "2026-02-30"}"#).is_err());
+
+    // Dates at the extremes of the protocol's range do not overflow.
+    for days in [i32::MIN, i32::MAX] {
+        let (y, m, d) = Date::from_days_since_epoch(days).ymd();
+        assert_eq!(
+            Date::from_ymd(y, m, d),
+            Some(Date::from_days_since_epoch(days))
+        );
+    }
+
+    assert_eq!(Date::from_ymd(i32::MAX, 12, 31), None);
+    assert_eq!(Datetime::from_timestamp_millis(i64::MAX).to_date(), None);
+    assert_eq!(
+        Datetime::from_timestamp_millis(i64::MIN).to_string(),
+        "-292275055-05-16T16:47:04.192Z"
+    );
+}
@@ -22,9 +22,11 @@ mod generic_sql_model;
 mod handler;
 mod server;
 
-pub use data::{RowPathStyle, SetVirtualDataColumn, VirtualDataCell, VirtualDataSlice};
+pub use data::{
+    DurationCell, Int64Cell, RowPathStyle, SetVirtualDataColumn, VirtualDataCell, VirtualDataSlice,
+};
 pub use error::{ResultExt, VirtualServerError};
-pub use features::{AggSpec, Features};
+pub use features::{AggSpec, Features, VIRTUAL_SERVER_CAPABILITIES};
 pub use generic_sql_model::{
     GenericSQLError, GenericSQLResult, GenericSQLVirtualServerModel,
     GenericSQLVirtualServerModelArgs,
@@ -10,13 +10,14 @@
 // ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
+use arrow_ipc::writer::{IpcWriteOptions, StreamWriter};
 use indexmap::IndexMap;
 use prost::Message as ProstMessage;
 use prost::bytes::{Bytes, BytesMut};
 
-use super::data::RowPathStyle;
+use super::data::{RowPathStyle, arrow_write_options};
 use super::error::VirtualServerError;
 use super::generic_sql_model::{column_path_source, sort_column_paths};
 use super::handler::VirtualServerHandler;
@@ -28,10 +29,36 @@ use crate::proto::{
     ServerError, TableMakePortResp, TableMakeViewResp, TableOnDeleteResp, TableRemoveDeleteResp,
     TableSchemaResp, TableSizeResp, TableValidateExprResp, ViewColumnPathsResp, ViewDeleteResp,
     ViewDimensionsResp, ViewExpressionSchemaResp, ViewGetConfigResp, ViewGetMinMaxResp,
-    ViewOnDeleteResp, ViewOnUpdateResp, ViewRemoveDeleteResp, ViewRemoveOnUpdateResp,
-    ViewSchemaResp, ViewToArrowResp, ViewToColumnsStringResp, ViewToCsvResp,
-    ViewToNdjsonStringResp, ViewToRowsStringResp,
+    ViewOnDeleteResp, ViewOnUpdateResp, ViewPort, ViewRemoveDeleteResp, ViewRemoveOnUpdateResp,
+    ViewSchemaResp, ViewToArrowResp, ViewToArrowStreamResp, ViewToColumnsStringResp, ViewToCsvResp,
+    ViewToCsvStreamResp, ViewToNdjsonStringResp, ViewToRowsStringResp,
 };
+use crate::protocol::Capabilities;
+
+/// The rows per chunk of a streaming export which doesn't specify
+/// `chunk_rows`, as in the native `perspective-server`.
+const DEFAULT_CHUNK_ROWS: u32 = 65536;
+
+/// A streaming export (`ViewToArrowStreamReq` or `ViewToCSVStreamReq`)
+/// awaiting the `ViewStreamNextReq` for its next chunk.
+struct StreamExport {
+    view_id: String,
+
+    /// The viewports of the chunks not yet sent.
+    viewports: VecDeque<ViewPort>,
+    format: StreamFormat,
+}
+
+enum StreamFormat {
+    Arrow {
+        options: IpcWriteOptions,
+        writer: Option<StreamWriter<Vec<u8>>>,
+    },
+    Csv {
+        /// Whether the next chunk is the first, and has the header row.
+        header: bool,
+    },
+}
 
 macro_rules! respond {
     ($msg:ident, $name:ident { $($rest:tt)* }) => {{
@@ -60,6 +87,8 @@ pub struct VirtualServer<T: VirtualServerHandler> {
     view_to_table: IndexMap<String, String>,
     view_configs: IndexMap<String, ViewConfig>,
     view_schemas: IndexMap<String, IndexMap<String, ColumnType>>,
+    client_capabilities: Capabilities,
+    exports: HashMap<u32, StreamExport>,
 }
 
 impl<T: VirtualServerHandler> VirtualServer<T> {
@@ -70,9 +99,17 @@ impl<T: VirtualServerHandler> VirtualServer<T> {
             view_configs: IndexMap::default(),
             view_to_table: IndexMap::default(),
             view_schemas: IndexMap::default(),
+            client_capabilities: Capabilities::empty(),
+            exports: HashMap::default(),
         }
     }
 
+    /// The [`Capabilities`] the client reported in its `GetFeaturesReq`
+    /// handshake, or none if it has not made one.
+    pub fn client_capabilities(&self) -> Capabilities {
+        self.client_capabilities
+    }
+
     /// Processes a Perspective protocol request and returns the response.
     ///
     /// Decodes the incoming protobuf message, dispatches to the appropriate
@@ -100,6 +137,126 @@ impl<T: VirtualServerHandler> VirtualServer<T> {
         }
     }
 
+    /// `viewport`'s rows split into viewports of at most `chunk_rows` rows
+    /// (`0` for the default). There is always at least one, so an empty
+    /// export still has a schema.
+    async fn chunk_viewports(
+        &self,
+        view_id: &str,
+        viewport: ViewPort,
+        chunk_rows: u32,
+    ) -> Result<VecDeque<ViewPort>, VirtualServerError<T::Error>> {
+        let chunk_rows = if chunk_rows == 0 {
+            DEFAULT_CHUNK_ROWS
+        } else {
+            chunk_rows
+        };
+
+        let start_row = viewport.start_row.unwrap_or_default();
+        let end_row = match viewport.end_row {
+            Some(end_row) => end_row,
+            None => self.handler.view_size(view_id).await?,
+        };
+
+        let mut viewports = VecDeque::new();
+        let mut row = start_row;
+        loop {
+            let chunk_end = end_row.min(row.saturating_add(chunk_rows));
+            viewports.push_back(ViewPort {
+                start_row: Some(row),
+                end_row: Some(chunk_end),
+                ..viewport.clone()
+            });
+
+            row = chunk_end;
+            if row >= end_row {
+                return Ok(viewports);
+            }
+        }
+    }
+
+    /// Begin a streaming export of the view `msg.entity_id`, and respond with
+    /// its first chunk.
+    async fn start_stream_export(
+        &mut self,
+        msg: &Request,
+        viewport: Option<ViewPort>,
+        chunk_rows: Option<u32>,
+        format: StreamFormat,
+    ) -> Result<Bytes, VirtualServerError<T::Error>> {
+        let viewport = viewport.unwrap_or_default();
+        let chunk_rows = chunk_rows.unwrap_or_default();
+        let export = StreamExport {
+            view_id: msg.entity_id.clone(),
+            viewports: self
+                .chunk_viewports(&msg.entity_id, viewport, chunk_rows)
+                .await?,
+            format,
+        };
+
+        self.stream_chunk(msg, export).await
+    }
+
+    /// Respond with the next chunk of `export`, keeping it for the next
+    /// `ViewStreamNextReq` unless it is done. Each chunk's rows are fetched
+    /// from the handler when the chunk is requested.
+    async fn stream_chunk(
+        &mut self,
+        msg: &Request,
+        mut export: StreamExport,
+    ) -> Result<Bytes, VirtualServerError<T::Error>> {
+        let other = |e: Box<dyn std::error::Error>| VirtualServerError::Other(e.to_string());
+        let viewport = export.viewports.pop_front().unwrap_or_default();
+        let done = export.viewports.is_empty();
+        let schema = self.get_cached_view_schema(&export.view_id, false).await?;
+        let config = self.view_configs.get(&export.view_id).unwrap();
+        let mut cols = self
+            .handler
+            .view_get_data(&export.view_id, config, &schema, &viewport)
+            .await?;
+
+        let client_resp = match &mut export.format {
+            StreamFormat::Arrow { options, writer } => {
+                let batch = cols.freeze();
+                if writer.is_none() {
+                    let schema = batch.schema();
+                    *writer = Some(
+                        StreamWriter::try_new_with_options(vec![], &schema, options.clone())
+                            .map_err(|e| other(e.into()))?,
+                    );
+                }
+
+                let writer = writer.as_mut().unwrap();
+                writer.write(batch).map_err(|e| other(e.into()))?;
+                if done {
+                    writer.finish().map_err(|e| other(e.into()))?;
+                }
+
+                let arrow = std::mem::take(writer.get_mut());
+                ClientResp::ViewToArrowStreamResp(ViewToArrowStreamResp { arrow, done })
+            },
+            StreamFormat::Csv { header } => {
+                let csv = cols.render_to_csv(std::mem::take(header));
+                ClientResp::ViewToCsvStreamResp(ViewToCsvStreamResp { csv, done })
+            },
+        };
+
+        if !done {
+            self.exports.insert(msg.msg_id, export);
+        }
+
+        let mut resp = BytesMut::new();
+        Response {
+            msg_id: msg.msg_id,
+            entity_id: msg.entity_id.clone(),
+            client_resp: Some(client_resp),
+        }
+        .encode(&mut resp)
+        .map_err(VirtualServerError::EncodeError)?;
+
+        Ok(resp.freeze())
+    }
+
     async fn get_cached_view_schema(
         &mut self,
         entity_id: &str,
@@ -143,7 +300,8 @@ impl<T: VirtualServerHandler> VirtualServer<T> {
     ) -> Result<Bytes, VirtualServerError<T::Error>> {
         use crate::proto::request::ClientReq::*;
         let resp = match msg.client_req.unwrap() {
-            GetFeaturesReq(_) => {
+            GetFeaturesReq(req) => {
+                self.client_capabilities = Capabilities::from_bits_truncate(req.capabilities);
                 let features = self.handler.get_features().await?;
                 respond!(msg, GetFeaturesResp { ..features.into() })
             },
@@ -317,7 +475,7 @@ impl<T: VirtualServerHandler> VirtualServer<T> {
                     .await?;
 
                 let arrow = cols
-                    .render_to_arrow_ipc()
+                    .render_to_arrow_ipc(view_to_arrow_req.compression.as_deref())
                     .map_err(|e| VirtualServerError::Other(e.to_string()))?;
 
                 respond!(msg, ViewToArrowResp { arrow })
@@ -331,24 +489,48 @@ impl<T: VirtualServerHandler> VirtualServer<T> {
                     .view_get_data(msg.entity_id.as_str(), config, &schema, &viewport)
                     .await?;
 
-                let rows = cols.render_to_rows(RowPathStyle::PerLevel);
-                let mut csv = String::new();
-                if let Some(first_row) = rows.first() {
-                    let headers: Vec<&str> = first_row.keys().map(|k| k.as_str()).collect();
-                    csv.push_str(&headers.join(","));
-                    csv.push('\n');
-                }
+                let csv = cols.render_to_csv(true);
+                respond!(msg, ViewToCsvResp { csv })
+            },
+            ViewToArrowStreamReq(req) => {
+                let other =
+                    |e: Box<dyn std::error::Error>| VirtualServerError::Other(e.to_string());
+                let options = arrow_write_options(req.compression.as_deref()).map_err(other)?;
+                let format = StreamFormat::Arrow {
+                    options,
+                    writer: None,
+                };
 
-                for row in &rows {
-                    let values: Vec<String> = row
-                        .values()
-                        .map(|cell| serde_json::to_string(cell).unwrap_or_default())
-                        .collect();
-                    csv.push_str(&values.join(","));
-                    csv.push('\n');
-                }
+                self.start_stream_export(&msg, req.viewport, req.chunk_rows, format)
+                    .await?
+            },
+            ViewToCsvStreamReq(req) => {
+                let format = StreamFormat::Csv { header: true };
+                self.start_stream_export(&msg, req.viewport, req.chunk_rows, format)
+                    .await?
+            },
+            ViewStreamNextReq(req) => {
+                let Some(export) = self.exports.remove(&msg.msg_id) else {
+                    return Err(VirtualServerError::Other(
+                        "No streaming export in progress".to_owned(),
+                    ));
+                };
 
-                respond!(msg, ViewToCsvResp { csv })
+                // The client dropped its stream, so release the export.
+                if req.cancel {
+                    match export.format {
+                        StreamFormat::Arrow { .. } => respond!(msg, ViewToArrowStreamResp {
+                            arrow: vec![],
+                            done: true
+                        }),
+                        StreamFormat::Csv { .. } => respond!(msg, ViewToCsvStreamResp {
+                            csv: String::new(),
+                            done: true
+                        }),
+                    }
+                } else {
+                    self.stream_chunk(&msg, export).await?
+                }
             },
             ViewToNdjsonStringReq(view_to_ndjson_req) => {
                 let viewport = view_to_ndjson_req.viewport.unwrap();
@@ -403,20 +585,30 @@ impl<T: VirtualServerHandler> VirtualServer<T> {
                 self.handler.view_delete(msg.entity_id.as_str()).await?;
                 self.view_to_table.shift_remove(&msg.entity_id);
                 self.view_configs.shift_remove(&msg.entity_id);
+                self.exports.retain(|_, x| x.view_id != msg.entity_id);
                 respond!(msg, ViewDeleteResp {})
             },
             MakeTableReq(req) => {
                 self.handler
                     .make_table(&msg.entity_id, req.data.as_ref().unwrap())
                     .await?;
-                respond!(msg, MakeTableResp {})
+                respond!(msg, MakeTableResp { schema: None })
             },
             ViewGetMinMaxReq(req) => {
                 let config = self.view_configs.get(&msg.entity_id).unwrap();
-                let (min, max) = self
+                let (mut min, mut max) = self
                     .handler
                     .view_get_min_max(&msg.entity_id, &req.column_name, config)
                     .await?;
+
+                if !self
+                    .client_capabilities
+                    .contains(Capabilities::TEMPORAL_SCALARS)
+                {
+                    min = min.to_legacy_temporal();
+                    max = max.to_legacy_temporal();
+                }
+
                 respond!(msg, ViewGetMinMaxResp {
                     min: Some(min.into()),
                     max: Some(max.into()),
@@ -445,6 +637,12 @@ impl<T: VirtualServerHandler> VirtualServer<T> {
             ViewRemoveDeleteReq(_) => {
                 respond!(msg, ViewRemoveDeleteResp {})
             },
+            MakeJoinTableReq(_) | MakeUnionTableReq(_) | MakeUnnestTableReq(_) => {
+                return Err(VirtualServerError::Other(
+                    "Join, union and unnest tables are not supported by a virtual server"
+                        .to_owned(),
+                ));
+            },
             x => {
                 // Return an error response instead of empty bytes
                 return Err(VirtualServerError::Other(format!(
@@ -36,9 +36,21 @@ extern "C" {
     #[wasm_bindgen(typescript_type = "TableInitOptions")]
     pub type JsTableInitOptions;
 
+    #[derive(Clone)]
+    #[wasm_bindgen(typescript_type = "JoinKeys")]
+    pub type JsJoinKeys;
+
     #[derive(Clone)]
     #[wasm_bindgen(typescript_type = "JoinOptions")]
     pub type JsJoinOptions;
+
+    #[derive(Clone)]
+    #[wasm_bindgen(typescript_type = "UnionOptions")]
+    pub type JsUnionOptions;
+
+    #[derive(Clone)]
+    #[wasm_bindgen(typescript_type = "UnnestOptions")]
+    pub type JsUnnestOptions;
 }
 
 async fn js_to_table_ref(val: &JsValue) -> ApiResult<TableRef> {
@@ -411,9 +423,9 @@ impl Client {
         Ok(Table(self.client.table(args, options).await?))
     }
 
-    /// Creates a new read-only [`Table`] by performing an INNER JOIN on two
-    /// source tables. The resulting table is reactive: when either source
-    /// table is updated, the join is automatically recomputed.
+    /// Creates a new read-only [`Table`] by performing a JOIN on two source
+    /// tables. The resulting table is reactive: when either source table is
+    /// updated, the join is automatically recomputed.
     ///
     /// # Arguments
     ///
@@ -421,25 +433,29 @@ impl Client {
     ///   string).
     /// - `right` - The right source table (a [`Table`] instance or a table name
     ///   string).
-    /// - `on` - The column name to join on. Must exist in both tables with the
-    ///   same type.
+    /// - `on` - The column name to join on, or an array of column names for a
+    ///   composite key. Must exist in both tables with the same type.
     /// - `options` - Optional join configuration: `{ join_type?: "inner" |
-    ///   "left" | "outer", name?: string }`.
+    ///   "left" | "outer" | "right" | "semi" | "anti", name?: string,
+    ///   right_on?: string, right_on_columns?: string[], left_suffix?: string,
+    ///   right_suffix?: string, as_of?: string, right_as_of?: string }`.
     ///
     /// # JavaScript Examples
     ///
     /// ```javascript
     /// const joined = await client.join(orders_table, products_table, "Product ID", { join_type: "left" });
     /// const joined = await client.join("orders", "products", "Product ID", { join_type: "left" });
+    /// const joined = await client.join("trades", "quotes", ["date", "ticker"], { as_of: "timestamp" });
     /// ```
     #[wasm_bindgen]
     pub async fn join(
         &self,
         left: JsValue,
         right: JsValue,
-        on: &str,
+        on: JsJoinKeys,
         options: Option<JsJoinOptions>,
     ) -> ApiResult<Table> {
+        let on = on.into_serde_ext::<perspective_client::JoinKeys>()?;
         let options = options
             .into_serde_ext::<Option<perspective_client::JoinOptions>>()?
             .unwrap_or_default();
@@ -451,6 +467,76 @@ impl Client {
         ))
     }
 
+    /// Creates a new read-only [`Table`] by concatenating the rows of several
+    /// source tables. The resulting table is reactive: when any source table
+    /// is updated, the union is automatically recomputed.
+    ///
+    /// # Arguments
+    ///
+    /// - `tables` - The source tables ([`Table`] instances or table name
+    ///   strings), which must have identical schemas.
+    /// - `options` - Optional union configuration: `{ name?: string,
+    ///   source_column?: string }`.
+    ///
+    /// # JavaScript Examples
+    ///
+    /// ```javascript
+    /// const all = await client.union([xnys_table, xnas_table]);
+    /// const all = await client.union(["xnys", "xnas"], { source_column: "venue" });
+    /// ```
+    #[wasm_bindgen]
+    pub async fn union(
+        &self,
+        tables: Vec<JsValue>,
+        options: Option<JsUnionOptions>,
+    ) -> ApiResult<Table> {
+        let options = options
+            .into_serde_ext::<Option<perspective_client::UnionOptions>>()?
+            .unwrap_or_default();
+
+        let mut table_refs = Vec::with_capacity(tables.len());
+        for table in tables.iter() {
+            table_refs.push(js_to_table_ref(table).await?);
+        }
+
+        Ok(Table(self.client.union(table_refs, options).await?))
+    }
+
+    /// Creates a new read-only [`Table`] with one row for each element of a
+    /// `list_string` or `list_float` column of a source table. The resulting
+    /// table is reactive: when the source table is updated, it is
+    /// automatically recomputed.
+    ///
+    /// # Arguments
+    ///
+    /// - `table` - The source table (a [`Table`] instance or table name
+    ///   string).
+    /// - `column` - The list column to unnest.
+    /// - `options` - Optional unnest configuration: `{ name?: string }`.
+    ///
+    /// # JavaScript Examples
+    ///
+    /// ```javascript
+    /// const tags = await client.unnest(orders_table, "tags");
+    /// const view = await tags.view({ group_by: ["tags"] });
+    /// ```
+    #[wasm_bindgen]
+    pub async fn unnest(
+        &self,
+        table: JsValue,
+        column: String,
+        options: Option<JsUnnestOptions>,
+    ) -> ApiResult<Table> {
+        let options = options
+            .into_serde_ext::<Option<perspective_client::UnnestOptions>>()?
+            .unwrap_or_default();
+
+        let table_ref = js_to_table_ref(&table).await?;
+        Ok(Table(
+            self.client.unnest(table_ref, &column, options).await?,
+        ))
+    }
+
     /// Terminates this [`Client`], cleaning up any [`crate::View`] handles the
     /// [`Client`] has open as well as its callbacks.
     #[wasm_bindgen]
@@ -545,6 +631,21 @@ impl Client {
         Ok(())
     }
 
+    /// Exchange protocol versions with the `perspective_server::Server`,
+    /// rejecting with an `IncompatibleServer` error if the two cannot
+    /// interoperate. This otherwise happens lazily on first use.
+    ///
+    /// # JavaScript Examples
+    ///
+    /// ```javascript
+    /// await client.handshake();
+    /// ```
+    #[wasm_bindgen]
+    pub async fn handshake(&self) -> ApiResult<()> {
+        self.client.handshake().await?;
+        Ok(())
+    }
+
     /// Provides the [`SystemInfo`] struct, implementation-specific metadata
     /// about the [`perspective_server::Server`] runtime such as Memory and
     /// CPU usage.
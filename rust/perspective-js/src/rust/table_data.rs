@@ -110,6 +110,10 @@ pub(crate) impl UpdateData {
                 Some(TableReadFormat::Ndjson) => Ok(Some(UpdateData::Ndjson(
                     value.as_string().expect("Ndjson???"),
                 ))),
+                Some(TableReadFormat::Parquet) => Ok(Some(UpdateData::Parquet {
+                    data: value.as_string().expect("Parquet???").into_bytes().into(),
+                    columns: None,
+                })),
             }
         } else if value.is_instance_of::<ArrayBuffer>() {
             let uint8array = Uint8Array::new(value);
@@ -125,6 +129,10 @@ pub(crate) impl UpdateData {
                 Some(TableReadFormat::Ndjson) => {
                     Ok(Some(UpdateData::Ndjson(String::from_utf8(slice)?)))
                 },
+                Some(TableReadFormat::Parquet) => Ok(Some(UpdateData::Parquet {
+                    data: slice.into(),
+                    columns: None,
+                })),
                 None | Some(TableReadFormat::Arrow) => Ok(Some(UpdateData::Arrow(slice.into()))),
             }
         } else if let Some(uint8array) = value.dyn_ref::<Uint8Array>() {
@@ -140,6 +148,10 @@ pub(crate) impl UpdateData {
                 Some(TableReadFormat::Ndjson) => {
                     Ok(Some(UpdateData::Ndjson(String::from_utf8(slice)?)))
                 },
+                Some(TableReadFormat::Parquet) => Ok(Some(UpdateData::Parquet {
+                    data: slice.into(),
+                    columns: None,
+                })),
                 None | Some(TableReadFormat::Arrow) => Ok(Some(UpdateData::Arrow(slice.into()))),
             }
         } else if value.is_instance_of::<Array>() {
@@ -12,7 +12,7 @@
 
 use js_sys::{Array, ArrayBuffer, Function, Object};
 use perspective_client::{
-    ColumnWindow, OnUpdateData, OnUpdateOptions, ViewWindow, assert_view_api,
+    ColumnWindow, OnUpdateData, OnUpdateOptions, ParquetWriteOptions, ViewWindow, assert_view_api,
 };
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::spawn_local;
@@ -37,6 +37,9 @@ unsafe extern "C" {
     #[wasm_bindgen(typescript_type = "OnUpdateOptions")]
     pub type JsOnUpdateOptions;
 
+    #[wasm_bindgen(typescript_type = "ParquetWriteOptions")]
+    pub type JsParquetWriteOptions;
+
 }
 
 impl From<ViewWindow> for JsViewWindow {
@@ -52,6 +55,11 @@ fn scalar_to_jsvalue(scalar: &perspective_client::config::Scalar) -> JsValue {
         perspective_client::config::Scalar::Float(x) => JsValue::from_f64(*x),
         perspective_client::config::Scalar::String(x) => JsValue::from_str(x),
         perspective_client::config::Scalar::Bool(x) => JsValue::from_bool(*x),
+        // As returned for native `"date"` and `"datetime"` columns.
+        perspective_client::config::Scalar::Date(x) => JsValue::from_str(&x.to_string()),
+        perspective_client::config::Scalar::Datetime(x) => {
+            JsValue::from_f64(x.timestamp_millis() as f64)
+        },
         perspective_client::config::Scalar::Null => JsValue::NULL,
     }
 }
@@ -150,7 +158,8 @@ impl View {
     /// created this [`View`].
     #[wasm_bindgen]
     pub async fn get_config(&self) -> ApiResult<JsValue> {
-        let config = self.0.get_config().await?;
+        let mut config = self.0.get_config().await?;
+        config.downgrade_temporal_scalars();
         Ok(JsValue::from_serde_ext(&config)?)
     }
 
@@ -204,6 +213,32 @@ impl View {
             .unchecked_into())
     }
 
+    /// Serializes a [`View`] to the Apache Parquet format.
+    ///
+    /// # Arguments
+    ///
+    /// - `window` - The rows and columns to export, as in [`View::to_arrow`].
+    /// - `options` - The `compression` codec (one of `"uncompressed"`,
+    ///   `"snappy"`, `"gzip"`, `"lz4"` or `"brotli"`) and
+    ///   `row_group_size`.
+    #[wasm_bindgen]
+    pub async fn to_parquet(
+        &self,
+        window: Option<JsViewWindow>,
+        options: Option<JsParquetWriteOptions>,
+    ) -> ApiResult<ArrayBuffer> {
+        let window = window.into_serde_ext::<Option<ViewWindow>>()?;
+        let options = options.into_serde_ext::<Option<ParquetWriteOptions>>()?;
+        let parquet = self
+            .0
+            .to_parquet(window.unwrap_or_default(), options.unwrap_or_default())
+            .await?;
+
+        Ok(js_sys::Uint8Array::from(&parquet[..])
+            .buffer()
+            .unchecked_into())
+    }
+
     /// Serializes this [`View`] to a string of JSON data. Useful if you want to
     /// save additional round trip serialize/deserialize cycles.
     #[wasm_bindgen]
@@ -18,10 +18,12 @@ use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 
 use indexmap::IndexMap;
-use js_sys::{Array, Date, Object, Reflect, Uint8Array};
+use js_sys::{Array, BigInt, Date, Object, Reflect, Uint8Array};
 use perspective_client::proto::{ColumnType, HostedTable};
 use perspective_client::virtual_server;
-use perspective_client::virtual_server::{Features, ResultExt, VirtualServerHandler};
+use perspective_client::virtual_server::{
+    DurationCell, Features, Int64Cell, ResultExt, VirtualServerHandler,
+};
 use serde::Serialize;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
@@ -69,6 +71,10 @@ fn jsvalue_to_scalar(val: &JsValue) -> perspective_client::config::Scalar {
         perspective_client::config::Scalar::Float(n)
     } else if let Some(s) = val.as_string() {
         perspective_client::config::Scalar::String(s)
+    } else if let Some(d) = val.dyn_ref::<Date>() {
+        perspective_client::config::Scalar::Datetime(
+            perspective_client::config::Datetime::from_timestamp_millis(d.get_time() as i64),
+        )
     } else {
         perspective_client::config::Scalar::Null
     }
@@ -137,6 +143,8 @@ impl VirtualServerHandler for JsServerHandler {
                         entity_id: s,
                         index: None,
                         limit: None,
+                        derived: false,
+                        retention: None,
                     });
                 } else if item.is_object() {
                     let name = Reflect::get(&item, &JsValue::from_str("name"))?
@@ -152,6 +160,8 @@ impl VirtualServerHandler for JsServerHandler {
                         entity_id: name,
                         index,
                         limit,
+                        derived: false,
+                        retention: None,
                     });
                 }
             }
@@ -614,6 +624,8 @@ impl VirtualDataSlice {
             "date" => self.set_datetime_col(name, index, val, group_by_index),
             "datetime" => self.set_datetime_col(name, index, val, group_by_index),
             "boolean" => self.set_boolean_col(name, index, val, group_by_index),
+            "int64" => self.set_int64_col(name, index, val, group_by_index),
+            "duration" => self.set_duration_col(name, index, val, group_by_index),
             _ => Err(JsValue::from_str("Unknown type")),
         }
     }
@@ -777,6 +789,67 @@ impl VirtualDataSlice {
 
         Ok(())
     }
+
+    #[wasm_bindgen(js_name = "setInt64Col")]
+    pub fn set_int64_col(
+        &self,
+        name: &str,
+        index: u32,
+        val: JsValue,
+        group_by_index: Option<usize>,
+    ) -> Result<(), JsValue> {
+        let Some(val) = js_to_i64(val) else {
+            tracing::error!("Unhandled int64 value");
+            return Ok(());
+        };
+
+        self.1
+            .lock()
+            .unwrap()
+            .as_mut()
+            .unwrap()
+            .set_col(name, group_by_index, index as usize, Int64Cell(val))
+            .unwrap();
+
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = "setDurationCol")]
+    pub fn set_duration_col(
+        &self,
+        name: &str,
+        index: u32,
+        val: JsValue,
+        group_by_index: Option<usize>,
+    ) -> Result<(), JsValue> {
+        let Some(val) = js_to_i64(val) else {
+            tracing::error!("Unhandled duration value");
+            return Ok(());
+        };
+
+        self.1
+            .lock()
+            .unwrap()
+            .as_mut()
+            .unwrap()
+            .set_col(name, group_by_index, index as usize, DurationCell(val))
+            .unwrap();
+
+        Ok(())
+    }
+}
+
+/// Reads a nullable `number` or `bigint` as an `i64`, or `None` if `val` is
+/// neither.
+fn js_to_i64(val: JsValue) -> Option<Option<i64>> {
+    if val.is_null() || val.is_undefined() {
+        Some(None)
+    } else if let Some(n) = val.as_f64() {
+        Some(Some(n as i64))
+    } else {
+        let n = val.dyn_into::<BigInt>().ok()?;
+        i64::try_from(n).ok().map(Some)
+    }
 }
 
 #[wasm_bindgen]
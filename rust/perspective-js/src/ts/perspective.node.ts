@@ -413,19 +413,49 @@ export function on_error(callback: Function) {
  * Create a read-only table from a JOIN of two source tables.
  * @param left - The left source table (a Table instance or a table name string).
  * @param right - The right source table (a Table instance or a table name string).
- * @param on
- * @param options - Optional join configuration: { join_type?: "inner"|"left"|"outer", name?: string }
+ * @param on - The join key column, or an array of columns for a composite key.
+ * @param options - Optional join configuration, see `JoinOptions`.
  * @returns
  */
 export function join(
     left: perspective_client.Table | string,
     right: perspective_client.Table | string,
-    on: string,
+    on: perspective_client.JoinKeys,
     options?: perspective_client.JoinOptions,
 ) {
     return SYNC_CLIENT.join(left as any, right as any, on, options);
 }
 
+/**
+ * Create a read-only table from the rows of several source tables with
+ * identical schemas.
+ * @param tables - The source tables (Table instances or table name strings).
+ * @param options - Optional union configuration, see `UnionOptions`.
+ * @returns
+ */
+export function union(
+    tables: (perspective_client.Table | string)[],
+    options?: perspective_client.UnionOptions,
+) {
+    return SYNC_CLIENT.union(tables as any, options);
+}
+
+/**
+ * Create a read-only table with one row for each element of a list column of
+ * a source table.
+ * @param table - The source table (a Table instance or a table name string).
+ * @param column - The `list_string` or `list_float` column to unnest.
+ * @param options - Optional unnest configuration, see `UnnestOptions`.
+ * @returns
+ */
+export function unnest(
+    table: perspective_client.Table | string,
+    column: string,
+    options?: perspective_client.UnnestOptions,
+) {
+    return SYNC_CLIENT.unnest(table as any, column, options);
+}
+
 /**
  * Create a table from the global Perspective instance.
  * @param init_data
@@ -510,6 +540,8 @@ export { perspective_client as wasmModule };
 export default {
     table,
     join,
+    union,
+    unnest,
     websocket,
     worker,
     get_hosted_table_names,
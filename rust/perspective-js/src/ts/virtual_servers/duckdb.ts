@@ -196,6 +196,7 @@ export class DuckDBHandler implements perspective.VirtualServerHandler {
             sort: true,
             expressions: true,
             group_rollup_mode: ["rollup", "flat", "total"],
+            nested_filters: true,
             filter_ops: {
                 integer: FILTER_OPS,
                 float: FILTER_OPS,
@@ -91,5 +91,6 @@ export async function websocket(
 
     client = new Client(send_message, on_close);
     await connect();
+    await client.handshake();
     return client;
 }
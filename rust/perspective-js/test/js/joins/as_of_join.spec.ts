@@ -0,0 +1,130 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+import { test, expect } from "@perspective-dev/test";
+import perspective from "../perspective_client.ts";
+
+const TRADES = [
+    { ticker: "A", ts: 1, qty: 100 },
+    { ticker: "A", ts: 5, qty: 200 },
+    { ticker: "B", ts: 3, qty: 300 },
+    { ticker: "A", ts: 0, qty: 50 },
+];
+
+const QUOTES = [
+    { ticker: "A", ts: 2, bid: 10.5 },
+    { ticker: "A", ts: 4, bid: 11 },
+    { ticker: "B", ts: 1, bid: 20 },
+];
+
+test.describe("As-of joins", function () {
+    test("left as-of join matches the latest prior row", async function () {
+        const trades = await perspective.table(TRADES);
+        const quotes = await perspective.table(QUOTES);
+        const joined = await perspective.join(trades, quotes, "ticker", {
+            join_type: "left",
+            as_of: "ts",
+        });
+
+        expect(await joined.schema()).toEqual({
+            ticker: "string",
+            ts: "integer",
+            qty: "integer",
+            bid: "float",
+        });
+
+        const view = await joined.view();
+        expect(await view.to_json()).toEqual([
+            { ticker: "A", ts: 1, qty: 100, bid: null },
+            { ticker: "A", ts: 5, qty: 200, bid: 11 },
+            { ticker: "B", ts: 3, qty: 300, bid: 20 },
+            { ticker: "A", ts: 0, qty: 50, bid: null },
+        ]);
+
+        await view.delete();
+        await joined.delete();
+        await quotes.delete();
+        await trades.delete();
+    });
+
+    test("inner as-of join reacts to right table updates", async function () {
+        const trades = await perspective.table(TRADES);
+        const quotes = await perspective.table(QUOTES);
+        const joined = await perspective.join(trades, quotes, "ticker", {
+            as_of: "ts",
+        });
+
+        const view = await joined.view();
+        expect(await view.to_json()).toEqual([
+            { ticker: "A", ts: 5, qty: 200, bid: 11 },
+            { ticker: "B", ts: 3, qty: 300, bid: 20 },
+        ]);
+
+        await quotes.update([{ ticker: "A", ts: 1, bid: 10 }]);
+        expect(await view.to_json()).toEqual([
+            { ticker: "A", ts: 1, qty: 100, bid: 10 },
+            { ticker: "A", ts: 5, qty: 200, bid: 11 },
+            { ticker: "B", ts: 3, qty: 300, bid: 20 },
+        ]);
+
+        await view.delete();
+        await joined.delete();
+        await quotes.delete();
+        await trades.delete();
+    });
+
+    test("as-of join on differently-named columns", async function () {
+        const trades = await perspective.table(TRADES);
+        const quotes = await perspective.table(
+            QUOTES.map(({ ticker, ts, bid }) => ({
+                symbol: ticker,
+                time: ts,
+                bid,
+            })),
+        );
+
+        const joined = await perspective.join(trades, quotes, "ticker", {
+            right_on: "symbol",
+            as_of: "ts",
+            right_as_of: "time",
+        });
+
+        const view = await joined.view();
+        expect(await view.to_json()).toEqual([
+            { ticker: "A", ts: 5, qty: 200, bid: 11 },
+            { ticker: "B", ts: 3, qty: 300, bid: 20 },
+        ]);
+
+        await view.delete();
+        await joined.delete();
+        await quotes.delete();
+        await trades.delete();
+    });
+
+    test("errors on outer as-of join", async function () {
+        const trades = await perspective.table(TRADES);
+        const quotes = await perspective.table(QUOTES);
+        let error;
+        try {
+            await perspective.join(trades, quotes, "ticker", {
+                join_type: "outer",
+                as_of: "ts",
+            });
+        } catch (e) {
+            error = e;
+        }
+
+        expect(error).toBeDefined();
+        await quotes.delete();
+        await trades.delete();
+    });
+});
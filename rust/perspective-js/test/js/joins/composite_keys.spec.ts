@@ -0,0 +1,129 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+import { test, expect } from "@perspective-dev/test";
+import perspective from "../perspective_client.ts";
+
+test.describe("Composite key joins", function () {
+    test("joins on multiple key columns", async function () {
+        const left = await perspective.table([
+            { date: "2024-01-01", ticker: "A", px: 1 },
+            { date: "2024-01-01", ticker: "B", px: 2 },
+            { date: "2024-01-02", ticker: "A", px: 3 },
+        ]);
+
+        const right = await perspective.table([
+            { date: "2024-01-01", ticker: "A", vol: 10 },
+            { date: "2024-01-02", ticker: "A", vol: 30 },
+            { date: "2024-01-02", ticker: "B", vol: 40 },
+        ]);
+
+        const joined = await perspective.join(left, right, ["date", "ticker"]);
+        const view = await joined.view();
+        const json = await view.to_json();
+
+        expect(json).toEqual([
+            { date: "2024-01-01", ticker: "A", px: 1, vol: 10 },
+            { date: "2024-01-02", ticker: "A", px: 3, vol: 30 },
+        ]);
+
+        await view.delete();
+        await joined.delete();
+        await right.delete();
+        await left.delete();
+    });
+
+    test("joins on differently-named composite keys", async function () {
+        const left = await perspective.table([
+            { date: "2024-01-01", ticker: "A", px: 1 },
+            { date: "2024-01-02", ticker: "B", px: 2 },
+        ]);
+
+        const right = await perspective.table([
+            { day: "2024-01-01", symbol: "A", vol: 10 },
+            { day: "2024-01-01", symbol: "B", vol: 20 },
+        ]);
+
+        const joined = await perspective.join(left, right, ["date", "ticker"], {
+            join_type: "left",
+            right_on_columns: ["day", "symbol"],
+        });
+
+        const schema = await joined.schema();
+        expect(schema).toEqual({
+            date: "string",
+            ticker: "string",
+            px: "integer",
+            vol: "integer",
+        });
+
+        const view = await joined.view();
+        const json = await view.to_json();
+        expect(json).toEqual([
+            { date: "2024-01-01", ticker: "A", px: 1, vol: 10 },
+            { date: "2024-01-02", ticker: "B", px: 2, vol: null },
+        ]);
+
+        await view.delete();
+        await joined.delete();
+        await right.delete();
+        await left.delete();
+    });
+
+    test("reacts to updates with composite keys", async function () {
+        const left = await perspective.table([
+            { date: "2024-01-01", ticker: "A", px: 1 },
+            { date: "2024-01-01", ticker: "B", px: 2 },
+        ]);
+
+        const right = await perspective.table([
+            { date: "2024-01-01", ticker: "A", vol: 10 },
+        ]);
+
+        const joined = await perspective.join(left, right, ["date", "ticker"]);
+        const view = await joined.view();
+        expect(await view.num_rows()).toEqual(1);
+
+        await right.update([{ date: "2024-01-01", ticker: "B", vol: 20 }]);
+        const json = await view.to_json();
+        expect(json).toEqual([
+            { date: "2024-01-01", ticker: "A", px: 1, vol: 10 },
+            { date: "2024-01-01", ticker: "B", px: 2, vol: 20 },
+        ]);
+
+        await view.delete();
+        await joined.delete();
+        await right.delete();
+        await left.delete();
+    });
+
+    test("errors when key column counts differ", async function () {
+        const left = await perspective.table({
+            date: "string",
+            ticker: "string",
+        });
+
+        const right = await perspective.table({ day: "string", vol: "float" });
+        let error;
+        try {
+            await perspective.join(left, right, ["date", "ticker"], {
+                right_on: "day",
+            });
+        } catch (e) {
+            error = e;
+        }
+
+        expect(error).toBeDefined();
+        await right.delete();
+        await left.delete();
+    });
+});
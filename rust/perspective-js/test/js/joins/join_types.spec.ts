@@ -0,0 +1,107 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+import { test, expect } from "@perspective-dev/test";
+import perspective from "../perspective_client.ts";
+
+test.describe("Right, semi and anti joins", function () {
+    test("right join keeps unmatched right rows", async function () {
+        const left = await perspective.table([
+            { id: 1, x: 10 },
+            { id: 2, x: 20 },
+            { id: 3, x: 30 },
+        ]);
+
+        const right = await perspective.table([
+            { id: 1, y: "a" },
+            { id: 1, y: "b" },
+            { id: 4, y: "d" },
+        ]);
+
+        const joined = await perspective.join(left, right, "id", {
+            join_type: "right",
+        });
+
+        const view = await joined.view();
+        const json = await view.to_json();
+        expect(json).toEqual([
+            { id: 1, x: 10, y: "a" },
+            { id: 1, x: 10, y: "b" },
+            { id: 4, x: null, y: "d" },
+        ]);
+
+        await view.delete();
+        await joined.delete();
+        await right.delete();
+        await left.delete();
+    });
+
+    test("semi join emits each matching left row once", async function () {
+        const left = await perspective.table([
+            { id: 1, x: 10 },
+            { id: 2, x: 20 },
+            { id: 3, x: 30 },
+        ]);
+
+        const right = await perspective.table([
+            { id: 1, y: "a" },
+            { id: 1, y: "b" },
+            { id: 4, y: "d" },
+        ]);
+
+        const joined = await perspective.join(left, right, "id", {
+            join_type: "semi",
+        });
+
+        expect(await joined.schema()).toEqual({ id: "integer", x: "integer" });
+        const view = await joined.view();
+        const json = await view.to_json();
+        expect(json).toEqual([{ id: 1, x: 10 }]);
+
+        await view.delete();
+        await joined.delete();
+        await right.delete();
+        await left.delete();
+    });
+
+    test("anti join emits unmatched left rows", async function () {
+        const left = await perspective.table([
+            { id: 1, x: 10 },
+            { id: 2, x: 20 },
+            { id: 3, x: 30 },
+        ]);
+
+        const right = await perspective.table([
+            { id: 1, y: "a" },
+            { id: 4, y: "d" },
+        ]);
+
+        const joined = await perspective.join(left, right, "id", {
+            join_type: "anti",
+        });
+
+        expect(await joined.schema()).toEqual({ id: "integer", x: "integer" });
+        const view = await joined.view();
+        expect(await view.to_json()).toEqual([
+            { id: 2, x: 20 },
+            { id: 3, x: 30 },
+        ]);
+
+        await right.update([{ id: 2, y: "b" }]);
+        expect(await view.to_json()).toEqual([{ id: 3, x: 30 }]);
+
+        await view.delete();
+        await joined.delete();
+        await right.delete();
+        await left.delete();
+    });
+});
@@ -0,0 +1,78 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+import { test, expect } from "@perspective-dev/test";
+import perspective from "../perspective_client.ts";
+
+test.describe("Join column suffixes", function () {
+    test("errors on conflicting columns without suffixes", async function () {
+        const left = await perspective.table({ id: "integer", value: "float" });
+        const right = await perspective.table({
+            id: "integer",
+            value: "string",
+        });
+
+        let error;
+        try {
+            await perspective.join(left, right, "id");
+        } catch (e) {
+            error = e;
+        }
+
+        expect(error).toBeDefined();
+        await right.delete();
+        await left.delete();
+    });
+
+    test("suffixes conflicting columns from both sides", async function () {
+        const left = await perspective.table([{ id: 1, value: 10 }]);
+        const right = await perspective.table([{ id: 1, value: "a" }]);
+        const joined = await perspective.join(left, right, "id", {
+            left_suffix: "_left",
+            right_suffix: "_right",
+        });
+
+        expect(await joined.schema()).toEqual({
+            id: "integer",
+            value_left: "integer",
+            value_right: "string",
+        });
+
+        const view = await joined.view();
+        expect(await view.to_json()).toEqual([
+            { id: 1, value_left: 10, value_right: "a" },
+        ]);
+
+        await view.delete();
+        await joined.delete();
+        await right.delete();
+        await left.delete();
+    });
+
+    test("suffixes only the right side", async function () {
+        const left = await perspective.table([{ id: 1, value: 10 }]);
+        const right = await perspective.table([{ id: 1, value: "a" }]);
+        const joined = await perspective.join(left, right, "id", {
+            right_suffix: "_right",
+        });
+
+        const view = await joined.view();
+        expect(await view.to_json()).toEqual([
+            { id: 1, value: 10, value_right: "a" },
+        ]);
+
+        await view.delete();
+        await joined.delete();
+        await right.delete();
+        await left.delete();
+    });
+});
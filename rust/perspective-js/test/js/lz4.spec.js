@@ -50,5 +50,37 @@ test.describe("Arrow IPC", function () {
             view.delete();
             table.delete();
         });
+
+        test("to_arrow() zstd compressed is serializable roundtrip", async () => {
+            let table = await perspective.table(
+                superstore_uncompressed.slice(),
+            );
+
+            let view = await table.view();
+            let arr = await view.to_arrow({ compression: "zstd" });
+            expect(arr.byteLength).toBeLessThan(
+                superstore_uncompressed.byteLength,
+            );
+
+            view.delete();
+            table.delete();
+            table = await perspective.table(arr);
+            expect(await table.size()).toEqual(9994);
+            table.delete();
+        });
+
+        test("to_arrow() rejects unknown compression", async () => {
+            const table = await perspective.table(
+                superstore_uncompressed.slice(),
+            );
+
+            const view = await table.view();
+            await expect(
+                view.to_arrow({ compression: "snappy" }),
+            ).rejects.toThrow();
+
+            view.delete();
+            table.delete();
+        });
     });
 });
@@ -0,0 +1,55 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+import { test, expect } from "@perspective-dev/test";
+import perspective from "../perspective_client";
+
+test.describe("to_parquet", () => {
+    test("roundtrips through Client::table", async () => {
+        const table = await perspective.table([
+            { x: 1, y: "a" },
+            { x: 2, y: "b" },
+        ]);
+        const view = await table.view();
+        const parquet = await view.to_parquet();
+        const magic = new TextDecoder().decode(new Uint8Array(parquet, 0, 4));
+        expect(magic).toEqual("PAR1");
+
+        const table2 = await perspective.table(parquet, { format: "parquet" });
+        const view2 = await table2.view();
+        expect(await view2.to_columns()).toEqual({
+            x: [1, 2],
+            y: ["a", "b"],
+        });
+
+        view2.delete();
+        table2.delete();
+        view.delete();
+        table.delete();
+    });
+
+    test("respects the viewport and compression options", async () => {
+        const table = await perspective.table({ x: [1, 2, 3, 4] });
+        const view = await table.view();
+        const parquet = await view.to_parquet(
+            { start_row: 1, end_row: 3 },
+            { compression: "gzip", row_group_size: 1 },
+        );
+
+        const table2 = await perspective.table(parquet, { format: "parquet" });
+        expect(await table2.size()).toEqual(2);
+        await table2.update(parquet, { format: "parquet" });
+        expect(await table2.size()).toEqual(4);
+        table2.delete();
+        view.delete();
+        table.delete();
+    });
+});
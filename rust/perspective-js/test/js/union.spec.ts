@@ -0,0 +1,117 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+import { test, expect } from "@perspective-dev/test";
+import perspective from "./perspective_client.ts";
+
+test.describe("Union", function () {
+    test("concatenates tables in order", async function () {
+        const a = await perspective.table([
+            { id: 1, x: 10 },
+            { id: 2, x: 20 },
+        ]);
+
+        const b = await perspective.table([{ id: 3, x: 30 }]);
+        const unioned = await perspective.union([a, b]);
+        const view = await unioned.view();
+        expect(await view.to_json()).toEqual([
+            { id: 1, x: 10 },
+            { id: 2, x: 20 },
+            { id: 3, x: 30 },
+        ]);
+
+        await view.delete();
+        await unioned.delete();
+        await b.delete();
+        await a.delete();
+    });
+
+    test("adds a source column", async function () {
+        const a = await perspective.table([{ x: 1 }], { name: "union_xnys" });
+        const b = await perspective.table([{ x: 2 }], { name: "union_xnas" });
+        const unioned = await perspective.union(["union_xnys", "union_xnas"], {
+            source_column: "venue",
+        });
+
+        expect(await unioned.schema()).toEqual({
+            x: "integer",
+            venue: "string",
+        });
+
+        const view = await unioned.view();
+        expect(await view.to_json()).toEqual([
+            { x: 1, venue: "union_xnys" },
+            { x: 2, venue: "union_xnas" },
+        ]);
+
+        await view.delete();
+        await unioned.delete();
+        await b.delete();
+        await a.delete();
+    });
+
+    test("reacts to source updates and removes", async function () {
+        const a = await perspective.table(
+            [
+                { id: 1, x: 10 },
+                { id: 2, x: 20 },
+            ],
+            { index: "id" },
+        );
+
+        const b = await perspective.table([{ id: 3, x: 30 }], { index: "id" });
+        const unioned = await perspective.union([a, b]);
+        const view = await unioned.view();
+        await b.update([{ id: 4, x: 40 }]);
+        await a.remove([1]);
+        expect(await view.to_json()).toEqual([
+            { id: 2, x: 20 },
+            { id: 3, x: 30 },
+            { id: 4, x: 40 },
+        ]);
+
+        await view.delete();
+        await unioned.delete();
+        await b.delete();
+        await a.delete();
+    });
+
+    test("errors on mismatched schemas", async function () {
+        const a = await perspective.table({ x: "integer" });
+        const b = await perspective.table({ x: "string" });
+        let error;
+        try {
+            await perspective.union([a, b]);
+        } catch (e) {
+            error = e;
+        }
+
+        expect(error).toBeDefined();
+        await b.delete();
+        await a.delete();
+    });
+
+    test("prevents deleting a source table", async function () {
+        const a = await perspective.table([{ x: 1 }]);
+        const unioned = await perspective.union([a]);
+        let error;
+        try {
+            await a.delete();
+        } catch (e) {
+            error = e;
+        }
+
+        expect(error).toBeDefined();
+        await unioned.delete();
+        await a.delete();
+    });
+});
@@ -390,6 +390,16 @@ def join(*args, **kwargs):
     return GLOBAL_CLIENT.join(*args, **kwargs)
 
 
+@functools.wraps(Client.union)
+def union(*args, **kwargs):
+    return GLOBAL_CLIENT.union(*args, **kwargs)
+
+
+@functools.wraps(Client.unnest)
+def unnest(*args, **kwargs):
+    return GLOBAL_CLIENT.unnest(*args, **kwargs)
+
+
 @functools.wraps(Client.system_info)
 def system_info(*args, **kwargs):
     return GLOBAL_CLIENT.system_info(*args, **kwargs)
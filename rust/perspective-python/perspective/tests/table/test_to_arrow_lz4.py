@@ -11,6 +11,7 @@
 #  ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
 import perspective as psp
+from pytest import raises
 
 client = psp.Server().new_local_client()
 Table = client.table
@@ -30,3 +31,17 @@ class TestToArrowLZ4(object):
         tbl3 = Table(arr)
         arr3 = tbl3.view().to_arrow(compression="lz4")
         assert len(arr3) == len(arr)
+
+    def test_to_arrow_zstd_roundtrip(self, superstore):
+        tbl = Table(superstore.to_dict(orient="records"))
+        arrow_uncompressed = tbl.view().to_arrow()
+        arr = tbl.view().to_arrow(compression="zstd")
+        assert len(arr) < len(arrow_uncompressed)
+        tbl2 = Table(arr)
+        assert tbl2.size() == tbl.size()
+        assert tbl2.view().to_arrow() == arrow_uncompressed
+
+    def test_to_arrow_unknown_compression(self, superstore):
+        tbl = Table(superstore.to_dict(orient="records"))
+        with raises(psp.PerspectiveError):
+            tbl.view().to_arrow(compression="snappy")
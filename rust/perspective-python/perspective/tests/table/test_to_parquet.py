@@ -0,0 +1,55 @@
+#  ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+#  ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+#  ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+#  ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+#  ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+#  ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+#  ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+#  ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+#  ┃ This file is part of the Perspective library, distributed under the terms ┃
+#  ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+#  ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+import io
+
+import pyarrow.parquet as pq
+import pytest
+
+import perspective as psp
+
+client = psp.Server().new_local_client()
+Table = client.table
+
+
+class TestToParquet(object):
+    def test_to_parquet_roundtrip(self):
+        tbl = Table({"a": [1, 2, 3], "b": ["x", "y", "z"]})
+        parquet = tbl.view().to_parquet()
+        assert parquet[:4] == b"PAR1"
+        tbl2 = Table(parquet, format="parquet")
+        assert tbl2.view().to_columns() == {"a": [1, 2, 3], "b": ["x", "y", "z"]}
+
+    def test_to_parquet_is_readable_by_pyarrow(self):
+        tbl = Table({"a": [1.5, 2.5], "b": [True, False]})
+        parquet = tbl.view().to_parquet(compression="gzip", start_row=1)
+        arrow = pq.read_table(io.BytesIO(parquet))
+        assert arrow.to_pydict() == {"a": [2.5], "b": [False]}
+
+    @pytest.mark.parametrize(
+        "compression", ["uncompressed", "snappy", "gzip", "lz4", "zstd", "brotli"]
+    )
+    def test_to_parquet_compression(self, compression):
+        tbl = Table({"a": list(range(100))})
+        parquet = tbl.view().to_parquet(compression=compression, row_group_size=10)
+        assert pq.ParquetFile(io.BytesIO(parquet)).num_row_groups == 10
+        assert Table(parquet, format="parquet").size() == 100
+
+    def test_to_parquet_unknown_compression(self):
+        tbl = Table({"a": [1]})
+        with pytest.raises(Exception):
+            tbl.view().to_parquet(compression="lzma")
+
+    def test_update_parquet(self):
+        tbl = Table({"a": [1, 2]})
+        tbl.update(tbl.view().to_parquet(), format="parquet")
+        assert tbl.size() == 4
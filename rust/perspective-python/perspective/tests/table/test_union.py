@@ -0,0 +1,131 @@
+#  ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+#  ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+#  ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+#  ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+#  ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+#  ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+#  ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+#  ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+#  ┃ This file is part of the Perspective library, distributed under the terms ┃
+#  ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+#  ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+import pytest
+
+import perspective as psp
+
+client = psp.Server().new_local_client()
+
+
+class TestUnion:
+    def test_union_concatenates_tables(self):
+        a = client.table([{"id": 1, "x": 10}, {"id": 2, "x": 20}])
+        b = client.table([{"id": 3, "x": 30}])
+        unioned = client.union([a, b])
+        view = unioned.view()
+        assert view.to_json() == [
+            {"id": 1, "x": 10},
+            {"id": 2, "x": 20},
+            {"id": 3, "x": 30},
+        ]
+        view.delete()
+        unioned.delete()
+        b.delete()
+        a.delete()
+
+    def test_union_source_column(self):
+        a = client.table([{"x": 1}], name="xnys")
+        b = client.table([{"x": 2}], name="xnas")
+        unioned = client.union(["xnys", "xnas"], source_column="venue")
+        assert unioned.schema() == {"x": "integer", "venue": "string"}
+        view = unioned.view()
+        assert view.to_json() == [
+            {"x": 1, "venue": "xnys"},
+            {"x": 2, "venue": "xnas"},
+        ]
+        view.delete()
+        unioned.delete()
+        b.delete()
+        a.delete()
+
+    def test_union_reacts_to_updates_and_removes(self):
+        a = client.table([{"id": 1, "x": 10}, {"id": 2, "x": 20}], index="id")
+        b = client.table([{"id": 3, "x": 30}], index="id")
+        unioned = client.union([a, b])
+        view = unioned.view()
+        b.update([{"id": 4, "x": 40}])
+        a.remove([1])
+        assert view.to_json() == [
+            {"id": 2, "x": 20},
+            {"id": 3, "x": 30},
+            {"id": 4, "x": 40},
+        ]
+        view.delete()
+        unioned.delete()
+        b.delete()
+        a.delete()
+
+    def test_union_keeps_rows_grouped_by_source(self):
+        a = client.table([{"id": 1, "x": 10}])
+        b = client.table([{"id": 2, "x": 20}])
+        unioned = client.union([a, b])
+        view = unioned.view()
+        a.update([{"id": 3, "x": 30}])
+        b.update([{"id": 4, "x": 40}])
+        assert view.to_json() == [
+            {"id": 1, "x": 10},
+            {"id": 3, "x": 30},
+            {"id": 2, "x": 20},
+            {"id": 4, "x": 40},
+        ]
+        view.delete()
+        unioned.delete()
+        b.delete()
+        a.delete()
+
+    def test_union_applies_updates_in_place(self):
+        a = client.table([{"id": 1, "x": 10}, {"id": 2, "x": 20}], index="id")
+        b = client.table([{"id": 1, "x": 30}], index="id")
+        unioned = client.union([a, b])
+        view = unioned.view()
+        a.update([{"id": 1, "x": None}, {"id": 2, "x": 21}])
+        assert view.to_json() == [
+            {"id": 1, "x": None},
+            {"id": 2, "x": 21},
+            {"id": 1, "x": 30},
+        ]
+        a.remove([1])
+        a.update([{"id": 1, "x": 11}])
+        assert unioned.size() == 3
+        view.delete()
+        unioned.delete()
+        b.delete()
+        a.delete()
+
+    def test_union_reacts_to_replace(self):
+        a = client.table([{"x": 1}, {"x": 2}])
+        b = client.table([{"x": 3}])
+        unioned = client.union([a, b])
+        view = unioned.view()
+        a.replace([{"x": 4}])
+        assert view.to_json() == [{"x": 4}, {"x": 3}]
+        view.delete()
+        unioned.delete()
+        b.delete()
+        a.delete()
+
+    def test_union_schema_mismatch_raises(self):
+        a = client.table({"x": "integer"})
+        b = client.table({"x": "string"})
+        with pytest.raises(psp.PerspectiveError):
+            client.union([a, b])
+        b.delete()
+        a.delete()
+
+    def test_union_is_read_only(self):
+        a = client.table([{"x": 1}])
+        unioned = client.union([a])
+        with pytest.raises(psp.PerspectiveError):
+            unioned.update([{"x": 2}])
+        unioned.delete()
+        a.delete()
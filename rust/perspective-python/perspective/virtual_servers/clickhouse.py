@@ -103,6 +103,7 @@ class ClickhouseVirtualServerHandler(VirtualServerHandler):
             "sort": True,
             "expressions": True,
             "group_rollup_mode": ["rollup", "flat", "total"],
+            "nested_filters": True,
             "filter_ops": {
                 "integer": FILTER_OPS,
                 "float": FILTER_OPS,
@@ -114,6 +114,7 @@ class DuckDBVirtualServerHandler(VirtualServerHandler):
             "sort": True,
             "expressions": True,
             "group_rollup_mode": ["rollup", "flat", "total"],
+            "nested_filters": True,
             "filter_ops": {
                 "integer": FILTER_OPS,
                 "float": FILTER_OPS,
@@ -17,9 +17,10 @@ use std::sync::Arc;
 
 use futures::FutureExt;
 use perspective_client::{
-    Client, ColumnWindow, DeleteOptions, OnUpdateData, OnUpdateMode, OnUpdateOptions, Table,
-    TableData, TableInitOptions, TableReadFormat, TableRef, UpdateData, UpdateOptions, View,
-    ViewWindow, assert_table_api, assert_view_api, asyncfn,
+    Client, ColumnWindow, DeleteOptions, OnUpdateData, OnUpdateMode, OnUpdateOptions,
+    ParquetCompression, ParquetWriteOptions, Table, TableData, TableInitOptions, TableReadFormat,
+    TableRef, UpdateData, UpdateOptions, View, ViewWindow, assert_table_api, assert_view_api,
+    asyncfn,
 };
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
@@ -169,6 +170,11 @@ impl AsyncClient {
     ///       and byte array alternative inputs.
     ///     - `page_to_disk` - Back this [`Table`]'s canonical data with the
     ///       on-disk (memory-mapped) storage backend instead of memory.
+    ///     - `retention` - A `dict` of the server-side retention policy for
+    ///       this [`Table`]: `idle_timeout` (seconds) deletes it once unused,
+    ///       `max_rows` removes the earliest inserted rows in excess, and
+    ///       `age_column` with `max_age` (seconds) removes rows older than
+    ///       `max_age`.
     ///
     /// # Python Examples
     ///
@@ -177,7 +183,8 @@ impl AsyncClient {
     /// ```python
     /// table = await client.table("x,y\n1,2\n3,4")
     /// ```
-    #[pyo3(signature=(input, limit=None, index=None, name=None, format=None, page_to_disk=None))]
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature=(input, limit=None, index=None, name=None, format=None, page_to_disk=None, retention=None))]
     pub async fn table(
         &self,
         input: Py<PyAny>,
@@ -186,6 +193,7 @@ impl AsyncClient {
         name: Option<Py<PyString>>,
         format: Option<Py<PyString>>,
         page_to_disk: Option<bool>,
+        retention: Option<Py<PyDict>>,
     ) -> PyResult<AsyncTable> {
         let client = self.client.clone();
         let py_client = Python::with_gil(|_| self.clone());
@@ -193,6 +201,7 @@ impl AsyncClient {
             let mut options = TableInitOptions {
                 name: name.map(|x| x.extract::<String>(py)).transpose()?,
                 page_to_disk,
+                retention: retention.map(|x| depythonize(x.bind(py))).transpose()?,
                 ..TableInitOptions::default()
             };
 
@@ -264,31 +273,118 @@ impl AsyncClient {
     /// ```python
     /// joined = await client.join(orders_table, products_table, "Product ID", "left")
     /// ```
-    #[pyo3(signature = (left, right, on, join_type=None, name=None, right_on=None))]
+    #[pyo3(signature = (left, right, on, join_type=None, name=None, right_on=None, left_suffix=None, right_suffix=None, as_of=None, right_as_of=None))]
+    #[allow(clippy::too_many_arguments, reason = "This is a Python API")]
     pub async fn join(
         &self,
         left: Py<PyAny>,
         right: Py<PyAny>,
-        on: String,
+        on: Py<PyAny>,
         join_type: Option<String>,
         name: Option<String>,
-        right_on: Option<String>,
+        right_on: Option<Py<PyAny>>,
+        left_suffix: Option<String>,
+        right_suffix: Option<String>,
+        as_of: Option<String>,
+        right_as_of: Option<String>,
     ) -> PyResult<AsyncTable> {
-        let (left_ref, right_ref) = Python::with_gil(|py| {
+        let (left_ref, right_ref, on, right_on) = Python::with_gil(|py| {
             let left_ref = py_to_table_ref_from_owned(py, &left)?;
             let right_ref = py_to_table_ref_from_owned(py, &right)?;
-            Ok::<_, PyErr>((left_ref, right_ref))
+            let on = super::client_sync::py_to_join_keys(on.bind(py))?;
+            let right_on = right_on
+                .map(|x| super::client_sync::py_to_join_keys(x.bind(py)))
+                .transpose()?;
+
+            Ok::<_, PyErr>((left_ref, right_ref, on, right_on))
         })?;
         let jt = super::client_sync::parse_join_type(join_type.as_deref())?;
         let options = perspective_client::JoinOptions {
             join_type: Some(jt),
             name,
-            right_on,
+            left_suffix,
+            right_suffix,
+            as_of,
+            right_as_of,
+            ..perspective_client::JoinOptions::default()
+        };
+
+        let options = match right_on {
+            Some(right_on) => options.with_right_on(right_on),
+            None => options,
+        };
+        let py_client = self.clone();
+        let table = self
+            .client
+            .join(left_ref, right_ref, on, options)
+            .await
+            .into_pyerr()?;
+        Ok(AsyncTable {
+            table: Arc::new(table),
+            client: py_client,
+        })
+    }
+
+    /// Creates a new read-only [`Table`] by concatenating the rows of
+    /// several source tables, which must have identical schemas. The
+    /// resulting table is reactive: when any source table is updated, the
+    /// union is automatically recomputed.
+    ///
+    /// # Python Examples
+    ///
+    /// ```python
+    /// venues = await client.union([xnys_table, xnas_table], source_column="venue")
+    /// ```
+    #[pyo3(signature = (tables, name=None, source_column=None))]
+    pub async fn union(
+        &self,
+        tables: Vec<Py<PyAny>>,
+        name: Option<String>,
+        source_column: Option<String>,
+    ) -> PyResult<AsyncTable> {
+        let tables = Python::with_gil(|py| {
+            tables
+                .iter()
+                .map(|x| py_to_table_ref_from_owned(py, x))
+                .collect::<PyResult<Vec<_>>>()
+        })?;
+
+        let options = perspective_client::UnionOptions {
+            name,
+            source_column,
         };
+
+        let py_client = self.clone();
+        let table = self.client.union(tables, options).await.into_pyerr()?;
+        Ok(AsyncTable {
+            table: Arc::new(table),
+            client: py_client,
+        })
+    }
+
+    /// Creates a new read-only [`Table`] with one row for each element of a
+    /// `list_string` or `list_float` column of a source table. The resulting
+    /// table is reactive: when the source table is updated, it is
+    /// automatically recomputed.
+    ///
+    /// # Python Examples
+    ///
+    /// ```python
+    /// tags = await client.unnest(orders_table, "tags")
+    /// ```
+    #[pyo3(signature = (table, column, name=None))]
+    pub async fn unnest(
+        &self,
+        table: Py<PyAny>,
+        column: String,
+        name: Option<String>,
+    ) -> PyResult<AsyncTable> {
+        let table_ref = Python::with_gil(|py| py_to_table_ref_from_owned(py, &table))?;
+        let options = perspective_client::UnnestOptions { name };
         let py_client = self.clone();
         let table = self
             .client
-            .join(left_ref, right_ref, &on, options)
+            .unnest(table_ref, &column, options)
             .await
             .into_pyerr()?;
         Ok(AsyncTable {
@@ -353,6 +449,14 @@ impl AsyncClient {
             .into_pyerr()
     }
 
+    /// Exchange protocol versions with the [`perspective_server::Server`],
+    /// raising if the two cannot interoperate. This otherwise happens lazily
+    /// on first use.
+    pub async fn handshake(&self) -> PyResult<()> {
+        self.client.handshake().await.into_pyerr()?;
+        Ok(())
+    }
+
     /// Provides the [`SystemInfo`] struct, implementation-specific metadata
     /// about the [`perspective_server::Server`] runtime such as Memory and
     /// CPU usage.
@@ -764,7 +868,8 @@ impl AsyncView {
     /// A copy of the config object passed to the [`Table::view`] method which
     /// created this [`View`].
     pub async fn get_config(&self) -> PyResult<Py<PyAny>> {
-        let config = self.view.get_config().await.into_pyerr()?;
+        let mut config = self.view.get_config().await.into_pyerr()?;
+        config.downgrade_temporal_scalars();
         Python::with_gil(|py| Ok(pythonize::pythonize(py, &config)?.unbind()))
     }
 
@@ -777,8 +882,8 @@ impl AsyncView {
         let (min, max) = self.view.get_min_max(name).await.into_pyerr()?;
         Python::with_gil(|py| {
             Ok((
-                super::client_sync::scalar_to_py(py, &min),
-                super::client_sync::scalar_to_py(py, &max),
+                super::client_sync::scalar_to_py(py, &min)?,
+                super::client_sync::scalar_to_py(py, &max)?,
             ))
         })
     }
@@ -934,6 +1039,36 @@ impl AsyncView {
         Ok(Python::with_gil(|py| PyBytes::new(py, &arrow).into()))
     }
 
+    /// Serializes a [`View`] to the Apache Parquet format.
+    #[pyo3(signature=(compression=None, row_group_size=None, **window))]
+    pub async fn to_parquet(
+        &self,
+        compression: Option<Py<PyString>>,
+        row_group_size: Option<usize>,
+        window: Option<Py<PyDict>>,
+    ) -> PyResult<Py<PyBytes>> {
+        let (window, compression) = Python::with_gil(|py| {
+            let window: ViewWindow = window
+                .map(|x| depythonize(x.bind(py)))
+                .transpose()?
+                .unwrap_or_default();
+
+            let compression: Option<ParquetCompression> = compression
+                .map(|x| depythonize(x.bind(py).as_any()))
+                .transpose()?;
+
+            PyResult::Ok((window, compression))
+        })?;
+
+        let options = ParquetWriteOptions {
+            compression,
+            row_group_size,
+        };
+
+        let parquet = self.view.to_parquet(window, options).await.into_pyerr()?;
+        Ok(Python::with_gil(|py| PyBytes::new(py, &parquet).into()))
+    }
+
     /// Serializes this [`View`] to CSV data in a standard format.
     #[pyo3(signature=(**window))]
     pub async fn to_csv(&self, window: Option<Py<PyDict>>) -> PyResult<String> {
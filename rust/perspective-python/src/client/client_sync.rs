@@ -15,7 +15,7 @@ use std::future::Future;
 use std::sync::Arc;
 
 use perspective_client::config::Scalar;
-use perspective_client::{JoinType, TableRef, assert_table_api, assert_view_api};
+use perspective_client::{JoinKeys, JoinType, TableRef, assert_table_api, assert_view_api};
 #[cfg(doc)]
 use perspective_client::{TableInitOptions, UpdateOptions, config::ViewConfigUpdate};
 use pyo3::exceptions::PyTypeError;
@@ -44,6 +44,9 @@ pub(crate) fn parse_join_type(join_type: Option<&str>) -> PyResult<JoinType> {
     match join_type {
         Some("left") => Ok(JoinType::Left),
         Some("outer") => Ok(JoinType::Outer),
+        Some("right") => Ok(JoinType::Right),
+        Some("semi") => Ok(JoinType::Semi),
+        Some("anti") => Ok(JoinType::Anti),
         None | Some("inner") => Ok(JoinType::Inner),
         Some(other) => Err(pyo3::exceptions::PyValueError::new_err(format!(
             "Unknown join type: \"{}\"",
@@ -52,15 +55,39 @@ pub(crate) fn parse_join_type(join_type: Option<&str>) -> PyResult<JoinType> {
     }
 }
 
-pub(crate) fn scalar_to_py(py: Python<'_>, scalar: &Scalar) -> PyObject {
-    match scalar {
-        Scalar::Float(x) => x.into_pyobject(py).unwrap().into_any().unbind(),
-        Scalar::String(x) => x.into_pyobject(py).unwrap().into_any().unbind(),
-        Scalar::Bool(x) => x.into_pyobject(py).unwrap().to_owned().into_any().unbind(),
-        Scalar::Null => py.None(),
+/// Extract a join key, either a column name or a list of column names.
+pub(crate) fn py_to_join_keys(on: &Bound<'_, PyAny>) -> PyResult<JoinKeys> {
+    if let Ok(column) = on.extract::<String>() {
+        Ok(JoinKeys::Column(column))
+    } else {
+        Ok(JoinKeys::Columns(on.extract::<Vec<String>>()?))
     }
 }
 
+pub(crate) fn scalar_to_py(py: Python<'_>, scalar: &Scalar) -> PyResult<PyObject> {
+    Ok(match scalar {
+        Scalar::Float(x) => x.into_pyobject(py)?.into_any().unbind(),
+        Scalar::String(x) => x.into_pyobject(py)?.into_any().unbind(),
+        Scalar::Bool(x) => x.into_pyobject(py)?.to_owned().into_any().unbind(),
+        Scalar::Date(x) => {
+            let (year, month, day) = x.ymd();
+            PyDate::new(py, year, month as u8, day as u8)?
+                .into_any()
+                .unbind()
+        },
+        Scalar::Datetime(x) => {
+            // Always timezone-aware, in UTC.
+            let datetime = py.import("datetime")?;
+            let utc = datetime.getattr("timezone")?.getattr("utc")?;
+            datetime
+                .getattr("datetime")?
+                .call_method1("fromtimestamp", (x.timestamp_millis() as f64 / 1000.0, utc))?
+                .unbind()
+        },
+        Scalar::Null => py.None(),
+    })
+}
+
 pub(crate) trait PyFutureExt: Future {
     fn py_block_on(self, py: Python<'_>) -> Self::Output
     where
@@ -157,6 +184,11 @@ impl Client {
     ///       `"json"`, `"columns"`, `"csv"` or `"arrow"`. This overrides
     ///       language-specific type dispatch behavior, which allows stringified
     ///       and byte array alternative inputs.
+    ///     - `retention` - A `dict` of the server-side retention policy for
+    ///       this [`Table`]: `idle_timeout` (seconds) deletes it once unused,
+    ///       `max_rows` removes the earliest inserted rows in excess, and
+    ///       `age_column` with `max_age` (seconds) removes rows older than
+    ///       `max_age`.
     ///
     /// # Python Examples
     ///
@@ -166,7 +198,7 @@ impl Client {
     /// table = client.table("x,y\n1,2\n3,4")
     /// ```
     #[allow(clippy::too_many_arguments)]
-    #[pyo3(signature = (input, limit=None, index=None, name=None, format=None, page_to_disk=None))]
+    #[pyo3(signature = (input, limit=None, index=None, name=None, format=None, page_to_disk=None, retention=None))]
     pub fn table(
         &self,
         py: Python<'_>,
@@ -176,10 +208,11 @@ impl Client {
         name: Option<Py<PyString>>,
         format: Option<Py<PyString>>,
         page_to_disk: Option<bool>,
+        retention: Option<Py<PyDict>>,
     ) -> PyResult<Table> {
         Ok(Table(
             self.0
-                .table(input, limit, index, name, format, page_to_disk)
+                .table(input, limit, index, name, format, page_to_disk, retention)
                 .py_block_on(py)?,
         ))
     }
@@ -206,35 +239,132 @@ impl Client {
     /// source tables. The resulting table is reactive: when either source
     /// table is updated, the join is automatically recomputed.
     ///
+    /// `on` (and `right_on`, if the right table's key columns are named
+    /// differently) may be a column name or a list of column names for a
+    /// composite key. `join_type` is one of `"inner"`, `"left"`,
+    /// `"outer"`, `"right"`, `"semi"` or `"anti"`. Conflicting column names
+    /// are disambiguated by `left_suffix` and `right_suffix`, and `as_of`
+    /// makes this an as-of join on a sorted column.
+    ///
     /// # Python Examples
     ///
     /// ```python
     /// joined = client.join(orders_table, products_table, "Product ID", "left")
+    /// quotes = client.join(trades, quotes, ["date", "ticker"], "left", as_of="timestamp")
     /// ```
-    #[pyo3(signature = (left, right, on, join_type=None, name=None, right_on=None))]
+    #[pyo3(signature = (left, right, on, join_type=None, name=None, right_on=None, left_suffix=None, right_suffix=None, as_of=None, right_as_of=None))]
     #[allow(clippy::too_many_arguments, reason = "This is a Python API")]
     pub fn join(
         &self,
         py: Python<'_>,
         left: &Bound<'_, PyAny>,
         right: &Bound<'_, PyAny>,
-        on: String,
+        on: &Bound<'_, PyAny>,
         join_type: Option<String>,
         name: Option<String>,
-        right_on: Option<String>,
+        right_on: Option<&Bound<'_, PyAny>>,
+        left_suffix: Option<String>,
+        right_suffix: Option<String>,
+        as_of: Option<String>,
+        right_as_of: Option<String>,
     ) -> PyResult<Table> {
         let left_ref = py_to_table_ref(left)?;
         let right_ref = py_to_table_ref(right)?;
+        let on = py_to_join_keys(on)?;
         let jt = parse_join_type(join_type.as_deref())?;
         let options = perspective_client::JoinOptions {
             join_type: Some(jt),
             name,
-            right_on,
+            left_suffix,
+            right_suffix,
+            as_of,
+            right_as_of,
+            ..perspective_client::JoinOptions::default()
+        };
+
+        let options = match right_on {
+            Some(right_on) => options.with_right_on(py_to_join_keys(right_on)?),
+            None => options,
         };
         let table = self
             .0
             .client
-            .join(left_ref, right_ref, &on, options)
+            .join(left_ref, right_ref, on, options)
+            .py_block_on(py)
+            .into_pyerr()?;
+        Ok(Table(AsyncTable {
+            table: Arc::new(table),
+            client: self.0.clone(),
+        }))
+    }
+
+    /// Creates a new read-only [`Table`] by concatenating the rows of
+    /// several source tables, which must have identical schemas. The
+    /// resulting table is reactive: when any source table is updated, the
+    /// union is automatically recomputed.
+    ///
+    /// `source_column` adds a `str` column holding each row's source table
+    /// name.
+    ///
+    /// # Python Examples
+    ///
+    /// ```python
+    /// venues = client.union([xnys_table, xnas_table], source_column="venue")
+    /// ```
+    #[pyo3(signature = (tables, name=None, source_column=None))]
+    pub fn union(
+        &self,
+        py: Python<'_>,
+        tables: Vec<Bound<'_, PyAny>>,
+        name: Option<String>,
+        source_column: Option<String>,
+    ) -> PyResult<Table> {
+        let tables = tables
+            .iter()
+            .map(py_to_table_ref)
+            .collect::<PyResult<Vec<_>>>()?;
+
+        let options = perspective_client::UnionOptions {
+            name,
+            source_column,
+        };
+
+        let table = self
+            .0
+            .client
+            .union(tables, options)
+            .py_block_on(py)
+            .into_pyerr()?;
+        Ok(Table(AsyncTable {
+            table: Arc::new(table),
+            client: self.0.clone(),
+        }))
+    }
+
+    /// Creates a new read-only [`Table`] with one row for each element of a
+    /// `list_string` or `list_float` column of a source table. The resulting
+    /// table is reactive: when the source table is updated, it is
+    /// automatically recomputed.
+    ///
+    /// # Python Examples
+    ///
+    /// ```python
+    /// tags = client.unnest(orders_table, "tags")
+    /// ```
+    #[pyo3(signature = (table, column, name=None))]
+    pub fn unnest(
+        &self,
+        py: Python<'_>,
+        table: Bound<'_, PyAny>,
+        column: String,
+        name: Option<String>,
+    ) -> PyResult<Table> {
+        let table_ref = py_to_table_ref(&table)?;
+        let options = perspective_client::UnnestOptions { name };
+        let table = self
+            .0
+            .client
+            .unnest(table_ref, &column, options)
             .py_block_on(py)
             .into_pyerr()?;
         Ok(Table(AsyncTable {
@@ -274,6 +404,13 @@ impl Client {
             .py_block_on(py)
     }
 
+    /// Exchange protocol versions with the [`perspective_server::Server`],
+    /// raising if the two cannot interoperate. This otherwise happens lazily
+    /// on first use.
+    pub fn handshake(&self, py: Python<'_>) -> PyResult<()> {
+        self.0.handshake().py_block_on(py)
+    }
+
     /// Provides the [`SystemInfo`] struct, implementation-specific metadata
     /// about the [`perspective_server::Server`] runtime such as Memory and
     /// CPU usage.
@@ -669,6 +806,27 @@ impl View {
         self.0.to_arrow(window).py_block_on(py)
     }
 
+    /// Renders this [`View`] as the Apache Parquet format.
+    ///
+    /// # Arguments
+    ///
+    /// - `compression` - One of `"uncompressed"`, `"snappy"` (the default),
+    ///   `"gzip"`, `"lz4"`, `"zstd"` or `"brotli"`.
+    /// - `row_group_size` - The maximum number of rows per row group.
+    /// - `window` - a [`ViewWindow`]
+    #[pyo3(signature = (compression=None, row_group_size=None, **window))]
+    pub fn to_parquet(
+        &self,
+        py: Python<'_>,
+        compression: Option<Py<PyString>>,
+        row_group_size: Option<usize>,
+        window: Option<Py<PyDict>>,
+    ) -> PyResult<Py<PyBytes>> {
+        self.0
+            .to_parquet(compression, row_group_size, window)
+            .py_block_on(py)
+    }
+
     /// Delete this [`View`] and clean up all resources associated with it.
     /// [`View`] objects do not stop consuming resources or processing
     /// updates when they are garbage collected - you must call this method
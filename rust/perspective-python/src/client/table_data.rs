@@ -34,6 +34,7 @@ fn psp_type_from_py_type(_py: Python<'_>, val: Bound<'_, PyAny>) -> PyResult<Col
             ("builtins", "bool") => Ok(ColumnType::Boolean),
             ("datetime", "date") => Ok(ColumnType::Date),
             ("datetime", "datetime") => Ok(ColumnType::Datetime),
+            ("datetime", "timedelta") => Ok(ColumnType::Duration),
             (modname, typename) => Err(PyTypeError::new_err(format!("{modname}.{typename}"))),
         }
     } else {
@@ -34,6 +34,10 @@ fn from_arrow(
         Some(TableReadFormat::ColumnsString) => {
             Ok(Some(UpdateData::JsonColumns(String::from_utf8(vec)?)))
         },
+        Some(TableReadFormat::Parquet) => Ok(Some(UpdateData::Parquet {
+            data: vec.into(),
+            columns: None,
+        })),
         None | Some(TableReadFormat::Arrow) => Ok(Some(UpdateData::Arrow(vec.into()))),
     }
 }
@@ -49,6 +53,9 @@ fn from_string(
         Some(TableReadFormat::Ndjson) => Ok(Some(UpdateData::Ndjson(string))),
         Some(TableReadFormat::ColumnsString) => Ok(Some(UpdateData::JsonColumns(string))),
         Some(TableReadFormat::Arrow) => Ok(Some(UpdateData::Arrow(string.into_bytes().into()))),
+        Some(TableReadFormat::Parquet) => Err(PyValueError::new_err(
+            "Parquet data must be `bytes`, not `str`",
+        )),
     }
 }
 
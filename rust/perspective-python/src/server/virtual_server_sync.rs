@@ -17,12 +17,13 @@ use chrono::{DateTime, TimeZone, Utc};
 use indexmap::IndexMap;
 use perspective_client::proto::{ColumnType, HostedTable};
 use perspective_client::virtual_server::{
-    Features, ResultExt, RowPathStyle, VirtualDataSlice, VirtualServer, VirtualServerFuture,
-    VirtualServerHandler,
+    DurationCell, Features, Int64Cell, ResultExt, RowPathStyle, VirtualDataSlice, VirtualServer,
+    VirtualServerFuture, VirtualServerHandler,
 };
 use pyo3::exceptions::PyValueError;
 use pyo3::types::{
-    PyAnyMethods, PyBytes, PyDate, PyDict, PyDictMethods, PyList, PyListMethods, PyString,
+    PyAnyMethods, PyBytes, PyDate, PyDateTime, PyDelta, PyDict, PyDictMethods, PyList,
+    PyListMethods, PyString,
 };
 use pyo3::{IntoPyObject, Py, PyAny, PyErr, PyResult, Python, pyclass, pymethods};
 use serde::Serialize;
@@ -36,6 +37,32 @@ fn py_to_scalar(val: pyo3::Bound<'_, PyAny>) -> PyResult<perspective_client::con
         Ok(perspective_client::config::Scalar::Float(f))
     } else if let Ok(s) = val.extract::<String>() {
         Ok(perspective_client::config::Scalar::String(s))
+    } else if val.downcast::<PyDateTime>().is_ok() {
+        // Naive datetimes are interpreted as UTC.
+        let py = val.py();
+        let val = if val.getattr("tzinfo")?.is_none() {
+            let utc = py.import("datetime")?.getattr("timezone")?.getattr("utc")?;
+            let kwargs = PyDict::new(py);
+            kwargs.set_item("tzinfo", utc)?;
+            val.call_method("replace", (), Some(&kwargs))?
+        } else {
+            val
+        };
+
+        let seconds = val.call_method0("timestamp")?.extract::<f64>()?;
+        Ok(perspective_client::config::Scalar::Datetime(
+            perspective_client::config::Datetime::from_timestamp_millis(
+                (seconds * 1000.0).round() as i64
+            ),
+        ))
+    } else if val.downcast::<PyDate>().is_ok() {
+        perspective_client::config::Date::from_ymd(
+            val.getattr("year")?.extract()?,
+            val.getattr("month")?.extract()?,
+            val.getattr("day")?.extract()?,
+        )
+        .map(perspective_client::config::Scalar::Date)
+        .ok_or_else(|| PyValueError::new_err("Invalid date"))
     } else {
         Ok(perspective_client::config::Scalar::Null)
     }
@@ -78,12 +105,16 @@ impl VirtualServerHandler for PyServerHandler {
                                 entity_id: x.to_string(),
                                 index: None,
                                 limit: None,
+                                derived: false,
+                                retention: None,
                             }
                         } else {
                             HostedTable {
                                 entity_id: x.get_item("name")?.to_string(),
                                 index: x.get_item("index").ok().and_then(|x| x.extract().ok()),
                                 limit: x.get_item("limit").ok().and_then(|x| x.extract().ok()),
+                                derived: false,
+                                retention: None,
                             }
                         })
                     })
@@ -436,6 +467,8 @@ impl PyVirtualDataSlice {
             "date" => self.set_datetime_col(name, index, val, grouping_id),
             "datetime" => self.set_datetime_col(name, index, val, grouping_id),
             "boolean" => self.set_boolean_col(name, index, val, grouping_id),
+            "int64" => self.set_int64_col(name, index, val, grouping_id),
+            "duration" => self.set_duration_col(name, index, val, grouping_id),
             _ => Err(PyValueError::new_err("Unknown type")),
         }
     }
@@ -606,6 +639,79 @@ impl PyVirtualDataSlice {
             Ok(())
         })
     }
+
+    #[pyo3(signature=(name, index, val, grouping_id = None))]
+    pub fn set_int64_col(
+        &self,
+        name: &str,
+        index: u32,
+        val: Py<PyAny>,
+        grouping_id: Option<usize>,
+    ) -> PyResult<()> {
+        Python::with_gil(|py| {
+            if val.is_none(py) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .set_col(name, grouping_id, index as usize, Int64Cell(None))
+                    .unwrap();
+            } else if let Ok(val) = val.extract::<i64>(py) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .set_col(name, grouping_id, index as usize, Int64Cell(Some(val)))
+                    .unwrap();
+            } else {
+                tracing::error!("Unhandled")
+            };
+
+            Ok(())
+        })
+    }
+
+    /// Sets a `"duration"` value from a `datetime.timedelta` or a number of
+    /// milliseconds.
+    #[pyo3(signature=(name, index, val, grouping_id = None))]
+    pub fn set_duration_col(
+        &self,
+        name: &str,
+        index: u32,
+        val: Py<PyAny>,
+        grouping_id: Option<usize>,
+    ) -> PyResult<()> {
+        Python::with_gil(|py| {
+            if val.is_none(py) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .set_col(name, grouping_id, index as usize, DurationCell(None))
+                    .unwrap();
+            } else if let Ok(val) = val.downcast_bound::<PyDelta>(py) {
+                let seconds: f64 = val.call_method0("total_seconds")?.extract()?;
+                let millis = (seconds * 1000.0).round() as i64;
+                self.0
+                    .lock()
+                    .unwrap()
+                    .set_col(
+                        name,
+                        grouping_id,
+                        index as usize,
+                        DurationCell(Some(millis)),
+                    )
+                    .unwrap();
+            } else if let Ok(val) = val.extract::<i64>(py) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .set_col(name, grouping_id, index as usize, DurationCell(Some(val)))
+                    .unwrap();
+            } else {
+                tracing::error!("Unhandled")
+            };
+
+            Ok(())
+        })
+    }
 }
 
 #[pyclass(name = "VirtualServer")]
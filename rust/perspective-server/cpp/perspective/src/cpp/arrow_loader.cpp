@@ -16,6 +16,7 @@
 #include <arrow/array/array_nested.h>
 #include <arrow/array/array_primitive.h>
 #include <arrow/type.h>
+#include <arrow/type_traits.h>
 #include <arrow/type_fwd.h>
 #include <cstdint>
 #include <exception>
@@ -25,6 +26,7 @@
 #include <perspective/arrow_loader.h>
 #include "perspective/exception.h"
 #include <sstream>
+#include <type_traits>
 #include <rapidjson/stringbuffer.h>
 #include <rapidjson/writer.h>
 
@@ -186,6 +188,9 @@ convert_type(const std::string& src) {
     if (src == "timestamp") {
         return DTYPE_TIME;
     }
+    if (src == "duration") {
+        return DTYPE_INT64;
+    }
     if (src == "time32" || src == "time64" || src == "time32[s]" ) {
         return DTYPE_UINT32;
     }
@@ -195,7 +200,7 @@ convert_type(const std::string& src) {
     if (src == "null") {
         return DTYPE_STR;
     }
-    if (src == "list") {
+    if (src == "list" || src == "large_list") {
         return DTYPE_STR;
     }
     std::stringstream ss;
@@ -323,112 +328,136 @@ iter_col_copy(
     }
 }
 
+/**
+ * Copy a `list` or `large_list` array into a `DTYPE_STR` column as JSON array
+ * text, calling `write_elem(writer, idx)` for each non-null element. Null
+ * lists are skipped, and are marked invalid by the caller's validity pass.
+ */
+template <typename L, typename F>
 void
-copy_string_list(
-    std::shared_ptr<arrow::ListArray>& list,
+copy_list(
+    const std::shared_ptr<L>& list,
     const std::shared_ptr<t_column>& dest,
-    const int64_t num_rows
+    const int64_t offset,
+    const int64_t num_rows,
+    F write_elem
 ) {
-    const auto typed_data =
-        std::static_pointer_cast<::arrow::StringArray>(list->values());
-
-    // the raw bytes of the strings.
-    const uint8_t* raw_data = typed_data->value_data()->data();
-    // col_offsets are the column level offsets, so each element is the boundary
-    // of a row.
-    const auto* col_offsets = list->raw_value_offsets();
-    // these are the offset locations of the strings within the raw data.
-    // each element is the boundary of a string.
-    const auto* string_offsets = typed_data->raw_value_offsets();
-
-    for (uint32_t i = 0; i < num_rows; i++) {
-        rapidjson::StringBuffer s;
-        rapidjson::Writer<rapidjson::StringBuffer> writer(s);
-        writer.StartArray();
-        auto row_array_length = col_offsets[i + 1] - col_offsets[i];
-        for (uint32_t j = 0; j < row_array_length; j++) {
-            const auto elem_location = col_offsets[i] + j;
-            const auto start_loc = string_offsets[elem_location];
-            const auto string_len =
-                string_offsets[elem_location + 1] - start_loc;
-            writer.String((const char*)raw_data + start_loc, string_len);
+    const auto& values = list->values();
+    for (int64_t i = 0; i < num_rows; i++) {
+        if (list->IsNull(i)) {
+            continue;
         }
-        writer.EndArray();
-        dest->set_nth(i, s.GetString());
-    }
-}
 
-template <typename T>
-void
-copy_integer_list(
-    std::shared_ptr<arrow::ListArray>& list,
-    const std::shared_ptr<t_column>& dest,
-    const int64_t num_rows
-) {
-    const auto typed_data = std::static_pointer_cast<T>(list->values());
-    const auto* array_offsets = list->raw_value_offsets();
-    const auto* raw_values = typed_data->raw_values();
-    for (uint32_t i = 0; i < num_rows; i++) {
         rapidjson::StringBuffer s;
         rapidjson::Writer<rapidjson::StringBuffer> writer(s);
         writer.StartArray();
-        const auto row_array_length = array_offsets[i + 1] - array_offsets[i];
-        for (uint32_t j = 0; j < row_array_length; j++) {
-            const auto elem_location = array_offsets[i] + j;
-            const auto elem = raw_values[elem_location];
-            writer.Int64(elem);
+        const auto begin = list->value_offset(i);
+        const auto end = begin + list->value_length(i);
+        for (auto j = begin; j < end; j++) {
+            if (values->IsNull(j)) {
+                writer.Null();
+            } else {
+                write_elem(writer, j);
+            }
         }
-        writer.EndArray();
-        dest->set_nth(i, s.GetString());
-    }
-}
 
-void
-copy_bool_list(
-    std::shared_ptr<arrow::ListArray>& list,
-    const std::shared_ptr<t_column>& dest,
-    const int64_t num_rows
-) {
-    const auto typed_data =
-        std::static_pointer_cast<arrow::BooleanArray>(list->values());
-    const auto* array_offsets = list->raw_value_offsets();
-    for (uint32_t i = 0; i < num_rows; i++) {
-        rapidjson::StringBuffer s;
-        rapidjson::Writer<rapidjson::StringBuffer> writer(s);
-        writer.StartArray();
-        const auto row_array_length = array_offsets[i + 1] - array_offsets[i];
-        for (uint32_t j = 0; j < row_array_length; j++) {
-            const auto elem_location = array_offsets[i] + j;
-            const auto elem = typed_data->Value(elem_location);
-            writer.Bool(elem);
-        }
         writer.EndArray();
-        dest->set_nth(i, s.GetString());
+        dest->set_nth(offset + i, s.GetString());
     }
 }
 
-template <typename T>
+template <typename L>
 void
-copy_float_list(
-    std::shared_ptr<arrow::ListArray>& list,
+copy_list_array(
+    const std::shared_ptr<L>& list,
     const std::shared_ptr<t_column>& dest,
-    const int64_t num_rows
+    const int64_t offset,
+    const int64_t len
 ) {
-    const auto typed_data = std::static_pointer_cast<T>(list->values());
-    const auto* array_offsets = list->raw_value_offsets();
-    const auto* raw_values = typed_data->raw_values();
-    for (uint32_t i = 0; i < num_rows; i++) {
-        rapidjson::StringBuffer s;
-        rapidjson::Writer<rapidjson::StringBuffer> writer(s);
-        writer.StartArray();
-        const auto row_array_length = array_offsets[i + 1] - array_offsets[i];
-        for (uint32_t j = 0; j < row_array_length; j++) {
-            const auto elem_location = array_offsets[i] + j;
-            const auto elem = raw_values[elem_location];
-            writer.Double(elem);
-        }
-        writer.EndArray();
-        dest->set_nth(i, s.GetString());
+    using t_writer = rapidjson::Writer<rapidjson::StringBuffer>;
+    auto copy_numbers = [&](auto* tag) {
+        using T = std::remove_pointer_t<decltype(tag)>;
+        auto values = std::static_pointer_cast<T>(list->values());
+        copy_list(list, dest, offset, len, [&](t_writer& writer, int64_t j) {
+            writer.Double(static_cast<double>(values->Value(j)));
+        });
+    };
+
+    auto copy_integers = [&](auto* tag) {
+        using T = std::remove_pointer_t<decltype(tag)>;
+        auto values = std::static_pointer_cast<T>(list->values());
+        copy_list(list, dest, offset, len, [&](t_writer& writer, int64_t j) {
+            writer.Int64(static_cast<std::int64_t>(values->Value(j)));
+        });
+    };
+
+    auto copy_strings = [&](auto* tag) {
+        using T = std::remove_pointer_t<decltype(tag)>;
+        auto values = std::static_pointer_cast<T>(list->values());
+        copy_list(list, dest, offset, len, [&](t_writer& writer, int64_t j) {
+            auto value = values->GetView(j);
+            writer.String(
+                value.data(), static_cast<rapidjson::SizeType>(value.size())
+            );
+        });
+    };
+
+    switch (list->value_type()->id()) {
+        case arrow::BooleanType::type_id: {
+            auto values =
+                std::static_pointer_cast<arrow::BooleanArray>(list->values());
+            copy_list(
+                list,
+                dest,
+                offset,
+                len,
+                [&](t_writer& writer, int64_t j) {
+                    writer.Bool(values->Value(j));
+                }
+            );
+        } break;
+        case arrow::HalfFloatType::type_id: {
+            copy_numbers(static_cast<arrow::HalfFloatArray*>(nullptr));
+        } break;
+        case arrow::FloatType::type_id: {
+            copy_numbers(static_cast<arrow::FloatArray*>(nullptr));
+        } break;
+        case arrow::DoubleType::type_id: {
+            copy_numbers(static_cast<arrow::DoubleArray*>(nullptr));
+        } break;
+        case ::arrow::Int8Type::type_id: {
+            copy_integers(static_cast<arrow::Int8Array*>(nullptr));
+        } break;
+        case ::arrow::UInt8Type::type_id: {
+            copy_integers(static_cast<arrow::UInt8Array*>(nullptr));
+        } break;
+        case ::arrow::Int16Type::type_id: {
+            copy_integers(static_cast<arrow::Int16Array*>(nullptr));
+        } break;
+        case ::arrow::UInt16Type::type_id: {
+            copy_integers(static_cast<arrow::UInt16Array*>(nullptr));
+        } break;
+        case ::arrow::Int32Type::type_id: {
+            copy_integers(static_cast<arrow::Int32Array*>(nullptr));
+        } break;
+        case ::arrow::UInt32Type::type_id: {
+            copy_integers(static_cast<arrow::UInt32Array*>(nullptr));
+        } break;
+        case ::arrow::Int64Type::type_id: {
+            copy_integers(static_cast<arrow::Int64Array*>(nullptr));
+        } break;
+        case ::arrow::StringType::type_id: {
+            copy_strings(static_cast<arrow::StringArray*>(nullptr));
+        } break;
+        case ::arrow::LargeStringType::type_id: {
+            copy_strings(static_cast<arrow::LargeStringArray*>(nullptr));
+        } break;
+        default:
+            auto val = list->value_type();
+            std::stringstream ss;
+            ss << "Given Unsupported type '" << *val << "'.\n";
+            auto cc = ss.str();
+            PSP_COMPLAIN_AND_ABORT(cc);
     }
 }
 
@@ -441,43 +470,20 @@ copy_array(
 ) {
     switch (src->type()->id()) {
         case arrow::ListType::type_id: {
-            auto list = std::static_pointer_cast<::arrow::ListArray>(src);
-
-            switch (list->value_type()->id()) {
-                case arrow::BooleanType::type_id: {
-                    copy_bool_list(list, dest, len);
-                } break;
-                case arrow::HalfFloatType::type_id: {
-                    copy_float_list<arrow::HalfFloatArray>(list, dest, len);
-                } break;
-                case arrow::FloatType::type_id: {
-                    copy_float_list<arrow::FloatArray>(list, dest, len);
-                } break;
-                case arrow::DoubleType::type_id: {
-                    copy_float_list<arrow::DoubleArray>(list, dest, len);
-                } break;
-                case ::arrow::Int8Type::type_id: {
-                    copy_integer_list<arrow::Int8Array>(list, dest, len);
-                } break;
-                case ::arrow::Int16Type::type_id: {
-                    copy_integer_list<arrow::Int16Array>(list, dest, len);
-                } break;
-                case ::arrow::Int32Type::type_id: {
-                    copy_integer_list<arrow::Int32Array>(list, dest, len);
-                } break;
-                case ::arrow::UInt32Type::type_id: {
-                    copy_integer_list<arrow::UInt32Array>(list, dest, len);
-                } break;
-                case ::arrow::StringType::type_id: {
-                    copy_string_list(list, dest, len);
-                } break;
-                default:
-                    auto val = list->value_type();
-                    std::stringstream ss;
-                    ss << "Given Unsupported type '" << *val << "'.\n";
-                    auto cc = ss.str();
-                    PSP_COMPLAIN_AND_ABORT(cc);
-            }
+            copy_list_array(
+                std::static_pointer_cast<::arrow::ListArray>(src),
+                dest,
+                offset,
+                len
+            );
+        } break;
+        case arrow::LargeListType::type_id: {
+            copy_list_array(
+                std::static_pointer_cast<::arrow::LargeListArray>(src),
+                dest,
+                offset,
+                len
+            );
         } break;
         case arrow::DictionaryType::type_id: {
             auto dictionary_type =
@@ -711,6 +717,36 @@ copy_array(
                 } break;
             }
         } break;
+        case arrow::DurationType::type_id: {
+            std::shared_ptr<arrow::DurationType> tunit =
+                std::static_pointer_cast<arrow::DurationType>(src->type());
+            auto scol = std::static_pointer_cast<arrow::DurationArray>(src);
+            const int64_t* vals = scol->raw_values();
+            switch (tunit->unit()) {
+                case arrow::TimeUnit::MILLI: {
+                    std::memcpy(
+                        dest->get_nth<std::int64_t>(offset),
+                        (void*)vals,
+                        len * 8
+                    );
+                } break;
+                case arrow::TimeUnit::NANO: {
+                    for (uint32_t i = 0; i < len; i++) {
+                        dest->set_nth<int64_t>(offset + i, vals[i] / 1000000);
+                    }
+                } break;
+                case arrow::TimeUnit::MICRO: {
+                    for (uint32_t i = 0; i < len; i++) {
+                        dest->set_nth<int64_t>(offset + i, vals[i] / 1000);
+                    }
+                } break;
+                case arrow::TimeUnit::SECOND: {
+                    for (uint32_t i = 0; i < len; i++) {
+                        dest->set_nth<int64_t>(offset + i, vals[i] * 1000);
+                    }
+                } break;
+            }
+        } break;
         case arrow::Date64Type::type_id: {
             std::shared_ptr<arrow::Date64Type> date_type =
                 std::static_pointer_cast<arrow::Date64Type>(src->type());
@@ -998,4 +1034,35 @@ ArrowLoader::types() const {
     return m_types;
 }
 
+std::map<std::string, t_logical_type>
+ArrowLoader::logical_types() const {
+    std::map<std::string, t_logical_type> logical_types;
+    for (const auto& field : m_table->schema()->fields()) {
+        switch (field->type()->id()) {
+            case arrow::DurationType::type_id: {
+                logical_types[field->name()] =
+                    t_logical_type{LOGICAL_TYPE_DURATION};
+            } break;
+            case arrow::ListType::type_id:
+            case arrow::LargeListType::type_id: {
+                auto value_type =
+                    std::static_pointer_cast<arrow::BaseListType>(field->type())
+                        ->value_type();
+
+                bool is_numeric = arrow::is_integer(value_type->id())
+                    || arrow::is_floating(value_type->id());
+
+                logical_types[field->name()] = t_logical_type{
+                    is_numeric ? LOGICAL_TYPE_LIST_FLOAT
+                               : LOGICAL_TYPE_LIST_STRING
+                };
+            } break;
+            default:
+                break;
+        }
+    }
+
+    return logical_types;
+}
+
 } // namespace perspective::apachearrow
\ No newline at end of file
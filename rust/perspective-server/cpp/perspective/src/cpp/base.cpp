@@ -279,6 +279,19 @@ dtype_to_str(t_dtype dtype) {
     return ss.str();
 }
 
+t_dtype
+logical_type_to_dtype(t_logical_type_tag tag) {
+    switch (tag) {
+        case LOGICAL_TYPE_LIST_STRING:
+        case LOGICAL_TYPE_LIST_FLOAT:
+            return DTYPE_STR;
+        case LOGICAL_TYPE_INT64:
+        case LOGICAL_TYPE_DURATION:
+        default:
+            return DTYPE_INT64;
+    }
+}
+
 t_dtype
 str_to_dtype(const std::string& typestring) {
     // returns most commonly used types in the JS/python public APIs.
@@ -355,6 +368,14 @@ filter_op_to_str(t_filter_op op) {
         case FILTER_OP_IS_NOT_NULL: {
             return "is not null";
         } break;
+        // `contains` on a list column is parsed as `list contains`, but is
+        // reported as the op the client requested.
+        case FILTER_OP_LIST_CONTAINS: {
+            return "contains";
+        } break;
+        case FILTER_OP_LIST_CONTAINS_ANY: {
+            return "contains any";
+        } break;
     }
     PSP_COMPLAIN_AND_ABORT("Reached end of function");
     return "";
@@ -395,6 +416,12 @@ str_to_filter_op(const std::string& str) {
     if (str == "not in") {
         return t_filter_op::FILTER_OP_NOT_IN;
     }
+    if (str == "list contains") {
+        return t_filter_op::FILTER_OP_LIST_CONTAINS;
+    }
+    if (str == "contains any") {
+        return t_filter_op::FILTER_OP_LIST_CONTAINS_ANY;
+    }
     if (str == "&" || str == "and") {
         return t_filter_op::FILTER_OP_AND;
     }
@@ -102,6 +102,18 @@ psp_poll(ProtoServer* server) {
     return encode_api_responses(responses);
 }
 
+PERSPECTIVE_EXPORT
+void
+psp_set_eviction_session(ProtoServer* server, std::uint32_t client_id) {
+    server->set_eviction_session(client_id);
+}
+
+PERSPECTIVE_EXPORT
+void
+psp_set_now(ProtoServer* server, std::int64_t now_ms) {
+    server->set_now(now_ms);
+}
+
 PERSPECTIVE_EXPORT
 std::size_t
 psp_residency_prepare(ProtoServer* server) {
@@ -22,8 +22,54 @@
 
 #include <sstream>
 #include <utility>
+#include <tsl/hopscotch_map.h>
 namespace perspective {
 
+/**
+ * @brief The results of a `FILTER_OP_LIST_CONTAINS` or
+ * `FILTER_OP_LIST_CONTAINS_ANY` term, by the vocab index of the (interned)
+ * list value, so each distinct list's JSON is parsed once per filter pass
+ * rather than once per row.
+ */
+using t_list_filter_cache = tsl::hopscotch_map<t_uindex, bool>;
+
+/**
+ * @brief Evaluate `ft` against row `ridx` of `column`, which must be the
+ * column of `ft.m_colname`.
+ */
+static bool
+eval_fterm(
+    const t_fterm& ft,
+    const t_column& column,
+    t_uindex ridx,
+    t_list_filter_cache& list_cache
+) {
+    t_tscalar cell_val;
+    if (ft.m_use_interned) {
+        cell_val.set(*(column.get_nth<t_uindex>(ridx)));
+        cell_val.set_status(*(column.get_nth_status(ridx)));
+        return ft(cell_val);
+    }
+
+    bool is_list_op = ft.m_op == FILTER_OP_LIST_CONTAINS
+        || ft.m_op == FILTER_OP_LIST_CONTAINS_ANY;
+
+    if (is_list_op && column.get_dtype() == DTYPE_STR
+        && column.is_valid(ridx)) {
+        auto vocab_idx = *(column.get_nth<t_uindex>(ridx));
+        auto cached = list_cache.find(vocab_idx);
+        if (cached != list_cache.end()) {
+            return cached->second;
+        }
+
+        bool rv = ft(column.get_scalar(ridx));
+        list_cache.emplace(vocab_idx, rv);
+        return rv;
+    }
+
+    return ft(column.get_scalar(ridx));
+}
+
 void
 t_data_table::set_capacity(t_uindex idx) {
     m_capacity = idx;
@@ -544,10 +590,9 @@ t_data_table::filter_cpp(
         }
     }
 
+    std::vector<t_list_filter_cache> list_caches(fterm_size);
     switch (combiner) {
         case FILTER_OP_AND: {
-            t_tscalar cell_val;
-
             for (t_uindex ridx = 0, rloop_end = size(); ridx < rloop_end;
                  ++ridx) {
                 bool pass = true;
@@ -557,21 +602,10 @@ t_data_table::filter_cpp(
                         break;
                     }
 
-                    // TODO we can make this faster by not constructing these on
-                    // every iteration?
-
-                    const auto& ft = fterms[cidx];
-                    bool tval;
-
-                    if (ft.m_use_interned) {
-                        cell_val.set(*(columns[cidx]->get_nth<t_uindex>(ridx)));
-                        cell_val.set_status(*(columns[cidx]->get_nth_status(ridx
-                        )));
-                    } else {
-                        cell_val = columns[cidx]->get_scalar(ridx);
-                    }
+                    bool tval = eval_fterm(
+                        fterms[cidx], *columns[cidx], ridx, list_caches[cidx]
+                    );
 
-                    tval = ft(cell_val);
                     if (!tval) {
                         pass = false;
                         break;
@@ -586,8 +620,12 @@ t_data_table::filter_cpp(
                  ++ridx) {
                 bool pass = false;
                 for (t_uindex cidx = 0; cidx < fterm_size; ++cidx) {
-                    t_tscalar cell_val = columns[cidx]->get_scalar(ridx);
-                    if (fterms[cidx](cell_val)) {
+                    if (eval_fterm(
+                            fterms[cidx],
+                            *columns[cidx],
+                            ridx,
+                            list_caches[cidx]
+                        )) {
                         pass = true;
                         break;
                     }
@@ -603,11 +641,59 @@ t_data_table::filter_cpp(
     return mask;
 }
 
+t_mask
+t_data_table::filter_cpp(
+    const t_filter_tree& filter_tree, const std::vector<t_fterm>& fterms_
+) const {
+    auto* self = const_cast<t_data_table*>(this);
+    auto fterms = fterms_;
+
+    t_mask mask(size());
+    t_uindex fterm_size = fterms.size();
+    std::vector<const t_column*> columns(fterm_size);
+
+    for (t_uindex idx = 0; idx < fterm_size; ++idx) {
+        columns[idx] = _get_const_column(fterms[idx].m_colname);
+        fterms[idx].coerce_numeric(columns[idx]->get_dtype());
+        if (fterms[idx].m_use_interned) {
+            t_tscalar& thr = fterms[idx].m_threshold;
+            auto col = self->get_column(fterms[idx].m_colname);
+            auto interned = col->get_interned(thr.get_char_ptr());
+            thr.set(interned);
+        }
+    }
+
+    // Evaluate every leaf term for the row, then reduce them by the tree.
+    std::vector<bool> leaf_results(fterm_size);
+    std::vector<t_list_filter_cache> list_caches(fterm_size);
+    for (t_uindex ridx = 0, rloop_end = size(); ridx < rloop_end; ++ridx) {
+        for (t_uindex cidx = 0; cidx < fterm_size; ++cidx) {
+            leaf_results[cidx] = eval_fterm(
+                fterms[cidx], *columns[cidx], ridx, list_caches[cidx]
+            );
+        }
+
+        mask.set(ridx, filter_tree.evaluate(leaf_results));
+    }
+
+    return mask;
+}
+
 t_uindex
 t_data_table::get_capacity() const {
     return m_capacity;
 }
 
+t_uindex
+t_data_table::nbytes() const {
+    t_uindex rv = 0;
+    for (const auto& column : m_columns) {
+        rv += column->nbytes();
+    }
+
+    return rv;
+}
+
 t_data_table*
 t_data_table::clone_(const t_mask& mask) const {
     PSP_TRACE_SENTINEL();
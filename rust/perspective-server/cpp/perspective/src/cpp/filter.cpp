@@ -13,10 +13,76 @@
 #include <perspective/first.h>
 #include <perspective/filter.h>
 
+#include <cstdlib>
+#include <cstring>
 #include <utility>
+#include <rapidjson/document.h>
 
 namespace perspective {
 
+static bool
+list_element_eq(const rapidjson::Value& elem, const t_tscalar& needle) {
+    if (!needle.is_valid()) {
+        return false;
+    }
+
+    if (elem.IsNumber()) {
+        if (needle.is_numeric()) {
+            return elem.GetDouble() == needle.to_double();
+        }
+
+        // A `string` needle, e.g. from a text filter input, matches a
+        // numeric element if it parses to the same number.
+        if (needle.get_dtype() == DTYPE_STR) {
+            const char* text = needle.get_char_ptr();
+            char* end = nullptr;
+            double value = std::strtod(text, &end);
+            return end != text && *end == '\0' && value == elem.GetDouble();
+        }
+
+        return false;
+    }
+
+    if (elem.IsString() && needle.get_dtype() == DTYPE_STR) {
+        return std::strcmp(elem.GetString(), needle.get_char_ptr()) == 0;
+    }
+
+    return false;
+}
+
+bool
+list_contains_any(
+    const t_tscalar& list, const t_tscalar* needles, t_uindex num_needles
+) {
+    if (!list.is_valid() || list.get_dtype() != DTYPE_STR) {
+        return false;
+    }
+
+    const char* text = list.get_char_ptr();
+    rapidjson::Document doc;
+    doc.Parse(text);
+    if (doc.HasParseError() || !doc.IsArray()) {
+        rapidjson::Value elem(rapidjson::StringRef(text));
+        for (t_uindex i = 0; i < num_needles; ++i) {
+            if (list_element_eq(elem, needles[i])) {
+                return true;
+            }
+        }
+
+        return false;
+    }
+
+    for (const auto& elem : doc.GetArray()) {
+        for (t_uindex i = 0; i < num_needles; ++i) {
+            if (list_element_eq(elem, needles[i])) {
+                return true;
+            }
+        }
+    }
+
+    return false;
+}
+
 t_fterm::t_fterm() = default;
 
 t_fterm::t_fterm(
@@ -74,12 +140,14 @@ t_fterm::get_expr() const {
         case FILTER_OP_GTEQ:
         case FILTER_OP_EQ:
         case FILTER_OP_NE:
-        case FILTER_OP_CONTAINS: {
+        case FILTER_OP_CONTAINS:
+        case FILTER_OP_LIST_CONTAINS: {
             ss << filter_op_to_str(m_op) << " ";
             ss << m_threshold.to_string(true);
         } break;
         case FILTER_OP_NOT_IN:
-        case FILTER_OP_IN: {
+        case FILTER_OP_IN:
+        case FILTER_OP_LIST_CONTAINS_ANY: {
             ss << " " << filter_op_to_str(m_op) << " (";
             for (auto v : m_bag) {
                 ss << v.to_string(true) << ", ";
@@ -99,6 +167,60 @@ t_fterm::get_expr() const {
     return ss.str();
 }
 
+t_filter_tree::t_filter_tree() :
+    m_op(FILTER_OP_AND),
+    m_negated(false),
+    m_is_leaf(false),
+    m_fterm_idx(0) {}
+
+t_filter_tree
+t_filter_tree::leaf(t_uindex fterm_idx) {
+    t_filter_tree tree;
+    tree.m_is_leaf = true;
+    tree.m_fterm_idx = fterm_idx;
+    return tree;
+}
+
+t_filter_tree
+t_filter_tree::group(
+    t_filter_op op, bool negated, std::vector<t_filter_tree> children
+) {
+    PSP_VERBOSE_ASSERT(
+        op == FILTER_OP_AND || op == FILTER_OP_OR,
+        "Filter group op must be `and` or `or`"
+    );
+
+    t_filter_tree tree;
+    tree.m_op = op;
+    tree.m_negated = negated;
+    tree.m_children = std::move(children);
+    return tree;
+}
+
+bool
+t_filter_tree::is_leaf() const {
+    return m_is_leaf;
+}
+
+bool
+t_filter_tree::evaluate(const std::vector<bool>& leaf_results) const {
+    if (m_is_leaf) {
+        return leaf_results[m_fterm_idx];
+    }
+
+    // Empty groups are the identity of their combiner, e.g. an empty `and`
+    // passes every row, matching an empty flat filter list.
+    bool rv = m_op == FILTER_OP_AND;
+    for (const auto& child : m_children) {
+        if (child.evaluate(leaf_results) != rv) {
+            rv = !rv;
+            break;
+        }
+    }
+
+    return m_negated ? !rv : rv;
+}
+
 t_filter::t_filter() : m_mode(SELECT_MODE_ALL) {}
 
 t_filter::t_filter(const std::vector<std::string>& columns) :
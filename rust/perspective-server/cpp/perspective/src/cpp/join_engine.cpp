@@ -16,6 +16,11 @@
 #include "perspective/gnode.h"
 #include "perspective/scalar.h"
 #include <algorithm>
+#include <cstdlib>
+#include <limits>
+#include <rapidjson/document.h>
+#include <rapidjson/stringbuffer.h>
+#include <rapidjson/writer.h>
 #include <sstream>
 #include <tsl/hopscotch_map.h>
 #include <tsl/hopscotch_set.h>
@@ -32,18 +37,19 @@ copy_column_typed(
     const t_column* src,
     const std::vector<std::pair<t_uindex, t_uindex>>& matched_rows,
     t_uindex num_matched,
-    bool use_first
+    bool use_first,
+    t_uindex offset
 ) {
     for (t_uindex i = 0; i < num_matched; ++i) {
         auto src_idx = use_first ? matched_rows[i].first : matched_rows[i].second;
         if (src_idx == static_cast<t_uindex>(-1)) {
-            dst->clear(i);
+            dst->clear(offset + i);
         } else {
             const T* val = src->get_nth<T>(src_idx);
             const t_status* st =
                 src->is_status_enabled() ? src->get_nth_status(src_idx)
                                          : nullptr;
-            dst->set_nth<T>(i, *val, st ? *st : STATUS_VALID);
+            dst->set_nth<T>(offset + i, *val, st ? *st : STATUS_VALID);
         }
     }
 }
@@ -54,14 +60,15 @@ copy_column_str(
     const t_column* src,
     const std::vector<std::pair<t_uindex, t_uindex>>& matched_rows,
     t_uindex num_matched,
-    bool use_first
+    bool use_first,
+    t_uindex offset
 ) {
     for (t_uindex i = 0; i < num_matched; ++i) {
         auto src_idx = use_first ? matched_rows[i].first : matched_rows[i].second;
         if (src_idx == static_cast<t_uindex>(-1)) {
-            dst->clear(i);
+            dst->clear(offset + i);
         } else {
-            dst->set_scalar(i, src->get_scalar(src_idx));
+            dst->set_scalar(offset + i, src->get_scalar(src_idx));
         }
     }
 }
@@ -72,77 +79,78 @@ copy_column_dispatch(
     const t_column* src,
     const std::vector<std::pair<t_uindex, t_uindex>>& matched_rows,
     t_uindex num_matched,
-    bool use_first
+    bool use_first,
+    t_uindex offset = 0
 ) {
     switch (dst->get_dtype()) {
         case DTYPE_INT64:
             copy_column_typed<std::int64_t>(
-                dst, src, matched_rows, num_matched, use_first
+                dst, src, matched_rows, num_matched, use_first, offset
             );
             break;
         case DTYPE_INT32:
             copy_column_typed<std::int32_t>(
-                dst, src, matched_rows, num_matched, use_first
+                dst, src, matched_rows, num_matched, use_first, offset
             );
             break;
         case DTYPE_INT16:
             copy_column_typed<std::int16_t>(
-                dst, src, matched_rows, num_matched, use_first
+                dst, src, matched_rows, num_matched, use_first, offset
             );
             break;
         case DTYPE_INT8:
             copy_column_typed<std::int8_t>(
-                dst, src, matched_rows, num_matched, use_first
+                dst, src, matched_rows, num_matched, use_first, offset
             );
             break;
         case DTYPE_UINT64:
             copy_column_typed<std::uint64_t>(
-                dst, src, matched_rows, num_matched, use_first
+                dst, src, matched_rows, num_matched, use_first, offset
             );
             break;
         case DTYPE_UINT32:
             copy_column_typed<std::uint32_t>(
-                dst, src, matched_rows, num_matched, use_first
+                dst, src, matched_rows, num_matched, use_first, offset
             );
             break;
         case DTYPE_UINT16:
             copy_column_typed<std::uint16_t>(
-                dst, src, matched_rows, num_matched, use_first
+                dst, src, matched_rows, num_matched, use_first, offset
             );
             break;
         case DTYPE_UINT8:
             copy_column_typed<std::uint8_t>(
-                dst, src, matched_rows, num_matched, use_first
+                dst, src, matched_rows, num_matched, use_first, offset
             );
             break;
         case DTYPE_FLOAT64:
             copy_column_typed<double>(
-                dst, src, matched_rows, num_matched, use_first
+                dst, src, matched_rows, num_matched, use_first, offset
             );
             break;
         case DTYPE_FLOAT32:
             copy_column_typed<float>(
-                dst, src, matched_rows, num_matched, use_first
+                dst, src, matched_rows, num_matched, use_first, offset
             );
             break;
         case DTYPE_BOOL:
             copy_column_typed<bool>(
-                dst, src, matched_rows, num_matched, use_first
+                dst, src, matched_rows, num_matched, use_first, offset
             );
             break;
         case DTYPE_TIME:
             copy_column_typed<std::int64_t>(
-                dst, src, matched_rows, num_matched, use_first
+                dst, src, matched_rows, num_matched, use_first, offset
             );
             break;
         case DTYPE_DATE:
             copy_column_typed<std::int32_t>(
-                dst, src, matched_rows, num_matched, use_first
+                dst, src, matched_rows, num_matched, use_first, offset
             );
             break;
         case DTYPE_STR:
             copy_column_str(
-                dst, src, matched_rows, num_matched, use_first
+                dst, src, matched_rows, num_matched, use_first, offset
             );
             break;
         default:
@@ -151,17 +159,17 @@ copy_column_dispatch(
                 auto src_idx = use_first ? matched_rows[i].first
                                          : matched_rows[i].second;
                 if (src_idx == static_cast<t_uindex>(-1)) {
-                    dst->clear(i);
+                    dst->clear(offset + i);
                 } else {
-                    dst->set_scalar(i, src->get_scalar(src_idx));
+                    dst->set_scalar(offset + i, src->get_scalar(src_idx));
                 }
             }
             break;
     }
 }
 
-// Copy the join key column for OUTER join rows where the left side has no
-// match but the right side does. Uses the right key column as the source
+// Copy the join key column for OUTER/RIGHT join rows where the left side has
+// no match but the right side does. Uses the right key column as the source
 // for just those rows; all other rows are already filled from the left.
 void
 copy_join_key_fallback(
@@ -180,22 +188,179 @@ copy_join_key_fallback(
     }
 }
 
-} // anonymous namespace
+// Read the (possibly composite) join key for `row_idx` into `key`, returning
+// `false` if any component is null, as null keys never match.
+bool
+read_join_key(
+    const std::vector<std::shared_ptr<t_column>>& key_cols,
+    t_uindex row_idx,
+    t_join_key& key
+) {
+    key.clear();
+    for (const auto& col : key_cols) {
+        auto scalar = col->get_scalar(row_idx);
+        if (scalar.is_none()) {
+            return false;
+        }
+
+        key.push_back(scalar);
+    }
+
+    return true;
+}
+
+std::vector<std::shared_ptr<t_column>>
+get_key_columns(
+    const std::shared_ptr<t_data_table>& data,
+    const std::vector<std::string>& names
+) {
+    std::vector<std::shared_ptr<t_column>> cols;
+    cols.reserve(names.size());
+    for (const auto& name : names) {
+        cols.push_back(data->get_column(name));
+    }
+
+    return cols;
+}
+
+// Union table primary keys order rows by source table, then by the order
+// they were added to the union.
+std::int64_t
+make_union_row_id(t_uindex source_idx, std::int64_t seq) {
+    return (static_cast<std::int64_t>(source_idx) << 40) | seq;
+}
 
+// Copy `source_rows[i]` of each of `tables[i]` into `union_data`, in
+// `def.table_ids` order, keyed by `row_ids`.
 void
-JoinEngine::register_join(
-    const t_id& join_table_id,
-    const t_id& left_table_id,
-    const t_id& right_table_id,
-    const std::string& on_column,
-    const std::string& right_on_column,
-    proto::JoinType join_type
+fill_union_rows(
+    const UnionDef& def,
+    const std::vector<std::shared_ptr<Table>>& tables,
+    const std::vector<std::vector<std::pair<t_uindex, t_uindex>>>& source_rows,
+    const std::vector<std::int64_t>& row_ids,
+    const t_schema& union_schema,
+    t_data_table& union_data
 ) {
-    auto effective_right_on = right_on_column.empty() ? on_column : right_on_column;
-    JoinDef def{left_table_id, right_table_id, on_column, effective_right_on, join_type};
-    m_join_defs.emplace(join_table_id, def);
-    m_table_to_join_tables.emplace(left_table_id, join_table_id);
-    m_table_to_join_tables.emplace(right_table_id, join_table_id);
+    t_uindex num_rows = row_ids.size();
+    union_data.init();
+    union_data.extend(num_rows);
+    for (const auto& col : union_schema.columns()) {
+        auto dst_col = union_data.get_column(col);
+        t_uindex offset = 0;
+        for (t_uindex i = 0; i < tables.size(); ++i) {
+            const auto& rows = source_rows[i];
+            if (col == def.source_column) {
+                for (t_uindex j = 0; j < rows.size(); ++j) {
+                    dst_col->set_nth<const char*>(
+                        offset + j, def.table_ids[i].c_str(), STATUS_VALID
+                    );
+                }
+            } else {
+                auto src_col =
+                    tables[i]->get_gnode()->get_table_sptr()->get_column(col);
+
+                copy_column_dispatch(
+                    dst_col.get(),
+                    src_col.get(),
+                    rows,
+                    rows.size(),
+                    true,
+                    offset
+                );
+            }
+
+            offset += rows.size();
+        }
+
+        // An invalid cell in an update keeps the row's previous value, so
+        // nulls are cleared explicitly.
+        for (t_uindex i = 0; i < num_rows; ++i) {
+            if (!dst_col->is_valid(i)) {
+                dst_col->unset(i);
+            }
+        }
+    }
+
+    union_data.set_size(num_rows);
+    auto* pkey_col = union_data.add_column("psp_pkey", DTYPE_INT64, true);
+    auto* okey_col = union_data.add_column("psp_okey", DTYPE_INT64, true);
+    for (t_uindex i = 0; i < num_rows; ++i) {
+        pkey_col->set_nth<std::int64_t>(i, row_ids[i], STATUS_VALID);
+        okey_col->set_nth<std::int64_t>(i, row_ids[i], STATUS_VALID);
+    }
+}
+
+struct UnnestElement {
+    t_uindex row_idx;
+    t_status status;
+    double number;
+    std::string text;
+};
+
+// Append the elements of the list column value `value` of source row
+// `row_idx` to `out`. As in filters, a value which is not a JSON array is a
+// list of itself. A null or empty list yields a single null element, so the
+// row is not dropped.
+void
+push_list_elements(
+    const t_tscalar& value,
+    t_uindex row_idx,
+    bool is_float,
+    std::vector<UnnestElement>& out
+) {
+    auto start = out.size();
+    auto push = [&](const rapidjson::Value& elem) {
+        UnnestElement e{row_idx, STATUS_INVALID, 0, ""};
+        if (is_float) {
+            if (elem.IsNumber()) {
+                e.status = STATUS_VALID;
+                e.number = elem.GetDouble();
+            } else if (elem.IsString()) {
+                char* end = nullptr;
+                e.number = std::strtod(elem.GetString(), &end);
+                if (end != elem.GetString() && *end == '\0') {
+                    e.status = STATUS_VALID;
+                }
+            }
+        } else if (elem.IsString()) {
+            e.status = STATUS_VALID;
+            e.text.assign(elem.GetString(), elem.GetStringLength());
+        } else if (!elem.IsNull()) {
+            rapidjson::StringBuffer buffer;
+            rapidjson::Writer<rapidjson::StringBuffer> writer(buffer);
+            elem.Accept(writer);
+            e.status = STATUS_VALID;
+            e.text = buffer.GetString();
+        }
+
+        out.push_back(std::move(e));
+    };
+
+    if (value.is_valid() && value.get_dtype() == DTYPE_STR) {
+        const char* text = value.get_char_ptr();
+        rapidjson::Document doc;
+        doc.Parse(text);
+        if (doc.HasParseError() || !doc.IsArray()) {
+            push(rapidjson::Value(rapidjson::StringRef(text)));
+        } else {
+            for (const auto& elem : doc.GetArray()) {
+                push(elem);
+            }
+        }
+    }
+
+    if (out.size() == start) {
+        out.push_back({row_idx, STATUS_INVALID, 0, ""});
+    }
+}
+
+} // anonymous namespace
+
+void
+JoinEngine::register_join(const t_id& join_table_id, JoinDef def) {
+    m_table_to_join_tables.emplace(def.left_table_id, join_table_id);
+    m_table_to_join_tables.emplace(def.right_table_id, join_table_id);
+    m_join_defs.emplace(join_table_id, std::move(def));
 }
 
 void
@@ -208,14 +373,7 @@ JoinEngine::unregister_join(const t_id& join_table_id) {
     auto& def = it->second;
 
     for (const auto& source_id : {def.left_table_id, def.right_table_id}) {
-        auto range = m_table_to_join_tables.equal_range(source_id);
-        for (auto jt = range.first; jt != range.second;) {
-            if (jt->second == join_table_id) {
-                jt = m_table_to_join_tables.erase(jt);
-            } else {
-                ++jt;
-            }
-        }
+        unlink_source(source_id, join_table_id);
     }
 
     m_join_defs.erase(it);
@@ -227,6 +385,69 @@ JoinEngine::is_join_table(const t_id& id) const {
     return m_join_defs.contains(id);
 }
 
+void
+JoinEngine::register_union(const t_id& union_table_id, UnionDef def) {
+    for (const auto& source_id : def.table_ids) {
+        m_table_to_join_tables.emplace(source_id, union_table_id);
+    }
+
+    m_union_defs.emplace(union_table_id, std::move(def));
+}
+
+void
+JoinEngine::unregister_union(const t_id& union_table_id) {
+    auto it = m_union_defs.find(union_table_id);
+    if (it == m_union_defs.end()) {
+        return;
+    }
+
+    for (const auto& source_id : it->second.table_ids) {
+        unlink_source(source_id, union_table_id);
+    }
+
+    m_union_defs.erase(it);
+    m_union_caches.erase(union_table_id);
+}
+
+bool
+JoinEngine::is_union_table(const t_id& id) const {
+    return m_union_defs.contains(id);
+}
+
+void
+JoinEngine::register_unnest(const t_id& unnest_table_id, UnnestDef def) {
+    m_table_to_join_tables.emplace(def.table_id, unnest_table_id);
+    m_unnest_defs.emplace(unnest_table_id, std::move(def));
+}
+
+void
+JoinEngine::unregister_unnest(const t_id& unnest_table_id) {
+    auto it = m_unnest_defs.find(unnest_table_id);
+    if (it == m_unnest_defs.end()) {
+        return;
+    }
+
+    unlink_source(it->second.table_id, unnest_table_id);
+    m_unnest_defs.erase(it);
+}
+
+bool
+JoinEngine::is_unnest_table(const t_id& id) const {
+    return m_unnest_defs.contains(id);
+}
+
+void
+JoinEngine::unlink_source(const t_id& source_id, const t_id& table_id) {
+    auto range = m_table_to_join_tables.equal_range(source_id);
+    for (auto jt = range.first; jt != range.second;) {
+        if (jt->second == table_id) {
+            jt = m_table_to_join_tables.erase(jt);
+        } else {
+            ++jt;
+        }
+    }
+}
+
 std::vector<JoinEngine::t_id>
 JoinEngine::get_dependent_join_tables(const t_id& source_table_id) const {
     std::vector<t_id> result;
@@ -243,59 +464,123 @@ JoinEngine::get_join_def(const t_id& join_table_id) const {
     return m_join_defs.at(join_table_id);
 }
 
+const UnionDef&
+JoinEngine::get_union_def(const t_id& union_table_id) const {
+    return m_union_defs.at(union_table_id);
+}
+
+const UnnestDef&
+JoinEngine::get_unnest_def(const t_id& unnest_table_id) const {
+    return m_unnest_defs.at(unnest_table_id);
+}
+
 MakeJoinResult
 JoinEngine::make_join_table(
-    const std::string& on_column,
-    const std::string& right_on_column,
-    proto::JoinType join_type,
+    JoinDef& def,
     const std::shared_ptr<Table>& left_table,
     const std::shared_ptr<Table>& right_table
 ) {
-    auto effective_right_on = right_on_column.empty() ? on_column : right_on_column;
+    if (def.right_on_columns.empty()) {
+        def.right_on_columns = def.on_columns;
+    }
+
+    if (def.right_as_of_column.empty()) {
+        def.right_as_of_column = def.as_of_column;
+    }
+
+    if (def.on_columns.size() != def.right_on_columns.size()) {
+        return {nullptr, "Join key column count mismatch"};
+    }
+
+    if (def.on_columns.empty() && !def.is_as_of()) {
+        return {nullptr, "Join requires at least one key column"};
+    }
+
+    if (def.is_as_of() && def.join_type != proto::INNER
+        && def.join_type != proto::LEFT) {
+        return {nullptr, "As-of joins must be INNER or LEFT"};
+    }
+
     auto left_schema = left_table->get_schema();
     auto right_schema = right_table->get_schema();
-    if (!left_schema.has_column(on_column)) {
-        std::stringstream ss;
-        ss << "Column \"" << on_column << "\" not found in left table";
-        return {nullptr, ss.str()};
+    std::vector<std::pair<std::string, std::string>> key_pairs;
+    for (t_uindex i = 0; i < def.on_columns.size(); ++i) {
+        key_pairs.emplace_back(def.on_columns[i], def.right_on_columns[i]);
     }
 
-    if (!right_schema.has_column(effective_right_on)) {
-        std::stringstream ss;
-        ss << "Column \"" << effective_right_on << "\" not found in right table";
-        return {nullptr, ss.str()};
+    if (def.is_as_of()) {
+        key_pairs.emplace_back(def.as_of_column, def.right_as_of_column);
     }
 
-    if (left_schema.get_dtype(on_column) != right_schema.get_dtype(effective_right_on)) {
-        return {nullptr, "Join column type mismatch"};
+    tsl::hopscotch_set<std::string> right_keys;
+    for (const auto& [left_col, right_col] : key_pairs) {
+        if (!left_schema.has_column(left_col)) {
+            std::stringstream ss;
+            ss << "Column \"" << left_col << "\" not found in left table";
+            return {nullptr, ss.str()};
+        }
+
+        if (!right_schema.has_column(right_col)) {
+            std::stringstream ss;
+            ss << "Column \"" << right_col << "\" not found in right table";
+            return {nullptr, ss.str()};
+        }
+
+        if (left_schema.get_dtype(left_col)
+            != right_schema.get_dtype(right_col)) {
+            return {nullptr, "Join column type mismatch"};
+        }
+
+        right_keys.insert(right_col);
     }
 
-    for (const auto& rcol : right_schema.columns()) {
-        if (rcol == effective_right_on) {
-            continue;
+    // `SEMI` and `ANTI` joins only filter the left table.
+    std::vector<std::string> right_columns;
+    if (def.join_type != proto::SEMI && def.join_type != proto::ANTI) {
+        for (const auto& rcol : right_schema.columns()) {
+            if (!right_keys.contains(rcol)) {
+                right_columns.push_back(rcol);
+            }
         }
+    }
 
+    tsl::hopscotch_set<std::string> conflicts;
+    for (const auto& rcol : right_columns) {
         if (left_schema.has_column(rcol)) {
-            std::stringstream ss;
-            ss << "Column \"" << rcol << "\" exists in both tables";
-            return {nullptr, ss.str()};
+            if (def.left_suffix.empty() && def.right_suffix.empty()) {
+                std::stringstream ss;
+                ss << "Column \"" << rcol << "\" exists in both tables";
+                return {nullptr, ss.str()};
+            }
+
+            conflicts.insert(rcol);
         }
     }
 
-    std::vector<std::string> merged_columns;
-    std::vector<t_dtype> merged_types;
-    for (t_uindex i = 0; i < left_schema.columns().size(); ++i) {
-        merged_columns.push_back(left_schema.columns()[i]);
-        merged_types.push_back(left_schema.types()[i]);
+    def.columns.clear();
+    for (const auto& lcol : left_schema.columns()) {
+        auto name = conflicts.contains(lcol) ? lcol + def.left_suffix : lcol;
+        def.columns.push_back({name, lcol, true});
     }
 
-    for (t_uindex i = 0; i < right_schema.columns().size(); ++i) {
-        if (right_schema.columns()[i] == effective_right_on) {
-            continue;
+    for (const auto& rcol : right_columns) {
+        auto name = conflicts.contains(rcol) ? rcol + def.right_suffix : rcol;
+        def.columns.push_back({name, rcol, false});
+    }
+
+    std::vector<std::string> merged_columns;
+    std::vector<t_dtype> merged_types;
+    tsl::hopscotch_set<std::string> seen;
+    for (const auto& col : def.columns) {
+        if (!seen.insert(col.name).second) {
+            std::stringstream ss;
+            ss << "Column \"" << col.name << "\" exists in both tables";
+            return {nullptr, ss.str()};
         }
 
-        merged_columns.push_back(right_schema.columns()[i]);
-        merged_types.push_back(right_schema.types()[i]);
+        const auto& schema = col.is_left ? left_schema : right_schema;
+        merged_columns.push_back(col.name);
+        merged_types.push_back(schema.get_dtype(col.source));
     }
 
     t_schema merged_schema(merged_columns, merged_types);
@@ -307,11 +592,16 @@ void
 JoinEngine::build_right_index(
     JoinCache& cache,
     const std::shared_ptr<Table>& right_table,
-    const std::string& on_column
+    const JoinDef& def
 ) {
     auto right_data = right_table->get_gnode()->get_table_sptr();
     const auto& right_pkey_map = right_table->get_gnode()->get_pkey_map();
-    auto right_key_col = right_data->get_column(on_column);
+    auto right_key_cols = get_key_columns(right_data, def.right_on_columns);
+    std::shared_ptr<t_column> right_as_of_col;
+    if (def.is_as_of()) {
+        right_as_of_col = right_data->get_column(def.right_as_of_column);
+    }
+
     cache.right_entries.assign(right_pkey_map.begin(), right_pkey_map.end());
     std::sort(
         cache.right_entries.begin(),
@@ -321,10 +611,33 @@ JoinEngine::build_right_index(
 
     cache.right_index.clear();
     cache.right_index.reserve(right_pkey_map.size());
+    t_join_key join_key;
     for (const auto& [pkey, row_idx] : cache.right_entries) {
-        auto join_key = right_key_col->get_scalar(row_idx);
-        if (!join_key.is_none()) {
-            cache.right_index[join_key].push_back(row_idx);
+        if (!read_join_key(right_key_cols, row_idx, join_key)) {
+            continue;
+        }
+
+        if (right_as_of_col != nullptr
+            && right_as_of_col->get_scalar(row_idx).is_none()) {
+            continue;
+        }
+
+        cache.right_index[join_key].push_back(row_idx);
+    }
+
+    // As-of joins binary search each key's rows by the as-of column.
+    if (right_as_of_col != nullptr) {
+        for (auto it = cache.right_index.begin(); it != cache.right_index.end();
+             ++it) {
+            auto& rows = it.value();
+            std::stable_sort(
+                rows.begin(),
+                rows.end(),
+                [&right_as_of_col](t_uindex a, t_uindex b) {
+                    return right_as_of_col->get_scalar(a)
+                        < right_as_of_col->get_scalar(b);
+                }
+            );
         }
     }
 
@@ -345,13 +658,19 @@ JoinEngine::recompute(
     auto left_data = left_table->get_gnode()->get_table_sptr();
     auto right_data = right_table->get_gnode()->get_table_sptr();
     const auto& left_pkey_map = left_table->get_gnode()->get_pkey_map();
-    auto left_key_col = left_data->get_column(def.on_column);
-    auto right_key_col = right_data->get_column(def.right_on_column);
+    auto left_key_cols = get_key_columns(left_data, def.on_columns);
+    auto right_key_cols = get_key_columns(right_data, def.right_on_columns);
+    std::shared_ptr<t_column> left_as_of_col;
+    std::shared_ptr<t_column> right_as_of_col;
+    if (def.is_as_of()) {
+        left_as_of_col = left_data->get_column(def.as_of_column);
+        right_as_of_col = right_data->get_column(def.right_as_of_column);
+    }
 
     // Rebuild right-side index only when the right table has changed,
     // or on the first recompute when no cache exists yet.
     if (right_changed || !cache.valid) {
-        build_right_index(cache, right_table, def.right_on_column);
+        build_right_index(cache, right_table, def);
     }
 
     const auto& right_join_key_to_rows = cache.right_index;
@@ -369,36 +688,78 @@ JoinEngine::recompute(
         [](const auto& a, const auto& b) { return a.first < b.first; }
     );
 
+    const bool keep_unmatched_left = def.join_type == proto::LEFT
+        || def.join_type == proto::OUTER || def.join_type == proto::ANTI;
+
+    const bool keep_unmatched_right =
+        def.join_type == proto::RIGHT || def.join_type == proto::OUTER;
+
     const t_uindex NO_MATCH = static_cast<t_uindex>(-1);
     std::vector<std::pair<t_uindex, t_uindex>> matched_rows;
     matched_rows.reserve(left_entries.size());
     tsl::hopscotch_set<t_uindex> matched_right_rows;
+    t_join_key join_key;
     for (const auto& [pkey, row_idx] : left_entries) {
-        auto join_key = left_key_col->get_scalar(row_idx);
-        if (join_key.is_none()) {
-            if (def.join_type == proto::LEFT
-                || def.join_type == proto::OUTER) {
+        const std::vector<t_uindex>* right_rows = nullptr;
+        if (read_join_key(left_key_cols, row_idx, join_key)) {
+            auto it = right_join_key_to_rows.find(join_key);
+            if (it != right_join_key_to_rows.end()) {
+                right_rows = &it->second;
+            }
+        }
+
+        if (right_rows != nullptr && def.is_as_of()) {
+            // The last right row whose as-of value is <= the left row's.
+            auto as_of = left_as_of_col->get_scalar(row_idx);
+            auto match = NO_MATCH;
+            if (!as_of.is_none()) {
+                auto upper = std::upper_bound(
+                    right_rows->begin(),
+                    right_rows->end(),
+                    as_of,
+                    [&right_as_of_col](const t_tscalar& x, t_uindex row) {
+                        return x < right_as_of_col->get_scalar(row);
+                    }
+                );
+
+                if (upper != right_rows->begin()) {
+                    match = *(upper - 1);
+                }
+            }
+
+            if (match != NO_MATCH || keep_unmatched_left) {
+                matched_rows.emplace_back(row_idx, match);
+            }
+
+            continue;
+        }
+
+        if (right_rows == nullptr) {
+            if (keep_unmatched_left) {
                 matched_rows.emplace_back(row_idx, NO_MATCH);
             }
 
             continue;
         }
 
-        auto it = right_join_key_to_rows.find(join_key);
-        if (it != right_join_key_to_rows.end()) {
-            for (auto right_row_idx : it->second) {
-                matched_rows.emplace_back(row_idx, right_row_idx);
-                if (def.join_type == proto::OUTER) {
-                    matched_right_rows.insert(right_row_idx);
+        switch (def.join_type) {
+            case proto::SEMI:
+                matched_rows.emplace_back(row_idx, NO_MATCH);
+                break;
+            case proto::ANTI:
+                break;
+            default:
+                for (auto right_row_idx : *right_rows) {
+                    matched_rows.emplace_back(row_idx, right_row_idx);
+                    if (keep_unmatched_right) {
+                        matched_right_rows.insert(right_row_idx);
+                    }
                 }
-            }
-        } else if (def.join_type == proto::LEFT
-                   || def.join_type == proto::OUTER) {
-            matched_rows.emplace_back(row_idx, NO_MATCH);
+                break;
         }
     }
 
-    if (def.join_type == proto::OUTER) {
+    if (keep_unmatched_right) {
         for (const auto& [pkey, row_idx] : right_entries) {
             if (matched_right_rows.find(row_idx)
                 == matched_right_rows.end()) {
@@ -412,24 +773,29 @@ JoinEngine::recompute(
     t_data_table joined_data(join_schema);
     joined_data.init();
     joined_data.extend(num_matched);
-    auto left_schema = left_table->get_schema();
-    auto right_schema = right_table->get_schema();
-    for (const auto& col_name : join_schema.columns()) {
-        auto dst_col = joined_data.get_column(col_name);
-        bool is_join_col = (col_name == def.on_column);
-        if (left_schema.has_column(col_name)) {
-            auto left_src_col = left_data->get_column(col_name);
+    for (const auto& col : def.columns) {
+        auto dst_col = joined_data.get_column(col.name);
+        if (col.is_left) {
+            auto left_src_col = left_data->get_column(col.source);
             copy_column_dispatch(
                 dst_col.get(), left_src_col.get(), matched_rows, num_matched, true
             );
 
-            if (is_join_col) {
+            // Key columns take the right key's value for right-only rows.
+            auto key = std::find(
+                def.on_columns.begin(), def.on_columns.end(), col.source
+            );
+
+            if (key != def.on_columns.end() && keep_unmatched_right) {
+                auto right_key_col =
+                    right_key_cols[std::distance(def.on_columns.begin(), key)];
+
                 copy_join_key_fallback(
                     dst_col.get(), right_key_col.get(), matched_rows, num_matched
                 );
             }
-        } else if (right_schema.has_column(col_name)) {
-            auto src_col = right_data->get_column(col_name);
+        } else {
+            auto src_col = right_data->get_column(col.source);
             copy_column_dispatch(
                 dst_col.get(), src_col.get(), matched_rows, num_matched, false
             );
@@ -448,4 +814,384 @@ JoinEngine::recompute(
     join_table->init(joined_data, num_matched, t_op::OP_INSERT, 0);
 }
 
+MakeJoinResult
+JoinEngine::make_union_table(
+    const UnionDef& def, const std::vector<std::shared_ptr<Table>>& tables
+) {
+    if (tables.empty()) {
+        return {nullptr, "Union requires at least one table"};
+    }
+
+    tsl::hopscotch_set<std::string> seen;
+    for (const auto& table_id : def.table_ids) {
+        if (!seen.insert(table_id).second) {
+            std::stringstream ss;
+            ss << "Table \"" << table_id
+               << "\" appears more than once in union";
+            return {nullptr, ss.str()};
+        }
+    }
+
+    // Columns are matched by name, in the first table's column order.
+    auto schema = tables[0]->get_schema();
+    for (t_uindex i = 1; i < tables.size(); ++i) {
+        auto other = tables[i]->get_schema();
+        bool matches = other.size() == schema.size();
+        for (const auto& col : schema.columns()) {
+            matches = matches && other.has_column(col)
+                && other.get_dtype(col) == schema.get_dtype(col);
+        }
+
+        if (!matches) {
+            std::stringstream ss;
+            ss << "Table \"" << def.table_ids[i]
+               << "\" schema does not match table \"" << def.table_ids[0]
+               << "\"";
+            return {nullptr, ss.str()};
+        }
+    }
+
+    if (!def.source_column.empty()) {
+        if (schema.has_column(def.source_column)) {
+            std::stringstream ss;
+            ss << "Column \"" << def.source_column
+               << "\" already exists in union source tables";
+            return {nullptr, ss.str()};
+        }
+
+        schema.add_column(def.source_column, DTYPE_STR);
+    }
+
+    // Rows are keyed by `make_union_row_id`, so unlike `Table::from_schema`
+    // the implicit primary key is an `int64`.
+    t_schema union_schema(schema.columns(), schema.types());
+    auto pool = std::make_shared<t_pool>();
+    pool->init();
+    t_data_table data_table(union_schema);
+    data_table.init();
+    data_table.add_column("psp_pkey", DTYPE_INT64, true);
+    data_table.add_column("psp_okey", DTYPE_INT64, true);
+    auto union_table = std::make_shared<Table>(
+        pool,
+        union_schema.columns(),
+        union_schema.types(),
+        std::numeric_limits<std::uint32_t>::max(),
+        ""
+    );
+
+    union_table->init(data_table, 0, t_op::OP_INSERT, 0);
+    pool->_process();
+    return {union_table, ""};
+}
+
+void
+JoinEngine::recompute_union(
+    const t_id& union_table_id,
+    const std::vector<std::shared_ptr<Table>>& tables,
+    const std::shared_ptr<Table>& union_table
+) {
+    const auto& def = m_union_defs.at(union_table_id);
+    auto& cache = m_union_caches[union_table_id];
+    if (!cache.valid) {
+        rebuild_union(def, cache, tables, union_table);
+        return;
+    }
+
+    // Each source's changed rows which still exist are upserted under their
+    // existing union key, or a new one, and the rest are removed.
+    const t_uindex NO_MATCH = static_cast<t_uindex>(-1);
+    std::vector<std::vector<std::pair<t_uindex, t_uindex>>> source_rows(
+        tables.size()
+    );
+
+    std::vector<std::int64_t> row_ids;
+    std::vector<std::int64_t> removed_ids;
+    for (t_uindex i = 0; i < tables.size(); ++i) {
+        const auto& pkey_map = tables[i]->get_gnode()->get_pkey_map();
+        auto& ids = cache.row_ids[i];
+        std::vector<t_tscalar> pkeys(
+            cache.pending[i].begin(), cache.pending[i].end()
+        );
+
+        std::sort(pkeys.begin(), pkeys.end());
+        for (const auto& pkey : pkeys) {
+            auto row = pkey_map.find(pkey);
+            auto id = ids.find(pkey);
+            if (row == pkey_map.end()) {
+                if (id != ids.end()) {
+                    removed_ids.push_back(id->second);
+                    ids.erase(id);
+                }
+
+                continue;
+            }
+
+            if (id == ids.end()) {
+                auto row_id = make_union_row_id(i, cache.next_row_ids[i]++);
+                id = ids.emplace(pkey, row_id).first;
+            }
+
+            source_rows[i].emplace_back(row->second, NO_MATCH);
+            row_ids.push_back(id->second);
+        }
+
+        cache.pending[i].clear();
+    }
+
+    if (!removed_ids.empty()) {
+        t_data_table removed_data(t_schema(
+            {"psp_pkey", "psp_okey"}, {DTYPE_INT64, DTYPE_INT64}
+        ));
+
+        removed_data.init();
+        removed_data.extend(removed_ids.size());
+        for (const auto& name : {"psp_pkey", "psp_okey"}) {
+            auto* column = removed_data._get_column(name);
+            for (t_uindex i = 0; i < removed_ids.size(); ++i) {
+                column->set_nth<std::int64_t>(
+                    i, removed_ids[i], STATUS_VALID
+                );
+            }
+        }
+
+        union_table->init(removed_data, 0, t_op::OP_DELETE, 0);
+    }
+
+    if (!row_ids.empty()) {
+        auto union_schema = union_table->get_schema();
+        t_data_table union_data(union_schema);
+        fill_union_rows(
+            def, tables, source_rows, row_ids, union_schema, union_data
+        );
+
+        union_table->init(union_data, row_ids.size(), t_op::OP_INSERT, 0);
+    }
+}
+
+void
+JoinEngine::rebuild_union(
+    const UnionDef& def,
+    UnionCache& cache,
+    const std::vector<std::shared_ptr<Table>>& tables,
+    const std::shared_ptr<Table>& union_table
+) {
+    const t_uindex NO_MATCH = static_cast<t_uindex>(-1);
+    cache.row_ids.assign(tables.size(), {});
+    cache.next_row_ids.assign(tables.size(), 0);
+    cache.pending.assign(tables.size(), {});
+
+    // Each source's rows, in that source's insertion order, concatenated in
+    // `def.table_ids` order.
+    std::vector<std::vector<std::pair<t_uindex, t_uindex>>> source_rows(
+        tables.size()
+    );
+
+    std::vector<std::int64_t> row_ids;
+    for (t_uindex i = 0; i < tables.size(); ++i) {
+        const auto& pkey_map = tables[i]->get_gnode()->get_pkey_map();
+        std::vector<std::pair<t_tscalar, t_uindex>> entries(
+            pkey_map.begin(), pkey_map.end()
+        );
+
+        std::sort(
+            entries.begin(),
+            entries.end(),
+            [](const auto& a, const auto& b) { return a.first < b.first; }
+        );
+
+        source_rows[i].reserve(entries.size());
+        cache.row_ids[i].reserve(entries.size());
+        for (const auto& [pkey, row_idx] : entries) {
+            auto row_id = make_union_row_id(i, cache.next_row_ids[i]++);
+            cache.row_ids[i].emplace(
+                m_union_symtable.get_interned_tscalar(pkey), row_id
+            );
+
+            source_rows[i].emplace_back(row_idx, NO_MATCH);
+            row_ids.push_back(row_id);
+        }
+    }
+
+    auto union_schema = union_table->get_schema();
+    t_data_table union_data(union_schema);
+    fill_union_rows(
+        def, tables, source_rows, row_ids, union_schema, union_data
+    );
+
+    union_table->clear();
+    union_table->init(union_data, row_ids.size(), t_op::OP_INSERT, 0);
+    cache.valid = true;
+}
+
+void
+JoinEngine::capture_union_delta(
+    const t_id& source_table_id, const std::shared_ptr<Table>& table
+) {
+    auto gnode = table->get_gnode();
+    const auto* flattened = gnode->_get_otable(PSP_PORT_FLATTENED);
+    auto range = m_table_to_join_tables.equal_range(source_table_id);
+    for (auto it = range.first; it != range.second; ++it) {
+        auto def = m_union_defs.find(it->second);
+        auto cache = m_union_caches.find(it->second);
+        if (def == m_union_defs.end() || cache == m_union_caches.end()
+            || !cache->second.valid) {
+            continue;
+        }
+
+        const auto& table_ids = def->second.table_ids;
+        auto source_idx = std::distance(
+            table_ids.begin(),
+            std::find(table_ids.begin(), table_ids.end(), source_table_id)
+        );
+
+        auto& pending = cache.value().pending[source_idx];
+
+        // The first update to an empty table releases the gnode's output
+        // ports, but then every row in it is new.
+        if (flattened->size() == 0) {
+            for (const auto& [pkey, _] : gnode->get_pkey_map()) {
+                pending.insert(m_union_symtable.get_interned_tscalar(pkey));
+            }
+
+            continue;
+        }
+
+        const auto* pkey_col = flattened->_get_const_column("psp_pkey");
+        for (t_uindex i = 0; i < flattened->size(); ++i) {
+            pending.insert(
+                m_union_symtable.get_interned_tscalar(pkey_col->get_scalar(i))
+            );
+        }
+    }
+}
+
+void
+JoinEngine::invalidate_union_sources(const t_id& source_table_id) {
+    auto range = m_table_to_join_tables.equal_range(source_table_id);
+    for (auto it = range.first; it != range.second; ++it) {
+        auto cache = m_union_caches.find(it->second);
+        if (cache != m_union_caches.end()) {
+            cache.value().valid = false;
+        }
+    }
+}
+
+MakeJoinResult
+JoinEngine::make_unnest_table(
+    const UnnestDef& def, const std::shared_ptr<Table>& table
+) {
+    const auto& logical_types = table->get_logical_types();
+    auto logical_type = logical_types.find(def.column);
+    if (logical_type == logical_types.end()
+        || (logical_type->second.m_tag != LOGICAL_TYPE_LIST_STRING
+            && logical_type->second.m_tag != LOGICAL_TYPE_LIST_FLOAT)) {
+        std::stringstream ss;
+        ss << "Column \"" << def.column << "\" is not a list column";
+        return {nullptr, ss.str()};
+    }
+
+    auto schema = table->get_schema();
+    std::vector<std::string> columns;
+    std::vector<t_dtype> types;
+    for (const auto& col : schema.columns()) {
+        columns.push_back(col);
+        if (col == def.column) {
+            types.push_back(
+                logical_type->second.m_tag == LOGICAL_TYPE_LIST_FLOAT
+                    ? DTYPE_FLOAT64
+                    : DTYPE_STR
+            );
+        } else {
+            types.push_back(schema.get_dtype(col));
+        }
+    }
+
+    // The other columns keep their logical types, while the unnested column
+    // becomes a plain `string` or `float` column of the list's elements.
+    auto unnest_logical_types = logical_types;
+    unnest_logical_types.erase(def.column);
+    auto unnest_table = Table::from_schema("", t_schema(columns, types));
+    unnest_table->set_logical_types(std::move(unnest_logical_types));
+    return {unnest_table, ""};
+}
+
+void
+JoinEngine::recompute_unnest(
+    const t_id& unnest_table_id,
+    const std::shared_ptr<Table>& table,
+    const std::shared_ptr<Table>& unnest_table
+) {
+    const auto& def = m_unnest_defs.at(unnest_table_id);
+    const t_uindex NO_MATCH = static_cast<t_uindex>(-1);
+    const auto& pkey_map = table->get_gnode()->get_pkey_map();
+    std::vector<std::pair<t_tscalar, t_uindex>> entries(
+        pkey_map.begin(), pkey_map.end()
+    );
+
+    std::sort(
+        entries.begin(),
+        entries.end(),
+        [](const auto& a, const auto& b) { return a.first < b.first; }
+    );
+
+    // One row per list element, in the source table's row order.
+    auto src_table = table->get_gnode()->get_table_sptr();
+    auto list_col = src_table->get_column(def.column);
+    auto unnest_schema = unnest_table->get_schema();
+    bool is_float = unnest_schema.get_dtype(def.column) == DTYPE_FLOAT64;
+    std::vector<UnnestElement> elements;
+    elements.reserve(entries.size());
+    for (const auto& [pkey, row_idx] : entries) {
+        push_list_elements(
+            list_col->get_scalar(row_idx), row_idx, is_float, elements
+        );
+    }
+
+    t_uindex num_rows = elements.size();
+    std::vector<std::pair<t_uindex, t_uindex>> rows;
+    rows.reserve(num_rows);
+    for (const auto& elem : elements) {
+        rows.emplace_back(elem.row_idx, NO_MATCH);
+    }
+
+    t_data_table unnest_data(unnest_schema);
+    unnest_data.init();
+    unnest_data.extend(num_rows);
+    for (const auto& col : unnest_schema.columns()) {
+        auto dst_col = unnest_data.get_column(col);
+        if (col != def.column) {
+            auto src_col = src_table->get_column(col);
+            copy_column_dispatch(
+                dst_col.get(), src_col.get(), rows, num_rows, true
+            );
+
+            continue;
+        }
+
+        for (t_uindex i = 0; i < num_rows; ++i) {
+            const auto& elem = elements[i];
+            if (elem.status != STATUS_VALID) {
+                dst_col->clear(i);
+            } else if (is_float) {
+                dst_col->set_nth<double>(i, elem.number, STATUS_VALID);
+            } else {
+                dst_col->set_nth<const char*>(
+                    i, elem.text.c_str(), STATUS_VALID
+                );
+            }
+        }
+    }
+
+    unnest_data.set_size(num_rows);
+    auto* pkey_col = unnest_data.add_column("psp_pkey", DTYPE_INT32, true);
+    auto* okey_col = unnest_data.add_column("psp_okey", DTYPE_INT32, true);
+    for (t_uindex i = 0; i < num_rows; ++i) {
+        pkey_col->set_nth<std::int32_t>(i, static_cast<std::int32_t>(i), STATUS_VALID);
+        okey_col->set_nth<std::int32_t>(i, static_cast<std::int32_t>(i), STATUS_VALID);
+    }
+
+    unnest_table->clear();
+    unnest_table->init(unnest_data, num_rows, t_op::OP_INSERT, 0);
+}
+
 } // namespace perspective::server
@@ -30,6 +30,7 @@
 #include <cstring>
 #include <limits>
 #include <memory>
+#include <optional>
 #include <perspective/server.h>
 #include <perspective/residency.h>
 #include <perspective/opfs.h>
@@ -64,6 +65,65 @@ tscalar_to_proto(const t_tscalar& scalar, proto::Scalar* out) {
     }
 }
 
+/**
+ * Flatten a nested `FilterGroup` into its leaf `Filter` terms (depth first),
+ * returning the equivalent `t_filter_tree` whose leaves index into `leaves`.
+ */
+static t_filter_tree
+filter_tree_from_proto(
+    const proto::ViewConfig_FilterGroup& group,
+    std::vector<const proto::ViewConfig_Filter*>& leaves
+) {
+    std::vector<t_filter_tree> children;
+    children.reserve(group.filters().size());
+    for (const auto& node : group.filters()) {
+        switch (node.node_case()) {
+            case proto::ViewConfig_FilterTree::kFilter: {
+                children.push_back(t_filter_tree::leaf(leaves.size()));
+                leaves.push_back(&node.filter());
+            } break;
+            case proto::ViewConfig_FilterTree::kGroup: {
+                children.push_back(
+                    filter_tree_from_proto(node.group(), leaves)
+                );
+            } break;
+            case proto::ViewConfig_FilterTree::NODE_NOT_SET:
+                break;
+        }
+    }
+
+    auto op = group.op() == proto::ViewConfig_FilterReducer_OR ? FILTER_OP_OR
+                                                                : FILTER_OP_AND;
+
+    return t_filter_tree::group(op, group.negated(), std::move(children));
+}
+
+/**
+ * The inverse of `filter_tree_from_proto`, where `leaves` are the flat filter
+ * terms previously serialized to `ViewConfig.filter`.
+ */
+static void
+filter_tree_to_proto(
+    const t_filter_tree& tree,
+    const google::protobuf::RepeatedPtrField<proto::ViewConfig_Filter>& leaves,
+    proto::ViewConfig_FilterGroup* out
+) {
+    out->set_op(
+        tree.m_op == FILTER_OP_OR ? proto::ViewConfig_FilterReducer_OR
+                                  : proto::ViewConfig_FilterReducer_AND
+    );
+
+    out->set_negated(tree.m_negated);
+    for (const auto& child : tree.m_children) {
+        auto* node = out->add_filters();
+        if (child.is_leaf()) {
+            *node->mutable_filter() = leaves.Get(child.m_fterm_idx);
+        } else {
+            filter_tree_to_proto(child, leaves, node->mutable_group());
+        }
+    }
+}
+
 std::uint32_t server::ProtoServer::m_client_id = 1;
 
 template <>
@@ -109,6 +169,10 @@ make_context(
 
     auto cfg = t_config(columns, fterm, filter_op, expressions);
     cfg.set_backing_store(table->get_backing_store());
+    if (view_config->get_filter_tree().has_value()) {
+        cfg.set_filter_tree(*view_config->get_filter_tree());
+    }
+
     auto ctx0 = std::make_shared<t_ctx0>(*schema, cfg);
     ctx0->init();
     ctx0->sort_by(sortspec);
@@ -142,6 +206,10 @@ make_context(
 
     auto cfg = t_config(row_pivots, aggspecs, fterm, filter_op, expressions);
     cfg.set_backing_store(table->get_backing_store());
+    if (view_config->get_filter_tree().has_value()) {
+        cfg.set_filter_tree(*view_config->get_filter_tree());
+    }
+
     auto ctx1 = std::make_shared<t_ctx1>(*schema, cfg);
 
     ctx1->init();
@@ -202,6 +270,10 @@ make_context(
         column_only
     );
     cfg.set_backing_store(table->get_backing_store());
+    if (view_config->get_filter_tree().has_value()) {
+        cfg.set_filter_tree(*view_config->get_filter_tree());
+    }
+
     auto ctx2 = std::make_shared<t_ctx2>(*schema, cfg);
 
     ctx2->init();
@@ -453,6 +525,12 @@ ServerResources::get_table_ids() {
     return vec;
 }
 
+tsl::hopscotch_map<std::uint32_t, std::vector<ServerResources::t_id>>
+ServerResources::get_client_view_ids() {
+    PSP_READ_LOCK(m_write_lock);
+    return m_client_to_view;
+}
+
 std::shared_ptr<Table>
 ServerResources::get_table_for_view(const t_id& view_id) {
     PSP_READ_LOCK(m_write_lock);
@@ -521,6 +599,14 @@ ServerResources::delete_view(const std::uint32_t& client_id, const t_id& id) {
 
         auto& vec = m_client_to_view[client_id];
         vec.erase(std::remove(vec.begin(), vec.end(), id), vec.end());
+        for (auto it = m_exports.begin(); it != m_exports.end();) {
+            if (it->second.view_id == id) {
+                it = m_exports.erase(it);
+            } else {
+                ++it;
+            }
+        }
+
         auto range = m_table_to_view.equal_range(table_id);
         for (auto it = range.first; it != range.second;) {
             if (it->second == id) {
@@ -535,6 +621,19 @@ ServerResources::delete_view(const std::uint32_t& client_id, const t_id& id) {
     drop_view_on_delete_sub(id);
 }
 
+std::uint32_t
+ServerResources::get_client_id_for_view(const t_id& id) {
+    PSP_READ_LOCK(m_write_lock);
+    for (const auto& [client_id, view_ids] : m_client_to_view) {
+        if (std::find(view_ids.begin(), view_ids.end(), id)
+            != view_ids.end()) {
+            return client_id;
+        }
+    }
+
+    throw PerspectiveViewNotFoundException();
+}
+
 void
 ServerResources::delete_table(const t_id& id) {
     PSP_WRITE_LOCK(m_write_lock);
@@ -543,12 +642,93 @@ ServerResources::delete_table(const t_id& id) {
             m_tables.erase(id);
             m_dirty_tables.erase(id);
             m_deleted_tables.erase(id);
+            m_table_idle_timeouts.erase(id);
         } else {
             PSP_COMPLAIN_AND_ABORT("Cannot delete table with views");
         }
     }
 }
 
+void
+ServerResources::set_table_idle_timeout(
+    const t_id& id, std::chrono::milliseconds timeout
+) {
+    PSP_WRITE_LOCK(m_write_lock);
+    m_table_idle_timeouts[id] = IdleTimeout{timeout, now()};
+}
+
+std::optional<std::chrono::milliseconds>
+ServerResources::get_table_idle_timeout(const t_id& id) {
+    PSP_READ_LOCK(m_write_lock);
+    auto it = m_table_idle_timeouts.find(id);
+    if (it == m_table_idle_timeouts.end()) {
+        return std::nullopt;
+    }
+
+    return it->second.timeout;
+}
+
+void
+ServerResources::touch_table(const t_id& id) {
+    PSP_WRITE_LOCK(m_write_lock);
+    auto it = m_table_idle_timeouts.find(id);
+    if (it != m_table_idle_timeouts.end()) {
+        it.value().last_used = now();
+    }
+}
+
+std::vector<ServerResources::t_id>
+ServerResources::get_idle_tables() {
+    PSP_READ_LOCK(m_write_lock);
+    std::vector<t_id> out;
+    const auto time = now();
+    for (const auto& [id, idle] : m_table_idle_timeouts) {
+        if (time - idle.last_used <= idle.timeout) {
+            continue;
+        }
+
+        bool has_on_update = false;
+        auto range = m_table_to_view.equal_range(id);
+        for (auto it = range.first; it != range.second; ++it) {
+            auto subs = m_view_on_update_subs.find(it->second);
+            if (subs != m_view_on_update_subs.end() && !subs->second.empty()) {
+                has_on_update = true;
+                break;
+            }
+        }
+
+        if (!has_on_update) {
+            out.push_back(id);
+        }
+    }
+
+    return out;
+}
+
+void
+ServerResources::set_now(std::chrono::steady_clock::time_point now) {
+    PSP_WRITE_LOCK(m_write_lock);
+    m_now = now;
+}
+
+std::chrono::steady_clock::time_point
+ServerResources::now() const {
+    return m_now.value_or(std::chrono::steady_clock::now());
+}
+
+std::vector<std::pair<std::shared_ptr<Table>, const ServerResources::t_id>>
+ServerResources::get_retention_due_tables() {
+    PSP_READ_LOCK(m_write_lock);
+    std::vector<std::pair<std::shared_ptr<Table>, const t_id>> out;
+    for (const auto& [id, table] : m_tables) {
+        if (table->is_retention_due()) {
+            out.emplace_back(table, id);
+        }
+    }
+
+    return out;
+}
+
 void
 ServerResources::mark_table_dirty(const t_id& id) {
     PSP_WRITE_LOCK(m_write_lock);
@@ -775,6 +955,54 @@ ServerResources::drop_client(std::uint32_t client_id) {
     );
 
     m_on_hosted_tables_update_subs = subs;
+
+    PSP_WRITE_LOCK(m_write_lock);
+    m_client_to_view.erase(client_id);
+    m_client_capabilities.erase(client_id);
+    for (auto it = m_exports.begin(); it != m_exports.end();) {
+        if (it->first.first == client_id) {
+            it = m_exports.erase(it);
+        } else {
+            ++it;
+        }
+    }
+}
+
+void
+ServerResources::set_client_capabilities(
+    std::uint32_t client_id, std::uint64_t capabilities
+) {
+    PSP_WRITE_LOCK(m_write_lock);
+    m_client_capabilities[client_id] = capabilities & CAPABILITIES;
+}
+
+bool
+ServerResources::client_has_capability(
+    std::uint32_t client_id, std::uint64_t capability
+) {
+    PSP_READ_LOCK(m_write_lock);
+    const auto caps = m_client_capabilities.find(client_id);
+    return caps != m_client_capabilities.end()
+        && (caps->second & capability) == capability;
+}
+
+void
+ServerResources::host_export(
+    std::uint32_t client_id, std::uint32_t msg_id, StreamExport export_
+) {
+    PSP_WRITE_LOCK(m_write_lock);
+    m_exports[{client_id, msg_id}] = std::move(export_);
+}
+
+std::optional<StreamExport>
+ServerResources::take_export(std::uint32_t client_id, std::uint32_t msg_id) {
+    PSP_WRITE_LOCK(m_write_lock);
+    auto node = m_exports.extract({client_id, msg_id});
+    if (node.empty()) {
+        return std::nullopt;
+    }
+
+    return std::move(node.mapped());
 }
 
 std::uint32_t
@@ -894,6 +1122,20 @@ ProtoServer::handle_request(
     return serialized_responses;
 }
 
+void
+ProtoServer::set_eviction_session(std::uint32_t client_id) {
+    m_eviction_session = client_id;
+}
+
+void
+ProtoServer::set_now(std::int64_t now_ms) {
+    m_resources.set_now(
+        std::chrono::steady_clock::time_point(
+            std::chrono::milliseconds(now_ms)
+        )
+    );
+}
+
 std::vector<ProtoServerResp<std::string>>
 ProtoServer::poll() {
     const auto start = std::chrono::high_resolution_clock::now();
@@ -1019,12 +1261,132 @@ column_type_to_dtype(const proto::ColumnType& t) {
             return t_dtype::DTYPE_TIME;
         case proto::ColumnType::STRING:
             return t_dtype::DTYPE_STR;
+        case proto::ColumnType::INT64:
+            return logical_type_to_dtype(LOGICAL_TYPE_INT64);
+        case proto::ColumnType::DURATION:
+            return logical_type_to_dtype(LOGICAL_TYPE_DURATION);
+        case proto::ColumnType::LIST_STRING:
+            return logical_type_to_dtype(LOGICAL_TYPE_LIST_STRING);
+        case proto::ColumnType::LIST_FLOAT:
+            return logical_type_to_dtype(LOGICAL_TYPE_LIST_FLOAT);
         default:
             PSP_COMPLAIN_AND_ABORT("Invalid column type");
             return t_dtype::DTYPE_STR;
     }
 }
 
+/**
+ * The `t_logical_type` of a `proto::Schema` column, if it has one.
+ */
+static std::optional<t_logical_type>
+key_type_pair_to_logical_type(const proto::Schema_KeyTypePair& ktp) {
+    switch (ktp.type()) {
+        case proto::ColumnType::INT64:
+            return t_logical_type{LOGICAL_TYPE_INT64};
+        case proto::ColumnType::DURATION:
+            return t_logical_type{LOGICAL_TYPE_DURATION};
+        case proto::ColumnType::LIST_STRING:
+            return t_logical_type{LOGICAL_TYPE_LIST_STRING};
+        case proto::ColumnType::LIST_FLOAT:
+            return t_logical_type{LOGICAL_TYPE_LIST_FLOAT};
+        default:
+            return std::nullopt;
+    }
+}
+
+/**
+ * Report a column with a `t_logical_type` as its `proto::ColumnType`.
+ */
+static proto::ColumnType
+logical_type_to_column_type(const t_logical_type& logical_type) {
+    switch (logical_type.m_tag) {
+        case LOGICAL_TYPE_DURATION:
+            return proto::ColumnType::DURATION;
+        case LOGICAL_TYPE_LIST_STRING:
+            return proto::ColumnType::LIST_STRING;
+        case LOGICAL_TYPE_LIST_FLOAT:
+            return proto::ColumnType::LIST_FLOAT;
+        case LOGICAL_TYPE_INT64:
+        default:
+            return proto::ColumnType::INT64;
+    }
+}
+
+/**
+ * Write the columns of `table`, in order, and their types to `output`.
+ */
+static void
+write_table_schema(const Table& table, proto::Schema* output) {
+    auto table_schema = table.get_schema();
+    auto columns = table_schema.columns();
+    auto types = table_schema.types();
+    const auto& logical_types = table.get_logical_types();
+    for (std::size_t i = 0; i < table_schema.size(); ++i) {
+        auto* ktp = output->add_schema();
+        ktp->set_name(columns[i]);
+        auto logical_type = logical_types.find(columns[i]);
+        if (logical_type != logical_types.end()) {
+            ktp->set_type(logical_type_to_column_type(logical_type->second));
+        } else {
+            ktp->set_type(dtype_to_column_type(types[i]));
+        }
+    }
+}
+
+/**
+ * The Arrow IPC body compression codec named by a
+ * `ViewToArrowReq::compression`.
+ */
+static arrow::Compression::type
+parse_arrow_compression(const std::string& compression) {
+    if (compression.empty() || compression == "uncompressed") {
+        return arrow::Compression::UNCOMPRESSED;
+    }
+
+    if (compression == "lz4") {
+        return arrow::Compression::LZ4_FRAME;
+    }
+
+    if (compression == "zstd") {
+        return arrow::Compression::ZSTD;
+    }
+
+    PSP_COMPLAIN_AND_ABORT("Unknown Arrow compression " + compression);
+    return arrow::Compression::UNCOMPRESSED;
+}
+
+/**
+ * The rows per chunk of a streaming export, where `0` is the default.
+ */
+static std::uint32_t
+parse_chunk_rows(std::uint32_t chunk_rows) {
+    static constexpr std::uint32_t DEFAULT_CHUNK_ROWS = 65536;
+    static constexpr std::uint32_t MAX_CHUNK_ROWS = 1 << 24;
+    return chunk_rows == 0 ? DEFAULT_CHUNK_ROWS
+                           : std::min(chunk_rows, MAX_CHUNK_ROWS);
+}
+
+/**
+ * @brief Serialize the next chunk of `stream` as a `ViewToArrowStreamResp` or
+ * `ViewToCsvStreamResp`, and whether it is the last.
+ */
+static std::pair<proto::Response, bool>
+next_stream_chunk(const StreamExport& stream) {
+    auto [chunk, done] = stream.data->next();
+    proto::Response resp;
+    if (stream.is_csv) {
+        auto* chunk_resp = resp.mutable_view_to_csv_stream_resp();
+        *chunk_resp->mutable_csv() = std::move(chunk);
+        chunk_resp->set_done(done);
+    } else {
+        auto* chunk_resp = resp.mutable_view_to_arrow_stream_resp();
+        *chunk_resp->mutable_arrow() = std::move(chunk);
+        chunk_resp->set_done(done);
+    }
+
+    return {std::move(resp), done};
+}
+
 struct ValidViewPort {
     std::uint32_t start_row;
     std::uint32_t end_row;
@@ -1089,6 +1451,8 @@ needs_poll(const proto::Request::ClientReqCase proto_case) {
         case ReqCase::kViewToRowsStringReq:
         case ReqCase::kViewToNdjsonStringReq:
         case ReqCase::kViewToArrowReq:
+        case ReqCase::kViewToArrowStreamReq:
+        case ReqCase::kViewToCsvStreamReq:
         case ReqCase::kViewSchemaReq:
         case ReqCase::kViewGetMinMaxReq:
         case ReqCase::kTableRemoveReq:
@@ -1115,6 +1479,9 @@ needs_poll(const proto::Request::ClientReqCase proto_case) {
         case ReqCase::kServerSystemInfoReq:
         case ReqCase::kGetFeaturesReq:
         case ReqCase::kMakeJoinTableReq:
+        case ReqCase::kMakeUnionTableReq:
+        case ReqCase::kMakeUnnestTableReq:
+        case ReqCase::kViewStreamNextReq:
             return false;
         case proto::Request::CLIENT_REQ_NOT_SET:
             throw std::runtime_error("Unhandled request type 2");
@@ -1143,6 +1510,8 @@ entity_type_is_table(const proto::Request::ClientReqCase proto_case) {
         case ReqCase::kTableDeleteReq:
         case ReqCase::kTableMakeViewReq:
         case ReqCase::kMakeJoinTableReq:
+        case ReqCase::kMakeUnionTableReq:
+        case ReqCase::kMakeUnnestTableReq:
             return true;
         case ReqCase::kViewOnDeleteReq:
         case ReqCase::kViewRemoveDeleteReq:
@@ -1152,6 +1521,8 @@ entity_type_is_table(const proto::Request::ClientReqCase proto_case) {
         case ReqCase::kViewToNdjsonStringReq:
         case ReqCase::kViewToRowsStringReq:
         case ReqCase::kViewToArrowReq:
+        case ReqCase::kViewToArrowStreamReq:
+        case ReqCase::kViewToCsvStreamReq:
         case ReqCase::kViewSchemaReq:
         case ReqCase::kViewGetMinMaxReq:
         case ReqCase::kViewOnUpdateReq:
@@ -1163,6 +1534,7 @@ entity_type_is_table(const proto::Request::ClientReqCase proto_case) {
         case ReqCase::kViewDeleteReq:
         case ReqCase::kViewExpressionSchemaReq:
         case ReqCase::kViewRemoveOnUpdateReq:
+        case ReqCase::kViewStreamNextReq:
         case ReqCase::kRemoveHostedTablesUpdateReq:
             return false;
         case proto::Request::CLIENT_REQ_NOT_SET:
@@ -1397,6 +1769,14 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
         proto_resp.emplace_back(std::move(resp2));
     };
 
+    // A request on a table, or on any of its views, resets its idle timeout.
+    if (m_resources.has_table(entity_id)) {
+        m_resources.touch_table(entity_id);
+    } else if (m_resources.has_view(entity_id)) {
+        m_resources.touch_table(m_resources.get_table_id_for_view(entity_id));
+    }
+
+    _enforce_retention(proto_resp);
     if (!m_realtime_mode) {
         handle_process_table(req, proto_resp);
     }
@@ -1404,12 +1784,21 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
     switch (req.client_req_case()) {
         case proto::Request::kGetFeaturesReq: {
             proto::Response resp;
+            const auto& features_req = req.get_features_req();
+            m_resources.set_client_capabilities(
+                client_id, features_req.capabilities()
+            );
+
             const auto& features = resp.mutable_get_features_resp();
+            features->set_protocol_version(PROTOCOL_VERSION);
+            features->set_min_protocol_version(MIN_PROTOCOL_VERSION);
+            features->set_capabilities(CAPABILITIES);
             features->set_group_by(true);
             features->set_split_by(true);
             features->set_sort(true);
             features->set_on_update(true);
             features->set_expressions(true);
+            features->set_nested_filters(true);
             features->add_group_rollup_mode(proto::GroupRollupMode::ROLLUP);
             features->add_group_rollup_mode(proto::GroupRollupMode::FLAT);
             features->add_group_rollup_mode(proto::GroupRollupMode::TOTAL);
@@ -1445,9 +1834,25 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
             (*features->mutable_filter_ops())[proto::ColumnType::DATE] = opts2;
             (*features->mutable_filter_ops())[proto::ColumnType::DATETIME] =
                 opts2;
+            (*features->mutable_filter_ops())[proto::ColumnType::INT64] = opts2;
+            (*features->mutable_filter_ops())[proto::ColumnType::DURATION] =
+                opts2;
             (*features->mutable_filter_ops())[proto::ColumnType::INTEGER] =
                 std::move(opts2);
 
+            // `contains` matches a list with an element equal to the filter
+            // value, and `contains any` one with an element in the filter
+            // list.
+            proto::GetFeaturesResp_ColumnTypeOptions list_opts;
+            list_opts.add_options("contains");
+            list_opts.add_options("contains any");
+            list_opts.add_options("is not null");
+            list_opts.add_options("is null");
+            (*features->mutable_filter_ops())[proto::ColumnType::LIST_STRING] =
+                list_opts;
+            (*features->mutable_filter_ops())[proto::ColumnType::LIST_FLOAT] =
+                std::move(list_opts);
+
             proto::GetFeaturesResp_ColumnTypeOptions opts3;
             opts3.add_options("==");
             // opts3.add_options("!=");
@@ -1481,6 +1886,12 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
             (*features->mutable_aggregates())[proto::ColumnType::BOOLEAN] =
                 string_opts;
 
+            (*features->mutable_aggregates())[proto::ColumnType::LIST_STRING] =
+                string_opts;
+
+            (*features->mutable_aggregates())[proto::ColumnType::LIST_FLOAT] =
+                string_opts;
+
             proto::GetFeaturesResp_AggregateOptions number_opts;
             number_opts.add_aggregates()->set_name("sum");
             number_opts.add_aggregates()->set_name("abs sum");
@@ -1519,6 +1930,37 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
             (*features->mutable_aggregates())[proto::ColumnType::FLOAT] =
                 number_opts;
 
+            (*features->mutable_aggregates())[proto::ColumnType::INT64] =
+                number_opts;
+
+            // Products and ratios of durations aren't durations.
+            proto::GetFeaturesResp_AggregateOptions duration_opts;
+            duration_opts.add_aggregates()->set_name("sum");
+            duration_opts.add_aggregates()->set_name("abs sum");
+            duration_opts.add_aggregates()->set_name("any");
+            duration_opts.add_aggregates()->set_name("avg");
+            duration_opts.add_aggregates()->set_name("count");
+            duration_opts.add_aggregates()->set_name("distinct count");
+            duration_opts.add_aggregates()->set_name("dominant");
+            duration_opts.add_aggregates()->set_name("first");
+            duration_opts.add_aggregates()->set_name("high");
+            duration_opts.add_aggregates()->set_name("low");
+            duration_opts.add_aggregates()->set_name("max");
+            duration_opts.add_aggregates()->set_name("min");
+            duration_opts.add_aggregates()->set_name("high minus low");
+            duration_opts.add_aggregates()->set_name("last by index");
+            duration_opts.add_aggregates()->set_name("last minus first");
+            duration_opts.add_aggregates()->set_name("last");
+            duration_opts.add_aggregates()->set_name("mean");
+            duration_opts.add_aggregates()->set_name("median");
+            duration_opts.add_aggregates()->set_name("q1");
+            duration_opts.add_aggregates()->set_name("q3");
+            duration_opts.add_aggregates()->set_name("sum abs");
+            duration_opts.add_aggregates()->set_name("sum not null");
+            duration_opts.add_aggregates()->set_name("unique");
+            (*features->mutable_aggregates())[proto::ColumnType::DURATION] =
+                duration_opts;
+
             proto::GetFeaturesResp_AggregateOptions datetime_opts;
             datetime_opts.add_aggregates()->set_name("count");
             datetime_opts.add_aggregates()->set_name("any");
@@ -1564,6 +2006,30 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                     if (tbl->get_limit() != std::numeric_limits<int>::max()) {
                         v->set_limit(tbl->get_limit());
                     }
+
+                    if (m_join_engine.is_join_table(name)
+                        || m_join_engine.is_union_table(name)
+                        || m_join_engine.is_unnest_table(name)) {
+                        v->set_derived(true);
+                    }
+
+                    auto idle_timeout = m_resources.get_table_idle_timeout(name);
+                    if (tbl->has_retention() || idle_timeout.has_value()) {
+                        auto* retention = v->mutable_retention();
+                        if (idle_timeout.has_value()) {
+                            retention->set_idle_timeout_ms(idle_timeout->count()
+                            );
+                        }
+
+                        if (auto max_rows = tbl->get_max_rows()) {
+                            retention->set_max_rows(*max_rows);
+                        }
+
+                        if (auto max_age = tbl->get_max_age()) {
+                            retention->set_age_column(tbl->get_age_column());
+                            retention->set_max_age_ms(*max_age);
+                        }
+                    }
                 }
 
                 push_resp(std::move(resp));
@@ -1620,6 +2086,10 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 ? BACKING_STORE_DISK
                 : BACKING_STORE_MEMORY;
 
+            // Copied, as `req` is moved-from once the data is parsed.
+            const auto retention = r.options().retention();
+            const auto schema_only = r.options().schema_only();
+
             switch (r.data().data_case()) {
                 case proto::MakeTableData::kFromView: {
                     auto view = m_resources.get_view(r.data().from_view());
@@ -1692,16 +2162,23 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 case proto::MakeTableData::kFromSchema: {
                     std::vector<std::string> columns;
                     std::vector<t_dtype> types;
+                    std::map<std::string, t_logical_type> logical_types;
                     const auto& schema = r.data().from_schema().schema();
                     for (const auto& it : schema) {
                         columns.push_back(it.name());
                         types.push_back(column_type_to_dtype(it.type()));
+                        if (auto logical_type =
+                                key_type_pair_to_logical_type(it)) {
+                            logical_types[it.name()] = *logical_type;
+                        }
                     }
 
                     t_schema table_schema(columns, types);
                     table = Table::from_schema(
                         index, table_schema, limit, backing_store
                     );
+
+                    table->set_logical_types(std::move(logical_types));
                     break;
                 }
                 case proto::MakeTableData::DATA_NOT_SET: {
@@ -1710,7 +2187,43 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 }
             }
 
+            // The table is only parsed for its schema, and is not hosted.
+            if (schema_only) {
+                proto::Response resp;
+                write_table_schema(
+                    *table, resp.mutable_make_table_resp()->mutable_schema()
+                );
+
+                push_resp(std::move(resp));
+                break;
+            }
+
+            if (retention.has_max_rows() || retention.has_max_age_ms()
+                || !retention.age_column().empty()) {
+                std::optional<std::uint32_t> max_rows;
+                std::optional<std::int64_t> max_age;
+                if (retention.has_max_rows()) {
+                    max_rows = retention.max_rows();
+                }
+
+                if (retention.has_max_age_ms()) {
+                    max_age = retention.max_age_ms();
+                }
+
+                table->set_retention(max_rows, retention.age_column(), max_age);
+                if (table->apply_retention()) {
+                    table->get_pool()->_process();
+                }
+            }
+
             m_resources.host_table(entity_id, table);
+            if (retention.has_idle_timeout_ms()) {
+                m_resources.set_table_idle_timeout(
+                    entity_id,
+                    std::chrono::milliseconds(retention.idle_timeout_ms())
+                );
+            }
+
             proto::Response resp;
             resp.mutable_make_table_resp();
             push_resp(std::move(resp));
@@ -1763,9 +2276,31 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
             auto left_table = m_resources.get_table(r.left_table_id());
             auto right_table = m_resources.get_table(r.right_table_id());
 
-            auto result = m_join_engine.make_join_table(
-                r.on_column(), r.right_on_column(), r.join_type(), left_table, right_table
-            );
+            JoinDef def;
+            def.left_table_id = r.left_table_id();
+            def.right_table_id = r.right_table_id();
+            def.join_type = r.join_type();
+            def.left_suffix = r.left_suffix();
+            def.right_suffix = r.right_suffix();
+            def.as_of_column = r.as_of_column();
+            def.right_as_of_column = r.right_as_of_column();
+            if (r.on_columns_size() > 0) {
+                def.on_columns.assign(
+                    r.on_columns().begin(), r.on_columns().end()
+                );
+
+                def.right_on_columns.assign(
+                    r.right_on_columns().begin(), r.right_on_columns().end()
+                );
+            } else if (!r.on_column().empty()) {
+                def.on_columns.push_back(r.on_column());
+                if (!r.right_on_column().empty()) {
+                    def.right_on_columns.push_back(r.right_on_column());
+                }
+            }
+
+            auto result =
+                m_join_engine.make_join_table(def, left_table, right_table);
 
             if (!result.ok()) {
                 proto::Response resp;
@@ -1775,14 +2310,7 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
             }
 
             m_resources.host_table(entity_id, result.table);
-            m_join_engine.register_join(
-                entity_id,
-                r.left_table_id(),
-                r.right_table_id(),
-                r.on_column(),
-                r.right_on_column(),
-                r.join_type()
-            );
+            m_join_engine.register_join(entity_id, std::move(def));
 
             // Compute initial join
             m_join_engine.recompute(
@@ -1812,6 +2340,134 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
 
             break;
         }
+        case proto::Request::kMakeUnionTableReq: {
+            const auto& r = req.make_union_table_req();
+            if (m_resources.has_table(entity_id)) {
+                proto::Response resp;
+                auto* err = resp.mutable_server_error()->mutable_message();
+                std::stringstream ss;
+                ss << "Table \"" << entity_id << "\" already exists";
+                *err = ss.str();
+                push_resp(std::move(resp));
+                break;
+            }
+
+            UnionDef def;
+            def.table_ids.assign(r.table_ids().begin(), r.table_ids().end());
+            def.source_column = r.source_column();
+            std::vector<std::shared_ptr<Table>> tables;
+            std::string missing_table_id;
+            for (const auto& table_id : def.table_ids) {
+                if (!m_resources.has_table(table_id)) {
+                    missing_table_id = table_id;
+                    break;
+                }
+
+                tables.push_back(m_resources.get_table(table_id));
+            }
+
+            if (!missing_table_id.empty()) {
+                proto::Response resp;
+                auto* err = resp.mutable_server_error()->mutable_message();
+                std::stringstream ss;
+                ss << "Table \"" << missing_table_id << "\" not found";
+                *err = ss.str();
+                push_resp(std::move(resp));
+                break;
+            }
+
+            auto result = m_join_engine.make_union_table(def, tables);
+            if (!result.ok()) {
+                proto::Response resp;
+                *resp.mutable_server_error()->mutable_message() = result.error;
+                push_resp(std::move(resp));
+                break;
+            }
+
+            m_resources.host_table(entity_id, result.table);
+            m_join_engine.register_union(entity_id, std::move(def));
+            m_join_engine.recompute_union(entity_id, tables, result.table);
+            auto ut = m_resources.get_table(entity_id);
+            ut->get_pool()->_process();
+            m_resources.mark_table_dirty(entity_id);
+            m_resources.mark_table_clean(entity_id);
+
+            proto::Response resp;
+            resp.mutable_make_union_table_resp();
+            push_resp(std::move(resp));
+
+            // Notify on_hosted_tables_update listeners
+            auto subscriptions = m_resources.get_on_hosted_tables_update_sub();
+            for (auto& subscription : subscriptions) {
+                Response out;
+                out.set_msg_id(subscription.id);
+                ProtoServerResp<ProtoServer::Response> resp2;
+                resp2.data = std::move(out);
+                resp2.client_id = subscription.client_id;
+                proto_resp.emplace_back(std::move(resp2));
+            }
+
+            break;
+        }
+        case proto::Request::kMakeUnnestTableReq: {
+            const auto& r = req.make_unnest_table_req();
+            if (m_resources.has_table(entity_id)) {
+                proto::Response resp;
+                auto* err = resp.mutable_server_error()->mutable_message();
+                std::stringstream ss;
+                ss << "Table \"" << entity_id << "\" already exists";
+                *err = ss.str();
+                push_resp(std::move(resp));
+                break;
+            }
+
+            if (!m_resources.has_table(r.table_id())) {
+                proto::Response resp;
+                auto* err = resp.mutable_server_error()->mutable_message();
+                std::stringstream ss;
+                ss << "Table \"" << r.table_id() << "\" not found";
+                *err = ss.str();
+                push_resp(std::move(resp));
+                break;
+            }
+
+            UnnestDef def;
+            def.table_id = r.table_id();
+            def.column = r.column();
+            auto table = m_resources.get_table(def.table_id);
+            auto result = m_join_engine.make_unnest_table(def, table);
+            if (!result.ok()) {
+                proto::Response resp;
+                *resp.mutable_server_error()->mutable_message() = result.error;
+                push_resp(std::move(resp));
+                break;
+            }
+
+            m_resources.host_table(entity_id, result.table);
+            m_join_engine.register_unnest(entity_id, std::move(def));
+            m_join_engine.recompute_unnest(entity_id, table, result.table);
+            auto ut = m_resources.get_table(entity_id);
+            ut->get_pool()->_process();
+            m_resources.mark_table_dirty(entity_id);
+            m_resources.mark_table_clean(entity_id);
+
+            proto::Response resp;
+            resp.mutable_make_unnest_table_resp();
+            push_resp(std::move(resp));
+
+            // Notify on_hosted_tables_update listeners
+            auto subscriptions = m_resources.get_on_hosted_tables_update_sub();
+            for (auto& subscription : subscriptions) {
+                Response out;
+                out.set_msg_id(subscription.id);
+                ProtoServerResp<ProtoServer::Response> resp2;
+                resp2.data = std::move(out);
+                resp2.client_id = subscription.client_id;
+                proto_resp.emplace_back(std::move(resp2));
+            }
+
+            break;
+        }
         case proto::Request::kTableSizeReq: {
             auto table = m_resources.get_table(req.entity_id());
             proto::Response resp;
@@ -1824,16 +2480,9 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
             auto table = m_resources.get_table(req.entity_id());
 
             proto::Response resp;
-            auto* output_schema =
-                resp.mutable_table_schema_resp()->mutable_schema();
-            auto table_schema = table->get_schema();
-            auto columns = table_schema.columns();
-            auto types = table_schema.types();
-            for (std::size_t i = 0; i < table_schema.size(); ++i) {
-                auto* ktp = output_schema->add_schema();
-                ktp->set_name(columns[i]);
-                ktp->set_type(dtype_to_column_type(types[i]));
-            }
+            write_table_schema(
+                *table, resp.mutable_table_schema_resp()->mutable_schema()
+            );
 
             push_resp(std::move(resp));
             break;
@@ -1920,8 +2569,26 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 push_resp(std::move(resp));
                 break;
             }
+
+            if (m_join_engine.is_union_table(req.entity_id())) {
+                proto::Response resp;
+                *resp.mutable_server_error()->mutable_message() =
+                    "Cannot update a read-only union table";
+                push_resp(std::move(resp));
+                break;
+            }
+
+            if (m_join_engine.is_unnest_table(req.entity_id())) {
+                proto::Response resp;
+                *resp.mutable_server_error()->mutable_message() =
+                    "Cannot update a read-only unnest table";
+                push_resp(std::move(resp));
+                break;
+            }
+
             auto table = m_resources.get_table(req.entity_id());
             table->clear();
+            m_join_engine.invalidate_union_sources(req.entity_id());
             const auto& r = req.table_replace_req();
             switch (r.data().data_case()) {
                 case proto::MakeTableData::kFromArrow: {
@@ -1961,6 +2628,23 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 push_resp(std::move(resp));
                 break;
             }
+
+            if (m_join_engine.is_union_table(req.entity_id())) {
+                proto::Response resp;
+                *resp.mutable_server_error()->mutable_message() =
+                    "Cannot update a read-only union table";
+                push_resp(std::move(resp));
+                break;
+            }
+
+            if (m_join_engine.is_unnest_table(req.entity_id())) {
+                proto::Response resp;
+                *resp.mutable_server_error()->mutable_message() =
+                    "Cannot update a read-only unnest table";
+                push_resp(std::move(resp));
+                break;
+            }
+
             const auto& r = req.table_remove_req();
             auto table = m_resources.get_table(req.entity_id());
             switch (r.data().data_case()) {
@@ -1997,6 +2681,23 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 push_resp(std::move(resp));
                 break;
             }
+
+            if (m_join_engine.is_union_table(req.entity_id())) {
+                proto::Response resp;
+                *resp.mutable_server_error()->mutable_message() =
+                    "Cannot update a read-only union table";
+                push_resp(std::move(resp));
+                break;
+            }
+
+            if (m_join_engine.is_unnest_table(req.entity_id())) {
+                proto::Response resp;
+                *resp.mutable_server_error()->mutable_message() =
+                    "Cannot update a read-only unnest table";
+                push_resp(std::move(resp));
+                break;
+            }
+
             const auto& r = req.table_update_req();
             auto table = m_resources.get_table(req.entity_id());
             switch (r.data().data_case()) {
@@ -2148,14 +2849,28 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 ));
             }
 
+            // Nested filter trees are flattened to their leaf terms, which
+            // the `t_filter_tree` references by index.
+            std::vector<const proto::ViewConfig_Filter*> filter_leaves;
+            std::optional<t_filter_tree> filter_tree;
+            if (cfg.has_filter_tree()) {
+                filter_tree =
+                    filter_tree_from_proto(cfg.filter_tree(), filter_leaves);
+            } else {
+                for (const auto& f : cfg.filter()) {
+                    filter_leaves.push_back(&f);
+                }
+            }
+
             t_vocab vocab;
             vocab.init(false);
             std::vector<
                 std::tuple<std::string, std::string, std::vector<t_tscalar>>>
                 filter;
-            filter.reserve(cfg.filter().size());
+            filter.reserve(filter_leaves.size());
 
-            for (const auto& f : cfg.filter()) {
+            for (const auto* leaf : filter_leaves) {
+                const auto& f = *leaf;
                 for (const auto& arg : f.value()) {
                     switch (arg.scalar_case()) {
                         case proto::Scalar::kString: {
@@ -2169,6 +2884,8 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                             break;
                         }
                         case proto::Scalar::kBool:
+                        case proto::Scalar::kDate:
+                        case proto::Scalar::kDatetime:
                         case proto::Scalar::kFloat:
                         case proto::Scalar::kNull:
                         case proto::Scalar::SCALAR_NOT_SET:
@@ -2177,7 +2894,18 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 }
             }
 
-            for (const auto& f : cfg.filter()) {
+            const auto& logical_types = table->get_logical_types();
+            for (const auto* leaf : filter_leaves) {
+                const auto& f = *leaf;
+
+                // List columns are stored as `string`, but their filter
+                // values are compared to each element of the list.
+                auto logical_type = logical_types.find(f.column());
+                bool is_list = logical_type != logical_types.end()
+                    && (logical_type->second.m_tag == LOGICAL_TYPE_LIST_STRING
+                        || logical_type->second.m_tag
+                            == LOGICAL_TYPE_LIST_FLOAT);
+
                 std::vector<t_tscalar> args;
                 args.reserve(f.value().size());
                 for (const auto& arg : f.value()) {
@@ -2190,9 +2918,13 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                             break;
                         }
                         case proto::Scalar::kFloat: {
-                            a = coerce_to(
-                                schema->get_dtype(f.column()), arg.float_()
-                            );
+                            if (is_list) {
+                                a.set(arg.float_());
+                            } else {
+                                a = coerce_to(
+                                    schema->get_dtype(f.column()), arg.float_()
+                                );
+                            }
 
                             args.push_back(a);
                             break;
@@ -2225,6 +2957,28 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                             args.push_back(a);
                             break;
                         }
+                        case proto::Scalar::kDate:
+                        case proto::Scalar::kDatetime: {
+                            auto dtype = schema->get_dtype(f.column());
+                            if (dtype != DTYPE_DATE && dtype != DTYPE_TIME) {
+                                PSP_COMPLAIN_AND_ABORT(
+                                    "Filter column is not a date or datetime: "
+                                    + f.column()
+                                );
+                            }
+
+                            // Both are UTC, so `date` is midnight UTC and a
+                            // `datetime` compared to a `date` column is
+                            // truncated to its UTC calendar date.
+                            auto millis = arg.scalar_case()
+                                    == proto::Scalar::kDate
+                                ? static_cast<double>(arg.date()) * 86400000.0
+                                : static_cast<double>(arg.datetime());
+
+                            a = coerce_to(dtype, millis);
+                            args.push_back(a);
+                            break;
+                        }
                         case proto::Scalar::kNull:
                             a.set(t_none());
                             args.push_back(a);
@@ -2238,7 +2992,12 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                     }
                 }
 
-                filter.emplace_back(f.column(), f.op(), args);
+                auto op = f.op();
+                if (is_list && op == "contains") {
+                    op = "list contains";
+                }
+
+                filter.emplace_back(f.column(), op, args);
             }
 
             const auto& cols = cfg.columns();
@@ -2309,6 +3068,10 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 config->set_row_pivot_depth(cfg.group_by_depth());
             }
 
+            if (filter_tree.has_value()) {
+                config->set_filter_tree(*filter_tree);
+            }
+
             std::uint32_t sides;
 
             if (!group_by.empty() || !split_by.empty()) {
@@ -2418,6 +3181,10 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 (*view_schema)[k] = dtype_to_column_type(str_to_dtype(v));
             }
 
+            for (const auto& [k, v] : view->logical_types()) {
+                (*view_schema)[k] = logical_type_to_column_type(v);
+            }
+
             push_resp(std::move(resp));
             break;
         }
@@ -2506,6 +3273,12 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 s->set_op(sort_op_to_proto(sort.m_sort_type));
             }
 
+            // Clients which predate typed `date`/`datetime` scalars expect
+            // dates as strings and datetimes as POSIX milliseconds.
+            const bool temporal_scalars = m_resources.client_has_capability(
+                client_id, CAPABILITY_TEMPORAL_SCALARS
+            );
+
             for (const auto& filter : view_config->get_fterm()) {
                 auto* proto_filter = view_config_proto->mutable_filter();
                 auto* f = proto_filter->Add();
@@ -2513,7 +3286,8 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 f->set_op(filter_op_to_str(filter.m_op));
                 auto vals = std::vector<t_tscalar>(filter.m_bag.size());
                 if (filter.m_op != FILTER_OP_NOT_IN
-                    && filter.m_op != FILTER_OP_IN) {
+                    && filter.m_op != FILTER_OP_IN
+                    && filter.m_op != FILTER_OP_LIST_CONTAINS_ANY) {
                     vals.push_back(filter.m_threshold);
                 } else {
                     for (const auto& scalar : filter.m_bag) {
@@ -2562,21 +3336,36 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                             break;
                         case DTYPE_DATE: {
                             auto tm = scalar.get<t_date>();
-                            std::stringstream ss;
-                            ss << std::setfill('0') << std::setw(4) << tm.year()
-                               << "-" << std::setfill('0')
-                               << std::setw(2)
-                               // Increment month by 1, as date::month is [1-12]
-                               // but t_date::month() is [0-11]
-                               << tm.month() + 1 << "-" << std::setfill('0')
-                               << std::setw(2) << tm.day();
-                            s->set_string(ss.str());
+
+                            // Increment month by 1, as date::month is [1-12]
+                            // but t_date::month() is [0-11]
+                            date::sys_days days{date::year_month_day{
+                                date::year{tm.year()},
+                                date::month{
+                                    static_cast<unsigned>(tm.month() + 1)
+                                },
+                                date::day{static_cast<unsigned>(tm.day())}
+                            }};
+
+                            if (temporal_scalars) {
+                                s->set_date(days.time_since_epoch().count());
+                            } else {
+                                s->set_string(date::format("%F", days));
+                            }
+
                             break;
                         }
                         case DTYPE_TIME:
-                            s->set_float_(
-                                (double)scalar.get<t_time>().raw_value()
-                            );
+                            if (temporal_scalars) {
+                                s->set_datetime(
+                                    scalar.get<t_time>().raw_value()
+                                );
+                            } else {
+                                s->set_float_(
+                                    (double)scalar.get<t_time>().raw_value()
+                                );
+                            }
+
                             break;
                         case DTYPE_NONE:
                             s->set_null(
@@ -2607,6 +3396,17 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                     break;
             }
 
+            const auto& filter_tree = view_config->get_filter_tree();
+            if (filter_tree.has_value()) {
+                filter_tree_to_proto(
+                    *filter_tree,
+                    view_config_proto->filter(),
+                    view_config_proto->mutable_filter_tree()
+                );
+
+                view_config_proto->clear_filter();
+            }
+
             if (view_config->get_row_pivot_depth() != -1) {
                 view_config_proto->set_group_by_depth(
                     view_config->get_row_pivot_depth()
@@ -2795,25 +3595,44 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
             break;
         }
         case proto::Request::kTableDeleteReq: {
-            // Prevent deleting a source table that feeds a join table
+            // Prevent deleting a source table that feeds a join, union or
+            // unnest table
             auto dependents =
                 m_join_engine.get_dependent_join_tables(req.entity_id());
             if (!dependents.empty()
-                && !m_join_engine.is_join_table(req.entity_id())) {
+                && !m_join_engine.is_join_table(req.entity_id())
+                && !m_join_engine.is_union_table(req.entity_id())
+                && !m_join_engine.is_unnest_table(req.entity_id())) {
+                const char* kind = "join";
+                if (m_join_engine.is_union_table(dependents[0])) {
+                    kind = "union";
+                } else if (m_join_engine.is_unnest_table(dependents[0])) {
+                    kind = "unnest";
+                }
+
                 proto::Response resp;
                 std::stringstream ss;
-                ss << "Cannot delete table: it is a source for join table \""
-                   << dependents[0] << "\"";
+                ss << "Cannot delete table: it is a source for " << kind
+                   << " table \"" << dependents[0] << "\"";
                 *resp.mutable_server_error()->mutable_message() = ss.str();
                 push_resp(std::move(resp));
                 break;
             }
 
-            // If this is a join table being deleted, clean up join metadata
+            // If this is a join, union or unnest table being deleted, clean up
+            // its metadata
             if (m_join_engine.is_join_table(req.entity_id())) {
                 m_join_engine.unregister_join(req.entity_id());
             }
 
+            if (m_join_engine.is_union_table(req.entity_id())) {
+                m_join_engine.unregister_union(req.entity_id());
+            }
+
+            if (m_join_engine.is_unnest_table(req.entity_id())) {
+                m_join_engine.unregister_unnest(req.entity_id());
+            }
+
             const auto is_immediate = req.table_delete_req().is_immediate();
             if (is_immediate
                 || m_resources.get_table_view_count(req.entity_id()) == 0) {
@@ -2965,7 +3784,7 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 dims.start_col,
                 dims.end_col,
                 true,
-                r.compression() == "lz4",
+                parse_arrow_compression(r.compression()),
                 legacy_names
             );
 
@@ -2996,6 +3815,117 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
             push_resp(std::move(resp));
             break;
         }
+        case proto::Request::kViewToArrowStreamReq: {
+            LOG_DEBUG("Handling ViewToArrowStreamReq");
+            auto view = m_resources.get_view(req.entity_id());
+            const auto& r = req.view_to_arrow_stream_req();
+            auto config = view->get_view_config();
+            auto num_hidden = calculate_num_hidden(*view, *config);
+            auto dims = parse_format_options(
+                r.viewport(),
+                view->num_columns(),
+                view->num_rows(),
+                view->sides(),
+                view->get_view_config()->is_column_only(),
+                num_hidden
+            );
+
+            bool legacy_names = r.viewport().has_emit_legacy_row_path_names()
+                ? r.viewport().emit_legacy_row_path_names()
+                : true;
+            StreamExport stream{
+                req.entity_id(),
+                false,
+                view->export_arrow(
+                    dims.start_row,
+                    dims.end_row,
+                    dims.start_col,
+                    dims.end_col,
+                    parse_chunk_rows(r.chunk_rows()),
+                    parse_arrow_compression(r.compression()),
+                    legacy_names
+                )
+            };
+
+            // The rest of the export is written one chunk per
+            // `ViewStreamNextReq`, so it is never held in memory at once.
+            auto [resp, done] = next_stream_chunk(stream);
+            if (!done) {
+                m_resources.host_export(
+                    client_id, req.msg_id(), std::move(stream)
+                );
+            }
+
+            push_resp(std::move(resp));
+            break;
+        }
+        case proto::Request::kViewToCsvStreamReq: {
+            LOG_DEBUG("Handling ViewToCsvStreamReq");
+            auto view = m_resources.get_view(req.entity_id());
+            const auto& r = req.view_to_csv_stream_req();
+            auto config = view->get_view_config();
+            auto num_hidden = calculate_num_hidden(*view, *config);
+            auto dims = parse_format_options(
+                r.viewport(),
+                view->num_columns(),
+                view->num_rows(),
+                view->sides(),
+                view->get_view_config()->is_column_only(),
+                num_hidden
+            );
+
+            StreamExport stream{
+                req.entity_id(),
+                true,
+                view->export_csv(
+                    dims.start_row,
+                    dims.end_row,
+                    dims.start_col,
+                    dims.end_col,
+                    parse_chunk_rows(r.chunk_rows())
+                )
+            };
+
+            auto [resp, done] = next_stream_chunk(stream);
+            if (!done) {
+                m_resources.host_export(
+                    client_id, req.msg_id(), std::move(stream)
+                );
+            }
+
+            push_resp(std::move(resp));
+            break;
+        }
+        case proto::Request::kViewStreamNextReq: {
+            LOG_DEBUG("Handling ViewStreamNextReq");
+            auto stream = m_resources.take_export(client_id, req.msg_id());
+            if (!stream.has_value()) {
+                PSP_COMPLAIN_AND_ABORT("No streaming export in progress");
+            }
+
+            // The client dropped its stream, so release the export.
+            if (req.view_stream_next_req().cancel()) {
+                proto::Response resp;
+                if (stream->is_csv) {
+                    resp.mutable_view_to_csv_stream_resp()->set_done(true);
+                } else {
+                    resp.mutable_view_to_arrow_stream_resp()->set_done(true);
+                }
+
+                push_resp(std::move(resp));
+                break;
+            }
+
+            auto [resp, done] = next_stream_chunk(*stream);
+            if (!done) {
+                m_resources.host_export(
+                    client_id, req.msg_id(), std::move(*stream)
+                );
+            }
+
+            push_resp(std::move(resp));
+            break;
+        }
         case proto::Request::kViewOnUpdateReq: {
             Subscription sub_info;
             sub_info.id = req.msg_id();
@@ -3109,6 +4039,44 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
 
             m_cpu_time_start = std::chrono::high_resolution_clock::now();
             m_cpu_time = 0;
+
+            // Per-table, per-view and per-session breakdown.
+            for (const auto& table_id : m_resources.get_table_ids()) {
+                auto table = m_resources.get_table(table_id);
+                auto* usage = sys_info->add_tables();
+                usage->set_table_id(table_id);
+                usage->set_num_rows(table->size());
+                usage->set_num_views(m_resources.get_table_view_count(table_id)
+                );
+                usage->set_bytes(table->nbytes());
+            }
+
+            // The requesting session is listed even if it has no views.
+            auto client_view_ids = m_resources.get_client_view_ids();
+            client_view_ids.try_emplace(client_id);
+            for (const auto& [session_id, view_ids] : client_view_ids) {
+                auto* session = sys_info->add_sessions();
+                session->set_session_id(session_id);
+                session->set_is_current(session_id == client_id);
+                session->set_num_views(view_ids.size());
+                std::uint64_t view_bytes = 0;
+                for (const auto& view_id : view_ids) {
+                    auto view = m_resources.get_view(view_id);
+                    auto* usage = sys_info->add_views();
+                    usage->set_view_id(view_id);
+                    usage->set_table_id(
+                        m_resources.get_table_id_for_view(view_id)
+                    );
+                    usage->set_session_id(session_id);
+                    usage->set_num_rows(view->num_rows());
+                    usage->set_num_columns(view->num_columns());
+                    usage->set_bytes(view->nbytes());
+                    view_bytes += usage->bytes();
+                }
+
+                session->set_view_bytes(view_bytes);
+            }
+
             push_resp(std::move(resp));
             break;
         }
@@ -3124,6 +4092,7 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
 std::vector<ProtoServerResp<ProtoServer::Response>>
 ProtoServer::_poll() {
     std::vector<ProtoServerResp<Response>> resp_envs;
+    _enforce_retention(resp_envs);
     auto tables = m_resources.get_dirty_tables();
     for (auto& [table, table_id] : tables) {
         _process_table_unchecked(table, table_id, resp_envs);
@@ -3139,8 +4108,8 @@ ProtoServer::_poll() {
         dirty_ids.insert(table_id);
     }
 
-    // Recompute join tables whose sources were dirty, using a worklist
-    // to handle chained joins (join of join) in dependency order.
+    // Recompute join, union and unnest tables whose sources were dirty, using
+    // a worklist to handle chained joins (join of join) in dependency order.
     tsl::hopscotch_set<ServerResources::t_id> processed_joins;
     std::vector<ServerResources::t_id> worklist;
     for (auto& [_, table_id] : tables) {
@@ -3159,20 +4128,34 @@ ProtoServer::_poll() {
             continue;
         }
 
-        const auto& def = m_join_engine.get_join_def(join_id);
-        bool left_changed = dirty_ids.contains(def.left_table_id);
-        bool right_changed = dirty_ids.contains(def.right_table_id);
-        auto left_table = m_resources.get_table(def.left_table_id);
-        auto right_table = m_resources.get_table(def.right_table_id);
         auto join_table = m_resources.get_table(join_id);
-        m_join_engine.recompute(
-            join_id,
-            left_table,
-            right_table,
-            join_table,
-            left_changed,
-            right_changed
-        );
+        if (m_join_engine.is_union_table(join_id)) {
+            const auto& def = m_join_engine.get_union_def(join_id);
+            std::vector<std::shared_ptr<Table>> sources;
+            for (const auto& table_id : def.table_ids) {
+                sources.push_back(m_resources.get_table(table_id));
+            }
+
+            m_join_engine.recompute_union(join_id, sources, join_table);
+        } else if (m_join_engine.is_unnest_table(join_id)) {
+            const auto& def = m_join_engine.get_unnest_def(join_id);
+            auto source = m_resources.get_table(def.table_id);
+            m_join_engine.recompute_unnest(join_id, source, join_table);
+        } else {
+            const auto& def = m_join_engine.get_join_def(join_id);
+            bool left_changed = dirty_ids.contains(def.left_table_id);
+            bool right_changed = dirty_ids.contains(def.right_table_id);
+            auto left_table = m_resources.get_table(def.left_table_id);
+            auto right_table = m_resources.get_table(def.right_table_id);
+            m_join_engine.recompute(
+                join_id,
+                left_table,
+                right_table,
+                join_table,
+                left_changed,
+                right_changed
+            );
+        }
 
         _process_table_unchecked(join_table, join_id, resp_envs);
         m_resources.mark_table_clean(join_id);
@@ -3198,7 +4181,9 @@ ProtoServer::_process_table_unchecked(
     const ServerResources::t_id& table_id,
     std::vector<ProtoServerResp<ProtoServer::Response>>& outs
 ) {
-    table->get_pool()->_process([this, table_id, &outs](auto port_id) {
+    auto on_process = [this, &table, table_id, &outs](auto port_id) {
+        m_join_engine.capture_union_delta(table_id, table);
+
         // record changes per port.
         auto view_ids = m_resources.get_view_ids(table_id);
         for (const auto& view_id : view_ids) {
@@ -3224,7 +4209,102 @@ ProtoServer::_process_table_unchecked(
                 outs.emplace_back(std::move(resp2));
             }
         }
-    });
+    };
+
+    table->get_pool()->_process(on_process);
+
+    // Rows the update pushed outside of the retention policy are removed in
+    // a second step, so `on_update` subscribers see both.
+    if (table->apply_retention()) {
+        table->get_pool()->_process(on_process);
+    }
+}
+
+void
+ProtoServer::_enforce_retention(
+    std::vector<ProtoServerResp<ProtoServer::Response>>& outs
+) {
+    for (const auto& table_id : m_resources.get_idle_tables()) {
+        // A table which feeds a join, union or unnest table can't be deleted,
+        // so it is kept until they are.
+        if (m_join_engine.get_dependent_join_tables(table_id).empty()) {
+            _evict_table(table_id, outs);
+        }
+    }
+
+    for (auto& [table, table_id] : m_resources.get_retention_due_tables()) {
+        if (table->apply_retention()) {
+            m_resources.mark_table_dirty(table_id);
+        }
+    }
+}
+
+void
+ProtoServer::_evict_table(
+    const ServerResources::t_id& table_id,
+    std::vector<ProtoServerResp<ProtoServer::Response>>& outs
+) {
+    for (const auto& view_id : m_resources.get_view_ids(table_id)) {
+        for (const auto& sub : m_resources.get_view_on_delete_sub(view_id)) {
+            proto::Response resp;
+            resp.mutable_view_on_delete_resp();
+            resp.set_msg_id(sub.id);
+            resp.set_entity_id(view_id);
+            ProtoServerResp<proto::Response> resp2;
+            resp2.data = std::move(resp);
+            resp2.client_id = sub.client_id;
+            outs.emplace_back(std::move(resp2));
+        }
+
+        m_resources.delete_view(
+            m_resources.get_client_id_for_view(view_id), view_id
+        );
+    }
+
+    // A client waiting on a lazy `Table::delete` is notified it completed.
+    if (m_resources.is_table_deleted(table_id)) {
+        auto deleted_id = m_resources.get_table_deleted_client(table_id);
+        proto::Response resp;
+        resp.mutable_table_delete_resp();
+        resp.set_msg_id(deleted_id.id);
+        resp.set_entity_id(table_id);
+        ProtoServerResp<proto::Response> resp2;
+        resp2.data = std::move(resp);
+        resp2.client_id = deleted_id.client_id;
+        outs.emplace_back(std::move(resp2));
+    } else if (m_eviction_session.has_value()
+               && !m_join_engine.is_join_table(table_id)
+               && !m_join_engine.is_union_table(table_id)
+               && !m_join_engine.is_unnest_table(table_id)) {
+        proto::Response resp;
+        resp.mutable_table_delete_resp();
+        resp.set_entity_id(table_id);
+        ProtoServerResp<proto::Response> resp2;
+        resp2.data = std::move(resp);
+        resp2.client_id = *m_eviction_session;
+        outs.emplace_back(std::move(resp2));
+    }
+
+    m_resources.delete_table(table_id);
+    for (const auto& sub : m_resources.get_table_on_delete_sub(table_id)) {
+        proto::Response resp;
+        resp.mutable_table_on_delete_resp();
+        resp.set_msg_id(sub.id);
+        resp.set_entity_id(table_id);
+        ProtoServerResp<proto::Response> resp2;
+        resp2.data = std::move(resp);
+        resp2.client_id = sub.client_id;
+        outs.emplace_back(std::move(resp2));
+    }
+
+    for (auto& subscription : m_resources.get_on_hosted_tables_update_sub()) {
+        Response out;
+        out.set_msg_id(subscription.id);
+        ProtoServerResp<ProtoServer::Response> resp2;
+        resp2.data = std::move(out);
+        resp2.client_id = subscription.client_id;
+        outs.emplace_back(std::move(resp2));
+    }
 }
 
 void
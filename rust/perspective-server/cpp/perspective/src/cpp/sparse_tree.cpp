@@ -1093,6 +1093,19 @@ t_stree::size() const {
     return m_nodes->size();
 }
 
+t_uindex
+t_stree::nbytes() const {
+    t_uindex rv = m_nodes->size() * sizeof(t_stnode)
+        + m_idxpkey->size() * sizeof(t_stpkey)
+        + m_idxleaf->size() * sizeof(t_stleaves);
+
+    if (m_aggregates) {
+        rv += m_aggregates->nbytes();
+    }
+
+    return rv;
+}
+
 void
 t_stree::get_child_nodes(t_uindex idx, t_tnodevec& nodes) const {
     t_index num_children = get_num_children(idx);
@@ -18,11 +18,14 @@
 #include "perspective/raw_types.h"
 #include "perspective/schema.h"
 #include "rapidjson/document.h"
+#include <algorithm>
 #include <chrono>
 #include <ctime>
+#include <limits>
 #include <memory>
 #include <optional>
 #include <perspective/table.h>
+#include <rapidjson/stringbuffer.h>
 #include <rapidjson/writer.h>
 #include <sstream>
 #include <string>
@@ -51,7 +54,9 @@ Table::Table(
     m_limit(limit),
     m_index(std::move(index)),
     m_gnode_set(false),
-    m_backing_store(backing_store) {
+    m_backing_store(backing_store),
+    m_next_expiry(0),
+    m_insertion_seq(0) {
 
     validate_columns(m_column_names);
 }
@@ -115,6 +120,13 @@ Table::size() const {
     return m_gnode->mapping_size();
 }
 
+t_uindex
+Table::nbytes() const {
+    PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
+    return m_gnode->get_table_sptr()->nbytes()
+        + m_gnode->mapping_size() * (sizeof(t_tscalar) + sizeof(t_uindex));
+}
+
 t_schema
 Table::get_schema() const {
     PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
@@ -308,6 +320,253 @@ Table::get_backing_store() const {
     return m_backing_store;
 }
 
+const std::map<std::string, t_logical_type>&
+Table::get_logical_types() const {
+    return m_logical_types;
+}
+
+void
+Table::set_logical_types(std::map<std::string, t_logical_type> types) {
+    m_logical_types = std::move(types);
+}
+
+void
+Table::set_retention(
+    std::optional<std::uint32_t> max_rows,
+    const std::string& age_column,
+    std::optional<std::int64_t> max_age_ms
+) {
+    PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
+    if (max_age_ms.has_value() != !age_column.empty()) {
+        PSP_COMPLAIN_AND_ABORT(
+            "Retention `age_column` and `max_age` must be set together"
+        );
+    }
+
+    if (max_age_ms.has_value()) {
+        const auto schema = get_schema();
+        if (!schema.has_column(age_column)
+            || schema.get_dtype(age_column) != DTYPE_TIME) {
+            std::stringstream ss;
+            ss << "Retention `age_column` \"" << age_column
+               << "\" must be a datetime column";
+            PSP_COMPLAIN_AND_ABORT(ss.str());
+        }
+    }
+
+    m_max_rows = max_rows;
+    m_age_column = age_column;
+    m_max_age = max_age_ms;
+    m_next_expiry = 0;
+
+    // Later rows are tracked as they are inserted, but rows already in the
+    // table are scanned once here.
+    m_expiries = {};
+    if (m_max_age.has_value()) {
+        const auto* column =
+            m_gnode->get_table_sptr()->_get_const_column(m_age_column);
+
+        for (const auto& [pkey, idx] : m_gnode->get_pkey_map()) {
+            if (column->is_valid(idx)) {
+                m_expiries.emplace(
+                    *column->get_nth<std::int64_t>(idx),
+                    m_retention_symtable.get_interned_tscalar(pkey)
+                );
+            }
+        }
+    }
+
+    // Rows already in the table were appended in insertion order, so seed
+    // the insertion order from their row indices.
+    m_insertion_order.clear();
+    m_insertion_seqs.clear();
+    if (m_max_rows.has_value()) {
+        std::vector<std::pair<t_uindex, t_tscalar>> rows;
+        for (const auto& [pkey, idx] : m_gnode->get_pkey_map()) {
+            rows.emplace_back(idx, pkey);
+        }
+
+        std::sort(
+            rows.begin(),
+            rows.end(),
+            [](const auto& a, const auto& b) { return a.first < b.first; }
+        );
+
+        for (const auto& [_, pkey] : rows) {
+            auto interned = m_retention_symtable.get_interned_tscalar(pkey);
+            m_insertion_seqs.emplace(interned, m_insertion_seq);
+            m_insertion_order.emplace_back(interned, m_insertion_seq);
+            m_insertion_seq++;
+        }
+    }
+}
+
+bool
+Table::has_retention() const {
+    return m_max_rows.has_value() || m_max_age.has_value();
+}
+
+std::optional<std::uint32_t>
+Table::get_max_rows() const {
+    return m_max_rows;
+}
+
+const std::string&
+Table::get_age_column() const {
+    return m_age_column;
+}
+
+std::optional<std::int64_t>
+Table::get_max_age() const {
+    return m_max_age;
+}
+
+static std::int64_t
+now_ms() {
+    return std::chrono::duration_cast<std::chrono::milliseconds>(
+               std::chrono::system_clock::now().time_since_epoch()
+    )
+        .count();
+}
+
+bool
+Table::is_retention_due() const {
+    return m_max_age.has_value() && now_ms() >= m_next_expiry;
+}
+
+bool
+Table::apply_retention() {
+    if (!m_init || !has_retention()) {
+        return false;
+    }
+
+    const auto& mapping = m_gnode->get_pkey_map();
+    tsl::hopscotch_set<t_tscalar> removed;
+    std::vector<t_tscalar> pkeys;
+    const auto now = now_ms();
+
+    // Only the rows which have aged out are visited, and only once one may
+    // have, i.e. when this `Table` `is_retention_due` or was just sent a row
+    // older than `max_age`.
+    if (m_max_age.has_value() && now >= m_next_expiry) {
+        const auto threshold = now - *m_max_age;
+        const auto* column = m_gnode->get_table_sptr()->_get_const_column(
+            m_age_column
+        );
+
+        while (!m_expiries.empty() && m_expiries.top().first < threshold) {
+            auto [time, pkey] = m_expiries.top();
+            m_expiries.pop();
+
+            // The row was removed, or its age updated since.
+            auto it = mapping.find(pkey);
+            if (it == mapping.end() || !column->is_valid(it->second)
+                || *column->get_nth<std::int64_t>(it->second) != time) {
+                continue;
+            }
+
+            if (removed.insert(pkey).second) {
+                pkeys.push_back(pkey);
+            }
+        }
+
+        m_next_expiry = m_expiries.empty()
+            ? std::numeric_limits<std::int64_t>::max()
+            : m_expiries.top().first + *m_max_age;
+    }
+
+    if (m_max_rows.has_value()) {
+        auto excess = static_cast<std::int64_t>(mapping.size())
+            - static_cast<std::int64_t>(removed.size()) - *m_max_rows;
+
+        while (excess > 0 && !m_insertion_order.empty()) {
+            auto [pkey, seq] = m_insertion_order.front();
+            m_insertion_order.pop_front();
+            auto it = m_insertion_seqs.find(pkey);
+            if (it == m_insertion_seqs.end() || it->second != seq
+                || !mapping.contains(pkey)) {
+                continue;
+            }
+
+            if (removed.insert(pkey).second) {
+                pkeys.push_back(pkey);
+                excess--;
+            }
+        }
+    }
+
+    if (pkeys.empty()) {
+        return false;
+    }
+
+    const auto pkey_dtype =
+        m_gnode->get_table_sptr()->get_const_column("psp_pkey")->get_dtype();
+
+    std::vector<std::string> column_names{"psp_pkey", "psp_okey"};
+    std::vector<t_dtype> data_types{pkey_dtype, pkey_dtype};
+    if (!m_index.empty()) {
+        column_names.push_back(m_index);
+        data_types.push_back(pkey_dtype);
+    }
+
+    t_data_table data_table(t_schema(column_names, data_types));
+    data_table.init();
+    data_table.extend(pkeys.size());
+    for (const auto& name : column_names) {
+        auto* column = data_table._get_column(name);
+        for (t_uindex i = 0; i < pkeys.size(); ++i) {
+            column->set_scalar(i, pkeys[i]);
+        }
+    }
+
+    process_op_column(data_table, OP_DELETE);
+    m_pool->send(m_gnode->get_id(), 0, data_table);
+    return true;
+}
+
+void
+Table::track_insertions(const t_data_table& data_table) {
+    const auto* pkey_col = data_table._get_const_column("psp_pkey");
+    const auto* op_col = data_table._get_const_column("psp_op");
+    for (t_uindex i = 0; i < data_table.size(); ++i) {
+        auto pkey =
+            m_retention_symtable.get_interned_tscalar(pkey_col->get_scalar(i));
+
+        if (*op_col->get_nth<std::uint8_t>(i) == OP_DELETE) {
+            m_insertion_seqs.erase(pkey);
+        } else if (!m_insertion_seqs.contains(pkey)) {
+            m_insertion_seqs.emplace(pkey, m_insertion_seq);
+            m_insertion_order.emplace_back(pkey, m_insertion_seq);
+            m_insertion_seq++;
+        }
+    }
+}
+
+void
+Table::track_expiries(const t_data_table& data_table) {
+    const auto* column = data_table._get_const_column_safe(m_age_column);
+    if (column == nullptr || column->get_dtype() != DTYPE_TIME) {
+        return;
+    }
+
+    const auto* pkey_col = data_table._get_const_column("psp_pkey");
+    const auto* op_col = data_table._get_const_column("psp_op");
+    for (t_uindex i = 0; i < data_table.size(); ++i) {
+        if (*op_col->get_nth<std::uint8_t>(i) == OP_DELETE
+            || !column->is_valid(i)) {
+            continue;
+        }
+
+        const auto time = *column->get_nth<std::int64_t>(i);
+        m_expiries.emplace(
+            time,
+            m_retention_symtable.get_interned_tscalar(pkey_col->get_scalar(i))
+        );
+
+        m_next_expiry = std::min(m_next_expiry, time + *m_max_age);
+    }
+}
+
 void
 Table::set_column_names(const std::vector<std::string>& column_names) {
     validate_columns(column_names);
@@ -530,8 +789,11 @@ rapidjson_type_to_dtype(const rapidjson::Value& value) {
             return t_dtype::DTYPE_BOOL;
         case rapidjson::kNullType:
             return t_dtype::DTYPE_NONE;
-        case rapidjson::kObjectType:
         case rapidjson::kArrayType:
+            // Lists are stored as their JSON text, see
+            // `rapidjson_list_logical_type`.
+            return t_dtype::DTYPE_STR;
+        case rapidjson::kObjectType:
             PSP_COMPLAIN_AND_ABORT("Unknown JSON type");
             return t_dtype::DTYPE_NONE;
         default:
@@ -540,9 +802,36 @@ rapidjson_type_to_dtype(const rapidjson::Value& value) {
     }
 }
 
+/**
+ * The `t_logical_type` of a column whose first non-null JSON value is `value`,
+ * if it is a list: `LOGICAL_TYPE_LIST_FLOAT` if its elements are all numbers,
+ * and `LOGICAL_TYPE_LIST_STRING` otherwise.
+ */
+static std::optional<t_logical_type>
+rapidjson_list_logical_type(const rapidjson::Value& value) {
+    if (!value.IsArray()) {
+        return std::nullopt;
+    }
+
+    if (value.Empty()) {
+        return t_logical_type{LOGICAL_TYPE_LIST_STRING};
+    }
+
+    for (const auto& elem : value.GetArray()) {
+        if (!elem.IsNumber() && !elem.IsNull()) {
+            return t_logical_type{LOGICAL_TYPE_LIST_STRING};
+        }
+    }
+
+    return t_logical_type{LOGICAL_TYPE_LIST_FLOAT};
+}
+
 void
 Table::clear() {
     reset_gnode(m_gnode->get_id());
+    m_insertion_order.clear();
+    m_insertion_seqs.clear();
+    m_expiries = {};
 }
 
 template <t_dtype A, t_dtype B>
@@ -605,8 +894,12 @@ json_into(const rapidjson::Value& value) {
                 return "true";
             case rapidjson::kObjectType:
                 PSP_COMPLAIN_AND_ABORT("Cannot coerce object to string");
-            case rapidjson::kArrayType:
-                PSP_COMPLAIN_AND_ABORT("Cannot coerce array to string");
+            case rapidjson::kArrayType: {
+                rapidjson::StringBuffer buffer;
+                rapidjson::Writer<rapidjson::StringBuffer> writer(buffer);
+                value.Accept(writer);
+                return buffer.GetString();
+            }
             case rapidjson::kStringType:
                 return value.GetString();
             case rapidjson::kNumberType:
@@ -1050,6 +1343,7 @@ Table::from_cols(
 
     std::vector<std::string> column_names;
     std::vector<t_dtype> data_types;
+    std::map<std::string, t_logical_type> logical_types;
     bool is_implicit = true;
     t_uindex nrows = 0;
 
@@ -1073,6 +1367,11 @@ Table::from_cols(
             auto dtype = rapidjson_type_to_dtype(column_value);
             if (dtype != DTYPE_NONE) {
                 data_types.push_back(dtype);
+                if (auto logical_type =
+                        rapidjson_list_logical_type(column_value)) {
+                    logical_types[it.name.GetString()] = *logical_type;
+                }
+
                 found = true;
                 break;
             }
@@ -1155,6 +1454,7 @@ Table::from_cols(
     tbl->init(*data_table, nrows, t_op::OP_INSERT, 0);
     data_table.reset();
     pool->_process();
+    tbl->set_logical_types(std::move(logical_types));
     return tbl;
 }
 
@@ -1284,6 +1584,7 @@ Table::from_rows(
 
     std::vector<std::string> column_names;
     std::vector<t_dtype> data_types;
+    std::map<std::string, t_logical_type> logical_types;
     bool is_implicit = true;
     std::set<std::string> columns_known_type;
     std::set<std::string> columns_seen;
@@ -1309,6 +1610,10 @@ Table::from_rows(
                     columns_known_type.insert(col.name.GetString());
                     data_types.push_back(rapidjson_type_to_dtype(col.value));
                     column_names.emplace_back(col.name.GetString());
+                    if (auto logical_type =
+                            rapidjson_list_logical_type(col.value)) {
+                        logical_types[col.name.GetString()] = *logical_type;
+                    }
                 }
 
                 // Theoretically there can end too early if the first
@@ -1393,6 +1698,7 @@ Table::from_rows(
     tbl->init(*data_table, document.Size(), t_op::OP_INSERT, 0);
     data_table.reset();
     pool->_process();
+    tbl->set_logical_types(std::move(logical_types));
     return tbl;
 }
 
@@ -1524,6 +1830,7 @@ Table::from_ndjson(
 
     std::vector<std::string> column_names;
     std::vector<t_dtype> data_types;
+    std::map<std::string, t_logical_type> logical_types;
     bool is_implicit = true;
     std::set<std::string> columns_known_type;
     std::set<std::string> columns_seen;
@@ -1551,6 +1858,10 @@ Table::from_ndjson(
                 columns_known_type.insert(col.name.GetString());
                 data_types.push_back(rapidjson_type_to_dtype(col.value));
                 column_names.emplace_back(col.name.GetString());
+                if (auto logical_type =
+                        rapidjson_list_logical_type(col.value)) {
+                    logical_types[col.name.GetString()] = *logical_type;
+                }
             }
 
             // Theoretically there can end too early if the first
@@ -1651,6 +1962,7 @@ Table::from_ndjson(
     tbl->init(*data_table, ii, t_op::OP_INSERT, 0);
     data_table.reset();
     pool->_process();
+    tbl->set_logical_types(std::move(logical_types));
     return tbl;
 }
 
@@ -1741,6 +2053,7 @@ Table::from_arrow(
     // Infer schema
     auto columns = arrow_loader.names();
     auto types = arrow_loader.types();
+    auto logical_types = arrow_loader.logical_types();
 
     t_schema input_schema{columns, types};
 
@@ -1772,6 +2085,7 @@ Table::from_arrow(
     pool->init();
     auto table =
         std::make_shared<Table>(pool, columns, types, limit, index, backing_store);
+    table->set_logical_types(std::move(logical_types));
     table->init(*data_table, data_table->num_rows(), t_op::OP_INSERT, 0);
     data_table.reset();
     pool->_process();
@@ -1858,6 +2172,14 @@ Table::process_op_column(t_data_table& data_table, const t_op op) {
             }
         }
     }
+
+    if (m_max_rows.has_value()) {
+        track_insertions(data_table);
+    }
+
+    if (m_max_age.has_value()) {
+        track_expiries(data_table);
+    }
 }
 
 } // namespace perspective
@@ -15,6 +15,7 @@
 #include <perspective/arrow_writer.h>
 #include <sstream>
 #include <utility>
+#include <rapidjson/document.h>
 #include <rapidjson/writer.h>
 #include <rapidjson/stringbuffer.h>
 #include <arrow/csv/writer.h>
@@ -603,6 +604,116 @@ View<t_ctx0>::expression_schema() const {
     return new_schema;
 }
 
+template <typename CTX_T>
+std::map<std::string, t_logical_type>
+View<CTX_T>::logical_types() const {
+    std::map<std::string, t_logical_type> logical_types;
+    const auto& table_types = m_table->get_logical_types();
+    if (table_types.empty()) {
+        return logical_types;
+    }
+
+    bool is_aggregated =
+        (!m_row_pivots.empty() || m_view_config->is_total_only())
+        && (!is_column_only() || m_view_config->is_total_only());
+
+    for (const auto& [name, typestring] : schema()) {
+        auto it = table_types.find(name);
+        if (it == table_types.end()
+            || typestring
+                != dtype_to_str(logical_type_to_dtype(it->second.m_tag))) {
+            continue;
+        }
+
+        // `count` of an `integer` column is still an `integer`, but not an
+        // `int64` or `duration`. Likewise the `join` of a list column is a
+        // `string`, but not a list.
+        bool is_list = it->second.m_tag == LOGICAL_TYPE_LIST_STRING
+            || it->second.m_tag == LOGICAL_TYPE_LIST_FLOAT;
+
+        bool is_retyped = false;
+        for (const t_aggspec& agg : m_aggregates) {
+            if (is_aggregated && agg.name() == name
+                && (agg.agg() == AGGTYPE_COUNT
+                    || agg.agg() == AGGTYPE_DISTINCT_COUNT
+                    || (is_list && agg.agg() == AGGTYPE_JOIN))) {
+                is_retyped = true;
+            }
+        }
+
+        if (!is_retyped) {
+            logical_types.emplace(name, it->second);
+        }
+    }
+
+    return logical_types;
+}
+
+template <typename CTX_T>
+std::set<std::string>
+View<CTX_T>::list_columns() const {
+    std::set<std::string> list_columns;
+    for (const auto& [name, logical_type] : logical_types()) {
+        if (logical_type.m_tag == LOGICAL_TYPE_LIST_STRING
+            || logical_type.m_tag == LOGICAL_TYPE_LIST_FLOAT) {
+            list_columns.insert(name);
+        }
+    }
+
+    return list_columns;
+}
+
+/**
+ * @brief Approximate bytes allocated for a context's expression columns.
+ */
+static t_uindex
+expression_tables_nbytes(const std::shared_ptr<t_expression_tables>& tables) {
+    t_uindex rv = 0;
+    if (tables == nullptr) {
+        return rv;
+    }
+
+    for (const auto& table :
+         {tables->m_master,
+          tables->m_flattened,
+          tables->m_prev,
+          tables->m_current,
+          tables->m_delta,
+          tables->m_transitions}) {
+        if (table != nullptr) {
+            rv += table->nbytes();
+        }
+    }
+
+    return rv;
+}
+
+template <typename CTX_T>
+t_uindex
+View<CTX_T>::nbytes() const {
+    t_uindex rv = expression_tables_nbytes(m_ctx->get_expression_tables());
+    for (const auto* tree : m_ctx->get_trees()) {
+        rv += tree->nbytes();
+    }
+
+    return rv;
+}
+
+template <>
+t_uindex
+View<t_ctx0>::nbytes() const {
+    // A flat context's only state is its sorted traversal.
+    return expression_tables_nbytes(m_ctx->get_expression_tables())
+        + m_ctx->get_row_count() * sizeof(t_mselem);
+}
+
+template <>
+t_uindex
+View<t_ctxunit>::nbytes() const {
+    // A unit context reads directly from the `Table`.
+    return 0;
+}
+
 template <typename T>
 std::pair<t_tscalar, t_tscalar>
 View<T>::get_min_max(const std::string& colname) const {
@@ -817,7 +928,7 @@ View<CTX_T>::to_arrow(
     std::int32_t start_col,
     std::int32_t end_col,
     bool emit_group_by,
-    bool compress,
+    arrow::Compression::type compression,
     bool emit_legacy_row_path_names
 ) const {
     PSP_GIL_UNLOCK();
@@ -825,7 +936,7 @@ View<CTX_T>::to_arrow(
 
     std::shared_ptr<t_data_slice<CTX_T>> data_slice =
         get_data(start_row, end_row, start_col, end_col);
-    return data_slice_to_arrow(data_slice, emit_group_by, compress, emit_legacy_row_path_names);
+    return data_slice_to_arrow(data_slice, emit_group_by, compression, emit_legacy_row_path_names);
 };
 
 template <>
@@ -890,7 +1001,7 @@ template <typename CTX_T>
 std::pair<std::shared_ptr<arrow::Schema>, std::shared_ptr<arrow::RecordBatch>>
 View<CTX_T>::data_slice_to_batches(
     bool emit_group_by, std::shared_ptr<t_data_slice<CTX_T>> data_slice,
-    bool emit_legacy_row_path_names
+    bool emit_legacy_row_path_names, bool lists_as_json
 ) const {
     // From the data slice, get all the metadata we need
     t_get_data_extents extents = data_slice->get_data_extents();
@@ -1225,6 +1336,7 @@ View<CTX_T>::data_slice_to_batches(
     const std::vector<t_uindex>& slice_col_indices =
         data_slice->get_column_indices();
 
+    const auto column_logical_types = logical_types();
     parallel_for(int(indices.size()), [&](auto iidx) {
         // for (auto iidx = 0; iidx < indices.size(); iidx++) {
         auto ccidx = iidx + num_output_row_paths;
@@ -1253,6 +1365,44 @@ View<CTX_T>::data_slice_to_batches(
             name = col_path.at(col_path.size() - 1).to_string();
         }
 
+        // Duration and list columns are serialized as their own
+        // Arrow types, rather than as the `t_dtype` which stores them.
+        auto logical_type = column_logical_types.find(
+            col_path.at(col_path.size() - 1).to_string()
+        );
+
+        if (logical_type != column_logical_types.end()
+            && dtype == logical_type_to_dtype(logical_type->second.m_tag)) {
+            const t_logical_type& type = logical_type->second;
+            auto get = [&](t_uindex ridx) {
+                return slice
+                    [(ridx - extents.m_srow) * stride
+                     + (cidx - extents.m_scol)];
+            };
+
+            if (type.m_tag == LOGICAL_TYPE_DURATION) {
+                fields[ccidx] = arrow::field(
+                    name, arrow::duration(arrow::TimeUnit::MILLI)
+                );
+                vectors[ccidx] =
+                    apachearrow::duration_col_to_array(extents, get);
+                return;
+            }
+
+            if (!lists_as_json
+                && (type.m_tag == LOGICAL_TYPE_LIST_STRING
+                    || type.m_tag == LOGICAL_TYPE_LIST_FLOAT)) {
+                bool is_float = type.m_tag == LOGICAL_TYPE_LIST_FLOAT;
+                fields[ccidx] = arrow::field(
+                    name,
+                    arrow::list(is_float ? arrow::float64() : arrow::utf8())
+                );
+                vectors[ccidx] =
+                    apachearrow::list_col_to_array(extents, is_float, get);
+                return;
+            }
+        }
+
         std::shared_ptr<arrow::Array> arr;
         switch (dtype) {
             case DTYPE_INT8: {
@@ -1432,12 +1582,35 @@ View<CTX_T>::data_slice_to_batches(
     return std::make_pair(arrow_schema, batches);
 }
 
+static arrow::ipc::IpcWriteOptions
+ipc_write_options(arrow::Compression::type compression) {
+    auto options = arrow::ipc::IpcWriteOptions::Defaults();
+    if (compression != arrow::Compression::UNCOMPRESSED) {
+        auto codec = arrow::util::Codec::Create(compression);
+        if (!codec.ok()) {
+            PSP_COMPLAIN_AND_ABORT(
+                "Failed to create Arrow codec: " + codec.status().message()
+            );
+        }
+
+        options.codec = std::move(codec).ValueUnsafe();
+    }
+
+#ifdef PSP_PARALLEL_FOR
+    options.use_threads = true;
+#else
+    options.use_threads = false;
+#endif
+
+    return options;
+}
+
 template <typename CTX_T>
 std::shared_ptr<std::string>
 View<CTX_T>::data_slice_to_arrow(
     std::shared_ptr<t_data_slice<CTX_T>> data_slice,
     bool emit_group_by,
-    bool compress,
+    arrow::Compression::type compression,
     bool emit_legacy_row_path_names
 ) const {
     std::pair<
@@ -1458,18 +1631,7 @@ View<CTX_T>::data_slice_to_arrow(
     std::shared_ptr<arrow::ResizableBuffer> buffer;
     buffer = *allocated;
     arrow::io::BufferOutputStream sink(buffer);
-    auto options = arrow::ipc::IpcWriteOptions::Defaults();
-    if (compress) {
-        auto codec = arrow::util::Codec::Create(arrow::Compression::LZ4_FRAME);
-        options.codec = std::move(codec).ValueUnsafe();
-    }
-
-#ifdef PSP_PARALLEL_FOR
-    options.use_threads = true;
-#else
-    options.use_threads = false;
-#endif
-
+    auto options = ipc_write_options(compression);
     auto res = arrow::ipc::MakeStreamWriter(&sink, arrow_schema, options);
     std::shared_ptr<arrow::ipc::RecordBatchWriter> writer = *res;
     PSP_CHECK_ARROW_STATUS(writer->WriteRecordBatch(*batches));
@@ -1485,7 +1647,7 @@ View<CTX_T>::data_slice_to_csv(std::shared_ptr<t_data_slice<CTX_T>> data_slice
     std::pair<
         std::shared_ptr<arrow::Schema>,
         std::shared_ptr<arrow::RecordBatch>>
-        pairs = data_slice_to_batches(true, data_slice);
+        pairs = data_slice_to_batches(true, data_slice, true, true);
     std::shared_ptr<arrow::RecordBatch> batches = pairs.second;
     std::shared_ptr<arrow::Schema> arrow_schema = pairs.first;
     arrow::Result<std::shared_ptr<arrow::ResizableBuffer>> allocated =
@@ -1510,6 +1672,180 @@ View<CTX_T>::data_slice_to_csv(std::shared_ptr<t_data_slice<CTX_T>> data_slice
     return std::make_shared<std::string>(buffer->ToString());
 }
 
+template <typename CTX_T>
+class View<CTX_T>::ChunkedExport : public t_view_export {
+public:
+    using t_make_writer = std::function<
+        arrow::Result<std::shared_ptr<arrow::ipc::RecordBatchWriter>>(
+            const std::shared_ptr<arrow::io::OutputStream>&,
+            const std::shared_ptr<arrow::Schema>&
+        )>;
+
+    /**
+     * @brief If `lists_as_json`, list columns are written as their JSON
+     * text, for writers which can't serialize Arrow lists.
+     */
+    ChunkedExport(
+        std::shared_ptr<View<CTX_T>> view,
+        std::int32_t start_row,
+        std::int32_t end_row,
+        std::int32_t start_col,
+        std::int32_t end_col,
+        std::int32_t chunk_rows,
+        bool emit_legacy_row_path_names,
+        bool lists_as_json,
+        t_make_writer make_writer
+    ) :
+        m_view(std::move(view)),
+        m_row(start_row),
+        m_end_row(end_row),
+        m_start_col(start_col),
+        m_end_col(end_col),
+        m_chunk_rows(std::max(chunk_rows, 1)),
+        m_emit_legacy_row_path_names(emit_legacy_row_path_names),
+        m_lists_as_json(lists_as_json),
+        m_make_writer(std::move(make_writer)) {
+        auto maybe_sink = arrow::io::BufferOutputStream::Create();
+        PSP_CHECK_ARROW_STATUS(maybe_sink.status());
+        m_sink = *maybe_sink;
+    }
+
+    std::pair<std::string, bool>
+    next() override {
+        PSP_GIL_UNLOCK();
+        PSP_READ_LOCK(*m_view->get_lock());
+
+        // Rows may have been removed since the previous chunk.
+        auto end_row =
+            std::max(std::min(m_end_row, m_view->num_rows()), m_row);
+        auto chunk_end =
+            end_row - m_row > m_chunk_rows ? m_row + m_chunk_rows : end_row;
+
+        // Always write one batch, so an empty `View` still has a
+        // schema/header.
+        if (m_writer == nullptr || chunk_end > m_row) {
+            auto data_slice =
+                m_view->get_data(m_row, chunk_end, m_start_col, m_end_col);
+            auto batches = m_view->data_slice_to_batches(
+                true, data_slice, m_emit_legacy_row_path_names, m_lists_as_json
+            );
+
+            if (m_writer == nullptr) {
+                auto maybe_writer = m_make_writer(m_sink, batches.first);
+                PSP_CHECK_ARROW_STATUS(maybe_writer.status());
+                m_writer = *maybe_writer;
+            }
+
+            PSP_CHECK_ARROW_STATUS(m_writer->WriteRecordBatch(*batches.second));
+        }
+
+        m_row = chunk_end;
+        bool done = m_row >= end_row;
+        if (done) {
+            PSP_CHECK_ARROW_STATUS(m_writer->Close());
+        }
+
+        // Hand off the bytes written so far, and start a new buffer for the
+        // writer to continue into.
+        auto buffer = m_sink->Finish();
+        PSP_CHECK_ARROW_STATUS(buffer.status());
+        PSP_CHECK_ARROW_STATUS(m_sink->Reset());
+        return {(*buffer)->ToString(), done};
+    }
+
+private:
+    std::shared_ptr<View<CTX_T>> m_view;
+    std::int32_t m_row;
+    std::int32_t m_end_row;
+    std::int32_t m_start_col;
+    std::int32_t m_end_col;
+    std::int32_t m_chunk_rows;
+    bool m_emit_legacy_row_path_names;
+    bool m_lists_as_json;
+    t_make_writer m_make_writer;
+    std::shared_ptr<arrow::io::BufferOutputStream> m_sink;
+    std::shared_ptr<arrow::ipc::RecordBatchWriter> m_writer;
+};
+
+/**
+ * @brief The export of a `View` with no columns, see `to_csv`.
+ */
+class t_empty_export : public t_view_export {
+public:
+    std::pair<std::string, bool>
+    next() override {
+        return {"", true};
+    }
+};
+
+template <typename CTX_T>
+std::shared_ptr<t_view_export>
+View<CTX_T>::export_arrow(
+    std::shared_ptr<View<CTX_T>> view,
+    std::int32_t start_row,
+    std::int32_t end_row,
+    std::int32_t start_col,
+    std::int32_t end_col,
+    std::int32_t chunk_rows,
+    arrow::Compression::type compression,
+    bool emit_legacy_row_path_names
+) {
+    auto options = ipc_write_options(compression);
+    return std::make_shared<ChunkedExport>(
+        std::move(view),
+        start_row,
+        end_row,
+        start_col,
+        end_col,
+        chunk_rows,
+        emit_legacy_row_path_names,
+        false,
+        [options](
+            const std::shared_ptr<arrow::io::OutputStream>& sink,
+            const std::shared_ptr<arrow::Schema>& schema
+        ) { return arrow::ipc::MakeStreamWriter(sink, schema, options); }
+    );
+}
+
+template <typename CTX_T>
+std::shared_ptr<t_view_export>
+View<CTX_T>::export_csv(
+    std::shared_ptr<View<CTX_T>> view,
+    std::int32_t start_row,
+    std::int32_t end_row,
+    std::int32_t start_col,
+    std::int32_t end_col,
+    std::int32_t chunk_rows
+) {
+    // See `to_csv`, which returns `""` for these.
+    bool no_columns = view->m_ctx->unity_get_column_count() == 0;
+    if constexpr (std::is_same_v<CTX_T, t_ctx1>) {
+        no_columns = false;
+    } else if constexpr (std::is_same_v<CTX_T, t_ctx2>) {
+        no_columns = no_columns && view->is_column_only();
+    }
+
+    if (no_columns) {
+        return std::make_shared<t_empty_export>();
+    }
+
+    auto options = arrow::csv::WriteOptions::Defaults();
+    return std::make_shared<ChunkedExport>(
+        std::move(view),
+        start_row,
+        end_row,
+        start_col,
+        end_col,
+        chunk_rows,
+        true,
+        true,
+        [options](
+            const std::shared_ptr<arrow::io::OutputStream>& sink,
+            const std::shared_ptr<arrow::Schema>& schema
+        ) { return arrow::csv::MakeCSVWriter(sink, schema, options); }
+    );
+}
+
 // Delta calculation
 template <typename CTX_T>
 bool
@@ -1794,11 +2130,31 @@ View<CTX_T>::_map_aggregate_types(
     return typestring;
 }
 
+/**
+ * Write the JSON text of a list column value as the array it encodes, or a
+ * value which is not a JSON array as a list of itself.
+ */
+static void
+write_list(
+    const char* text, rapidjson::Writer<rapidjson::StringBuffer>& writer
+) {
+    rapidjson::Document doc;
+    doc.Parse(text);
+    if (doc.HasParseError() || !doc.IsArray()) {
+        writer.StartArray();
+        writer.String(text);
+        writer.EndArray();
+    } else {
+        doc.Accept(writer);
+    }
+}
+
 void
 write_scalar(
     t_tscalar scalar,
     bool is_formatted,
-    rapidjson::Writer<rapidjson::StringBuffer>& writer
+    rapidjson::Writer<rapidjson::StringBuffer>& writer,
+    bool is_list
 ) {
 
     if (!scalar.is_valid()) {
@@ -1806,6 +2162,11 @@ write_scalar(
         return;
     }
 
+    if (is_list && scalar.get_dtype() == DTYPE_STR) {
+        write_list(scalar.get<const char*>(), writer);
+        return;
+    }
+
     switch (scalar.get_dtype()) {
         case DTYPE_NONE:
             writer.Null();
@@ -1926,11 +2287,13 @@ View<CTX_T>::write_column(
 
     writer.Key(col_path_to_legacy(col_names.at(c)).c_str());
     writer.StartArray();
+    bool is_list =
+        list_columns().count(col_names.at(c).back().to_string()) > 0;
 
     for (auto r = start_row; r < end_row; ++r) {
         auto scalar = slice->get(r, c);
 
-        write_scalar(scalar, is_formatted, writer);
+        write_scalar(scalar, is_formatted, writer, is_list);
     }
 
     writer.EndArray();
@@ -1997,10 +2360,15 @@ View<T>::to_rows(
     }
 
     std::vector<std::string> column_names;
+    std::vector<bool> is_list_column;
+    const auto list_columns = this->list_columns();
     for (auto c = start_col; c < end_col; ++c) {
         column_names.emplace_back(
             col_names[c][col_names[c].size() - 1].template get<const char*>()
         );
+        is_list_column.push_back(
+            list_columns.count(col_names[c].back().to_string()) > 0
+        );
     }
 
     // These columns don't exist as far as the view/table is concerned. They're
@@ -2042,7 +2410,9 @@ View<T>::to_rows(
             for (auto c = start_col; c < end_col; ++c) {
                 writer.Key(column_names[c - start_col].c_str());
                 auto scalar = slice->get(r, c);
-                write_scalar(scalar, is_formatted, writer);
+                write_scalar(
+                    scalar, is_formatted, writer, is_list_column[c - start_col]
+                );
             }
 
             writer.EndObject();
@@ -2084,6 +2454,8 @@ View<t_ctx1>::to_rows(
     }
 
     std::vector<std::string> column_names;
+    std::vector<bool> is_list_column;
+    const auto list_columns = this->list_columns();
     for (auto c = start_col + 1; c < end_col; ++c) {
         if (c > columns_length) {
             continue;
@@ -2092,6 +2464,9 @@ View<t_ctx1>::to_rows(
         column_names.emplace_back(
             col_names[c][col_names[c].size() - 1].template get<const char*>()
         );
+        is_list_column.push_back(
+            list_columns.count(col_names[c].back().to_string()) > 0
+        );
     }
 
     for (auto r = start_row; r < end_row; ++r) {
@@ -2140,7 +2515,12 @@ View<t_ctx1>::to_rows(
 
             writer.Key(column_names[c - (start_col + 1)].c_str());
             auto scalar = slice->get(r, c);
-            write_scalar(scalar, is_formatted, writer);
+            write_scalar(
+                scalar,
+                is_formatted,
+                writer,
+                is_list_column[c - (start_col + 1)]
+            );
         }
 
         writer.EndObject();
@@ -2181,8 +2561,13 @@ View<t_ctx2>::to_rows(
     }
 
     std::vector<std::string> column_names;
+    std::vector<bool> is_list_column;
+    const auto list_columns = this->list_columns();
     for (auto c = start_col + 1; c < end_col; ++c) {
         column_names.emplace_back(col_path_to_legacy(col_names.at(c)));
+        is_list_column.push_back(
+            list_columns.count(col_names[c].back().to_string()) > 0
+        );
     }
 
     bool column_only = is_column_only();
@@ -2237,7 +2622,12 @@ View<t_ctx2>::to_rows(
             }
             writer.Key(column_names[c - (start_col + 1)].c_str());
             auto scalar = slice->get(r, c);
-            write_scalar(scalar, is_formatted, writer);
+            write_scalar(
+                scalar,
+                is_formatted,
+                writer,
+                is_list_column[c - (start_col + 1)]
+            );
         }
 
         writer.EndObject();
@@ -2324,10 +2714,15 @@ View<T>::to_ndjson(
     }
 
     std::vector<std::string> column_names;
+    std::vector<bool> is_list_column;
+    const auto list_columns = this->list_columns();
     for (auto c = start_col; c < end_col; ++c) {
         column_names.emplace_back(
             col_names[c][col_names[c].size() - 1].template get<const char*>()
         );
+        is_list_column.push_back(
+            list_columns.count(col_names[c].back().to_string()) > 0
+        );
     }
 
     // These columns don't exist as far as the view/table is concerned. They're
@@ -2376,7 +2771,9 @@ View<T>::to_ndjson(
             for (auto c = start_col; c < end_col; ++c) {
                 writer.Key(column_names[c - start_col].c_str());
                 auto scalar = slice->get(r, c);
-                write_scalar(scalar, is_formatted, writer);
+                write_scalar(
+                    scalar, is_formatted, writer, is_list_column[c - start_col]
+                );
             }
 
             writer.EndObject();
@@ -2413,6 +2810,8 @@ View<t_ctx1>::to_ndjson(
     }
 
     std::vector<std::string> column_names;
+    std::vector<bool> is_list_column;
+    const auto list_columns = this->list_columns();
     for (auto c = start_col + 1; c < end_col; ++c) {
         if (c > columns_length) {
             continue;
@@ -2421,6 +2820,9 @@ View<t_ctx1>::to_ndjson(
         column_names.emplace_back(
             col_names[c][col_names[c].size() - 1].template get<const char*>()
         );
+        is_list_column.push_back(
+            list_columns.count(col_names[c].back().to_string()) > 0
+        );
     }
 
     std::stringstream ndjson;
@@ -2477,7 +2879,12 @@ View<t_ctx1>::to_ndjson(
 
             writer.Key(column_names[c - (start_col + 1)].c_str());
             auto scalar = slice->get(r, c);
-            write_scalar(scalar, is_formatted, writer);
+            write_scalar(
+                scalar,
+                is_formatted,
+                writer,
+                is_list_column[c - (start_col + 1)]
+            );
         }
 
         writer.EndObject();
@@ -2514,8 +2921,13 @@ View<t_ctx2>::to_ndjson(
     }
 
     std::vector<std::string> column_names;
+    std::vector<bool> is_list_column;
+    const auto list_columns = this->list_columns();
     for (auto c = start_col + 1; c < end_col; ++c) {
         column_names.emplace_back(col_path_to_legacy(col_names.at(c)));
+        is_list_column.push_back(
+            list_columns.count(col_names[c].back().to_string()) > 0
+        );
     }
 
     bool column_only = is_column_only();
@@ -2578,7 +2990,12 @@ View<t_ctx2>::to_ndjson(
             }
             writer.Key(column_names[c - (start_col + 1)].c_str());
             auto scalar = slice->get(r, c);
-            write_scalar(scalar, is_formatted, writer);
+            write_scalar(
+                scalar,
+                is_formatted,
+                writer,
+                is_list_column[c - (start_col + 1)]
+            );
         }
 
         writer.EndObject();
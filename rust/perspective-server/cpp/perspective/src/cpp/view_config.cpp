@@ -196,6 +196,12 @@ t_view_config::add_filter_term(
     m_filter.push_back(term);
 }
 
+void
+t_view_config::set_filter_tree(const t_filter_tree& filter_tree) {
+    PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
+    m_filter_tree = filter_tree;
+}
+
 void
 t_view_config::set_row_pivot_depth(std::int32_t depth) {
     PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
@@ -262,6 +268,12 @@ t_view_config::get_filter_op() const {
     return str_to_filter_op(m_filter_op);
 }
 
+const std::optional<t_filter_tree>&
+t_view_config::get_filter_tree() const {
+    PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
+    return m_filter_tree;
+}
+
 bool
 t_view_config::is_column_only() const {
     PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
@@ -385,7 +397,8 @@ t_view_config::fill_fterm() {
         t_filter_op op = str_to_filter_op(std::get<1>(filter));
         switch (op) {
             case FILTER_OP_NOT_IN:
-            case FILTER_OP_IN: {
+            case FILTER_OP_IN:
+            case FILTER_OP_LIST_CONTAINS_ANY: {
                 m_fterm.emplace_back(
                     std::get<0>(filter), op, mktscalar(0), std::get<2>(filter)
                 );
@@ -76,6 +76,12 @@ namespace apachearrow {
         std::vector<t_dtype> types() const;
         std::uint32_t row_count() const;
 
+        /**
+         * @brief The `t_logical_type` of each `duration`, `list` and
+         * `large_list` column, by name.
+         */
+        std::map<std::string, t_logical_type> logical_types() const;
+
     private:
         void fill_column(
             t_data_table& tbl,
@@ -28,6 +28,9 @@
 
 #include <chrono>
 #include <date/date.h>
+#include <rapidjson/document.h>
+#include <rapidjson/stringbuffer.h>
+#include <rapidjson/writer.h>
 
 namespace perspective {
 namespace apachearrow {
@@ -352,5 +355,126 @@ namespace apachearrow {
         return array;
     }
 
+    /**
+     * @brief Build an `arrow::Array` of millisecond `arrow::duration()` from a
+     * `LOGICAL_TYPE_DURATION` column, stored as `DTYPE_INT64`.
+     */
+    template <typename F>
+    std::shared_ptr<arrow::Array>
+    duration_col_to_array(t_get_data_extents extents, F f) {
+        std::shared_ptr<arrow::DataType> type =
+            arrow::duration(arrow::TimeUnit::MILLI);
+        arrow::DurationBuilder array_builder(type, arrow::default_memory_pool());
+        auto reserve_status =
+            array_builder.Reserve(extents.m_erow - extents.m_srow);
+        if (!reserve_status.ok()) {
+            std::stringstream ss;
+            ss << "Failed to allocate buffer for column: "
+               << reserve_status.message() << "\n";
+            PSP_COMPLAIN_AND_ABORT(ss.str());
+        }
+
+        for (int ridx = extents.m_srow; ridx < extents.m_erow; ++ridx) {
+            t_tscalar scalar = f(ridx);
+            if (scalar.is_valid() && scalar.get_dtype() != DTYPE_NONE) {
+                array_builder.UnsafeAppend(get_scalar<std::int64_t>(scalar));
+            } else {
+                array_builder.UnsafeAppendNull();
+            }
+        }
+
+        std::shared_ptr<arrow::Array> array;
+        arrow::Status status = array_builder.Finish(&array);
+        if (!status.ok()) {
+            PSP_COMPLAIN_AND_ABORT(
+                "Could not serialize duration column: " + status.message()
+            );
+        }
+        return array;
+    }
+
+    /**
+     * @brief Build an `arrow::Array` of `arrow::list(arrow::utf8())` or, if
+     * `is_float`, `arrow::list(arrow::float64())` from a
+     * `LOGICAL_TYPE_LIST_STRING` or `LOGICAL_TYPE_LIST_FLOAT` column, stored as
+     * the `DTYPE_STR` JSON text of each list.
+     */
+    template <typename F>
+    std::shared_ptr<arrow::Array>
+    list_col_to_array(t_get_data_extents extents, bool is_float, F f) {
+        auto* pool = arrow::default_memory_pool();
+        std::shared_ptr<arrow::ArrayBuilder> value_builder;
+        if (is_float) {
+            value_builder = std::make_shared<arrow::DoubleBuilder>(pool);
+        } else {
+            value_builder = std::make_shared<arrow::StringBuilder>(pool);
+        }
+
+        arrow::ListBuilder array_builder(pool, value_builder);
+        auto append_elem = [&](const rapidjson::Value& elem) {
+            if (elem.IsNull()) {
+                return value_builder->AppendNull();
+            }
+
+            if (is_float) {
+                auto* builder =
+                    static_cast<arrow::DoubleBuilder*>(value_builder.get());
+                return elem.IsNumber() ? builder->Append(elem.GetDouble())
+                                       : builder->AppendNull();
+            }
+
+            auto* builder =
+                static_cast<arrow::StringBuilder*>(value_builder.get());
+            if (elem.IsString()) {
+                return builder->Append(
+                    elem.GetString(), elem.GetStringLength()
+                );
+            }
+
+            rapidjson::StringBuffer buffer;
+            rapidjson::Writer<rapidjson::StringBuffer> writer(buffer);
+            elem.Accept(writer);
+            return builder->Append(buffer.GetString(), buffer.GetSize());
+        };
+
+        for (int ridx = extents.m_srow; ridx < extents.m_erow; ++ridx) {
+            t_tscalar scalar = f(ridx);
+            arrow::Status status;
+            if (!scalar.is_valid() || scalar.get_dtype() != DTYPE_STR) {
+                status = array_builder.AppendNull();
+            } else {
+                status = array_builder.Append();
+                const char* text = scalar.get_char_ptr();
+                rapidjson::Document doc;
+                doc.Parse(text);
+
+                // A value which is not a JSON array is a list of itself.
+                if (doc.HasParseError() || !doc.IsArray()) {
+                    rapidjson::Value elem(rapidjson::StringRef(text));
+                    status &= append_elem(elem);
+                } else {
+                    for (const auto& elem : doc.GetArray()) {
+                        status &= append_elem(elem);
+                    }
+                }
+            }
+
+            if (!status.ok()) {
+                PSP_COMPLAIN_AND_ABORT(
+                    "Could not serialize list column: " + status.message()
+                );
+            }
+        }
+
+        std::shared_ptr<arrow::Array> array;
+        arrow::Status status = array_builder.Finish(&array);
+        if (!status.ok()) {
+            PSP_COMPLAIN_AND_ABORT(
+                "Could not serialize list column: " + status.message()
+            );
+        }
+        return array;
+    }
+
 } // namespace apachearrow
 } // namespace perspective
\ No newline at end of file
@@ -217,6 +217,27 @@ enum t_select_mode {
 
 enum t_backing_store { BACKING_STORE_MEMORY, BACKING_STORE_DISK };
 
+// A column type which the engine stores as a wider `t_dtype`, and which is
+// restored when the column is reported to clients or serialized to Arrow.
+enum t_logical_type_tag {
+    // Stored as `DTYPE_INT64`.
+    LOGICAL_TYPE_INT64,
+
+    // Stored as `DTYPE_INT64` milliseconds.
+    LOGICAL_TYPE_DURATION,
+
+    // Stored as `DTYPE_STR`, each value a JSON array of strings, e.g.
+    // `["a","b"]`.
+    LOGICAL_TYPE_LIST_STRING,
+
+    // Stored as `DTYPE_STR`, each value a JSON array of numbers.
+    LOGICAL_TYPE_LIST_FLOAT
+};
+
+struct t_logical_type {
+    t_logical_type_tag m_tag;
+};
+
 enum t_filter_op {
     FILTER_OP_LT,
     FILTER_OP_LTEQ,
@@ -232,7 +253,12 @@ enum t_filter_op {
     FILTER_OP_NOT_IN,
     FILTER_OP_AND,
     FILTER_OP_IS_NULL,
-    FILTER_OP_IS_NOT_NULL
+    FILTER_OP_IS_NOT_NULL,
+
+    // Whether a list column's value has an element equal to the threshold
+    // (`LIST_CONTAINS`), or to any value in the bag (`LIST_CONTAINS_ANY`).
+    FILTER_OP_LIST_CONTAINS,
+    FILTER_OP_LIST_CONTAINS_ANY
 };
 
 PERSPECTIVE_EXPORT std::string filter_op_to_str(t_filter_op op);
@@ -427,6 +453,7 @@ PERSPECTIVE_EXPORT bool is_linear_order_type(t_dtype dtype);
 PERSPECTIVE_EXPORT std::string get_dtype_descr(t_dtype dtype);
 PERSPECTIVE_EXPORT std::string dtype_to_str(t_dtype dtype);
 PERSPECTIVE_EXPORT t_dtype str_to_dtype(const std::string& typestring);
+PERSPECTIVE_EXPORT t_dtype logical_type_to_dtype(t_logical_type_tag tag);
 PERSPECTIVE_EXPORT std::string get_status_descr(t_status dtype);
 PERSPECTIVE_EXPORT t_uindex get_dtype_size(t_dtype dtype);
 PERSPECTIVE_EXPORT bool is_vlen_dtype(t_dtype dtype);
@@ -20,6 +20,7 @@
 #include <perspective/schema.h>
 #include <perspective/sort_specification.h>
 #include <perspective/computed_expression.h>
+#include <optional>
 
 namespace perspective {
 
@@ -218,6 +219,18 @@ public:
         return m_backing_store;
     }
 
+    // An optional nested combination of `m_fterms`, which supersedes
+    // `m_combiner` when set.
+    inline void
+    set_filter_tree(const t_filter_tree& filter_tree) {
+        m_filter_tree = filter_tree;
+    }
+
+    inline const std::optional<t_filter_tree>&
+    get_filter_tree() const {
+        return m_filter_tree;
+    }
+
 protected:
     void populate_sortby(const std::vector<t_pivot>& pivots);
 
@@ -249,6 +262,7 @@ private:
     t_fmode m_fmode;
     bool m_has_pkey_agg;
     t_backing_store m_backing_store = BACKING_STORE_MEMORY;
+    std::optional<t_filter_tree> m_filter_tree;
 };
 
 } // end namespace perspective
@@ -90,6 +90,9 @@ public:
 
     t_uindex size() const;
     t_uindex get_capacity() const;
+
+    // Approximate bytes allocated for this table's columns.
+    t_uindex nbytes() const;
     t_dtype get_dtype(const std::string& colname) const;
 
     std::shared_ptr<t_column> get_column(std::string_view colname);
@@ -147,6 +150,9 @@ public:
 
     t_mask
     filter_cpp(t_filter_op combiner, const std::vector<t_fterm>& fterms_) const;
+    t_mask filter_cpp(
+        const t_filter_tree& filter_tree, const std::vector<t_fterm>& fterms_
+    ) const;
     t_data_table* clone_(const t_mask& mask) const;
     std::shared_ptr<t_data_table> clone(const t_mask& mask) const;
     std::shared_ptr<t_data_table> clone() const;
@@ -129,6 +129,16 @@ struct t_operator_contains<t_uindex, DTYPE_STR> {
     }
 };
 
+/**
+ * @brief Whether `list`, a `LOGICAL_TYPE_LIST_STRING` or
+ * `LOGICAL_TYPE_LIST_FLOAT` column value, has an element equal to any of the
+ * `num_needles` scalars at `needles`. A value which is not a JSON array is
+ * treated as a list of just itself.
+ */
+PERSPECTIVE_EXPORT bool list_contains_any(
+    const t_tscalar& list, const t_tscalar* needles, t_uindex num_needles
+);
+
 struct PERSPECTIVE_EXPORT t_fterm {
     t_fterm();
 
@@ -158,6 +168,12 @@ struct PERSPECTIVE_EXPORT t_fterm {
             case FILTER_OP_IN: {
                 rv = std::find(m_bag.begin(), m_bag.end(), s) != m_bag.end();
             } break;
+            case FILTER_OP_LIST_CONTAINS: {
+                rv = list_contains_any(s, &m_threshold, 1);
+            } break;
+            case FILTER_OP_LIST_CONTAINS_ANY: {
+                rv = list_contains_any(s, m_bag.data(), m_bag.size());
+            } break;
             default: {
                 rv = s.cmp(m_op, m_threshold);
             } break;
@@ -179,6 +195,34 @@ struct PERSPECTIVE_EXPORT t_fterm {
     bool m_use_interned;
 };
 
+/**
+ * @brief A nested boolean combination of filter terms. Leaf nodes reference a
+ * `t_fterm` by its index in the config's flat `fterms` list, and group nodes
+ * combine their children with `FILTER_OP_AND` or `FILTER_OP_OR`, optionally
+ * negating the result.
+ */
+struct PERSPECTIVE_EXPORT t_filter_tree {
+    t_filter_tree();
+
+    static t_filter_tree leaf(t_uindex fterm_idx);
+    static t_filter_tree
+    group(t_filter_op op, bool negated, std::vector<t_filter_tree> children);
+
+    bool is_leaf() const;
+
+    /**
+     * @brief Evaluate this tree given the result of each leaf `t_fterm`, in
+     * `fterms` order.
+     */
+    bool evaluate(const std::vector<bool>& leaf_results) const;
+
+    t_filter_op m_op;
+    bool m_negated;
+    bool m_is_leaf;
+    t_uindex m_fterm_idx;
+    std::vector<t_filter_tree> m_children;
+};
+
 class PERSPECTIVE_EXPORT t_filter {
 public:
     t_filter();
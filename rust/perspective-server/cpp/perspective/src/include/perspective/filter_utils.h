@@ -23,6 +23,11 @@ filter_table_for_config(const t_data_table& tbl, const t_config& config) {
 
     switch (config.get_fmode()) {
         case FMODE_SIMPLE_CLAUSES: {
+            const auto& filter_tree = config.get_filter_tree();
+            if (filter_tree.has_value()) {
+                return tbl.filter_cpp(*filter_tree, config.get_fterms());
+            }
+
             return tbl.filter_cpp(config.get_combiner(), config.get_fterms());
         } break;
         default: {
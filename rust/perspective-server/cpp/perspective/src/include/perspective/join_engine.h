@@ -16,22 +16,63 @@
 #include "perspective/raw_types.h"
 #include "perspective/scalar.h"
 #include "perspective/schema.h"
+#include "perspective/sym_table.h"
 #include "perspective/table.h"
 #include <map>
 #include <memory>
 #include <string>
+#include <vector>
 #include <tsl/hopscotch_map.h>
 #include <tsl/hopscotch_set.h>
 #include <perspective.pb.h>
 
 namespace perspective::server {
 
+struct JoinColumn {
+    std::string name;
+    std::string source;
+    bool is_left;
+};
+
 struct JoinDef {
     std::string left_table_id;
     std::string right_table_id;
-    std::string on_column;
-    std::string right_on_column;
+    std::vector<std::string> on_columns;
+    std::vector<std::string> right_on_columns;
     proto::JoinType join_type;
+    std::string left_suffix;
+    std::string right_suffix;
+
+    // As-of join, matching each left row to the right row (among those with
+    // equal `on_columns` keys) with the greatest `right_as_of_column` value
+    // less than or equal to its `as_of_column` value. Empty if this is an
+    // equi-join.
+    std::string as_of_column;
+    std::string right_as_of_column;
+
+    // The joined table's columns, derived from the source schemas by
+    // `JoinEngine::make_join_table`.
+    std::vector<JoinColumn> columns;
+
+    bool
+    is_as_of() const {
+        return !as_of_column.empty();
+    }
+};
+
+struct UnionDef {
+    std::vector<std::string> table_ids;
+
+    // Name of a synthetic `string` column holding each row's source table
+    // name. Empty if there is no such column.
+    std::string source_column;
+};
+
+struct UnnestDef {
+    std::string table_id;
+
+    // The `list_string` or `list_float` column to unnest.
+    std::string column;
 };
 
 struct MakeJoinResult {
@@ -44,35 +85,73 @@ struct MakeJoinResult {
     }
 };
 
+using t_join_key = std::vector<t_tscalar>;
+
+struct JoinKeyHash {
+    std::size_t
+    operator()(const t_join_key& key) const {
+        std::size_t seed = key.size();
+        for (const auto& scalar : key) {
+            seed ^= std::hash<t_tscalar>{}(scalar) + 0x9e3779b9 + (seed << 6)
+                + (seed >> 2);
+        }
+
+        return seed;
+    }
+};
+
 struct JoinCache {
-    tsl::hopscotch_map<t_tscalar, std::vector<t_uindex>> right_index;
+    // Right row indices by join key. For as-of joins, each row list is
+    // sorted by the right as-of column.
+    tsl::hopscotch_map<t_join_key, std::vector<t_uindex>, JoinKeyHash>
+        right_index;
     std::vector<std::pair<t_tscalar, t_uindex>> right_entries;
     bool valid = false;
 };
 
+struct UnionCache {
+    // The union table's primary key for each source row, by source index
+    // and source primary key.
+    std::vector<tsl::hopscotch_map<t_tscalar, std::int64_t>> row_ids;
+    std::vector<std::int64_t> next_row_ids;
+
+    // Source primary keys updated or removed since the last recompute, by
+    // source index.
+    std::vector<tsl::hopscotch_set<t_tscalar>> pending;
+    bool valid = false;
+};
+
 class PERSPECTIVE_EXPORT JoinEngine {
 public:
     using t_id = std::string;
 
-    void register_join(
-        const t_id& join_table_id,
-        const t_id& left_table_id,
-        const t_id& right_table_id,
-        const std::string& on_column,
-        const std::string& right_on_column,
-        proto::JoinType join_type
-    );
-
+    void register_join(const t_id& join_table_id, JoinDef def);
     void unregister_join(const t_id& join_table_id);
     bool is_join_table(const t_id& id) const;
+    void register_union(const t_id& union_table_id, UnionDef def);
+    void unregister_union(const t_id& union_table_id);
+    bool is_union_table(const t_id& id) const;
+    void register_unnest(const t_id& unnest_table_id, UnnestDef def);
+    void unregister_unnest(const t_id& unnest_table_id);
+    bool is_unnest_table(const t_id& id) const;
+
+    /**
+     * @brief The join, union and unnest tables which read from
+     * `source_table_id`.
+     */
     std::vector<t_id> get_dependent_join_tables(const t_id& source_table_id
     ) const;
+
     const JoinDef& get_join_def(const t_id& join_table_id) const;
+    const UnionDef& get_union_def(const t_id& union_table_id) const;
+    const UnnestDef& get_unnest_def(const t_id& unnest_table_id) const;
 
+    /**
+     * @brief Validate `def` against the source tables and create the empty
+     * join table, filling in `def.columns`.
+     */
     MakeJoinResult make_join_table(
-        const std::string& on_column,
-        const std::string& right_on_column,
-        proto::JoinType join_type,
+        JoinDef& def,
         const std::shared_ptr<Table>& left_table,
         const std::shared_ptr<Table>& right_table
     );
@@ -86,15 +165,77 @@ public:
         bool right_changed = true
     );
 
+    /**
+     * @brief Validate that `tables` have identical schemas and create the
+     * empty union table.
+     */
+    MakeJoinResult make_union_table(
+        const UnionDef& def, const std::vector<std::shared_ptr<Table>>& tables
+    );
+
+    /**
+     * @brief Apply the source rows changed since the last call to the union
+     * table, or copy every source row if the union has not been computed
+     * yet or a source was replaced.
+     */
+    void recompute_union(
+        const t_id& union_table_id,
+        const std::vector<std::shared_ptr<Table>>& tables,
+        const std::shared_ptr<Table>& union_table
+    );
+
+    /**
+     * @brief Record the rows of `source_table_id` changed by the port just
+     * processed on `table`, for the union tables which read from it. Must
+     * be called before the gnode's output ports are cleared.
+     */
+    void capture_union_delta(
+        const t_id& source_table_id, const std::shared_ptr<Table>& table
+    );
+
+    /**
+     * @brief Rebuild the union tables which read from `source_table_id` on
+     * their next recompute, as its rows were replaced without a delta.
+     */
+    void invalidate_union_sources(const t_id& source_table_id);
+
+    /**
+     * @brief Validate that `def.column` is a list column of `table` and
+     * create the empty unnest table, whose unnested column has the list's
+     * element type.
+     */
+    MakeJoinResult make_unnest_table(
+        const UnnestDef& def, const std::shared_ptr<Table>& table
+    );
+
+    void recompute_unnest(
+        const t_id& unnest_table_id,
+        const std::shared_ptr<Table>& table,
+        const std::shared_ptr<Table>& unnest_table
+    );
+
 private:
+    void unlink_source(const t_id& source_id, const t_id& table_id);
+
+    void rebuild_union(
+        const UnionDef& def,
+        UnionCache& cache,
+        const std::vector<std::shared_ptr<Table>>& tables,
+        const std::shared_ptr<Table>& union_table
+    );
+
     void build_right_index(
         JoinCache& cache,
         const std::shared_ptr<Table>& right_table,
-        const std::string& on_column
+        const JoinDef& def
     );
 
     tsl::hopscotch_map<t_id, JoinDef> m_join_defs;
     tsl::hopscotch_map<t_id, JoinCache> m_caches;
+    tsl::hopscotch_map<t_id, UnionDef> m_union_defs;
+    tsl::hopscotch_map<t_id, UnionCache> m_union_caches;
+    t_symtable m_union_symtable;
+    tsl::hopscotch_map<t_id, UnnestDef> m_unnest_defs;
     std::multimap<t_id, t_id> m_table_to_join_tables;
 };
 
@@ -19,8 +19,11 @@
 #include "perspective/schema.h"
 #include "perspective/view.h"
 #include "perspective/view_config.h"
+#include <chrono>
 #include <cstdint>
 #include <memory>
+#include <map>
+#include <optional>
 #include <tsl/hopscotch_set.h>
 #include <utility>
 #include <perspective/table.h>
@@ -145,11 +148,16 @@ namespace server {
         [[nodiscard]]
         virtual std::map<std::string, std::string> schema() const = 0;
 
+        [[nodiscard]]
+        virtual std::map<std::string, t_logical_type> logical_types() const = 0;
+
         [[nodiscard]]
         virtual std::uint32_t num_rows() const = 0;
         [[nodiscard]]
         virtual std::uint32_t num_columns() const = 0;
         [[nodiscard]]
+        virtual t_uindex nbytes() const = 0;
+        [[nodiscard]]
         virtual std::shared_ptr<t_view_config> get_view_config() const = 0;
 
         [[nodiscard]]
@@ -159,7 +167,8 @@ namespace server {
             t_uindex start_col,
             t_uindex end_col,
             bool emit_group_by = true,
-            bool compress = true,
+            arrow::Compression::type compression =
+                arrow::Compression::LZ4_FRAME,
             bool emit_legacy_row_path_names = true
         ) const = 0;
 
@@ -222,6 +231,26 @@ namespace server {
             t_uindex end_col
         ) const = 0;
 
+        [[nodiscard]]
+        virtual std::shared_ptr<t_view_export> export_arrow(
+            t_uindex start_row,
+            t_uindex end_row,
+            t_uindex start_col,
+            t_uindex end_col,
+            t_uindex chunk_rows,
+            arrow::Compression::type compression,
+            bool emit_legacy_row_path_names
+        ) const = 0;
+
+        [[nodiscard]]
+        virtual std::shared_ptr<t_view_export> export_csv(
+            t_uindex start_row,
+            t_uindex end_row,
+            t_uindex start_col,
+            t_uindex end_col,
+            t_uindex chunk_rows
+        ) const = 0;
+
         [[nodiscard]]
         virtual std::uint32_t sides() const = 0;
 
@@ -266,6 +295,12 @@ namespace server {
             return m_view->schema();
         }
 
+        [[nodiscard]]
+        std::map<std::string, t_logical_type>
+        logical_types() const override {
+            return m_view->logical_types();
+        }
+
         [[nodiscard]]
         std::uint32_t
         num_rows() const override {
@@ -278,6 +313,12 @@ namespace server {
             return m_view->num_columns();
         }
 
+        [[nodiscard]]
+        t_uindex
+        nbytes() const override {
+            return m_view->nbytes();
+        }
+
         [[nodiscard]]
         std::shared_ptr<t_view_config>
         get_view_config() const override {
@@ -292,11 +333,12 @@ namespace server {
             t_uindex start_col,
             t_uindex end_col,
             bool emit_group_by = true,
-            bool compress = true,
+            arrow::Compression::type compression =
+                arrow::Compression::LZ4_FRAME,
             bool emit_legacy_row_path_names = true
         ) const override {
             return m_view->to_arrow(
-                start_row, end_row, start_col, end_col, emit_group_by, compress, emit_legacy_row_path_names
+                start_row, end_row, start_col, end_col, emit_group_by, compression, emit_legacy_row_path_names
             );
         }
 
@@ -413,6 +455,43 @@ namespace server {
             return m_view->to_csv(start_row, end_row, start_col, end_col);
         }
 
+        [[nodiscard]]
+        std::shared_ptr<t_view_export>
+        export_arrow(
+            t_uindex start_row,
+            t_uindex end_row,
+            t_uindex start_col,
+            t_uindex end_col,
+            t_uindex chunk_rows,
+            arrow::Compression::type compression,
+            bool emit_legacy_row_path_names
+        ) const override {
+            return View<CTX_T>::export_arrow(
+                m_view,
+                start_row,
+                end_row,
+                start_col,
+                end_col,
+                chunk_rows,
+                compression,
+                emit_legacy_row_path_names
+            );
+        }
+
+        [[nodiscard]]
+        std::shared_ptr<t_view_export>
+        export_csv(
+            t_uindex start_row,
+            t_uindex end_row,
+            t_uindex start_col,
+            t_uindex end_col,
+            t_uindex chunk_rows
+        ) const override {
+            return View<CTX_T>::export_csv(
+                m_view, start_row, end_row, start_col, end_col, chunk_rows
+            );
+        }
+
         [[nodiscard]]
         std::uint32_t
         sides() const override {
@@ -474,7 +553,9 @@ namespace server {
         std::shared_ptr<std::string>
         get_row_delta_as_arrow() const override {
             auto delta = m_view->get_row_delta();
-            return m_view->data_slice_to_arrow(delta, false, false);
+            return m_view->data_slice_to_arrow(
+                delta, false, arrow::Compression::UNCOMPRESSED
+            );
         }
 
         void
@@ -533,6 +614,35 @@ namespace server {
         uint32_t client_id;
     };
 
+    /**
+     * @brief A streaming export (`ViewToArrowStreamReq` or
+     * `ViewToCsvStreamReq`) awaiting the `ViewStreamNextReq` for its next
+     * chunk.
+     */
+    struct StreamExport {
+        std::string view_id;
+        bool is_csv;
+        std::shared_ptr<t_view_export> data;
+    };
+
+    /**
+     * @brief Protocol version and capability bits exchanged in the
+     * `GetFeaturesReq` handshake. These must match
+     * `perspective_client::protocol`.
+     */
+    static constexpr std::uint32_t PROTOCOL_VERSION = 1;
+    static constexpr std::uint32_t MIN_PROTOCOL_VERSION = 1;
+    static constexpr std::uint64_t CAPABILITY_TEMPORAL_SCALARS = 1 << 0;
+    static constexpr std::uint64_t CAPABILITY_JOIN_OPTIONS = 1 << 1;
+    static constexpr std::uint64_t CAPABILITY_UNION = 1 << 2;
+    static constexpr std::uint64_t CAPABILITY_STREAMING_EXPORT = 1 << 3;
+    static constexpr std::uint64_t CAPABILITY_UNNEST = 1 << 4;
+    static constexpr std::uint64_t CAPABILITY_SCHEMA_ONLY = 1 << 5;
+    static constexpr std::uint64_t CAPABILITIES = CAPABILITY_TEMPORAL_SCALARS
+        | CAPABILITY_JOIN_OPTIONS | CAPABILITY_UNION
+        | CAPABILITY_STREAMING_EXPORT | CAPABILITY_UNNEST
+        | CAPABILITY_SCHEMA_ONLY;
+
     /**
      * @brief ServerResources is a container for all the resources that the
      * server requires.
@@ -563,9 +673,56 @@ namespace server {
         std::shared_ptr<ErasedView> get_view(const t_id& id);
         std::vector<t_id> get_table_ids();
 
+        /**
+         * @brief The ids of each session's views, by session (client) id.
+         */
+        tsl::hopscotch_map<std::uint32_t, std::vector<t_id>>
+        get_client_view_ids();
+
+        /**
+         * @brief The id of the session (client) which created the view `id`.
+         */
+        std::uint32_t get_client_id_for_view(const t_id& id);
+
         void delete_view(const std::uint32_t& client_id, const t_id& id);
         void delete_table(const t_id& id);
 
+        /**
+         * @brief Evict the table `id` once no request has been made on it,
+         * or any of its views, for `timeout`. See `touch_table`.
+         */
+        void set_table_idle_timeout(
+            const t_id& id, std::chrono::milliseconds timeout
+        );
+
+        std::optional<std::chrono::milliseconds>
+        get_table_idle_timeout(const t_id& id);
+
+        /**
+         * @brief Reset the idle timeout of the table `id`, if it has one.
+         */
+        void touch_table(const t_id& id);
+
+        /**
+         * @brief The ids of the tables which have exceeded their idle
+         * timeout. A table with a view which has an `on_update` callback is
+         * never idle.
+         */
+        std::vector<t_id> get_idle_tables();
+
+        /**
+         * @brief Measure idle timeouts from `now`, a time supplied by the
+         * host, rather than the system's steady clock, until it is next set.
+         */
+        void set_now(std::chrono::steady_clock::time_point now);
+
+        /**
+         * @brief The tables whose rows may have aged out of their retention
+         * policy, see `Table::is_retention_due`.
+         */
+        std::vector<std::pair<std::shared_ptr<Table>, const t_id>>
+        get_retention_due_tables();
+
         // `on_update()`
         void create_view_on_update_sub(const t_id& view_id, Subscription sub);
         std::vector<Subscription> get_view_on_update_sub(const t_id& view_id);
@@ -605,6 +762,22 @@ namespace server {
         bool is_table_dirty(const t_id& id);
         void drop_client(std::uint32_t);
 
+        // `GetFeaturesReq` handshake
+        void set_client_capabilities(
+            std::uint32_t client_id, std::uint64_t capabilities
+        );
+        bool
+        client_has_capability(std::uint32_t client_id, std::uint64_t capability);
+
+        // Streaming exports, by the session (client) and `msg_id` of the
+        // request which began them. An export is dropped when it is done,
+        // or when its view or session is.
+        void host_export(
+            std::uint32_t client_id, std::uint32_t msg_id, StreamExport export_
+        );
+        std::optional<StreamExport>
+        take_export(std::uint32_t client_id, std::uint32_t msg_id);
+
         std::uint32_t get_table_view_count(const t_id& table_id);
         void mark_table_deleted(
             const t_id& table_id, std::uint32_t client_id, std::uint32_t msg_id
@@ -632,6 +805,19 @@ namespace server {
 
         tsl::hopscotch_set<t_id> m_dirty_tables;
         tsl::hopscotch_map<t_id, Subscription> m_deleted_tables;
+        tsl::hopscotch_map<std::uint32_t, std::uint64_t> m_client_capabilities;
+        std::map<std::pair<std::uint32_t, std::uint32_t>, StreamExport>
+            m_exports;
+
+        struct IdleTimeout {
+            std::chrono::milliseconds timeout;
+            std::chrono::steady_clock::time_point last_used;
+        };
+
+        tsl::hopscotch_map<t_id, IdleTimeout> m_table_idle_timeouts;
+        std::optional<std::chrono::steady_clock::time_point> m_now;
+
+        std::chrono::steady_clock::time_point now() const;
 
 #ifdef PSP_PARALLEL_FOR
         std::shared_mutex m_write_lock;
@@ -656,6 +842,19 @@ namespace server {
         handle_request(std::uint32_t client_id, const std::string_view& data);
         std::vector<ProtoServerResp<std::string>> poll();
 
+        /**
+         * @brief Notify `client_id` of each table evicted by its idle timeout
+         * with a `TableDeleteResp`, e.g. so the host can log its deletion.
+         */
+        void set_eviction_session(std::uint32_t client_id);
+
+        /**
+         * @brief Set the time, in milliseconds since an arbitrary epoch of
+         * the host's choosing, by which table idle timeouts are measured. A
+         * host which calls this must do so before each request and poll.
+         */
+        void set_now(std::int64_t now_ms);
+
         std::size_t residency_prepare();
         const char* residency_victim_fname(std::size_t i);
         void residency_commit();
@@ -683,8 +882,24 @@ namespace server {
             std::vector<ProtoServerResp<Response>>& outs
         );
 
+        /**
+         * @brief Evict idle tables, and queue the removal of rows which have
+         * aged out of their table's retention policy.
+         */
+        void _enforce_retention(std::vector<ProtoServerResp<Response>>& outs);
+
+        /**
+         * @brief Delete the table `table_id` and its views, notifying their
+         * `on_delete` and `on_hosted_tables_update` subscribers.
+         */
+        void _evict_table(
+            const ServerResources::t_id& table_id,
+            std::vector<ProtoServerResp<Response>>& outs
+        );
+
         static std::uint32_t m_client_id;
         bool m_realtime_mode;
+        std::optional<std::uint32_t> m_eviction_session;
         std::atomic<std::chrono::high_resolution_clock::time_point>
             m_cpu_time_start;
         std::atomic<long long> m_cpu_time;
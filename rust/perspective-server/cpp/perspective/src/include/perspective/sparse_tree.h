@@ -245,6 +245,10 @@ public:
 
     t_uindex size() const;
 
+    // Approximate bytes allocated for this tree's nodes, indices and
+    // aggregates.
+    t_uindex nbytes() const;
+
     t_uindex get_num_children(t_uindex idx) const;
     void get_child_nodes(t_uindex idx, t_tnodevec& nodes) const;
     std::vector<t_uindex> zero_strands() const;
@@ -19,6 +19,11 @@
 #include <perspective/gnode.h>
 #include <perspective/pool.h>
 #include <perspective/data_table.h>
+#include <deque>
+#include <functional>
+#include <map>
+#include <optional>
+#include <queue>
 
 namespace perspective {
 
@@ -99,6 +104,15 @@ public:
      */
     t_uindex size() const;
 
+    /**
+     * @brief Approximate bytes allocated for this table's rows, including
+     * rows which have been removed but not yet reclaimed, and its primary
+     * key index.
+     *
+     * @return t_uindex
+     */
+    t_uindex nbytes() const;
+
     /**
      * @brief The schema of the underlying `t_data_table`, which contains the
      * `psp_pkey`, `psp_op` and `psp_pkey` meta columns, and none of the
@@ -210,9 +224,53 @@ public:
     const std::string& get_index() const;
     t_backing_store get_backing_store() const;
 
+    /**
+     * @brief The `t_logical_type` of each column which has one, by column
+     * name.
+     */
+    const std::map<std::string, t_logical_type>& get_logical_types() const;
+
+    /**
+     * @brief Limit the rows this `Table` retains to the `max_rows` earliest
+     * inserted, and to those whose `age_column` (a `DTYPE_TIME` column) is
+     * no older than `max_age_ms`. Rows outside these bounds are removed by
+     * `apply_retention`.
+     *
+     * @param max_rows
+     * @param age_column
+     * @param max_age_ms
+     */
+    void set_retention(
+        std::optional<std::uint32_t> max_rows,
+        const std::string& age_column,
+        std::optional<std::int64_t> max_age_ms
+    );
+
+    bool has_retention() const;
+    std::optional<std::uint32_t> get_max_rows() const;
+    const std::string& get_age_column() const;
+    std::optional<std::int64_t> get_max_age() const;
+
+    /**
+     * @brief Whether a row may have aged out of this `Table`'s retention
+     * window since the last `apply_retention`, without any update.
+     *
+     * @return bool
+     */
+    bool is_retention_due() const;
+
+    /**
+     * @brief Queue the removal of any rows outside of this `Table`'s
+     * retention policy, which takes effect when the pool is next processed.
+     *
+     * @return bool whether any rows were removed.
+     */
+    bool apply_retention();
+
     // Setters
     void set_column_names(const std::vector<std::string>& column_names);
     void set_data_types(const std::vector<t_dtype>& data_types);
+    void set_logical_types(std::map<std::string, t_logical_type> types);
 
     void remove_cols(const std::string_view& data);
     void remove_rows(const std::string_view& data);
@@ -292,6 +350,22 @@ private:
      */
     void process_op_column(t_data_table& data_table, const t_op op);
 
+    /**
+     * @brief Record the first insertion of each primary key in `data_table`,
+     * and forget removed ones, for `max_rows` retention.
+     *
+     * @param data_table
+     */
+    void track_insertions(const t_data_table& data_table);
+
+    /**
+     * @brief Record the `age_column` value of each row in `data_table`, for
+     * `max_age` retention.
+     *
+     * @param data_table
+     */
+    void track_expiries(const t_data_table& data_table);
+
     bool m_init;
     t_uindex m_id;
     std::shared_ptr<t_pool> m_pool;
@@ -324,6 +398,47 @@ private:
     const std::string m_index;
     bool m_gnode_set;
     const t_backing_store m_backing_store;
+
+    /**
+     * @brief Columns whose type is narrower than their `t_dtype`, e.g.
+     * `duration` columns stored as `DTYPE_INT64` or `list` columns stored as
+     * `DTYPE_STR`.
+     */
+    std::map<std::string, t_logical_type> m_logical_types;
+
+    /**
+     * @brief The retention policy, see `set_retention`.
+     */
+    std::optional<std::uint32_t> m_max_rows;
+    std::string m_age_column;
+    std::optional<std::int64_t> m_max_age;
+
+    /**
+     * @brief The time (in milliseconds since the epoch) by which the oldest
+     * retained row ages out.
+     */
+    std::int64_t m_next_expiry;
+
+    /**
+     * @brief The `age_column` value and primary key of each inserted or
+     * updated row, oldest first. Entries for rows which were since removed
+     * or updated are skipped by `apply_retention`.
+     */
+    std::priority_queue<
+        std::pair<std::int64_t, t_tscalar>,
+        std::vector<std::pair<std::int64_t, t_tscalar>>,
+        std::greater<>>
+        m_expiries;
+
+    /**
+     * @brief Primary keys in the order they were first inserted, paired with
+     * a sequence number so that entries for keys which were since removed
+     * (and perhaps re-inserted) can be skipped.
+     */
+    std::deque<std::pair<t_tscalar, std::uint64_t>> m_insertion_order;
+    tsl::hopscotch_map<t_tscalar, std::uint64_t> m_insertion_seqs;
+    std::uint64_t m_insertion_seq;
+    t_symtable m_retention_symtable;
 };
 
 } // namespace perspective
\ No newline at end of file
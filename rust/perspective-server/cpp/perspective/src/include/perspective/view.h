@@ -25,21 +25,49 @@
 #include <rapidjson/writer.h>
 #include <rapidjson/stringbuffer.h>
 #include <cstddef>
+#include <functional>
 #include <memory>
 #include <map>
+#include <set>
 #include <arrow/api.h>
+#include <arrow/io/interfaces.h>
+#include <arrow/ipc/writer.h>
+#include <arrow/util/compression.h>
 #ifdef PSP_ENABLE_PYTHON
 #include <thread>
 #endif
 
 namespace perspective {
 
+/**
+ * @brief Write `scalar` as JSON. If `is_list`, `scalar` is the JSON text of a
+ * list column value, and is written as the array it encodes.
+ */
 void write_scalar(
     t_tscalar scalar,
     bool is_formatted,
-    rapidjson::Writer<rapidjson::StringBuffer>& writer
+    rapidjson::Writer<rapidjson::StringBuffer>& writer,
+    bool is_list = false
 );
 
+/**
+ * @brief A streaming export of a `View`, serialized one chunk at a time so
+ * that only the chunk being written is held in memory. See
+ * `View::export_arrow`.
+ */
+class PERSPECTIVE_EXPORT t_view_export {
+public:
+    virtual ~t_view_export() = default;
+
+    /**
+     * @brief Serialize the next chunk, reading the `View` as it is now (so
+     * an update between chunks is reflected in the chunks after it).
+     *
+     * @return The chunk's bytes, and whether it is the last chunk.
+     */
+    virtual std::pair<std::string, bool> next() = 0;
+};
+
 template <typename CTX_T>
 class PERSPECTIVE_EXPORT View {
 public:
@@ -98,6 +126,31 @@ public:
      */
     std::map<std::string, std::string> schema() const;
 
+    /**
+     * @brief The `t_logical_type` of each column of this View whose `Table`
+     * column has one, unless its aggregate changes its type (e.g. `count`).
+     *
+     * @return std::map<std::string, t_logical_type>
+     */
+    std::map<std::string, t_logical_type> logical_types() const;
+
+    /**
+     * @brief The names of this View's `LOGICAL_TYPE_LIST_STRING` and
+     * `LOGICAL_TYPE_LIST_FLOAT` columns, per `logical_types()`.
+     *
+     * @return std::set<std::string>
+     */
+    std::set<std::string> list_columns() const;
+
+    /**
+     * @brief Approximate bytes allocated for this View's context, i.e. its
+     * aggregate trees, sort order and expression columns, but not the
+     * `Table` it reads from.
+     *
+     * @return t_uindex
+     */
+    t_uindex nbytes() const;
+
     /**
      * @brief The expression schema of this View. An expression schema is an
      * std::map, the keys of which are the columns of this View, and the values
@@ -277,7 +330,7 @@ public:
         std::int32_t start_col,
         std::int32_t end_col,
         bool emit_group_by,
-        bool compress,
+        arrow::Compression::type compression,
         bool emit_legacy_row_path_names = true
     ) const;
 
@@ -299,6 +352,56 @@ public:
         std::int32_t end_col
     ) const;
 
+    /**
+     * @brief Begin a streaming export of `view`'s data to the Apache Arrow
+     * IPC stream format, in batches of at most `chunk_rows` rows. Each
+     * `t_view_export::next` call serializes one batch (the first also
+     * carries the schema, the last the end-of-stream marker), so that
+     * concatenated, the chunks are the same stream `to_arrow` returns
+     * (batched). The export keeps `view` alive until it is dropped.
+     *
+     * @param view
+     * @param start_row
+     * @param end_row
+     * @param start_col
+     * @param end_col
+     * @param chunk_rows
+     * @param compression
+     * @param emit_legacy_row_path_names
+     * @return std::shared_ptr<t_view_export>
+     */
+    static std::shared_ptr<t_view_export> export_arrow(
+        std::shared_ptr<View<CTX_T>> view,
+        std::int32_t start_row,
+        std::int32_t end_row,
+        std::int32_t start_col,
+        std::int32_t end_col,
+        std::int32_t chunk_rows,
+        arrow::Compression::type compression,
+        bool emit_legacy_row_path_names
+    );
+
+    /**
+     * @brief As `export_arrow`, but serializes to CSV, with the header row
+     * in the first chunk.
+     *
+     * @param view
+     * @param start_row
+     * @param end_row
+     * @param start_col
+     * @param end_col
+     * @param chunk_rows
+     * @return std::shared_ptr<t_view_export>
+     */
+    static std::shared_ptr<t_view_export> export_csv(
+        std::shared_ptr<View<CTX_T>> view,
+        std::int32_t start_row,
+        std::int32_t end_row,
+        std::int32_t start_col,
+        std::int32_t end_col,
+        std::int32_t chunk_rows
+    );
+
     /**
      * @brief Serializes a given data slice into the Apache Arrow format. Can
      * be directly called with a pointer to a data slice in order to serialize
@@ -313,7 +416,7 @@ public:
     std::shared_ptr<std::string> data_slice_to_arrow(
         std::shared_ptr<t_data_slice<CTX_T>> data_slice,
         bool emit_group_b,
-        bool compress,
+        arrow::Compression::type compression,
         bool emit_legacy_row_path_names = true
     ) const;
 
@@ -430,9 +533,16 @@ private:
         std::shared_ptr<arrow::RecordBatch>>
     data_slice_to_batches(
         bool emit_group_by, std::shared_ptr<t_data_slice<CTX_T>> data_slice,
-        bool emit_legacy_row_path_names = true
+        bool emit_legacy_row_path_names = true, bool lists_as_json = false
     ) const;
 
+    /**
+     * @brief The `t_view_export` of `export_arrow` and `export_csv`, which
+     * writes the data slices of `start_row` to `end_row`, `chunk_rows` at a
+     * time, to the `RecordBatchWriter` it creates.
+     */
+    class ChunkedExport;
+
     void _find_hidden_sort(const std::vector<t_sortspec>& sort);
 
     std::shared_ptr<Table> m_table;
@@ -21,6 +21,7 @@
 #include <tsl/ordered_map.h>
 #include <tsl/hopscotch_set.h>
 #include <unordered_set>
+#include <optional>
 #include <tuple>
 
 namespace perspective {
@@ -93,6 +94,15 @@ public:
         const std::tuple<std::string, std::string, std::vector<t_tscalar>>& term
     );
 
+    /**
+     * @brief Set a nested combination of the filter terms, whose leaves
+     * index into the filter terms in the order they were provided. When set,
+     * this supersedes `filter_op`.
+     *
+     * @param filter_tree
+     */
+    void set_filter_tree(const t_filter_tree& filter_tree);
+
     /**
      * @brief Set the number of pivot levels the engine should generate.
      *
@@ -119,6 +129,8 @@ public:
 
     t_filter_op get_filter_op() const;
 
+    const std::optional<t_filter_tree>& get_filter_tree() const;
+
     bool is_column_only() const;
 
     bool is_leaves_only() const;
@@ -237,6 +249,12 @@ private:
      */
     std::string m_filter_op;
 
+    /**
+     * @brief An optional nested combination of `m_fterm`, which supersedes
+     * `m_filter_op` when set.
+     */
+    std::optional<t_filter_tree> m_filter_tree;
+
     /**
      * @brief whether the view is `column_only`, i.e. having > 1 `column_pivots`
      * without any `row_pivots`.
@@ -0,0 +1,306 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Pluggable authorization for [`LocalSession`] requests, see
+//! [`Server::with_authorization_policy`].
+
+use std::collections::HashMap;
+
+use perspective_client::config::Filter;
+use perspective_client::proto::request::ClientReq;
+use perspective_client::proto::response::ClientResp;
+use perspective_client::proto::view_config::{FilterGroup, FilterReducer, FilterTree, filter_tree};
+use perspective_client::proto::{self, Request, Response, StatusCode};
+
+#[cfg(doc)]
+use crate::{LocalSession, Server};
+
+/// The identity a [`LocalSession`] makes requests as, which is passed to the
+/// [`AuthorizationPolicy`] for every request the session makes. How this is
+/// derived (e.g. from a cookie or token when a WebSocket connects) is up to
+/// the host application.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SessionIdentity {
+    /// The user or service this session acts on behalf of, if known.
+    pub principal: Option<String>,
+
+    /// Arbitrary metadata, e.g. roles or claims.
+    pub metadata: HashMap<String, String>,
+}
+
+/// The class of operation a request performs on a table.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Operation {
+    /// Open a table, read its schema or size, or create and query a `View`.
+    Read,
+
+    /// Modify a table via `update`, `remove`, `replace` or `clear`.
+    Write,
+
+    /// Create a table, including join and union tables.
+    Create,
+
+    /// Delete a table.
+    Delete,
+}
+
+/// A policy which decides which requests a [`LocalSession`] may make.
+///
+/// Requests which are denied receive an error response with
+/// [`StatusCode::PermissionDenied`]; the session stays open.
+pub trait AuthorizationPolicy: Send + Sync {
+    /// Decide whether `session` may perform `operation` on `table`, returning
+    /// an `Err` message to deny. A request may be checked more than once,
+    /// e.g. [`Client::join`] is a [`Operation::Create`] for the new table and
+    /// an [`Operation::Read`] for each source table. `request` is the
+    /// request being authorized.
+    ///
+    /// [`Client::join`]: perspective_client::Client::join
+    fn authorize(
+        &self,
+        session: &SessionIdentity,
+        operation: Operation,
+        table: &str,
+        request: &Request,
+    ) -> Result<(), String>;
+
+    /// Additional filters which are forced on every `View` `session` creates
+    /// on `table` (combined with the `View`'s own filters via `AND`), for
+    /// row-level access control. A session may not use a `table` with
+    /// filters as the source of a join, union or unnest table.
+    fn view_filters(&self, _session: &SessionIdentity, _table: &str) -> Vec<Filter> {
+        vec![]
+    }
+}
+
+/// The result of authorizing a request.
+pub(crate) enum Authorized {
+    /// Forward the request as-is.
+    Request,
+
+    /// Forward this re-encoded request instead, which has had
+    /// [`AuthorizationPolicy::view_filters`] injected.
+    Rewritten(Request),
+
+    /// Forward the request as-is, but the `GetHostedTablesResp` or
+    /// `ServerSystemInfoResp` must be filtered with [`filter_hosted_tables`].
+    HostedTables,
+}
+
+/// Authorize `request` against `policy`, given the `View` ids (and their
+/// tables) this session has created so far.
+pub(crate) fn authorize_request(
+    policy: &dyn AuthorizationPolicy,
+    session: &SessionIdentity,
+    views: &mut HashMap<String, String>,
+    request: &Request,
+) -> Result<Authorized, String> {
+    use ClientReq::*;
+    use Operation::*;
+
+    let entity_id = request.entity_id.as_str();
+    let check = |operation, table: &str| policy.authorize(session, operation, table, request);
+    // A derived table's `View`s don't inherit its sources' `view_filters`,
+    // so a session with row-level filters on a source can't derive from it.
+    let check_source = |table: &str| -> Result<(), String> {
+        check(Read, table)?;
+        if policy.view_filters(session, table).is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Table \"{table}\" has row-level filters"))
+        }
+    };
+
+    let view_table = |views: &HashMap<String, String>| {
+        views
+            .get(entity_id)
+            .cloned()
+            .ok_or_else(|| format!("View \"{entity_id}\" was not created by this session"))
+    };
+
+    match request.client_req.as_ref() {
+        None => Err("Malformed request".to_owned()),
+        Some(GetFeaturesReq(_) | RemoveHostedTablesUpdateReq(_)) => Ok(Authorized::Request),
+        Some(GetHostedTablesReq(_) | ServerSystemInfoReq(_)) => Ok(Authorized::HostedTables),
+        Some(
+            TableSchemaReq(_)
+            | TableSizeReq(_)
+            | TableValidateExprReq(_)
+            | TableOnDeleteReq(_)
+            | TableRemoveDeleteReq(_),
+        ) => {
+            check(Read, entity_id)?;
+            Ok(Authorized::Request)
+        },
+        Some(TableMakePortReq(_) | TableUpdateReq(_) | TableRemoveReq(_) | TableReplaceReq(_)) => {
+            check(Write, entity_id)?;
+            Ok(Authorized::Request)
+        },
+        Some(TableDeleteReq(_)) => {
+            check(Delete, entity_id)?;
+            Ok(Authorized::Request)
+        },
+        Some(MakeTableReq(_)) => {
+            check(Create, entity_id)?;
+            Ok(Authorized::Request)
+        },
+        Some(MakeJoinTableReq(req)) => {
+            check_source(&req.left_table_id)?;
+            check_source(&req.right_table_id)?;
+            check(Create, entity_id)?;
+            Ok(Authorized::Request)
+        },
+        Some(MakeUnionTableReq(req)) => {
+            for table_id in req.table_ids.iter() {
+                check_source(table_id)?;
+            }
+
+            check(Create, entity_id)?;
+            Ok(Authorized::Request)
+        },
+        Some(MakeUnnestTableReq(req)) => {
+            check_source(&req.table_id)?;
+            check(Create, entity_id)?;
+            Ok(Authorized::Request)
+        },
+        Some(TableMakeViewReq(req)) => {
+            check(Read, entity_id)?;
+            views.insert(req.view_id.clone(), entity_id.to_owned());
+            let filters = policy.view_filters(session, entity_id);
+            if filters.is_empty() {
+                return Ok(Authorized::Request);
+            }
+
+            let mut request = request.clone();
+            if let Some(TableMakeViewReq(req)) = request.client_req.as_mut() {
+                inject_filters(req.config.get_or_insert_with(Default::default), filters);
+            }
+
+            Ok(Authorized::Rewritten(request))
+        },
+        Some(ViewDeleteReq(_)) => {
+            check(Read, &view_table(views)?)?;
+            views.remove(entity_id);
+            Ok(Authorized::Request)
+        },
+        Some(
+            ViewColumnPathsReq(_)
+            | ViewDimensionsReq(_)
+            | ViewExpressionSchemaReq(_)
+            | ViewGetConfigReq(_)
+            | ViewSchemaReq(_)
+            | ViewToArrowReq(_)
+            | ViewToArrowStreamReq(_)
+            | ViewCollapseReq(_)
+            | ViewExpandReq(_)
+            | ViewGetMinMaxReq(_)
+            | ViewOnUpdateReq(_)
+            | ViewRemoveOnUpdateReq(_)
+            | ViewSetDepthReq(_)
+            | ViewToColumnsStringReq(_)
+            | ViewToCsvReq(_)
+            | ViewToCsvStreamReq(_)
+            | ViewStreamNextReq(_)
+            | ViewToRowsStringReq(_)
+            | ViewToNdjsonStringReq(_)
+            | ViewOnDeleteReq(_)
+            | ViewRemoveDeleteReq(_),
+        ) => {
+            check(Read, &view_table(views)?)?;
+            Ok(Authorized::Request)
+        },
+    }
+}
+
+/// `AND` `filters` with the filters already in `config`, keeping the flat
+/// `filter` list when possible so the config round-trips to older clients.
+fn inject_filters(config: &mut proto::ViewConfig, filters: Vec<Filter>) {
+    let filters = filters.into_iter().map(proto::view_config::Filter::from);
+    if config.filter_tree.is_none() && config.filter_op() == FilterReducer::And {
+        config.filter.extend(filters);
+        return;
+    }
+
+    let leaf = |filter| FilterTree {
+        node: Some(filter_tree::Node::Filter(filter)),
+    };
+
+    let existing = config.filter_tree.take().unwrap_or_else(|| FilterGroup {
+        op: config.filter_op,
+        negated: false,
+        filters: config.filter.drain(..).map(leaf).collect(),
+    });
+
+    config.filter.clear();
+    config.filter_tree = Some(FilterGroup {
+        op: FilterReducer::And as i32,
+        negated: false,
+        filters: std::iter::once(FilterTree {
+            node: Some(filter_tree::Node::Group(existing)),
+        })
+        .chain(filters.map(leaf))
+        .collect(),
+    });
+}
+
+/// Remove tables `session` may not [`Operation::Read`] (and their views)
+/// from a `GetHostedTablesResp` or `ServerSystemInfoResp` to `request`, and
+/// the sessions whose views are all on such tables.
+pub(crate) fn filter_hosted_tables(
+    policy: &dyn AuthorizationPolicy,
+    session: &SessionIdentity,
+    request: &Request,
+    response: &mut Response,
+) {
+    let can_read = |table_id: &str| {
+        policy
+            .authorize(session, Operation::Read, table_id, request)
+            .is_ok()
+    };
+
+    match response.client_resp.as_mut() {
+        Some(ClientResp::GetHostedTablesResp(resp)) => {
+            resp.table_infos.retain(|info| can_read(&info.entity_id));
+        },
+        Some(ClientResp::ServerSystemInfoResp(resp)) => {
+            resp.tables.retain(|x| can_read(&x.table_id));
+            resp.views.retain(|x| can_read(&x.table_id));
+
+            // Recount each session's `View`s from those which remain, and
+            // omit other sessions which have none.
+            for session in resp.sessions.iter_mut() {
+                let views = resp
+                    .views
+                    .iter()
+                    .filter(|x| x.session_id == session.session_id);
+
+                session.num_views = views.clone().count() as u32;
+                session.view_bytes = views.map(|x| x.bytes).sum();
+            }
+
+            resp.sessions.retain(|x| x.is_current || x.num_views > 0);
+        },
+        _ => {},
+    }
+}
+
+/// The error response for a denied `request`.
+pub(crate) fn permission_denied(request: &Request, message: String) -> Response {
+    Response {
+        msg_id: request.msg_id,
+        entity_id: request.entity_id.clone(),
+        client_resp: Some(ClientResp::ServerError(proto::ServerError {
+            message,
+            status_code: StatusCode::PermissionDenied as i32,
+        })),
+    }
+}
@@ -37,6 +37,8 @@ unsafe extern "C" {
     ) -> ResponseBatch;
     fn psp_poll(server: *const u8) -> ResponseBatch;
     fn psp_close_session(server: *const u8, client_id: u32);
+    fn psp_set_eviction_session(server: *const u8, client_id: u32);
+    fn psp_set_now(server: *const u8, now_ms: i64);
     fn psp_num_cpus() -> i32;
     fn psp_set_num_cpus(num_cpus: i32);
 }
@@ -147,6 +149,14 @@ impl Server {
     pub fn close_session(&self, session_id: u32) {
         unsafe { psp_close_session(self.0, session_id) }
     }
+
+    pub fn set_eviction_session(&self, session_id: u32) {
+        unsafe { psp_set_eviction_session(self.0, session_id) }
+    }
+
+    pub fn set_now(&self, now_ms: i64) {
+        unsafe { psp_set_now(self.0, now_ms) }
+    }
 }
 
 impl Drop for Server {
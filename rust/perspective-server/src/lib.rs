@@ -12,12 +12,24 @@
 
 extern crate link_cplusplus;
 
+mod authorization;
 mod ffi;
 mod local_client;
 mod local_session;
+mod poll_driver;
+mod replay;
+mod replica;
 mod server;
+mod session_limits;
+mod snapshot;
 
+pub use authorization::{AuthorizationPolicy, Operation, SessionIdentity};
 pub use ffi::{num_cpus, set_num_cpus};
 pub use local_client::LocalClient;
 pub use local_session::LocalSession;
-pub use server::{Server, ServerError, ServerResult, SessionHandler};
+pub use poll_driver::{PollDriver, PollDriverOptions, PollRuntime, PollStats};
+pub use replay::{ReplayReport, ResponseDiff};
+pub use replica::{Replica, ReplicaLag, ReplicaOptions, ReplicaTableStats};
+pub use server::{OnPollRequestCallback, Server, ServerError, ServerResult, SessionHandler};
+pub use session_limits::SessionLimits;
+pub use snapshot::SnapshotOptions;
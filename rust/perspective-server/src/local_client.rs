@@ -16,6 +16,7 @@ use std::sync::{Arc, OnceLock};
 use async_lock::{RwLock, RwLockReadGuard};
 use perspective_client::*;
 
+use crate::authorization::SessionIdentity;
 use crate::local_session::LocalSession;
 use crate::server::{Server, ServerError, SessionHandler};
 
@@ -24,6 +25,7 @@ struct LocalClientState {
     client: Arc<OnceLock<Client>>,
     session: Arc<OnceLock<RwLock<Option<LocalSession>>>>,
     server: Server,
+    identity: SessionIdentity,
 }
 
 impl SessionHandler for LocalClientState {
@@ -53,7 +55,11 @@ impl LocalClientState {
 
     async fn get_session(&self) -> RwLockReadGuard<'_, Option<LocalSession>> {
         if self.session.get().is_none() {
-            let session = self.server.new_session(self.clone()).await;
+            let session = self
+                .server
+                .new_session(self.clone())
+                .await
+                .with_identity(self.identity.clone());
             self.session
                 .get_or_init(|| RwLock::new(Some(session)))
                 .read()
@@ -96,11 +102,23 @@ impl LocalClient {
             server: server.clone(),
             client: Arc::default(),
             session: Arc::default(),
+            identity: SessionIdentity::default(),
         };
 
         LocalClient(Some(state))
     }
 
+    /// Set the [`SessionIdentity`] this client's requests are authorized as,
+    /// see [`Server::with_authorization_policy`]. Must be called before this
+    /// client makes its first request.
+    pub fn with_identity(mut self, identity: SessionIdentity) -> Self {
+        if let Some(state) = self.0.as_mut() {
+            state.identity = identity;
+        }
+
+        self
+    }
+
     pub fn take(mut self) -> Result<Client, &'static str> {
         self.0.take().map(|x| x.get_client().clone()).ok_or("Empty")
     }
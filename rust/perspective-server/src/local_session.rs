@@ -10,10 +10,49 @@
 // ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
 use perspective_client::Session;
+use perspective_client::proto::request::ClientReq;
+use perspective_client::proto::response::ClientResp;
+use perspective_client::proto::{Request, Response};
+use prost::Message;
 
+use crate::authorization::{self, Authorized, SessionIdentity};
 use crate::ffi;
 use crate::server::{Server, ServerError};
+use crate::session_limits::{self, Acquired, SessionResources};
+use crate::snapshot::UpdateLog;
+
+/// How to post-process the responses to a request which was authorized,
+/// counted against the session's limits or logged.
+struct Inspect<'a> {
+    request: &'a Request,
+    hosted_tables: bool,
+    acquired: Acquired,
+
+    /// The update log, held until the request is handled so the log and
+    /// `Server::snapshot` agree on the order of updates.
+    log: Option<async_lock::MutexGuard<'a, UpdateLog>>,
+
+    /// Whether the request has responded with an error.
+    failed: bool,
+}
+
+impl Inspect<'_> {
+    /// Whether any response needs to be decoded at all.
+    fn is_needed(&self, server: &Server) -> bool {
+        self.hosted_tables
+            || !matches!(self.acquired, Acquired::Nothing)
+            || self.log.is_some()
+            || (server.session_limits.is_some()
+                && matches!(
+                    self.request.client_req,
+                    Some(ClientReq::ServerSystemInfoReq(_))
+                ))
+    }
+}
 
 /// A struct for implementing [`perspective_client::Session`] against an
 /// same-process [`Server`] instance.
@@ -25,6 +64,15 @@ pub struct LocalSession {
     pub(crate) id: u32,
     pub(crate) server: Server,
     pub(crate) closed: bool,
+    pub(crate) identity: SessionIdentity,
+
+    /// The `View`s this session has created, and the tables they were
+    /// created from, for authorizing `View` requests.
+    pub(crate) views: Mutex<HashMap<String, String>>,
+
+    /// The resources this session holds, shared with [`Server`] for
+    /// [`Server::evict_idle_views`].
+    pub(crate) resources: Arc<Mutex<SessionResources>>,
 }
 
 impl Drop for LocalSession {
@@ -35,12 +83,36 @@ impl Drop for LocalSession {
     }
 }
 
-impl Session<ServerError> for LocalSession {
-    async fn handle_request(&self, request: &[u8]) -> Result<(), ServerError> {
+impl LocalSession {
+    /// Set the [`SessionIdentity`] this session's requests are authorized
+    /// as, if the [`Server`] has an [`crate::AuthorizationPolicy`].
+    pub fn with_identity(mut self, identity: SessionIdentity) -> Self {
+        self.identity = identity;
+        self
+    }
+
+    /// The [`SessionIdentity`] this session's requests are authorized as.
+    pub fn identity(&self) -> &SessionIdentity {
+        &self.identity
+    }
+
+    /// Handle an encoded `request`, and send the responses to their
+    /// sessions, first passing this session's responses through `inspect`.
+    async fn dispatch(
+        &self,
+        request: &[u8],
+        mut inspect: Option<Inspect<'_>>,
+    ) -> Result<(), ServerError> {
+        inspect = inspect.filter(|x| x.is_needed(&self.server));
         let request = ffi::Request::from(request);
+        self.server.sync_clock();
         let responses = self.server.server.handle_request(self.id, &request);
         let mut results = Vec::with_capacity(responses.size());
         for response in responses.iter_responses() {
+            if self.server.take_evicted_table(&response)? {
+                continue;
+            }
+
             let cb = self
                 .server
                 .callbacks
@@ -49,9 +121,37 @@ impl Session<ServerError> for LocalSession {
                 .get(&response.client_id())
                 .cloned();
 
-            if let Some(f) = cb {
-                results.push(f(response.msg()).await);
+            let Some(f) = cb else {
+                continue;
+            };
+
+            match inspect.as_mut() {
+                Some(inspect) if response.client_id() == self.id => {
+                    let mut resp = Response::decode(response.msg())?;
+                    self.inspect_response(inspect, &mut resp);
+                    results.push(f(&resp.encode_to_vec()).await);
+                },
+                _ => results.push(f(response.msg()).await),
+            }
+        }
+
+        // A lazy `Table::delete` responds only once the table's `View`s are
+        // deleted, so the request succeeded unless it already failed.
+        let mut held_log = None;
+        let mut logged = Ok(());
+        if let Some(inspect) = inspect {
+            if let Some(mut log) = inspect.log {
+                // Tables evicted before the request was handled are logged
+                // before it, e.g. in case it re-creates one.
+                logged = log.append_evictions(&self.server.evicted_tables);
+                if !inspect.failed && logged.is_ok() {
+                    logged = log.append(inspect.request);
+                }
+
+                held_log = Some(log);
             }
+
+            self.resources.lock().unwrap().commit(inspect.acquired);
         }
 
         if let Some(cb) = &self.server.on_poll_request {
@@ -60,12 +160,147 @@ impl Session<ServerError> for LocalSession {
             results.push(self.server.poll().await);
         }
 
+        // `Server::poll` can't log the evictions it finds while the log is
+        // held here.
+        if let Some(log) = held_log.as_mut()
+            && logged.is_ok()
+        {
+            logged = log.append_evictions(&self.server.evicted_tables);
+        }
+
+        logged?;
         results.into_iter().collect()
     }
 
+    fn inspect_response(&self, inspect: &mut Inspect<'_>, response: &mut Response) {
+        if response.msg_id != inspect.request.msg_id {
+            return;
+        }
+
+        if inspect.hosted_tables
+            && let Some(policy) = &self.server.authorization_policy
+        {
+            authorization::filter_hosted_tables(
+                policy.as_ref(),
+                &self.identity,
+                inspect.request,
+                response,
+            );
+        }
+
+        match response.client_resp.as_mut() {
+            Some(ClientResp::ServerError(_)) => {
+                inspect.failed = true;
+                let acquired = std::mem::replace(&mut inspect.acquired, Acquired::Nothing);
+                self.resources.lock().unwrap().release(acquired);
+            },
+            Some(ClientResp::ServerSystemInfoResp(resp)) => {
+                resp.session_usage = Some(self.resources.lock().unwrap().usage());
+            },
+            _ => {},
+        }
+    }
+
+    async fn send_response(&self, response: &Response) -> Result<(), ServerError> {
+        let cb = self.server.callbacks.read().await.get(&self.id).cloned();
+        if let Some(f) = cb {
+            f(&response.encode_to_vec()).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Session<ServerError> for LocalSession {
+    async fn handle_request(&self, request: &[u8]) -> Result<(), ServerError> {
+        let server = &self.server;
+        if server.authorization_policy.is_none()
+            && server.session_limits.is_none()
+            && server.update_log.is_none()
+        {
+            return self.dispatch(request, None).await;
+        }
+
+        let mut decoded = Request::decode(request)?;
+        let mut rewritten = false;
+        let mut hosted_tables = false;
+        if let Some(policy) = &server.authorization_policy {
+            let authorized = authorization::authorize_request(
+                policy.as_ref(),
+                &self.identity,
+                &mut self.views.lock().unwrap(),
+                &decoded,
+            );
+
+            match authorized {
+                Ok(Authorized::Request) => {},
+                Ok(Authorized::Rewritten(req)) => {
+                    decoded = req;
+                    rewritten = true;
+                },
+                Ok(Authorized::HostedTables) => hosted_tables = true,
+                Err(message) => {
+                    tracing::debug!("Denied request {}: {}", decoded.msg_id, message);
+                    let response = authorization::permission_denied(&decoded, message);
+                    return self.send_response(&response).await;
+                },
+            }
+        }
+
+        let mut acquired = Acquired::Nothing;
+        if let Some(limits) = &server.session_limits {
+            if let Some(timeout) = limits.view_idle_timeout {
+                let idle = self
+                    .resources
+                    .lock()
+                    .unwrap()
+                    .take_idle_views(timeout, server.now());
+                server.evict_views(self.id, idle).await?;
+            }
+
+            let result = self
+                .resources
+                .lock()
+                .unwrap()
+                .acquire(limits, &decoded, server.now());
+            match result {
+                Ok(x) => acquired = x,
+                Err(message) => {
+                    tracing::debug!("Rejected request {}: {}", decoded.msg_id, message);
+                    let response = session_limits::resource_exhausted(&decoded, message);
+                    return self.send_response(&response).await;
+                },
+            }
+        }
+
+        let log = match &server.update_log {
+            Some(log) if UpdateLog::is_logged(&decoded) => Some(log.lock().await),
+            _ => None,
+        };
+
+        let encoded;
+        let request = if rewritten {
+            encoded = decoded.encode_to_vec();
+            &encoded
+        } else {
+            request
+        };
+
+        let inspect = Inspect {
+            request: &decoded,
+            hosted_tables,
+            acquired,
+            log,
+            failed: false,
+        };
+
+        self.dispatch(request, Some(inspect)).await
+    }
+
     async fn close(mut self) {
         self.closed = true;
         self.server.server.close_session(self.id);
+        self.server.sessions.lock().unwrap().remove(&self.id);
         self.server
             .callbacks
             .write()
@@ -0,0 +1,211 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! A [`PollDriver`] schedules [`Server::poll`] calls for a [`Server`] created
+//! with an `on_poll_request` callback, coalescing bursts of poll requests into
+//! a single poll and limiting how often polls run.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use futures::future::BoxFuture;
+
+use crate::server::{OnPollRequestCallback, Server, ServerResult};
+
+/// The async runtime a [`PollDriver`] runs its polls on.
+pub trait PollRuntime: Send + Sync + 'static {
+    /// Run `task` in the background.
+    fn spawn(&self, task: BoxFuture<'static, ()>);
+
+    /// A future which resolves after `duration`.
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+
+    /// The current time by this runtime's clock, which `sleep` is measured
+    /// against.
+    fn now(&self) -> Instant;
+}
+
+/// Options for [`PollDriver::new`].
+#[derive(Clone, Debug, Default)]
+pub struct PollDriverOptions {
+    /// The minimum time between the start of consecutive polls. Poll requests
+    /// made sooner than this are deferred, and coalesced into a single poll.
+    /// Defaults to zero, which polls as soon as possible (but still coalesces
+    /// poll requests made while a poll is pending).
+    pub min_interval: Duration,
+
+    /// Also poll at least this often when no poll is requested, so that
+    /// time-based [`perspective_client::RetentionOptions`] (`idle_timeout`
+    /// and `max_age`) apply to a [`Server`] with no traffic. The timer starts
+    /// with the first poll request, and stops once the [`Server`] is
+    /// dropped. Defaults to `None`, which polls only when requested.
+    pub max_interval: Option<Duration>,
+}
+
+/// Poll statistics for a [`PollDriver`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PollStats {
+    /// Polls which have completed.
+    pub polls: u64,
+
+    /// Poll requests which were coalesced into an already pending poll.
+    pub coalesced: u64,
+
+    /// The duration of the most recent poll.
+    pub last_latency: Duration,
+
+    /// The longest duration of any poll.
+    pub max_latency: Duration,
+
+    /// The total duration of all polls.
+    pub total_latency: Duration,
+}
+
+impl PollStats {
+    /// The average duration of a poll.
+    pub fn mean_latency(&self) -> Duration {
+        self.total_latency
+            .checked_div(self.polls as u32)
+            .unwrap_or_default()
+    }
+}
+
+struct PollDriverState {
+    runtime: Box<dyn PollRuntime>,
+    options: PollDriverOptions,
+    pending: AtomicBool,
+    ticking: AtomicBool,
+    last_poll: Mutex<Option<Instant>>,
+    stats: Mutex<PollStats>,
+}
+
+/// Runs [`Server::poll`] in the background, off of the request path, so that
+/// a high-frequency stream of updates does not cause one engine poll per
+/// update.
+///
+/// ```rust,ignore
+/// let driver = PollDriver::new(runtime, PollDriverOptions {
+///     min_interval: Duration::from_millis(50),
+///     max_interval: Some(Duration::from_secs(1)),
+/// });
+///
+/// let server = Server::new(Some(driver.on_poll_request()));
+/// ```
+#[derive(Clone)]
+pub struct PollDriver(Arc<PollDriverState>);
+
+impl std::fmt::Debug for PollDriver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PollDriver")
+            .field("options", &self.0.options)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+impl PollDriver {
+    /// Create a new [`PollDriver`] which runs polls on `runtime`.
+    pub fn new(runtime: impl PollRuntime, options: PollDriverOptions) -> Self {
+        Self(Arc::new(PollDriverState {
+            runtime: Box::new(runtime),
+            options,
+            pending: AtomicBool::new(false),
+            ticking: AtomicBool::new(false),
+            last_poll: Mutex::default(),
+            stats: Mutex::default(),
+        }))
+    }
+
+    /// The `on_poll_request` callback to construct a [`Server`] with, via
+    /// [`Server::new`].
+    pub fn on_poll_request(&self) -> OnPollRequestCallback {
+        let driver = self.clone();
+        Arc::new(
+            move |server: &Server| -> BoxFuture<'static, ServerResult<()>> {
+                driver.request_poll(server);
+                Box::pin(async { Ok(()) })
+            },
+        )
+    }
+
+    /// Statistics about the polls this [`PollDriver`] has run.
+    pub fn stats(&self) -> PollStats {
+        *self.0.stats.lock().unwrap()
+    }
+
+    /// Schedule a poll of `server`, unless one is already pending.
+    pub fn request_poll(&self, server: &Server) {
+        if let Some(interval) = self.0.options.max_interval
+            && !self.0.ticking.swap(true, Ordering::AcqRel)
+        {
+            self.start_timer(server, interval);
+        }
+
+        if self.0.pending.swap(true, Ordering::AcqRel) {
+            self.0.stats.lock().unwrap().coalesced += 1;
+            return;
+        }
+
+        let state = self.0.clone();
+        let server = server.clone();
+        self.0.runtime.spawn(Box::pin(async move {
+            let delay = state
+                .last_poll
+                .lock()
+                .unwrap()
+                .map(|x| {
+                    let elapsed = state.runtime.now().saturating_duration_since(x);
+                    state.options.min_interval.saturating_sub(elapsed)
+                })
+                .unwrap_or_default();
+
+            if !delay.is_zero() {
+                state.runtime.sleep(delay).await;
+            }
+
+            // Clear `pending` before polling, so updates which arrive during
+            // this poll schedule another.
+            state.pending.store(false, Ordering::Release);
+            let start = state.runtime.now();
+            *state.last_poll.lock().unwrap() = Some(start);
+            if let Err(e) = server.poll().await {
+                tracing::error!("Poll failed: {}", e);
+            }
+
+            let latency = state.runtime.now().saturating_duration_since(start);
+            let mut stats = state.stats.lock().unwrap();
+            stats.polls += 1;
+            stats.last_latency = latency;
+            stats.max_latency = stats.max_latency.max(latency);
+            stats.total_latency += latency;
+        }));
+    }
+
+    /// Request a poll of `server` every `interval`, until it is dropped.
+    fn start_timer(&self, server: &Server, interval: Duration) {
+        let driver = self.clone();
+        let server = server.clone();
+        self.0.runtime.spawn(Box::pin(async move {
+            loop {
+                driver.0.runtime.sleep(interval).await;
+                // This task holds the last reference to the `Server`.
+                if Arc::strong_count(&server.server) == 1 {
+                    driver.0.ticking.store(false, Ordering::Release);
+                    break;
+                }
+
+                driver.request_poll(&server);
+            }
+        }));
+    }
+}
@@ -0,0 +1,143 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Replay a recording from a `perspective_client::SessionRecorder` against a
+//! [`Server`], see [`Server::replay`].
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use perspective_client::Session;
+use perspective_client::proto::recorded_message::Message as RecordedKind;
+use perspective_client::proto::response::ClientResp;
+use perspective_client::proto::{RecordedMessage, Response};
+use prost::Message;
+
+use crate::server::{Server, ServerResult};
+
+/// A response which differs between a recording and its replay, see
+/// [`ReplayReport::diffs`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResponseDiff {
+    /// The `msg_id` of the request this is a response to.
+    pub msg_id: u32,
+
+    /// The recorded response, or `None` if the replay produced an extra
+    /// response.
+    pub expected: Option<Response>,
+
+    /// The replayed response, or `None` if the replay is missing a response.
+    pub actual: Option<Response>,
+}
+
+/// The result of [`Server::replay`].
+#[derive(Clone, Debug, Default)]
+pub struct ReplayReport {
+    /// The number of requests replayed.
+    pub requests: usize,
+
+    /// The number of responses the replay produced.
+    pub responses: usize,
+
+    /// The wall-clock time spent handling the replayed requests.
+    pub elapsed: Duration,
+
+    /// The responses which differ from the recording, in `msg_id` order.
+    /// `ServerSystemInfoResp`s, which report runtime metrics, are not
+    /// compared.
+    pub diffs: Vec<ResponseDiff>,
+}
+
+impl ReplayReport {
+    /// Whether the replay produced exactly the recorded responses.
+    pub fn is_identical(&self) -> bool {
+        self.diffs.is_empty()
+    }
+}
+
+/// The responses in `responses` by `msg_id`, in the order they were sent.
+fn group_responses<'a>(
+    responses: impl Iterator<Item = &'a [u8]>,
+) -> ServerResult<BTreeMap<u32, Vec<Response>>> {
+    let mut groups = BTreeMap::<u32, Vec<Response>>::new();
+    for bytes in responses {
+        let response = Response::decode(bytes)?;
+        if !matches!(
+            response.client_resp,
+            Some(ClientResp::ServerSystemInfoResp(_))
+        ) {
+            groups.entry(response.msg_id).or_default().push(response);
+        }
+    }
+
+    Ok(groups)
+}
+
+pub(crate) async fn replay(
+    server: &Server,
+    recording: &[RecordedMessage],
+) -> ServerResult<ReplayReport> {
+    let replayed = Arc::new(Mutex::new(Vec::<Vec<u8>>::new()));
+    let responses = replayed.clone();
+    let session = server
+        .new_session_with_callback(move |msg| {
+            responses.lock().unwrap().push(msg.to_vec());
+            Box::pin(async { Ok(()) })
+        })
+        .await;
+
+    let mut report = ReplayReport::default();
+    let start = Instant::now();
+    for msg in recording {
+        if let Some(RecordedKind::Request(request)) = &msg.message {
+            report.requests += 1;
+            session.handle_request(request).await?;
+        }
+    }
+
+    report.elapsed = start.elapsed();
+    session.close().await;
+    let replayed = std::mem::take(&mut *replayed.lock().unwrap());
+    report.responses = replayed.len();
+    let mut expected = group_responses(recording.iter().filter_map(|x| match &x.message {
+        Some(RecordedKind::Response(response)) => Some(response.as_slice()),
+        _ => None,
+    }))?;
+
+    let mut actual = group_responses(replayed.iter().map(|x| x.as_slice()))?;
+    let msg_ids = expected
+        .keys()
+        .chain(actual.keys())
+        .copied()
+        .collect::<BTreeSet<_>>();
+
+    for msg_id in msg_ids {
+        let expected = expected.remove(&msg_id).unwrap_or_default();
+        let actual = actual.remove(&msg_id).unwrap_or_default();
+        let len = expected.len().max(actual.len());
+        let mut expected = expected.into_iter();
+        let mut actual = actual.into_iter();
+        for _ in 0..len {
+            let (expected, actual) = (expected.next(), actual.next());
+            if expected != actual {
+                report.diffs.push(ResponseDiff {
+                    msg_id,
+                    expected,
+                    actual,
+                });
+            }
+        }
+    }
+
+    Ok(report)
+}
@@ -0,0 +1,309 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! A [`Replica`] mirrors tables from an upstream [`Client`] into a local
+//! [`Server`], so that many viewers can be served from the replica rather than
+//! the upstream server.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use perspective_client::utils::ClientResult;
+use perspective_client::{
+    Client, ClientError, OnUpdateData, OnUpdateMode, OnUpdateOptions, Table, TableInitOptions,
+    UpdateData, UpdateOptions, View,
+};
+
+use crate::local_client::LocalClient;
+use crate::server::Server;
+
+/// Options for [`Replica::new`].
+#[derive(Clone, Debug, Default)]
+pub struct ReplicaOptions {
+    /// The names of the upstream tables to mirror. Each is mirrored to a
+    /// local table of the same name.
+    pub tables: Vec<String>,
+}
+
+/// Replication statistics for a single table of a [`Replica`].
+#[derive(Clone, Debug, Default)]
+pub struct ReplicaTableStats {
+    /// Updates applied to the local table since it was created.
+    pub updates: u64,
+
+    /// Times the local table has been resynced, see [`Replica::resync`].
+    pub resyncs: u64,
+
+    /// When the last update was applied to the local table.
+    pub last_update: Option<Instant>,
+
+    /// The time taken to apply the last update to the local table.
+    pub last_apply_latency: Duration,
+
+    /// The longest time taken to apply any update to the local table.
+    pub max_apply_latency: Duration,
+}
+
+impl ReplicaTableStats {
+    /// The time since the last update was applied, or `None` if no update
+    /// has been applied yet.
+    pub fn since_last_update(&self) -> Option<Duration> {
+        self.last_update.map(|x| x.elapsed())
+    }
+}
+
+/// The difference in size between an upstream table and its local mirror,
+/// see [`Replica::lag`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplicaLag {
+    pub table: String,
+    pub upstream_rows: usize,
+    pub local_rows: usize,
+}
+
+/// Where deltas for a table are applied; deltas which arrive while the local
+/// table is (re)created are buffered and applied in order afterwards.
+enum Target {
+    Pending(Vec<Vec<u8>>),
+    Live(Table),
+}
+
+struct Mirror {
+    upstream: Table,
+    view: View,
+    update_id: u32,
+}
+
+/// Mirrors tables from an upstream [`Client`] into a local [`Server`]. Each
+/// table is copied once, then kept up to date via [`View::on_update`] deltas
+/// in Arrow format.
+///
+/// Removes and clears of upstream tables are not included in these deltas,
+/// and are only reflected on [`Replica::resync`]. The local tables should be
+/// treated as read-only, e.g. via [`Server::with_authorization_policy`].
+///
+/// If the upstream connection fails, the local tables stop receiving updates
+/// until [`Replica::resync`] is called, typically after reconnecting the
+/// upstream [`Client`] from a [`Client::on_error`] callback.
+pub struct Replica {
+    upstream: Client,
+    local: LocalClient,
+    tables: Vec<String>,
+    mirrors: async_lock::Mutex<HashMap<String, Mirror>>,
+    stats: Arc<Mutex<HashMap<String, ReplicaTableStats>>>,
+    is_connected: Arc<AtomicBool>,
+}
+
+impl Replica {
+    /// Copy `options.tables` from `upstream` into `server` and subscribe to
+    /// their updates.
+    pub async fn new(
+        server: &Server,
+        upstream: Client,
+        options: ReplicaOptions,
+    ) -> ClientResult<Self> {
+        let is_connected = Arc::new(AtomicBool::new(true));
+        upstream
+            .on_error({
+                let is_connected = is_connected.clone();
+                move |error, _| {
+                    tracing::warn!("Replica disconnected from upstream: {}", error);
+                    is_connected.store(false, Ordering::Release);
+                    async { Ok::<_, ClientError>(()) }
+                }
+            })
+            .await?;
+
+        let replica = Self {
+            upstream,
+            local: LocalClient::new(server),
+            tables: options.tables,
+            mirrors: async_lock::Mutex::default(),
+            stats: Arc::default(),
+            is_connected,
+        };
+
+        replica.sync().await?;
+        Ok(replica)
+    }
+
+    /// Whether the upstream connection is healthy. When `false`, the local
+    /// tables are stale until [`Replica::resync`] succeeds.
+    pub fn is_connected(&self) -> bool {
+        self.is_connected.load(Ordering::Acquire)
+    }
+
+    /// Replication statistics for each mirrored table.
+    pub fn stats(&self) -> HashMap<String, ReplicaTableStats> {
+        self.stats.lock().unwrap().clone()
+    }
+
+    /// Compare the size of each upstream table with its local mirror.
+    pub async fn lag(&self) -> ClientResult<Vec<ReplicaLag>> {
+        let mirrors = self.mirrors.lock().await;
+        let mut lag = vec![];
+        for name in self.tables.iter() {
+            let Some(mirror) = mirrors.get(name) else {
+                continue;
+            };
+
+            let local = self.local.open_table(name.clone()).await?;
+            lag.push(ReplicaLag {
+                table: name.clone(),
+                upstream_rows: mirror.upstream.size().await?,
+                local_rows: local.size().await?,
+            });
+        }
+
+        Ok(lag)
+    }
+
+    /// Re-copy every mirrored table from upstream and re-subscribe to its
+    /// updates, e.g. after the upstream [`Client`] reconnects. Local tables
+    /// are replaced in place, so `View`s on them remain valid.
+    pub async fn resync(&self) -> ClientResult<()> {
+        self.sync().await?;
+        let mut stats = self.stats.lock().unwrap();
+        for name in self.tables.iter() {
+            stats.entry(name.clone()).or_default().resyncs += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Unsubscribe from upstream updates and close the local [`Client`]. The
+    /// local tables remain hosted by the [`Server`].
+    pub async fn close(self) {
+        for (name, mirror) in self.mirrors.lock().await.drain() {
+            if let Err(e) = unsubscribe(mirror).await {
+                tracing::debug!("Failed to unsubscribe from {}: {}", name, e);
+            }
+        }
+
+        self.local.close().await;
+    }
+
+    async fn sync(&self) -> ClientResult<()> {
+        let mut mirrors = self.mirrors.lock().await;
+        for name in self.tables.iter() {
+            if let Some(mirror) = mirrors.remove(name)
+                && self.is_connected()
+                && let Err(e) = unsubscribe(mirror).await
+            {
+                tracing::debug!("Failed to unsubscribe from {}: {}", name, e);
+            }
+
+            let mirror = self.sync_table(name).await?;
+            mirrors.insert(name.clone(), mirror);
+        }
+
+        self.is_connected.store(true, Ordering::Release);
+        Ok(())
+    }
+
+    /// Copy `name` from upstream, replacing the local table if it exists,
+    /// then apply the updates which arrived meanwhile.
+    async fn sync_table(&self, name: &str) -> ClientResult<Mirror> {
+        let upstream = self.upstream.open_table(name.to_owned()).await?;
+        let view = upstream.view(None).await?;
+        let target = Arc::new(async_lock::Mutex::new(Target::Pending(vec![])));
+        let on_update = {
+            let target = target.clone();
+            let stats = self.stats.clone();
+            let name = name.to_owned();
+            move |data: OnUpdateData| {
+                let target = target.clone();
+                let stats = stats.clone();
+                let name = name.clone();
+                async move {
+                    let Some(delta) = data.delta.clone() else {
+                        return;
+                    };
+
+                    match &mut *target.lock().await {
+                        Target::Pending(deltas) => deltas.push(delta),
+                        Target::Live(table) => apply(table, delta, &stats, &name).await,
+                    }
+                }
+            }
+        };
+
+        let options = OnUpdateOptions {
+            mode: Some(OnUpdateMode::Row),
+        };
+
+        let (arrow, update_id) = view.to_arrow_and_on_update(on_update, options).await?;
+        let local = match self.local.open_table(name.to_owned()).await {
+            Ok(local) => {
+                local.replace(UpdateData::Arrow(arrow)).await?;
+                local
+            },
+            Err(_) => {
+                let options = TableInitOptions {
+                    name: Some(name.to_owned()),
+                    index: upstream.get_index(),
+                    limit: upstream.get_limit(),
+                    ..TableInitOptions::default()
+                };
+
+                self.local
+                    .table(UpdateData::Arrow(arrow).into(), options)
+                    .await?
+            },
+        };
+
+        let mut target = target.lock().await;
+        if let Target::Pending(deltas) = &mut *target {
+            for delta in std::mem::take(deltas) {
+                apply(&local, delta, &self.stats, name).await;
+            }
+        }
+
+        *target = Target::Live(local);
+        Ok(Mirror {
+            upstream,
+            view,
+            update_id,
+        })
+    }
+}
+
+async fn apply(
+    table: &Table,
+    delta: Vec<u8>,
+    stats: &Mutex<HashMap<String, ReplicaTableStats>>,
+    name: &str,
+) {
+    let start = Instant::now();
+    if let Err(e) = table
+        .update(UpdateData::Arrow(delta.into()), UpdateOptions::default())
+        .await
+    {
+        tracing::error!("Failed to apply update to replica of {}: {}", name, e);
+        return;
+    }
+
+    let latency = start.elapsed();
+    let mut stats = stats.lock().unwrap();
+    let stats = stats.entry(name.to_owned()).or_default();
+    stats.updates += 1;
+    stats.last_update = Some(Instant::now());
+    stats.last_apply_latency = latency;
+    stats.max_apply_latency = stats.max_apply_latency.max(latency);
+}
+
+async fn unsubscribe(mirror: Mirror) -> ClientResult<()> {
+    mirror.view.remove_update(mirror.update_id).await?;
+    mirror.view.delete().await
+}
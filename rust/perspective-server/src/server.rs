@@ -12,24 +12,34 @@
 
 use std::collections::HashMap;
 use std::error::Error;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use async_lock::RwLock;
 use futures::Future;
 use futures::future::BoxFuture;
+use perspective_client::proto::Response;
+use prost::Message;
 
+use crate::authorization::{AuthorizationPolicy, SessionIdentity};
 use crate::ffi;
 use crate::local_client::LocalClient;
 use crate::local_session::LocalSession;
+use crate::replay::{self, ReplayReport};
+use crate::session_limits::{self, SessionLimits, SessionResources};
+use crate::snapshot::{self, SnapshotOptions, UpdateLog};
 
 pub type ServerError = Box<dyn Error + Send + Sync>;
 
 pub type ServerResult<T> = Result<T, ServerError>;
 
+type Clock = Arc<dyn Fn() -> Instant + Send + Sync>;
+
 type SessionCallback =
     Arc<dyn for<'a> Fn(&'a [u8]) -> BoxFuture<'a, Result<(), ServerError>> + Send + Sync>;
 
-type OnPollRequestCallback =
+pub type OnPollRequestCallback =
     Arc<dyn Fn(&Server) -> BoxFuture<'static, Result<(), ServerError>> + Send + Sync>;
 
 /// Use [`SessionHandler`] to implement a callback for messages emitted from
@@ -54,6 +64,18 @@ pub struct Server {
     pub(crate) server: Arc<ffi::Server>,
     pub(crate) callbacks: Arc<RwLock<HashMap<u32, SessionCallback>>>,
     pub(crate) on_poll_request: Option<OnPollRequestCallback>,
+    pub(crate) authorization_policy: Option<Arc<dyn AuthorizationPolicy>>,
+    pub(crate) session_limits: Option<Arc<SessionLimits>>,
+    pub(crate) sessions: Arc<Mutex<HashMap<u32, Arc<Mutex<SessionResources>>>>>,
+    pub(crate) update_log: Option<Arc<async_lock::Mutex<UpdateLog>>>,
+
+    /// The session notified of tables evicted by their `idle_timeout`, and
+    /// the evicted tables not yet written to the update log.
+    pub(crate) eviction_session: Option<u32>,
+    pub(crate) evicted_tables: Arc<Mutex<Vec<String>>>,
+
+    /// The clock set by [`Server::with_clock`], and its time when set.
+    pub(crate) clock: Option<(Clock, Instant)>,
 }
 
 impl std::fmt::Debug for Server {
@@ -73,7 +95,8 @@ impl Server {
     ///   when there are updates that need to be flushed, after which you must
     ///   _eventually_ call [`Server::poll`] (or else no updates will be
     ///   processed). This optimization allows batching updates, depending on
-    ///   context.
+    ///   context. [`crate::PollDriver::on_poll_request`] implements this
+    ///   callback by polling in the background on an async runtime.
     pub fn new(on_poll_request: Option<OnPollRequestCallback>) -> Self {
         let server = Arc::new(ffi::Server::new(on_poll_request.is_some()));
         let callbacks = Arc::default();
@@ -81,7 +104,165 @@ impl Server {
             server,
             callbacks,
             on_poll_request,
+            authorization_policy: None,
+            session_limits: None,
+            sessions: Arc::default(),
+            update_log: None,
+            eviction_session: None,
+            evicted_tables: Arc::default(),
+            clock: None,
+        }
+    }
+
+    /// Authorize every request from this [`Server`]'s [`Session`]s against
+    /// `policy`. Without a policy, every [`Session`] has full access to every
+    /// hosted [`perspective_client::Table`].
+    ///
+    /// Each [`Session`]'s identity is set via [`LocalSession::with_identity`].
+    pub fn with_authorization_policy(mut self, policy: impl AuthorizationPolicy + 'static) -> Self {
+        self.authorization_policy = Some(Arc::new(policy));
+        self
+    }
+
+    /// Limit the resources each of this [`Server`]'s [`Session`]s may hold,
+    /// and track their usage, which is reported to each [`Session`] via
+    /// [`perspective_client::Client::system_info`].
+    pub fn with_session_limits(mut self, limits: SessionLimits) -> Self {
+        self.session_limits = Some(Arc::new(limits));
+        self
+    }
+
+    /// Measure [`SessionLimits::view_idle_timeout`] and `idle_timeout`
+    /// [`perspective_client::RetentionOptions`] by `clock`, which must be
+    /// monotonic, rather than the system clock, e.g. to control time in
+    /// tests. Must be called before any [`Session`] is created.
+    pub fn with_clock(mut self, clock: impl Fn() -> Instant + Send + Sync + 'static) -> Self {
+        let start = clock();
+        self.clock = Some((Arc::new(clock), start));
+        self
+    }
+
+    /// Append every successful request which modifies a hosted table to the
+    /// log file at `path`, so that updates made since the last
+    /// [`Server::snapshot`] are replayed by [`Server::restore`]. The log is
+    /// truncated by each [`Server::snapshot`]. Tables evicted by their
+    /// `idle_timeout` are logged as deleted.
+    pub fn with_update_log(mut self, path: impl Into<PathBuf>) -> Self {
+        self.update_log = Some(Arc::new(async_lock::Mutex::new(UpdateLog::new(
+            path.into(),
+        ))));
+
+        let session_id = self.server.new_session();
+        self.server.set_eviction_session(session_id);
+        self.eviction_session = Some(session_id);
+        self
+    }
+
+    /// Write the schema, index, limit and retention options, and data (as
+    /// Arrow IPC) of this [`Server`]'s hosted tables to the directory `dir`,
+    /// replacing their previous snapshot there, then remove their entries
+    /// from the update log (see [`Server::with_update_log`]). Tables not in
+    /// [`SnapshotOptions::tables`] keep their previous snapshot and log
+    /// entries. Join and union tables are not included, and must be
+    /// recreated after [`Server::restore`]. Writing a
+    /// snapshot resets the `idle_timeout` of each table it includes (see
+    /// [`perspective_client::RetentionOptions`]).
+    pub async fn snapshot(
+        &self,
+        dir: impl AsRef<Path>,
+        options: SnapshotOptions,
+    ) -> Result<(), ServerError> {
+        snapshot::write_snapshot(self, dir.as_ref(), &options).await
+    }
+
+    /// Restore the tables from a [`Server::snapshot`] in `dir`, then replay
+    /// the update log (see [`Server::with_update_log`]). This should be
+    /// called at startup, before any [`Session`] creates a table. Returns
+    /// the names of the restored tables.
+    pub async fn restore(&self, dir: impl AsRef<Path>) -> Result<Vec<String>, ServerError> {
+        snapshot::restore_snapshot(self, dir.as_ref()).await
+    }
+
+    /// Replay the requests in `recording` (see
+    /// [`perspective_client::SessionRecorder`] and
+    /// [`perspective_client::read_recording`]) in a new [`Session`], and diff
+    /// its responses against the recorded ones. Tables the recorded
+    /// [`Session`] opened but did not create must already be hosted by this
+    /// [`Server`], e.g. via [`Server::restore`].
+    ///
+    /// The [`ReplayReport`] also reports the time spent handling requests,
+    /// so this doubles as a benchmark of a realistic workload.
+    pub async fn replay(
+        &self,
+        recording: &[perspective_client::proto::RecordedMessage],
+    ) -> Result<ReplayReport, ServerError> {
+        replay::replay(self, recording).await
+    }
+
+    /// Delete every [`Session`]'s `View`s which have been idle for longer
+    /// than [`SessionLimits::view_idle_timeout`]. Idle `View`s are otherwise
+    /// only evicted when their [`Session`] makes its next request, so this
+    /// should be called periodically to reclaim `View`s of inactive
+    /// [`Session`]s.
+    pub async fn evict_idle_views(&self) -> Result<(), ServerError> {
+        let Some(timeout) = self
+            .session_limits
+            .as_ref()
+            .and_then(|x| x.view_idle_timeout)
+        else {
+            return Ok(());
+        };
+
+        let sessions = self.sessions.lock().unwrap().clone();
+        for (session_id, resources) in sessions {
+            let idle = resources
+                .lock()
+                .unwrap()
+                .take_idle_views(timeout, self.now());
+            self.evict_views(session_id, idle).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Delete `views` on behalf of `session_id`. The responses to the
+    /// deletions themselves are discarded, but other [`Session`]s are still
+    /// notified (e.g. via `View::on_delete`).
+    pub(crate) async fn evict_views(
+        &self,
+        session_id: u32,
+        views: Vec<String>,
+    ) -> Result<(), ServerError> {
+        for view_id in views {
+            tracing::debug!("Evicting idle view {view_id}");
+            let request = session_limits::delete_view_request(view_id);
+            let request = ffi::Request::from(request.encode_to_vec().as_slice());
+            self.sync_clock();
+            let responses = self.server.handle_request(session_id, &request);
+            for response in responses.iter_responses() {
+                if self.take_evicted_table(&response)? {
+                    continue;
+                }
+
+                let cb = self
+                    .callbacks
+                    .read()
+                    .await
+                    .get(&response.client_id())
+                    .cloned();
+
+                let is_eviction = response.client_id() == session_id
+                    && Response::decode(response.msg())?.msg_id == session_limits::EVICTION_MSG_ID;
+
+                if let Some(f) = cb
+                    && !is_eviction
+                {
+                    f(response.msg()).await?;
+                }
+            }
         }
+
+        Ok(())
     }
 
     /// An alternative method for creating a new [`Session`] for this
@@ -104,10 +285,16 @@ impl Server {
             .await
             .insert(id, Arc::new(send_response));
 
+        let resources = Arc::<Mutex<SessionResources>>::default();
+        self.sessions.lock().unwrap().insert(id, resources.clone());
+
         LocalSession {
             id,
             server,
             closed: false,
+            identity: SessionIdentity::default(),
+            views: Mutex::default(),
+            resources,
         }
     }
 
@@ -155,9 +342,14 @@ impl Server {
     /// `poll()` _must_ be called after [`Table::update`] or [`Table::remove`]
     /// and `on_poll_request` is notified, or the changes will not be applied.
     pub async fn poll(&self) -> Result<(), ServerError> {
+        self.sync_clock();
         let responses = self.server.poll();
         let mut results = Vec::with_capacity(responses.size());
         for response in responses.iter_responses() {
+            if self.take_evicted_table(&response)? {
+                continue;
+            }
+
             let cb = self
                 .callbacks
                 .read()
@@ -170,6 +362,44 @@ impl Server {
             }
         }
 
+        // If the update log is held, its holder writes the evictions instead.
+        if let Some(log) = &self.update_log
+            && let Some(mut log) = log.try_lock()
+        {
+            log.append_evictions(&self.evicted_tables)?;
+        }
+
         results.into_iter().collect()
     }
+
+    /// The current time, by the [`Server::with_clock`] clock if set.
+    pub(crate) fn now(&self) -> Instant {
+        match &self.clock {
+            Some((clock, _)) => clock(),
+            None => Instant::now(),
+        }
+    }
+
+    /// Pass the [`Server::with_clock`] clock's time to the engine, which
+    /// otherwise uses the system clock, before a request or poll.
+    pub(crate) fn sync_clock(&self) {
+        if let Some((clock, start)) = &self.clock {
+            let now = clock().saturating_duration_since(*start);
+            self.server.set_now(now.as_millis() as i64);
+        }
+    }
+
+    /// If `response` is to the eviction session (see
+    /// [`Server::with_update_log`]), queue its table to be logged as deleted
+    /// and return `true`.
+    pub(crate) fn take_evicted_table(&self, response: &ffi::Response) -> ServerResult<bool> {
+        if Some(response.client_id()) != self.eviction_session {
+            return Ok(false);
+        }
+
+        let table_id = Response::decode(response.msg())?.entity_id;
+        tracing::debug!("Logging evicted table {table_id}");
+        self.evicted_tables.lock().unwrap().push(table_id);
+        Ok(true)
+    }
 }
@@ -0,0 +1,365 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Per-session resource limits for a [`Server`], see
+//! [`Server::with_session_limits`].
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use perspective_client::proto::make_table_data::Data;
+use perspective_client::proto::request::ClientReq;
+use perspective_client::proto::response::ClientResp;
+use perspective_client::proto::{self, MakeTableData, Request, Response, SessionUsage, StatusCode};
+use serde_json::value::RawValue;
+
+#[cfg(doc)]
+use crate::{LocalSession, Server};
+
+/// Limits on the resources a single [`LocalSession`] may hold. Requests
+/// which would exceed a limit receive an error response with
+/// [`StatusCode::ResourceExhausted`]; the session stays open. Every limit
+/// defaults to `None` (unlimited).
+#[derive(Clone, Debug, Default)]
+pub struct SessionLimits {
+    /// The maximum number of `View`s a session may have open.
+    pub max_views: Option<usize>,
+
+    /// The maximum number of `Table`s (including join and union tables) a
+    /// session may have created and not yet deleted.
+    pub max_tables: Option<usize>,
+
+    /// The maximum number of rows a session may send via `Client::table` and
+    /// `Table::update` per second. A single request with more rows than
+    /// this is always rejected.
+    pub max_rows_per_second: Option<u64>,
+
+    /// The maximum number of `View::on_update` callbacks a session may have
+    /// registered.
+    pub max_on_update: Option<usize>,
+
+    /// Delete `View`s a session has not made a request on for this long.
+    /// `View`s with an `on_update` callback registered are never evicted.
+    /// Eviction happens when the session makes its next request, or when
+    /// [`Server::evict_idle_views`] is called.
+    pub view_idle_timeout: Option<Duration>,
+}
+
+#[derive(Debug)]
+struct ViewResources {
+    last_used: Instant,
+    on_update: HashSet<u32>,
+}
+
+/// The resources a [`LocalSession`] holds, counted against its
+/// [`SessionLimits`].
+#[derive(Debug, Default)]
+pub(crate) struct SessionResources {
+    views: HashMap<String, ViewResources>,
+    tables: HashSet<String>,
+    window: Option<(Instant, u64)>,
+    rows_ingested: u64,
+    evicted_views: u64,
+    rejected_requests: u64,
+}
+
+/// A resource a request acquired, which must be released if the request
+/// fails, or a resource it frees, which is committed unless the request
+/// fails.
+#[derive(Debug)]
+pub(crate) enum Acquired {
+    Nothing,
+    View(String),
+    Table(String),
+    OnUpdate(String, u32),
+    DeletedTable(String),
+}
+
+impl SessionResources {
+    /// Check `request`, made at `now`, against `limits`, and acquire any
+    /// resources it creates, returning an `Err` message to reject it.
+    pub(crate) fn acquire(
+        &mut self,
+        limits: &SessionLimits,
+        request: &Request,
+        now: Instant,
+    ) -> Result<Acquired, String> {
+        let acquired = self.try_acquire(limits, request, now);
+        if acquired.is_err() {
+            self.rejected_requests += 1;
+        }
+
+        acquired
+    }
+
+    fn try_acquire(
+        &mut self,
+        limits: &SessionLimits,
+        request: &Request,
+        now: Instant,
+    ) -> Result<Acquired, String> {
+        use ClientReq::*;
+
+        let entity_id = &request.entity_id;
+        if let Some(view) = self.views.get_mut(entity_id) {
+            view.last_used = now;
+        }
+
+        match request.client_req.as_ref() {
+            Some(TableMakeViewReq(req)) => {
+                check_limit("Views", self.views.len(), limits.max_views)?;
+                self.views.insert(req.view_id.clone(), ViewResources {
+                    last_used: now,
+                    on_update: HashSet::new(),
+                });
+
+                Ok(Acquired::View(req.view_id.clone()))
+            },
+            Some(ViewDeleteReq(_)) => {
+                self.views.remove(entity_id);
+                Ok(Acquired::Nothing)
+            },
+            Some(ViewOnUpdateReq(_)) => {
+                let num_on_update = self.views.values().map(|x| x.on_update.len()).sum();
+                check_limit("on_update callbacks", num_on_update, limits.max_on_update)?;
+                if let Some(view) = self.views.get_mut(entity_id) {
+                    view.on_update.insert(request.msg_id);
+                }
+
+                Ok(Acquired::OnUpdate(entity_id.clone(), request.msg_id))
+            },
+            Some(ViewRemoveOnUpdateReq(req)) => {
+                if let Some(view) = self.views.get_mut(entity_id) {
+                    view.on_update.remove(&req.id);
+                }
+
+                Ok(Acquired::Nothing)
+            },
+            // A `schema_only` table is parsed, but never hosted.
+            Some(MakeTableReq(req)) if is_schema_only(req) => {
+                self.ingest(limits, req.data.as_ref(), now)?;
+                Ok(Acquired::Nothing)
+            },
+            Some(MakeTableReq(req)) => {
+                check_limit("Tables", self.tables.len(), limits.max_tables)?;
+                self.ingest(limits, req.data.as_ref(), now)?;
+                self.acquire_table(limits, entity_id)
+            },
+            Some(MakeJoinTableReq(_) | MakeUnionTableReq(_) | MakeUnnestTableReq(_)) => {
+                self.acquire_table(limits, entity_id)
+            },
+            Some(TableUpdateReq(req)) => {
+                self.ingest(limits, req.data.as_ref(), now)?;
+                Ok(Acquired::Nothing)
+            },
+            Some(TableReplaceReq(req)) => {
+                self.ingest(limits, req.data.as_ref(), now)?;
+                Ok(Acquired::Nothing)
+            },
+            Some(TableDeleteReq(_)) if self.tables.contains(entity_id) => {
+                Ok(Acquired::DeletedTable(entity_id.clone()))
+            },
+            _ => Ok(Acquired::Nothing),
+        }
+    }
+
+    fn acquire_table(&mut self, limits: &SessionLimits, name: &str) -> Result<Acquired, String> {
+        check_limit("Tables", self.tables.len(), limits.max_tables)?;
+        self.tables.insert(name.to_owned());
+        Ok(Acquired::Table(name.to_owned()))
+    }
+
+    /// Count `data`'s rows against [`SessionLimits::max_rows_per_second`],
+    /// over a fixed one second window.
+    fn ingest(
+        &mut self,
+        limits: &SessionLimits,
+        data: Option<&MakeTableData>,
+        now: Instant,
+    ) -> Result<(), String> {
+        let rows = data.map(count_rows).unwrap_or_default();
+        let window_rows = match self.window {
+            Some((start, rows)) if now.duration_since(start) < Duration::from_secs(1) => rows,
+            _ => {
+                self.window = Some((now, 0));
+                0
+            },
+        };
+
+        if let Some(max) = limits.max_rows_per_second
+            && window_rows + rows > max
+        {
+            return Err(format!(
+                "Session limit of {max} rows per second exceeded ({rows} rows requested)"
+            ));
+        }
+
+        self.window = self.window.map(|(start, _)| (start, window_rows + rows));
+        self.rows_ingested += rows;
+        Ok(())
+    }
+
+    /// Commit a resource freed by a request which did not fail.
+    pub(crate) fn commit(&mut self, acquired: Acquired) {
+        if let Acquired::DeletedTable(name) = acquired {
+            self.tables.remove(&name);
+        }
+    }
+
+    /// Release a resource acquired by a request which failed.
+    pub(crate) fn release(&mut self, acquired: Acquired) {
+        match acquired {
+            Acquired::Nothing | Acquired::DeletedTable(_) => {},
+            Acquired::View(view_id) => {
+                self.views.remove(&view_id);
+            },
+            Acquired::Table(name) => {
+                self.tables.remove(&name);
+            },
+            Acquired::OnUpdate(view_id, id) => {
+                if let Some(view) = self.views.get_mut(&view_id) {
+                    view.on_update.remove(&id);
+                }
+            },
+        }
+    }
+
+    /// Remove and return the `View`s which have been idle for longer than
+    /// `timeout` at `now`, and count them as evicted.
+    pub(crate) fn take_idle_views(&mut self, timeout: Duration, now: Instant) -> Vec<String> {
+        let idle = self
+            .views
+            .iter()
+            .filter(|(_, view)| view.on_update.is_empty())
+            .filter(|(_, view)| now.duration_since(view.last_used) > timeout)
+            .map(|(view_id, _)| view_id.clone())
+            .collect::<Vec<_>>();
+
+        for view_id in idle.iter() {
+            self.views.remove(view_id);
+        }
+
+        self.evicted_views += idle.len() as u64;
+        idle
+    }
+
+    pub(crate) fn usage(&self) -> SessionUsage {
+        SessionUsage {
+            num_views: self.views.len() as u32,
+            num_tables: self.tables.len() as u32,
+            num_on_update: self.views.values().map(|x| x.on_update.len() as u32).sum(),
+            rows_ingested: self.rows_ingested,
+            evicted_views: self.evicted_views,
+            rejected_requests: self.rejected_requests,
+        }
+    }
+}
+
+fn check_limit(name: &str, count: usize, max: Option<usize>) -> Result<(), String> {
+    match max {
+        Some(max) if count >= max => Err(format!("Session limit of {max} {name} reached")),
+        _ => Ok(()),
+    }
+}
+
+/// Whether `req` only infers a schema, see
+/// [`perspective_client::Client::infer_schema`].
+pub(crate) fn is_schema_only(req: &proto::MakeTableReq) -> bool {
+    req.options
+        .as_ref()
+        .and_then(|x| x.schema_only)
+        .unwrap_or_default()
+}
+
+/// Estimate the number of rows in `data`, without fully parsing it. Data
+/// which fails to parse counts as zero rows, and is rejected by the engine
+/// instead.
+fn count_rows(data: &MakeTableData) -> u64 {
+    match data.data.as_ref() {
+        Some(Data::FromCsv(csv)) => csv
+            .lines()
+            .filter(|x| !x.is_empty())
+            .count()
+            .saturating_sub(1) as u64,
+        Some(Data::FromNdjson(ndjson)) => {
+            ndjson.lines().filter(|x| !x.trim().is_empty()).count() as u64
+        },
+        Some(Data::FromRows(rows)) => serde_json::from_str::<Vec<&RawValue>>(rows)
+            .map(|x| x.len() as u64)
+            .unwrap_or_default(),
+        Some(Data::FromCols(cols)) => serde_json::from_str::<HashMap<&str, Vec<&RawValue>>>(cols)
+            .ok()
+            .and_then(|x| x.values().map(|x| x.len() as u64).max())
+            .unwrap_or_default(),
+        Some(Data::FromArrow(arrow)) => count_arrow_rows(arrow).unwrap_or_default(),
+        Some(Data::FromSchema(_) | Data::FromView(_)) | None => 0,
+    }
+}
+
+/// Sum the `length` of every record batch message in an Arrow IPC file or
+/// stream, reading only the message headers rather than decoding the batches.
+fn count_arrow_rows(arrow: &[u8]) -> Option<u64> {
+    // The file format is the stream format between a magic prefix and a
+    // footer, which is never reached as the stream ends with an EOS marker.
+    let mut buf = arrow.strip_prefix(b"ARROW1\0\0").unwrap_or(arrow);
+    let mut rows = 0;
+    while let Some((prefix, rest)) = buf.split_first_chunk::<4>() {
+        // Messages are prefixed with a continuation marker, except in the
+        // legacy format before Arrow 0.15.
+        let (len, rest) = if *prefix == [0xff; 4] {
+            let (len, rest) = rest.split_first_chunk::<4>()?;
+            (i32::from_le_bytes(*len), rest)
+        } else {
+            (i32::from_le_bytes(*prefix), rest)
+        };
+
+        if len == 0 {
+            break;
+        }
+
+        let len = usize::try_from(len).ok()?;
+        let message = arrow_ipc::root_as_message(rest.get(..len)?).ok()?;
+        if let Some(batch) = message.header_as_record_batch() {
+            rows += u64::try_from(batch.length()).ok()?;
+        }
+
+        let body_len = usize::try_from(message.bodyLength()).ok()?;
+        buf = rest.get(len + body_len..)?;
+    }
+
+    Some(rows)
+}
+
+/// The error response for a `request` which exceeds a session limit.
+pub(crate) fn resource_exhausted(request: &Request, message: String) -> Response {
+    Response {
+        msg_id: request.msg_id,
+        entity_id: request.entity_id.clone(),
+        client_resp: Some(ClientResp::ServerError(proto::ServerError {
+            message,
+            status_code: StatusCode::ResourceExhausted as i32,
+        })),
+    }
+}
+
+/// The `msg_id` of the `ViewDeleteReq`s the [`Server`] makes on behalf of a
+/// session to evict idle `View`s, whose responses are not forwarded.
+pub(crate) const EVICTION_MSG_ID: u32 = u32::MAX;
+
+/// A request to delete `view_id`, see [`SessionLimits::view_idle_timeout`].
+pub(crate) fn delete_view_request(view_id: String) -> Request {
+    Request {
+        msg_id: EVICTION_MSG_ID,
+        entity_id: view_id,
+        client_req: Some(ClientReq::ViewDeleteReq(proto::ViewDeleteReq {})),
+    }
+}
@@ -0,0 +1,427 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Durable [`Server`] state: snapshots of hosted tables to a directory, and
+//! an append-only log of the updates made since the last snapshot, see
+//! [`Server::snapshot`] and [`Server::restore`].
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use perspective_client::RetentionOptions;
+use perspective_client::proto::make_table_data::Data;
+use perspective_client::proto::make_table_req::MakeTableOptions;
+use perspective_client::proto::make_table_req::make_table_options::MakeTableType;
+use perspective_client::proto::request::ClientReq;
+use perspective_client::proto::response::ClientResp;
+use perspective_client::proto::{
+    GetHostedTablesReq, HostedTable, MakeTableData, MakeTableReq, Request, Response,
+    TableDeleteReq, TableMakeViewReq, ViewDeleteReq, ViewPort, ViewToArrowReq,
+};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::server::{Server, ServerResult};
+use crate::{ffi, session_limits};
+
+const MANIFEST: &str = "manifest.json";
+
+/// Options for [`Server::snapshot`].
+#[derive(Clone, Debug, Default)]
+pub struct SnapshotOptions {
+    /// The names of the tables to snapshot, or every hosted table if `None`.
+    /// Other tables keep their entries from the previous snapshot, and their
+    /// entries in the update log.
+    pub tables: Option<Vec<String>>,
+}
+
+/// The index of a snapshot directory, written after the table files it
+/// references so a partially written snapshot is never restored.
+#[derive(Debug, Deserialize, Serialize)]
+struct Manifest {
+    generation: u128,
+    tables: Vec<TableSnapshot>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct TableSnapshot {
+    name: String,
+    index: Option<String>,
+    limit: Option<u32>,
+    #[serde(default)]
+    retention: Option<RetentionOptions>,
+    file: String,
+}
+
+/// An append-only log of the requests which modify hosted tables, stored as
+/// length-delimited `Request` messages.
+#[derive(Debug)]
+pub(crate) struct UpdateLog {
+    path: PathBuf,
+    file: Option<File>,
+}
+
+impl UpdateLog {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        Self { path, file: None }
+    }
+
+    /// Whether `request` modifies a hosted table, and so must be logged.
+    /// Join and union tables are computed from their source tables, and
+    /// `schema_only` tables are never hosted, so neither is logged.
+    pub(crate) fn is_logged(request: &Request) -> bool {
+        match &request.client_req {
+            Some(ClientReq::MakeTableReq(req)) => !session_limits::is_schema_only(req),
+            Some(
+                ClientReq::TableUpdateReq(_)
+                | ClientReq::TableRemoveReq(_)
+                | ClientReq::TableReplaceReq(_)
+                | ClientReq::TableDeleteReq(_),
+            ) => true,
+            _ => false,
+        }
+    }
+
+    pub(crate) fn append(&mut self, request: &Request) -> io::Result<()> {
+        let mut request = request.clone();
+
+        // Ports are per-session, and will not exist when the log is replayed.
+        if let Some(ClientReq::TableUpdateReq(req)) = request.client_req.as_mut() {
+            req.port_id = 0;
+        }
+
+        let file = match &mut self.file {
+            Some(file) => file,
+            file @ None => file.insert(
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)?,
+            ),
+        };
+
+        file.write_all(&request.encode_length_delimited_to_vec())
+    }
+
+    /// Log the tables queued in `evicted` (see [`Server::take_evicted_table`])
+    /// as deleted, so they are not brought back by [`Server::restore`].
+    pub(crate) fn append_evictions(&mut self, evicted: &Mutex<Vec<String>>) -> io::Result<()> {
+        let tables = std::mem::take(&mut *evicted.lock().unwrap());
+        for entity_id in tables {
+            self.append(&Request {
+                msg_id: 0,
+                entity_id,
+                client_req: Some(ClientReq::TableDeleteReq(TableDeleteReq {
+                    is_immediate: true,
+                })),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn truncate(&mut self) -> io::Result<()> {
+        self.file = Some(File::create(&self.path)?);
+        Ok(())
+    }
+
+    /// Remove the logged requests for which `f` returns `false`.
+    fn retain(&mut self, f: impl Fn(&Request) -> bool) -> io::Result<()> {
+        let requests = self.read()?;
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        let mut file = File::create(&tmp)?;
+        for request in requests.iter().filter(|x| f(x)) {
+            file.write_all(&request.encode_length_delimited_to_vec())?;
+        }
+
+        drop(file);
+        fs::rename(&tmp, &self.path)?;
+
+        // Reopen the new file on the next `UpdateLog::append`.
+        self.file = None;
+        Ok(())
+    }
+
+    /// The logged requests. A truncated final request, e.g. from a crash
+    /// mid-write, is ignored.
+    fn read(&self) -> io::Result<Vec<Request>> {
+        let bytes = match fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+
+        let mut buf = bytes.as_slice();
+        let mut requests = vec![];
+        while !buf.is_empty() {
+            match Request::decode_length_delimited(&mut buf) {
+                Ok(request) => requests.push(request),
+                Err(e) => {
+                    tracing::warn!("Ignoring truncated update log entry: {}", e);
+                    break;
+                },
+            }
+        }
+
+        Ok(requests)
+    }
+}
+
+/// A session for the [`Server`]'s own requests, which bypasses
+/// authorization, session limits and the update log.
+struct InternalSession<'a> {
+    server: &'a Server,
+    id: u32,
+    msg_id: u32,
+}
+
+impl Drop for InternalSession<'_> {
+    fn drop(&mut self) {
+        self.server.server.close_session(self.id);
+    }
+}
+
+impl<'a> InternalSession<'a> {
+    fn new(server: &'a Server) -> Self {
+        let id = server.server.new_session();
+        Self {
+            server,
+            id,
+            msg_id: 0,
+        }
+    }
+
+    /// Make a request, returning its response. Responses for other sessions
+    /// (e.g. `on_update` callbacks) are dispatched as usual.
+    async fn request(&mut self, entity_id: &str, req: ClientReq) -> ServerResult<ClientResp> {
+        self.msg_id += 1;
+        let request = Request {
+            msg_id: self.msg_id,
+            entity_id: entity_id.to_owned(),
+            client_req: Some(req),
+        };
+
+        let request = ffi::Request::from(request.encode_to_vec().as_slice());
+        self.server.sync_clock();
+        let responses = self.server.server.handle_request(self.id, &request);
+        let mut result = None;
+        for response in responses.iter_responses() {
+            if self.server.take_evicted_table(&response)? {
+                continue;
+            }
+
+            if response.client_id() == self.id {
+                let resp = Response::decode(response.msg())?;
+                if resp.msg_id == self.msg_id {
+                    result = resp.client_resp;
+                }
+            } else {
+                let cb = self
+                    .server
+                    .callbacks
+                    .read()
+                    .await
+                    .get(&response.client_id())
+                    .cloned();
+
+                if let Some(f) = cb {
+                    f(response.msg()).await?;
+                }
+            }
+        }
+
+        match result {
+            Some(ClientResp::ServerError(e)) => Err(e.message.into()),
+            Some(resp) => Ok(resp),
+            None => Err(format!("No response for \"{entity_id}\"").into()),
+        }
+    }
+
+    async fn hosted_tables(&mut self) -> ServerResult<Vec<HostedTable>> {
+        let req = ClientReq::GetHostedTablesReq(GetHostedTablesReq { subscribe: false });
+        match self.request("", req).await? {
+            ClientResp::GetHostedTablesResp(resp) => Ok(resp.table_infos),
+            resp => Err(format!("Unexpected response {resp:?}").into()),
+        }
+    }
+
+    async fn to_arrow(&mut self, table: &str) -> ServerResult<Vec<u8>> {
+        let view_id = format!("__snapshot_{}_{}", self.id, self.msg_id);
+        let req = ClientReq::TableMakeViewReq(TableMakeViewReq {
+            view_id: view_id.clone(),
+            config: None,
+        });
+
+        self.request(table, req).await?;
+        let req = ClientReq::ViewToArrowReq(ViewToArrowReq {
+            viewport: Some(ViewPort::default()),
+            compression: None,
+        });
+
+        let arrow = self.request(&view_id, req).await;
+        self.request(&view_id, ClientReq::ViewDeleteReq(ViewDeleteReq {}))
+            .await?;
+
+        match arrow? {
+            ClientResp::ViewToArrowResp(resp) => Ok(resp.arrow),
+            resp => Err(format!("Unexpected response {resp:?}").into()),
+        }
+    }
+}
+
+/// Write a snapshot of `server`'s tables to `dir`, then remove their entries
+/// from its update log. Join and union tables are not included, and tables
+/// not selected by `options` keep their previous snapshot and log entries.
+pub(crate) async fn write_snapshot(
+    server: &Server,
+    dir: &Path,
+    options: &SnapshotOptions,
+) -> ServerResult<()> {
+    // Hold the update log for the duration, so no update is both in the
+    // snapshot and the log (or neither).
+    let mut log = match &server.update_log {
+        Some(log) => Some(log.lock().await),
+        None => None,
+    };
+
+    server.poll().await?;
+    fs::create_dir_all(dir)?;
+    let previous = read_manifest(dir)?;
+    let generation = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_millis();
+
+    let mut session = InternalSession::new(server);
+    let mut manifest = Manifest {
+        generation,
+        tables: vec![],
+    };
+
+    let is_selected = |name: &str| {
+        options
+            .tables
+            .as_ref()
+            .is_none_or(|x| x.iter().any(|x| x == name))
+    };
+
+    for info in session.hosted_tables().await? {
+        if info.derived || !is_selected(&info.entity_id) {
+            continue;
+        }
+
+        let file = format!("{}-{}.arrow", generation, manifest.tables.len());
+        fs::write(dir.join(&file), session.to_arrow(&info.entity_id).await?)?;
+        manifest.tables.push(TableSnapshot {
+            name: info.entity_id,
+            index: info.index,
+            limit: info.limit,
+            retention: info.retention.map(RetentionOptions::from),
+            file,
+        });
+    }
+
+    let mut stale = vec![];
+    for table in previous.map(|x| x.tables).unwrap_or_default() {
+        if is_selected(&table.name) {
+            stale.push(table.file);
+        } else {
+            manifest.tables.push(table);
+        }
+    }
+
+    let tmp = dir.join(format!("{MANIFEST}.tmp"));
+    fs::write(&tmp, serde_json::to_vec_pretty(&manifest)?)?;
+    fs::rename(&tmp, dir.join(MANIFEST))?;
+    match (log.as_mut(), &options.tables) {
+        (Some(log), None) => log.truncate()?,
+        (Some(log), Some(_)) => log.retain(|x| !is_selected(&x.entity_id))?,
+        (None, _) => {},
+    }
+
+    if let Some(log) = log.as_mut() {
+        log.append_evictions(&server.evicted_tables)?;
+    }
+
+    for file in stale {
+        if let Err(e) = fs::remove_file(dir.join(&file)) {
+            tracing::warn!("Failed to remove {}: {}", file, e);
+        }
+    }
+
+    Ok(())
+}
+
+fn read_manifest(dir: &Path) -> ServerResult<Option<Manifest>> {
+    match fs::read(dir.join(MANIFEST)) {
+        Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Restore the tables in the snapshot in `dir` (if there is one) to `server`,
+/// then replay its update log. Returns the names of the restored tables.
+pub(crate) async fn restore_snapshot(server: &Server, dir: &Path) -> ServerResult<Vec<String>> {
+    let log = match &server.update_log {
+        Some(log) => Some(log.lock().await),
+        None => None,
+    };
+
+    let mut session = InternalSession::new(server);
+    let mut names = vec![];
+    for table in read_manifest(dir)?.map(|x| x.tables).unwrap_or_default() {
+        let make_table_type = match (table.index, table.limit) {
+            (Some(index), _) => Some(MakeTableType::MakeIndexTable(index)),
+            (None, Some(limit)) => Some(MakeTableType::MakeLimitTable(limit)),
+            (None, None) => None,
+        };
+
+        let req = ClientReq::MakeTableReq(MakeTableReq {
+            data: Some(MakeTableData {
+                data: Some(Data::FromArrow(fs::read(dir.join(&table.file))?)),
+            }),
+            options: Some(MakeTableOptions {
+                make_table_type,
+                page_to_disk: None,
+                retention: table.retention.map(Into::into),
+                schema_only: None,
+            }),
+        });
+
+        session.request(&table.name, req).await?;
+        names.push(table.name);
+    }
+
+    if let Some(log) = log {
+        let requests = log.read()?;
+        tracing::info!("Replaying {} updates", requests.len());
+        for request in requests {
+            let Some(req) = request.client_req else {
+                continue;
+            };
+
+            if let Err(e) = session.request(&request.entity_id, req).await {
+                tracing::warn!("Failed to replay update to {}: {}", request.entity_id, e);
+            }
+
+            // Apply each update before the next, as a later request may
+            // delete or replace its table.
+            server.poll().await?;
+        }
+    }
+
+    server.poll().await?;
+    Ok(names)
+}
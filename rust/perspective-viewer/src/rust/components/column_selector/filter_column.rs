@@ -252,13 +252,15 @@ impl Component for FilterColumn {
         });
 
         let type_class = match col_type {
-            Some(ColumnType::Float) | Some(ColumnType::Integer) => "num-filter",
-            Some(ColumnType::String) => "string-filter",
+            Some(x) if x.is_numeric() => "num-filter",
+            Some(ColumnType::String | ColumnType::ListString | ColumnType::ListFloat) => {
+                "string-filter"
+            },
             _ => "",
         };
 
         let input_elem = match col_type {
-            Some(ColumnType::Integer) => html! {
+            Some(ColumnType::Integer | ColumnType::Int64 | ColumnType::Duration) => html! {
                 <input
                     type="number"
                     placeholder="Value"
@@ -281,7 +283,7 @@ impl Component for FilterColumn {
                     oninput={input}
                 />
             },
-            Some(ColumnType::String) => html! {
+            Some(ColumnType::String | ColumnType::ListString | ColumnType::ListFloat) => html! {
                 <input
                     type="search"
                     size="4"
@@ -427,6 +429,9 @@ impl FilterColumnProps {
             (ColumnType::Datetime, FilterTerm::Scalar(Scalar::Float(x))) => {
                 posix_to_utc_str(*x).ok()
             },
+            (ColumnType::Datetime, FilterTerm::Scalar(Scalar::Datetime(x))) => {
+                posix_to_utc_str(x.timestamp_millis() as f64).ok()
+            },
             (ColumnType::Boolean, FilterTerm::Scalar(Scalar::Bool(x))) => {
                 Some((if *x { "true" } else { "false" }).to_owned())
             },
@@ -462,7 +467,10 @@ impl FilterColumnProps {
         let filter_column = &mut filters.get_mut(self.idx).expect("Filter on no column");
 
         // TODO This belongs in the Features API.
-        let filter_input = if filter_column.op() == "in" || filter_column.op() == "not in" {
+        let filter_input = if filter_column.op() == "in"
+            || filter_column.op() == "not in"
+            || filter_column.op() == "contains any"
+        {
             Some(FilterTerm::Array(
                 val.split(',')
                     .map(|x| Scalar::String(x.trim().to_owned()))
@@ -470,8 +478,10 @@ impl FilterColumnProps {
             ))
         } else {
             match self.get_current_filter_type() {
-                Some(ColumnType::String) => Some(FilterTerm::Scalar(Scalar::String(val))),
-                Some(ColumnType::Integer) => {
+                Some(ColumnType::String | ColumnType::ListString) => {
+                    Some(FilterTerm::Scalar(Scalar::String(val)))
+                },
+                Some(ColumnType::Integer | ColumnType::Int64 | ColumnType::Duration) => {
                     if val.is_empty() {
                         None
                     } else if let Ok(num) = val.parse::<f64>() {
@@ -480,7 +490,7 @@ impl FilterColumnProps {
                         None
                     }
                 },
-                Some(ColumnType::Float) => {
+                Some(ColumnType::Float | ColumnType::ListFloat) => {
                     if val.is_empty() {
                         None
                     } else if let Ok(num) = val.parse::<f64>() {
@@ -14,6 +14,7 @@
 //! "ignore the active view config and re-fetch the un-pivoted table view";
 //! `flat = false` means "use the session's bound view".
 
+use futures::TryStreamExt;
 use perspective_client::{View, ViewWindow};
 use perspective_js::utils::{ApiError, ApiResult};
 use wasm_bindgen::JsCast;
@@ -94,3 +95,48 @@ pub async fn csv_as_jsvalue(
     let csv = flat_view(session, flat).await?.to_csv(window).await;
     Ok(csv.map(js_sys::JsString::from)?)
 }
+
+/// Like [`csv_as_jsvalue`] as a [`web_sys::Blob`], assembled from
+/// [`View::to_csv_stream`] chunks so the export is never one (potentially
+/// enormous) string.
+pub async fn csv_as_blob(
+    session: &Session,
+    flat: bool,
+    window: Option<ViewWindow>,
+) -> ApiResult<web_sys::Blob> {
+    let mut stream = flat_view(session, flat)
+        .await?
+        .to_csv_stream(window.unwrap_or_default())
+        .await?;
+
+    let parts = js_sys::Array::new();
+    while let Some(chunk) = stream.try_next().await? {
+        parts.push(&js_sys::JsString::from(chunk));
+    }
+
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type("text/plain");
+    Ok(web_sys::Blob::new_with_str_sequence_and_options(
+        &parts, &options,
+    )?)
+}
+
+/// Like [`arrow_as_jsvalue`] as a [`web_sys::Blob`], assembled from
+/// [`View::to_arrow_stream`] chunks.
+pub async fn arrow_as_blob(
+    session: &Session,
+    flat: bool,
+    window: Option<ViewWindow>,
+) -> ApiResult<web_sys::Blob> {
+    let mut stream = flat_view(session, flat)
+        .await?
+        .to_arrow_stream(window.unwrap_or_default())
+        .await?;
+
+    let parts = js_sys::Array::new();
+    while let Some(chunk) = stream.try_next().await? {
+        parts.push(&js_sys::Uint8Array::from(&chunk[..]));
+    }
+
+    Ok(web_sys::Blob::new_with_u8_array_sequence(&parts)?)
+}
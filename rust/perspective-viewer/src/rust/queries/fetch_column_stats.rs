@@ -21,7 +21,7 @@
 //! defaults are ready before the user can trigger any UI update.
 
 use perspective_client::View;
-use perspective_client::config::{ColumnType, Scalar};
+use perspective_client::config::Scalar;
 use perspective_js::utils::ApiFuture;
 
 use crate::session::{Session, SessionMetadata};
@@ -41,14 +41,14 @@ pub fn min_max_to_abs_max(min: &Scalar, max: &Scalar) -> Option<f64> {
     Some(min.abs().max(max.abs()))
 }
 
-/// `true` if the column's view type is `Integer` or `Float`. Used to
-/// skip the `View::get_min_max` round trip for non-numeric columns
-/// (which would either error or return non-`Float` scalars).
+/// `true` if the column's view type is numeric (see
+/// [`perspective_client::config::ColumnType::is_numeric`]). Used to skip the
+/// `View::get_min_max` round trip for non-numeric columns (which would
+/// either error or return non-`Float` scalars).
 pub fn is_numeric_column(metadata: &SessionMetadata, col_name: &str) -> bool {
-    matches!(
-        metadata.get_column_view_type(col_name),
-        Some(ColumnType::Integer | ColumnType::Float)
-    )
+    metadata
+        .get_column_view_type(col_name)
+        .is_some_and(|x| x.is_numeric())
 }
 
 /// Resolve the cached `abs_max` for `col_name`; on miss, await
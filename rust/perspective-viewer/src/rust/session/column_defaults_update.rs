@@ -75,10 +75,9 @@ pub impl ViewConfigUpdate {
                 .iter()
                 .flatten()
                 .filter(|x| {
-                    matches!(
-                        metadata.get_column_table_type(x),
-                        Some(ColumnType::Float | ColumnType::Integer)
-                    )
+                    metadata
+                        .get_column_table_type(x)
+                        .is_some_and(|x| x.is_numeric())
                 })
                 .take(min_cols)
                 .cloned()
@@ -108,10 +107,9 @@ pub impl ViewConfigUpdate {
                                     .any(|y| y.as_ref() == Some(*x))
                             })
                             .filter(|x| {
-                                matches!(
-                                    metadata.get_column_table_type(x),
-                                    Some(ColumnType::Float | ColumnType::Integer)
-                                )
+                                metadata
+                                    .get_column_table_type(x)
+                                    .is_some_and(|x| x.is_numeric())
                             })
                             .cloned()
                             .map(Some),
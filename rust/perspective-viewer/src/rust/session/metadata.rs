@@ -321,10 +321,9 @@ impl SessionMetadata {
                             .into_iter()
                             .filter(move |(_, coltype)| {
                                 *coltype as i32 == *dtype
-                                    || (coltype == &ColumnType::Integer
-                                        && *dtype == ColumnType::Float as i32)
-                                    || (coltype == &ColumnType::Float
-                                        && *dtype == ColumnType::Integer as i32)
+                                    || (coltype.is_numeric()
+                                        && (*dtype == ColumnType::Float as i32
+                                            || *dtype == ColumnType::Integer as i32))
                             })
                             .map(|(name, _)| {
                                 Aggregate::MultiAggregate(x.name.to_string(), vec![name])
@@ -116,15 +116,9 @@ pub async fn export_method_to_blob(
     let viewport = renderer.get_selection();
 
     match method {
-        ExportMethod::Csv => crate::queries::csv_as_jsvalue(session, false, None)
-            .await?
-            .as_blob(),
-        ExportMethod::CsvSelected => crate::queries::csv_as_jsvalue(session, false, viewport)
-            .await?
-            .as_blob(),
-        ExportMethod::CsvAll => crate::queries::csv_as_jsvalue(session, true, None)
-            .await?
-            .as_blob(),
+        ExportMethod::Csv => crate::queries::csv_as_blob(session, false, None).await,
+        ExportMethod::CsvSelected => crate::queries::csv_as_blob(session, false, viewport).await,
+        ExportMethod::CsvAll => crate::queries::csv_as_blob(session, true, None).await,
         ExportMethod::Json => crate::queries::json_as_jsvalue(session, false, None)
             .await?
             .as_blob(),
@@ -143,15 +137,11 @@ pub async fn export_method_to_blob(
         ExportMethod::NdjsonAll => crate::queries::ndjson_as_jsvalue(session, true, None)
             .await?
             .as_blob(),
-        ExportMethod::Arrow => crate::queries::arrow_as_jsvalue(session, false, None)
-            .await?
-            .as_blob(),
-        ExportMethod::ArrowSelected => crate::queries::arrow_as_jsvalue(session, false, viewport)
-            .await?
-            .as_blob(),
-        ExportMethod::ArrowAll => crate::queries::arrow_as_jsvalue(session, true, None)
-            .await?
-            .as_blob(),
+        ExportMethod::Arrow => crate::queries::arrow_as_blob(session, false, None).await,
+        ExportMethod::ArrowSelected => {
+            crate::queries::arrow_as_blob(session, false, viewport).await
+        },
+        ExportMethod::ArrowAll => crate::queries::arrow_as_blob(session, true, None).await,
         ExportMethod::Html => html_as_jsvalue(session, renderer, presentation)
             .await?
             .as_blob(),
@@ -54,11 +54,25 @@ export interface DateFormatConfig {
     fractionalSecondDigits?: 1 | 2 | 3;
 }
 
+/**
+ * Whether values of column `type` are numbers, which are formatted with
+ * `number_format` and support numeric styles (bars, gradients, etc.).
+ */
+export function is_numeric_type(type: string): boolean {
+    return (
+        type === "integer" ||
+        type === "float" ||
+        type === "int64" ||
+        type === "duration"
+    );
+}
+
 /**
  * Default `Intl.NumberFormatOptions` applied when a numeric column has no
  * `number_format` configured. Float columns get two fractional digits to
- * match the legacy datagrid behavior; integer columns get an empty
- * options bag (locale-default integer rendering).
+ * match the legacy datagrid behavior; durations are rendered in
+ * milliseconds; integer and int64 columns get an empty options bag
+ * (locale-default integer rendering).
  */
 const NUMERIC_LEGACY_DEFAULTS: Record<string, Intl.NumberFormatOptions> = {
     float: {
@@ -66,6 +80,11 @@ const NUMERIC_LEGACY_DEFAULTS: Record<string, Intl.NumberFormatOptions> = {
         minimumFractionDigits: 2,
         maximumFractionDigits: 2,
     },
+    duration: {
+        style: "unit",
+        unit: "millisecond",
+        unitDisplay: "short",
+    },
 };
 
 /**
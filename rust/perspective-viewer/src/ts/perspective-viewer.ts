@@ -40,6 +40,7 @@ export {
     createNumberFormatter,
     createDatetimeFormatter,
     createDateFormatter,
+    is_numeric_type,
     sourceColumn,
 } from "./column-format";
 export type { NumberFormatConfig, DateFormatConfig } from "./column-format";
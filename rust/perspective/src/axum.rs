@@ -10,82 +10,138 @@
 // ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
+use std::collections::HashMap;
+use std::future::Future;
 use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 
+use axum::Router;
 use axum::body::Bytes;
-use axum::extract::State;
 use axum::extract::connect_info::ConnectInfo;
-use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
-use axum::response::IntoResponse;
+use axum::extract::ws::{CloseFrame, Message, Utf8Bytes, WebSocket, WebSocketUpgrade, close_code};
+use axum::extract::{FromRef, Request, State};
+use axum::http::request::Parts;
+use axum::http::{Extensions, StatusCode};
+use axum::middleware::map_request;
+use axum::response::{IntoResponse, Json};
 use axum::routing::{MethodRouter, get};
-use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender, unbounded};
-use futures::future::{Either, select};
-use futures::{FutureExt, SinkExt, StreamExt};
+use futures::{SinkExt, StreamExt};
+use tokio::net::TcpListener;
+use tokio::sync::{Notify, watch};
+use tower_http::services::{ServeDir, ServeFile};
 
-use crate::client::Session;
-use crate::server::{LocalSession, Server, SessionHandler};
+use crate::server::{Server, SessionIdentity};
+use crate::transport::{CloseReason, ConnectionMetrics, TransportOptions, run_session};
+pub use crate::transport::{ConnectionStats, OverflowPolicy};
 
-/// A local error synonym for this module only.
-type PerspectiveWSError = Box<dyn std::error::Error + Send + Sync>;
+mod export;
 
-/// We must share access to the [`WebSocket`] for both sending and receiving
-/// messages, a message flow which this enum is used to model. When the
-/// client disconnects or the message stream is unrecoverably errored,
-/// we must emit an _end_ message as well.
-enum PerspectiveWSMessage {
-    Incoming(Bytes),
-    Outgoing(Bytes),
-    End,
+pub use export::export_router;
+
+/// [`TransportOptions`] for WebSocket connections, whose heartbeats are
+/// sent as WebSocket `Ping`s.
+pub type WebSocketOptions = TransportOptions;
+
+/// Derives the [`SessionIdentity`] of a request, see
+/// [`PerspectiveApp::with_identity`].
+type IdentityFn = dyn Fn(&Parts) -> SessionIdentity + Send + Sync;
+
+/// The [`SessionIdentity`] a request is authorized as: the request extension
+/// inserted by [`PerspectiveApp::with_identity`] (or by an authentication
+/// middleware), else the anonymous default identity.
+pub(crate) fn request_identity(extensions: &Extensions) -> SessionIdentity {
+    extensions
+        .get::<SessionIdentity>()
+        .cloned()
+        .unwrap_or_default()
 }
 
-/// A new-type wrapper for an [`UnboundedSender`], whic bypasses the orphan
-/// instance rule allowing us to write a [`SessionHandler`] impl for this
-/// struct.
-#[derive(Clone)]
-struct PerspectiveWSConnection(UnboundedSender<Bytes>);
-
-/// The [`SessionHandler`] implementation provides a method for a
-/// [`Session`] to send messages to this
-/// [`axum::extract::ws::WebSocket`], which may (or may
-/// not) be solicited (e.g. within the async call stack of
-/// [`perspective::Session::handle_request`]).
-impl SessionHandler for PerspectiveWSConnection {
-    async fn send_response<'a>(&'a mut self, resp: &'a [u8]) -> Result<(), PerspectiveWSError> {
-        Ok(self.0.send(Bytes::copy_from_slice(resp)).await?)
-    }
-}
-
-/// The inner message loop handles the full-duplex stream of messages
-/// between the [`perspective::Client`] and [`Session`]. When this
-/// funciton returns, messages are no longer processed.
-async fn process_message_loop(
-    socket: &mut WebSocket,
-    receiver: &mut UnboundedReceiver<Bytes>,
-    session: &mut LocalSession,
-) -> Result<(), PerspectiveWSError> {
-    use Either::*;
-    use Message::*;
-    use PerspectiveWSMessage::*;
-
-    loop {
-        let msg = match select(socket.recv().boxed(), receiver.next()).await {
-            Right((Some(bytes), _)) => Ok(Outgoing(bytes)),
-            Left((Some(Ok(Binary(bytes))), _)) => Ok(Incoming(bytes)),
-            Right((None, _)) | Left((None | Some(Ok(Close(_))), _)) => Ok(End),
-            Left((Some(Ok(_)), _)) => Err("Unexpected message type".to_string()),
-            Left((Some(Err(err)), _)) => Err(format!("{err}")),
-        }?;
-
-        match msg {
-            End => break,
-            Outgoing(bytes) => socket.send(Binary(bytes)).await?,
-            Incoming(bytes) => {
-                session.handle_request(&bytes).await?;
-            },
+/// Resolves when `shutdown` is signalled, or never if there is no `shutdown`
+/// channel or its sender has been dropped.
+async fn shutdown_requested(shutdown: &mut Option<watch::Receiver<bool>>) {
+    if let Some(shutdown) = shutdown
+        && shutdown.wait_for(|x| *x).await.is_ok()
+    {
+        return;
+    }
+
+    futures::future::pending().await
+}
+
+/// Runs a single WebSocket connection's [`crate::server::LocalSession`]
+/// until the client disconnects, a [`WebSocketOptions`] limit is exceeded or
+/// `shutdown` is signalled, then closes the session and the WebSocket with an
+/// appropriate close code.
+async fn handle_socket(
+    socket: WebSocket,
+    server: Server,
+    addr: SocketAddr,
+    identity: SessionIdentity,
+    options: Arc<WebSocketOptions>,
+    metrics: Arc<ConnectionMetrics>,
+    mut shutdown: Option<watch::Receiver<bool>>,
+) {
+    let (sink, stream) = socket.split();
+    let incoming = stream.map_while(move |msg| match msg {
+        Ok(Message::Binary(bytes)) => Some(bytes),
+        Ok(Message::Ping(_) | Message::Pong(_)) => Some(Bytes::new()),
+        Ok(Message::Close(_)) => None,
+        Ok(Message::Text(_)) => {
+            tracing::error!("{addr} Unexpected message type");
+            None
+        },
+        Err(err) => {
+            tracing::error!("{addr} Internal error {}", err);
+            None
+        },
+    });
+
+    let mut outgoing = sink.with(|bytes: Bytes| {
+        futures::future::ready(Ok::<_, axum::Error>(if bytes.is_empty() {
+            Message::Ping(bytes)
+        } else {
+            Message::Binary(bytes)
+        }))
+    });
+
+    let shutdown = shutdown_requested(&mut shutdown);
+    let result = run_session(
+        &server,
+        incoming,
+        &mut outgoing,
+        &options,
+        identity,
+        metrics,
+        shutdown,
+    )
+    .await;
+
+    let close = match result {
+        Ok(CloseReason::Disconnected) => None,
+        Ok(CloseReason::MessageTooBig) => Some((close_code::SIZE, "Message too big")),
+        Ok(CloseReason::Overflowed) => {
+            tracing::warn!("{addr} Outgoing queue overflowed");
+            Some((close_code::POLICY, "Outgoing queue overflowed"))
+        },
+        Ok(CloseReason::IdleTimeout) => Some((close_code::AWAY, "Idle timeout")),
+        Ok(CloseReason::Shutdown) => Some((close_code::AWAY, "Server shutting down")),
+        Err(err) => {
+            tracing::error!("Internal error {}", err);
+            Some((close_code::ERROR, "Internal error"))
+        },
+    };
+
+    if let Some((code, reason)) = close {
+        let reason = Utf8Bytes::from_static(reason);
+        let frame = Message::Close(Some(CloseFrame { code, reason }));
+        if let Err(err) = outgoing.get_mut().send(frame).await {
+            tracing::debug!("{addr} Failed to close {}", err);
         }
     }
 
-    Ok(())
+    tracing::info!("{addr} Disconnected.");
 }
 
 /// This handler is responsible for the beginning-to-end lifecycle of a
@@ -96,25 +152,394 @@ async fn process_message_loop(
 /// [`perspective::Session::handle_request`]. The server may generate
 /// one or more responses, which it will then send back to
 /// the [`axum::extract::ws::WebSocket::send`] method via its
-/// [`SessionHandler`] impl.
+/// [`crate::server::SessionHandler`] impl.
+///
+/// Each session is authorized as the request's [`SessionIdentity`]
+/// extension, if an authentication middleware inserted one.
 pub fn websocket_handler() -> MethodRouter<Server> {
-    async fn websocket_handler_internal(
-        ws: WebSocketUpgrade,
-        State(server): State<Server>,
-        ConnectInfo(addr): ConnectInfo<SocketAddr>,
-    ) -> impl IntoResponse {
-        tracing::info!("{addr} Connected.");
-        ws.on_upgrade(move |mut socket| async move {
-            let (send, mut receiver) = unbounded::<Bytes>();
-            let mut session = server.new_session(PerspectiveWSConnection(send)).await;
-            if let Err(msg) = process_message_loop(&mut socket, &mut receiver, &mut session).await {
-                tracing::error!("Internal error {}", msg);
+    websocket_handler_with_options(WebSocketOptions::default())
+}
+
+/// Like [`websocket_handler`], with [`WebSocketOptions`] for outgoing queue
+/// bounds, incoming message size limits and keepalive.
+pub fn websocket_handler_with_options(options: WebSocketOptions) -> MethodRouter<Server> {
+    let options = Arc::new(options);
+    get(
+        move |ws: WebSocketUpgrade,
+              State(server): State<Server>,
+              ConnectInfo(addr): ConnectInfo<SocketAddr>,
+              extensions: Extensions| async move {
+            tracing::info!("{addr} Connected.");
+            let metrics = Arc::default();
+            let identity = request_identity(&extensions);
+            ws.max_message_size(options.max_message_size)
+                .on_upgrade(move |socket| {
+                    handle_socket(socket, server, addr, identity, options, metrics, None)
+                })
+        },
+    )
+}
+
+/// The open WebSocket connections of a [`PerspectiveApp`], so that
+/// [`PerspectiveApp::serve`] can wait for every [`crate::server::LocalSession`]
+/// to close before returning.
+#[derive(Default)]
+struct SessionTracker {
+    next_id: AtomicU64,
+    connections: Mutex<HashMap<u64, (SocketAddr, Arc<ConnectionMetrics>)>>,
+    notify: Notify,
+}
+
+/// Held for the lifetime of a single WebSocket session.
+struct SessionGuard(Arc<SessionTracker>, u64);
+
+impl SessionGuard {
+    fn new(
+        tracker: &Arc<SessionTracker>,
+        addr: SocketAddr,
+        metrics: Arc<ConnectionMetrics>,
+    ) -> Self {
+        let id = tracker.next_id.fetch_add(1, Ordering::SeqCst);
+        tracker
+            .connections
+            .lock()
+            .unwrap()
+            .insert(id, (addr, metrics));
+        Self(tracker.clone(), id)
+    }
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        self.0.connections.lock().unwrap().remove(&self.1);
+        self.0.notify.notify_waiters();
+    }
+}
+
+impl SessionTracker {
+    fn len(&self) -> usize {
+        self.connections.lock().unwrap().len()
+    }
+
+    fn stats(&self) -> Vec<(SocketAddr, ConnectionStats)> {
+        let mut stats = self
+            .connections
+            .lock()
+            .unwrap()
+            .values()
+            .map(|(addr, metrics)| (*addr, metrics.stats()))
+            .collect::<Vec<_>>();
+
+        stats.sort_by_key(|x| x.0);
+        stats
+    }
+
+    async fn wait_for_idle(&self) {
+        loop {
+            let notified = self.notify.notified();
+            if self.len() == 0 {
+                break;
             }
 
-            tracing::info!("{addr} Disconnected.");
-            session.close().await;
+            notified.await;
+        }
+    }
+}
+
+/// Shared state for the routes of a [`PerspectiveApp`].
+#[derive(Clone)]
+struct AppState {
+    server: Server,
+    options: Arc<WebSocketOptions>,
+    sessions: Arc<SessionTracker>,
+    shutdown: watch::Receiver<bool>,
+}
+
+impl FromRef<AppState> for Server {
+    fn from_ref(state: &AppState) -> Self {
+        state.server.clone()
+    }
+}
+
+/// A builder for an [`axum`] app which hosts a [`Server`]'s tables, serving
+/// a WebSocket endpoint for [`perspective::Client`] connections, the
+/// viewer's static assets and, optionally, HTTP export routes and a JSON
+/// status endpoint.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let server = Server::new(None);
+/// let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await?;
+/// PerspectiveApp::new(server)
+///     .with_assets("dist")
+///     .serve(listener, async { tokio::signal::ctrl_c().await.unwrap() })
+///     .await?;
+/// ```
+pub struct PerspectiveApp {
+    server: Server,
+    websocket_path: String,
+    status_path: Option<String>,
+    assets: Option<PathBuf>,
+    index: Option<PathBuf>,
+    export: bool,
+    identity: Option<Arc<IdentityFn>>,
+    options: WebSocketOptions,
+    sessions: Arc<SessionTracker>,
+    shutdown: watch::Sender<bool>,
+}
+
+impl PerspectiveApp {
+    /// Create a new [`PerspectiveApp`] for `server`, with the WebSocket
+    /// endpoint at `/ws`, no status endpoint and no static assets.
+    pub fn new(server: Server) -> Self {
+        Self {
+            server,
+            websocket_path: "/ws".to_owned(),
+            status_path: None,
+            assets: None,
+            index: None,
+            export: false,
+            identity: None,
+            options: WebSocketOptions::default(),
+            sessions: Arc::default(),
+            shutdown: watch::Sender::new(false),
+        }
+    }
+
+    /// Set the path of the WebSocket endpoint.
+    pub fn with_websocket_path(mut self, path: impl Into<String>) -> Self {
+        self.websocket_path = path.into();
+        self
+    }
+
+    /// Serve the JSON status endpoint at `path`, e.g. `"/status"`. Its table
+    /// list is subject to the [`Server`]'s authorization policy for the
+    /// request's [`SessionIdentity`], but it lists the address of every open
+    /// connection, so it should not be exposed to untrusted callers.
+    pub fn with_status_path(mut self, path: impl Into<String>) -> Self {
+        self.status_path = Some(path.into());
+        self
+    }
+
+    /// Derive the [`SessionIdentity`] of each WebSocket session, status and
+    /// export request from its HTTP request (e.g. a cookie or bearer token
+    /// header), for the [`Server`]'s authorization policy. Without this,
+    /// every session uses the anonymous default identity.
+    pub fn with_identity<F>(mut self, identity: F) -> Self
+    where
+        F: Fn(&Parts) -> SessionIdentity + Send + Sync + 'static,
+    {
+        self.identity = Some(Arc::new(identity));
+        self
+    }
+
+    /// Serve static files (e.g. the viewer's HTML, JavaScript and
+    /// WebAssembly assets) from `dir` for all paths which do not match
+    /// another route. Directories serve their `index.html`.
+    pub fn with_assets(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.assets = Some(dir.into());
+        self
+    }
+
+    /// Serve `file` at `/`, instead of the assets directory's `index.html`.
+    pub fn with_index(mut self, file: impl Into<PathBuf>) -> Self {
+        self.index = Some(file.into());
+        self
+    }
+
+    /// Set the [`WebSocketOptions`] of every connection.
+    pub fn with_websocket_options(mut self, options: WebSocketOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Metrics for each open WebSocket connection, by client address.
+    pub fn connections(&self) -> Vec<(SocketAddr, ConnectionStats)> {
+        self.sessions.stats()
+    }
+
+    /// Mount the HTTP export routes of [`export_router`], for clients which
+    /// cannot speak the Perspective WebSocket protocol.
+    pub fn with_export_routes(mut self) -> Self {
+        self.export = true;
+        self
+    }
+
+    /// Build the [`Router`] for this app, for composition with other routes.
+    ///
+    /// The WebSocket endpoint requires [`ConnectInfo`], so the resulting
+    /// [`Router`] must be served via
+    /// [`Router::into_make_service_with_connect_info`]. Sessions opened by
+    /// this [`Router`] are closed by [`PerspectiveApp::shutdown`].
+    pub fn router(&self) -> Router {
+        let state = AppState {
+            server: self.server.clone(),
+            options: Arc::new(self.options.clone()),
+            sessions: self.sessions.clone(),
+            shutdown: self.shutdown.subscribe(),
+        };
+
+        let router = Router::new().route(&self.websocket_path, get(app_websocket_handler));
+        let router = match &self.status_path {
+            Some(path) => router.route(path, get(status_handler)),
+            None => router,
+        };
+
+        let router = if self.export {
+            router.merge(export_router())
+        } else {
+            router
+        };
+
+        let router = match &self.index {
+            Some(file) => router.route_service("/", ServeFile::new(file)),
+            None => router,
+        };
+
+        let router = match &self.assets {
+            Some(dir) => router.fallback_service(ServeDir::new(dir)),
+            None => router,
+        };
+
+        let router = match &self.identity {
+            Some(identity) => {
+                let identity = identity.clone();
+                router.layer(map_request(move |request: Request| {
+                    let identity = identity.clone();
+                    async move {
+                        let (mut parts, body) = request.into_parts();
+                        let session_identity = identity(&parts);
+                        parts.extensions.insert(session_identity);
+                        Request::from_parts(parts, body)
+                    }
+                }))
+            },
+            None => router,
+        };
+
+        router.with_state(state)
+    }
+
+    /// Signal every open WebSocket session to close, and wait until each
+    /// [`crate::server::LocalSession`] has been closed.
+    pub async fn shutdown(&self) {
+        self.shutdown.send_replace(true);
+        self.sessions.wait_for_idle().await;
+    }
+
+    /// Serve this app on `listener` until `signal` resolves, then shut
+    /// down gracefully: stop accepting connections, close every open
+    /// [`crate::server::LocalSession`] and wait for in-flight requests to
+    /// complete.
+    pub async fn serve<F>(self, listener: TcpListener, signal: F) -> std::io::Result<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let service = self
+            .router()
+            .into_make_service_with_connect_info::<SocketAddr>();
+
+        let shutdown = self.shutdown.clone();
+        tracing::info!("listening on {}", listener.local_addr()?);
+        axum::serve(listener, service)
+            .with_graceful_shutdown(async move {
+                signal.await;
+                tracing::info!("Shutting down");
+                shutdown.send_replace(true);
+            })
+            .await?;
+
+        self.shutdown().await;
+        Ok(())
+    }
+}
+
+async fn app_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    extensions: Extensions,
+) -> impl IntoResponse {
+    tracing::info!("{addr} Connected.");
+    let metrics = Arc::default();
+    let identity = request_identity(&extensions);
+    let guard = SessionGuard::new(&state.sessions, addr, Arc::clone(&metrics));
+    let ws = ws.max_message_size(state.options.max_message_size);
+    ws.on_upgrade(move |socket| async move {
+        let (server, options, shutdown) = (state.server, state.options, Some(state.shutdown));
+        handle_socket(socket, server, addr, identity, options, metrics, shutdown).await;
+        drop(guard);
+    })
+}
+
+/// Responds with the hosted tables the request's [`SessionIdentity`] may
+/// read and their sizes, and metrics for each open connection, e.g.
+///
+/// ```json
+/// {
+///     "sessions": 1,
+///     "connections": [{"addr": "127.0.0.1:52100", "messages_in": 12, ...}],
+///     "tables": [{"name": "trades", "size": 100, "columns": 4}]
+/// }
+/// ```
+async fn status_handler(
+    State(state): State<AppState>,
+    extensions: Extensions,
+) -> impl IntoResponse {
+    let client = state
+        .server
+        .new_local_client()
+        .with_identity(request_identity(&extensions));
+
+    let tables = table_status(&client).await;
+    client.close().await;
+    let connections = state
+        .sessions
+        .stats()
+        .into_iter()
+        .map(|(addr, x)| {
+            serde_json::json!({
+                "addr": addr.to_string(),
+                "messages_in": x.messages_in,
+                "messages_out": x.messages_out,
+                "bytes_in": x.bytes_in,
+                "bytes_out": x.bytes_out,
+                "coalesced": x.coalesced,
+                "queue_depth": x.queue_depth,
+                "max_queue_depth": x.max_queue_depth,
+            })
         })
+        .collect::<Vec<_>>();
+
+    match tables {
+        Ok(tables) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "sessions": connections.len(),
+                "connections": connections,
+                "tables": tables
+            })),
+        ),
+        Err(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": err.to_string() })),
+        ),
+    }
+}
+
+async fn table_status(
+    client: &crate::client::Client,
+) -> crate::client::utils::ClientResult<Vec<serde_json::Value>> {
+    let mut tables = vec![];
+    for name in client.get_hosted_table_names().await? {
+        let table = client.open_table(name.clone()).await?;
+        tables.push(serde_json::json!({
+            "name": name,
+            "size": table.size().await?,
+            "columns": table.columns().await?.len(),
+        }));
     }
 
-    get(websocket_handler_internal)
+    Ok(tables)
 }
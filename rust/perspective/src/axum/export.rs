@@ -0,0 +1,385 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! HTTP routes for reading a [`Server`]'s tables without the Perspective
+//! WebSocket protocol, e.g. from `curl` or BI tools.
+
+use axum::Router;
+use axum::body::{Body, Bytes};
+use axum::extract::{FromRef, Path, State};
+use axum::http::{Extensions, HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Json, Response};
+use axum::routing::{get, post};
+
+use super::request_identity;
+use crate::client::config::ViewConfigUpdate;
+use crate::client::utils::ClientResult;
+use crate::client::{ClientError, Table, View, ViewWindow};
+use crate::server::{LocalClient, Server};
+
+/// The number of rows serialized per chunk of a streaming query response.
+const EXPORT_CHUNK_ROWS: u32 = 10_000;
+
+/// The response formats of `POST /tables/{name}/query`, chosen by the
+/// request's `Accept` header.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ExportFormat {
+    Json,
+    Ndjson,
+    Csv,
+    Arrow,
+}
+
+impl ExportFormat {
+    /// The first supported media type of an `Accept` header, defaulting to
+    /// JSON when the header is missing.
+    fn from_accept(headers: &HeaderMap) -> Option<Self> {
+        let Some(accept) = headers.get(header::ACCEPT) else {
+            return Some(Self::Json);
+        };
+
+        let accept = accept.to_str().ok()?;
+        accept.split(',').find_map(|media| {
+            match media.split(';').next().unwrap_or_default().trim() {
+                "application/json" | "application/*" | "*/*" => Some(Self::Json),
+                "application/x-ndjson" | "application/ndjson" => Some(Self::Ndjson),
+                "text/csv" | "text/*" => Some(Self::Csv),
+                "application/vnd.apache.arrow.stream" => Some(Self::Arrow),
+                _ => None,
+            }
+        })
+    }
+
+    fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Ndjson => "application/x-ndjson",
+            Self::Csv => "text/csv",
+            Self::Arrow => "application/vnd.apache.arrow.stream",
+        }
+    }
+}
+
+/// An error response, rendered as a plain text body.
+struct ExportError(StatusCode, String);
+
+impl IntoResponse for ExportError {
+    fn into_response(self) -> Response {
+        (self.0, self.1).into_response()
+    }
+}
+
+impl From<ClientError> for ExportError {
+    fn from(err: ClientError) -> Self {
+        match err {
+            ClientError::PermissionDenied(_) => Self(StatusCode::FORBIDDEN, err.to_string()),
+            err => Self(StatusCode::BAD_REQUEST, err.to_string()),
+        }
+    }
+}
+
+/// Routes for exporting a [`Server`]'s tables over plain HTTP:
+///
+/// - `GET /tables` lists the hosted table names as a JSON array.
+/// - `GET /tables/{name}/schema` returns the table's schema as a JSON object.
+/// - `POST /tables/{name}/query` creates a temporary [`View`] from a
+///   [`ViewConfigUpdate`] JSON body and streams its contents as JSON, NDJSON,
+///   CSV or Arrow IPC according to the `Accept` header.
+///
+/// Each request uses its own [`LocalClient`], authorized as the request's
+/// [`crate::server::SessionIdentity`] extension (see
+/// [`super::PerspectiveApp::with_identity`]). Without one, requests use the
+/// anonymous default identity, so mount these routes behind an
+/// authentication middleware if the [`Server`] has an authorization policy.
+pub fn export_router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    Server: FromRef<S>,
+{
+    Router::new()
+        .route("/tables", get(list_tables))
+        .route("/tables/{name}/schema", get(table_schema))
+        .route("/tables/{name}/query", post(query_table))
+}
+
+/// A [`LocalClient`] for a single export request.
+fn request_client(server: &Server, extensions: &Extensions) -> LocalClient {
+    LocalClient::new(server).with_identity(request_identity(extensions))
+}
+
+async fn list_tables(
+    State(server): State<Server>,
+    extensions: Extensions,
+) -> Result<Response, ExportError> {
+    let client = request_client(&server, &extensions);
+    let names = client.get_hosted_table_names().await;
+    client.close().await;
+    Ok(Json(names?).into_response())
+}
+
+async fn table_schema(
+    State(server): State<Server>,
+    Path(name): Path<String>,
+    extensions: Extensions,
+) -> Result<Response, ExportError> {
+    let client = request_client(&server, &extensions);
+    let schema = async {
+        let table = open_table(&client, &name).await?;
+        Ok::<_, ExportError>(table.schema().await?)
+    }
+    .await;
+
+    client.close().await;
+    Ok(Json(schema?).into_response())
+}
+
+async fn query_table(
+    State(server): State<Server>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    extensions: Extensions,
+    Json(config): Json<ViewConfigUpdate>,
+) -> Result<Response, ExportError> {
+    let format = ExportFormat::from_accept(&headers).ok_or_else(|| {
+        ExportError(
+            StatusCode::NOT_ACCEPTABLE,
+            "Supported formats are application/json, application/x-ndjson, text/csv and \
+             application/vnd.apache.arrow.stream"
+                .to_owned(),
+        )
+    })?;
+
+    let client = request_client(&server, &extensions);
+    let view = async {
+        let table = open_table(&client, &name).await?;
+        let view = table.view(Some(config)).await?;
+        let num_rows = view.num_rows().await?;
+        Ok::<_, ExportError>((view, num_rows))
+    }
+    .await;
+
+    let (view, num_rows) = match view {
+        Ok(view) => view,
+        Err(err) => {
+            client.close().await;
+            return Err(err);
+        },
+    };
+
+    let export = Export {
+        client: Some(client),
+        view: Some(view),
+        format,
+        num_rows,
+        next_row: 0,
+        started: false,
+        wrote_rows: false,
+    };
+
+    let stream = futures::stream::unfold(export, |mut export| async move {
+        match export.next_chunk().await {
+            Ok(Some(chunk)) => Some((Ok(chunk), export)),
+            Ok(None) => {
+                export.close().await;
+                None
+            },
+            Err(err) => {
+                export.close().await;
+                Some((Err(err), export))
+            },
+        }
+    });
+
+    Ok((
+        [(header::CONTENT_TYPE, format.content_type())],
+        Body::from_stream(stream),
+    )
+        .into_response())
+}
+
+async fn open_table(client: &LocalClient, name: &str) -> Result<Table, ExportError> {
+    if !client
+        .get_hosted_table_names()
+        .await?
+        .iter()
+        .any(|x| x == name)
+    {
+        return Err(ExportError(
+            StatusCode::NOT_FOUND,
+            format!("No table named \"{name}\""),
+        ));
+    }
+
+    Ok(client.open_table(name.to_owned()).await?)
+}
+
+/// The state of a streaming query response. The [`View`] and [`LocalClient`]
+/// are released when the stream completes, or when the response is dropped
+/// early because the HTTP client disconnected.
+struct Export {
+    client: Option<LocalClient>,
+    view: Option<View>,
+    format: ExportFormat,
+    num_rows: u32,
+    next_row: u32,
+    started: bool,
+    wrote_rows: bool,
+}
+
+impl Export {
+    /// Serialize the next [`EXPORT_CHUNK_ROWS`] rows, such that the
+    /// concatenated chunks are a single valid document of this export's
+    /// format.
+    async fn next_chunk(&mut self) -> ClientResult<Option<Bytes>> {
+        let Some(view) = &self.view else {
+            return Ok(None);
+        };
+
+        if self.started && self.next_row >= self.num_rows {
+            return Ok(None);
+        }
+
+        let is_first = !self.started;
+        let start_row = self.next_row;
+        let end_row = (start_row + EXPORT_CHUNK_ROWS).min(self.num_rows);
+        let is_last = end_row >= self.num_rows;
+        self.started = true;
+        self.next_row = end_row;
+        let window = ViewWindow {
+            start_row: Some(start_row as f64),
+            end_row: Some(end_row as f64),
+            ..ViewWindow::default()
+        };
+
+        let chunk = match self.format {
+            ExportFormat::Csv => {
+                let csv = view.to_csv(window).await?;
+                if is_first {
+                    Bytes::from(csv)
+                } else {
+                    let rows = csv.split_once('\n').map(|x| x.1).unwrap_or_default();
+                    Bytes::copy_from_slice(rows.as_bytes())
+                }
+            },
+            ExportFormat::Ndjson => {
+                let mut ndjson = view.to_ndjson(window).await?;
+                if !ndjson.is_empty() && !ndjson.ends_with('\n') {
+                    ndjson.push('\n');
+                }
+
+                Bytes::from(ndjson)
+            },
+            ExportFormat::Json => {
+                let json = view.to_json_string(window).await?;
+                let rows = json
+                    .trim()
+                    .strip_prefix('[')
+                    .and_then(|x| x.strip_suffix(']'))
+                    .unwrap_or_default()
+                    .trim();
+
+                let mut chunk = String::with_capacity(rows.len() + 2);
+                if is_first {
+                    chunk.push('[');
+                }
+
+                if !rows.is_empty() {
+                    if self.wrote_rows {
+                        chunk.push(',');
+                    }
+
+                    chunk.push_str(rows);
+                    self.wrote_rows = true;
+                }
+
+                if is_last {
+                    chunk.push(']');
+                }
+
+                Bytes::from(chunk)
+            },
+            ExportFormat::Arrow => {
+                let arrow = view.to_arrow(window).await?;
+                let (schema_len, eos_len) = arrow_stream_frame(&arrow);
+                let start = if is_first { 0 } else { schema_len };
+                let end = if is_last {
+                    arrow.len()
+                } else {
+                    arrow.len() - eos_len
+                };
+
+                arrow.slice(start.min(end)..end)
+            },
+        };
+
+        Ok(Some(chunk))
+    }
+
+    async fn close(&mut self) {
+        if let Some(view) = self.view.take()
+            && let Err(err) = view.delete().await
+        {
+            tracing::warn!("Failed to delete export view: {err}");
+        }
+
+        if let Some(client) = self.client.take() {
+            client.close().await;
+        }
+    }
+}
+
+impl Drop for Export {
+    fn drop(&mut self) {
+        if (self.view.is_some() || self.client.is_some())
+            && let Ok(handle) = tokio::runtime::Handle::try_current()
+        {
+            let view = self.view.take();
+            let client = self.client.take();
+            handle.spawn(async move {
+                if let Some(view) = view {
+                    let _ = view.delete().await;
+                }
+
+                if let Some(client) = client {
+                    client.close().await;
+                }
+            });
+        }
+    }
+}
+
+/// The byte lengths of the leading schema message and the trailing
+/// end-of-stream marker of an Arrow IPC stream, so that consecutive streams
+/// with the same schema can be concatenated into one.
+fn arrow_stream_frame(bytes: &[u8]) -> (usize, usize) {
+    let read_i32 = |at: usize| {
+        bytes
+            .get(at..at + 4)
+            .map(|x| i32::from_le_bytes([x[0], x[1], x[2], x[3]]))
+    };
+
+    let schema_len = match read_i32(0) {
+        Some(-1) => 8 + read_i32(4).unwrap_or_default().max(0) as usize,
+        Some(len) => 4 + len.max(0) as usize,
+        None => 0,
+    };
+
+    let eos_len = if bytes.ends_with(&[0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0]) {
+        8
+    } else if bytes.ends_with(&[0, 0, 0, 0]) {
+        4
+    } else {
+        0
+    };
+
+    (schema_len.min(bytes.len()), eos_len.min(bytes.len()))
+}
@@ -0,0 +1,459 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! A command-line client for Perspective, which can convert between
+//! Perspective's supported formats, query a file with a [`ViewConfigUpdate`],
+//! or host files over a WebSocket.
+
+use std::io::{Read, Write};
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use perspective::axum::PerspectiveApp;
+use perspective::client::config::{Filter, Sort, ViewConfigUpdate};
+use perspective::client::{
+    ParquetWriteOptions, Table, TableInitOptions, TableReadFormat, UpdateData, View, ViewWindow,
+};
+use perspective::server::{LocalClient, Server};
+
+/// A local error synonym for this module only.
+type CliError = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Parser)]
+#[command(name = "perspective", version)]
+#[command(
+    about = "A command-line client for Perspective. Can convert between Perspective supported \
+             formats, query a file, or host files on a local web server."
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Convert a file into a new format. Reads from stdin if no file is
+    /// provided.
+    Convert {
+        file: Option<PathBuf>,
+
+        #[command(flatten)]
+        input: InputArgs,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+
+    /// Query a file with a view config, and print the result. Reads from
+    /// stdin if no file is provided.
+    Query {
+        file: Option<PathBuf>,
+
+        #[command(flatten)]
+        input: InputArgs,
+
+        #[command(flatten)]
+        query: QueryArgs,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+
+    /// Host files as tables (named by their file stem) over a WebSocket at
+    /// `/ws`. Reads a table named `stdin` from stdin if no file is provided.
+    Host {
+        files: Vec<PathBuf>,
+
+        #[command(flatten)]
+        input: InputArgs,
+
+        /// The address to listen on. Use `0.0.0.0` to accept connections
+        /// from other machines.
+        #[arg(long, default_value = "127.0.0.1")]
+        host: IpAddr,
+
+        /// The port to listen on.
+        #[arg(short, long, default_value_t = 8080)]
+        port: u16,
+
+        /// A directory of static assets (e.g. an HTML page embedding
+        /// `<perspective-viewer>`) to serve. No assets are served if omitted.
+        #[arg(short, long)]
+        assets: Option<PathBuf>,
+    },
+}
+
+#[derive(Args)]
+struct InputArgs {
+    /// The input format: csv, json, columns, arrow, ndjson or parquet.
+    /// Inferred from the file's extension or contents if omitted.
+    #[arg(short, long, value_parser = parse_read_format)]
+    input_format: Option<TableReadFormat>,
+
+    /// The column to use as the table's index.
+    #[arg(long)]
+    index: Option<String>,
+
+    /// Columns to read from a Parquet input, by default all of them.
+    #[arg(long = "parquet-column", value_name = "COLUMN")]
+    parquet_columns: Vec<String>,
+}
+
+#[derive(Args)]
+struct OutputArgs {
+    /// The output format [default: arrow for `convert`, csv for `query`].
+    #[arg(short, long, value_enum)]
+    format: Option<OutputFormat>,
+
+    /// The file to write to, instead of stdout.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct QueryArgs {
+    /// A `ViewConfigUpdate` as JSON, e.g. `{"group_by": ["Region"]}`. Other
+    /// query flags are applied on top of it.
+    #[arg(long, value_name = "JSON")]
+    config: Option<String>,
+
+    /// Columns to include, in order.
+    #[arg(long = "column", value_name = "COLUMN")]
+    columns: Vec<String>,
+
+    /// Group by a column.
+    #[arg(short, long, value_name = "COLUMN")]
+    group_by: Vec<String>,
+
+    /// Split by a column.
+    #[arg(short, long, value_name = "COLUMN")]
+    split_by: Vec<String>,
+
+    /// Filter by a column, e.g. `--filter Sales ">" 100`. The value is
+    /// parsed as JSON, or used as a string if that fails.
+    #[arg(long, num_args = 3, value_names = ["COLUMN", "OP", "VALUE"])]
+    filter: Vec<Vec<String>>,
+
+    /// Sort by a column, optionally followed by a direction (asc, desc, col
+    /// asc, col desc, asc abs, ...). Defaults to asc.
+    #[arg(long, num_args = 1..=2, value_names = ["COLUMN", "DIR"])]
+    sort: Vec<Vec<String>>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Arrow,
+    Csv,
+    Json,
+    Columns,
+    Ndjson,
+    Parquet,
+}
+
+fn parse_read_format(value: &str) -> Result<TableReadFormat, String> {
+    TableReadFormat::parse(Some(value.to_owned())).map(|x| x.unwrap())
+}
+
+impl QueryArgs {
+    fn view_config(&self) -> Result<ViewConfigUpdate, CliError> {
+        let mut config = match &self.config {
+            Some(json) => serde_json::from_str(json)?,
+            None => ViewConfigUpdate::default(),
+        };
+
+        if !self.columns.is_empty() {
+            config.columns = Some(self.columns.iter().cloned().map(Some).collect());
+        }
+
+        if !self.group_by.is_empty() {
+            config.group_by = Some(self.group_by.clone());
+        }
+
+        if !self.split_by.is_empty() {
+            config.split_by = Some(self.split_by.clone());
+        }
+
+        for filter in &self.filter {
+            let value = serde_json::from_str(&filter[2])
+                .unwrap_or_else(|_| serde_json::Value::String(filter[2].clone()));
+
+            let filter: Filter =
+                serde_json::from_value(serde_json::json!([filter[0], filter[1], value]))?;
+
+            config.filter.get_or_insert_default().push(filter);
+        }
+
+        for sort in &self.sort {
+            let dir = sort.get(1).map(String::as_str).unwrap_or("asc");
+            let sort: Sort = serde_json::from_value(serde_json::json!([sort[0], dir]))?;
+            config.sort.get_or_insert_default().push(sort);
+        }
+
+        Ok(config)
+    }
+}
+
+/// Read `file`, or stdin if there is no `file`.
+fn read_input(file: Option<&Path>) -> Result<Vec<u8>, CliError> {
+    let mut data = vec![];
+    match file {
+        Some(file) => data = std::fs::read(file)?,
+        None => {
+            std::io::stdin().lock().read_to_end(&mut data)?;
+        },
+    };
+
+    Ok(data)
+}
+
+/// Infer the format of `data` from the extension of `file`, falling back to
+/// sniffing its contents.
+fn infer_format(file: Option<&Path>, data: &[u8]) -> TableReadFormat {
+    let extension = file
+        .and_then(|x| x.extension())
+        .and_then(|x| x.to_str())
+        .map(|x| x.to_ascii_lowercase());
+
+    match extension.as_deref() {
+        Some("csv") => return TableReadFormat::Csv,
+        Some("arrow" | "feather" | "ipc") => return TableReadFormat::Arrow,
+        Some("ndjson" | "jsonl") => return TableReadFormat::Ndjson,
+        Some("parquet" | "pq") => return TableReadFormat::Parquet,
+        _ => {},
+    }
+
+    if data.starts_with(b"PAR1") {
+        return TableReadFormat::Parquet;
+    }
+
+    if data.starts_with(b"ARROW1") || data.starts_with(&[0xff, 0xff, 0xff, 0xff]) {
+        return TableReadFormat::Arrow;
+    }
+
+    let text = String::from_utf8_lossy(&data[..data.len().min(64 * 1024)]);
+    let text = text.trim_start();
+    if text.starts_with('[') {
+        TableReadFormat::JsonString
+    } else if text.starts_with('{') {
+        let mut lines = text.lines().filter(|x| !x.trim().is_empty());
+        let is_ndjson = lines
+            .next()
+            .is_some_and(|x| serde_json::from_str::<serde_json::Value>(x).is_ok())
+            && lines.next().is_some();
+
+        if is_ndjson {
+            TableReadFormat::Ndjson
+        } else {
+            TableReadFormat::ColumnsString
+        }
+    } else {
+        TableReadFormat::Csv
+    }
+}
+
+/// Create a [`Table`] named `name` from `file` (or stdin).
+async fn load_table(
+    client: &LocalClient,
+    file: Option<&Path>,
+    input: &InputArgs,
+    name: Option<String>,
+) -> Result<Table, CliError> {
+    let data = read_input(file)?;
+    let format = input
+        .input_format
+        .unwrap_or_else(|| infer_format(file, &data));
+
+    let data = match format {
+        TableReadFormat::Arrow => UpdateData::Arrow(data.into()),
+        TableReadFormat::Csv => UpdateData::Csv(String::from_utf8(data)?),
+        TableReadFormat::JsonString => UpdateData::JsonRows(String::from_utf8(data)?),
+        TableReadFormat::ColumnsString => UpdateData::JsonColumns(String::from_utf8(data)?),
+        TableReadFormat::Ndjson => UpdateData::Ndjson(String::from_utf8(data)?),
+        TableReadFormat::Parquet => UpdateData::Parquet {
+            data: data.into(),
+            columns: (!input.parquet_columns.is_empty()).then(|| input.parquet_columns.clone()),
+        },
+    };
+
+    let options = TableInitOptions {
+        name,
+        index: input.index.clone(),
+        ..TableInitOptions::default()
+    };
+
+    Ok(client.table(data.into(), options).await?)
+}
+
+/// Serialize `view` in `format`, to `output` or stdout.
+async fn write_output(
+    view: &View,
+    format: OutputFormat,
+    output: Option<&Path>,
+) -> Result<(), CliError> {
+    let window = ViewWindow::default();
+    let mut bytes: Vec<u8> = match format {
+        OutputFormat::Arrow => view.to_arrow(window).await?.to_vec(),
+        OutputFormat::Csv => view.to_csv(window).await?.into_bytes(),
+        OutputFormat::Json => view.to_json_string(window).await?.into_bytes(),
+        OutputFormat::Columns => view.to_columns_string(window).await?.into_bytes(),
+        OutputFormat::Ndjson => view.to_ndjson(window).await?.into_bytes(),
+        OutputFormat::Parquet => view
+            .to_parquet(window, ParquetWriteOptions::default())
+            .await?
+            .to_vec(),
+    };
+
+    match output {
+        Some(output) => std::fs::write(output, bytes)?,
+        None => {
+            if !matches!(format, OutputFormat::Arrow | OutputFormat::Parquet)
+                && !bytes.ends_with(b"\n")
+            {
+                bytes.push(b'\n');
+            }
+
+            let mut stdout = std::io::stdout().lock();
+            stdout.write_all(&bytes)?;
+            stdout.flush()?;
+        },
+    }
+
+    Ok(())
+}
+
+async fn query(
+    client: &LocalClient,
+    file: Option<&Path>,
+    input: &InputArgs,
+    config: Option<ViewConfigUpdate>,
+    output: &OutputArgs,
+    default_format: OutputFormat,
+) -> Result<(), CliError> {
+    let table = load_table(client, file, input, None).await?;
+    let view = table.view(config).await?;
+    let format = output.format.unwrap_or(default_format);
+    let result = write_output(&view, format, output.output.as_deref()).await;
+    view.delete().await?;
+    table.delete(Default::default()).await?;
+    result
+}
+
+async fn host(
+    files: &[PathBuf],
+    input: &InputArgs,
+    addr: SocketAddr,
+    assets: Option<&Path>,
+) -> Result<(), CliError> {
+    let server = Server::new(None);
+    let client = server.new_local_client();
+    let loaded = async {
+        if files.is_empty() {
+            load_table(&client, None, input, Some("stdin".to_owned())).await?;
+        }
+
+        for file in files {
+            let name = file
+                .file_stem()
+                .map(|x| x.to_string_lossy().into_owned())
+                .ok_or_else(|| format!("Invalid file name {}", file.display()))?;
+
+            load_table(&client, Some(file), input, Some(name)).await?;
+        }
+
+        Ok::<_, CliError>(client.get_hosted_table_names().await?)
+    }
+    .await;
+
+    client.close().await;
+    let tables = loaded?;
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    eprintln!("Hosting {} at ws://{addr}/ws", tables.join(", "));
+    let app = PerspectiveApp::new(server).with_export_routes();
+    let app = match assets {
+        Some(dir) => app.with_assets(dir),
+        None => app,
+    };
+
+    app.serve(listener, async {
+        let _ = tokio::signal::ctrl_c().await;
+    })
+    .await?;
+
+    Ok(())
+}
+
+async fn run(cli: Cli) -> Result<(), CliError> {
+    match cli.command {
+        Command::Host {
+            files,
+            input,
+            host: ip,
+            port,
+            assets,
+        } => host(&files, &input, (ip, port).into(), assets.as_deref()).await,
+        Command::Convert {
+            file,
+            input,
+            output,
+        } => {
+            let server = Server::new(None);
+            let client = server.new_local_client();
+            let result = query(
+                &client,
+                file.as_deref(),
+                &input,
+                None,
+                &output,
+                OutputFormat::Arrow,
+            )
+            .await;
+
+            client.close().await;
+            result
+        },
+        Command::Query {
+            file,
+            input,
+            query: args,
+            output,
+        } => {
+            let config = args.view_config()?;
+            let server = Server::new(None);
+            let client = server.new_local_client();
+            let result = query(
+                &client,
+                file.as_deref(),
+                &input,
+                Some(config),
+                &output,
+                OutputFormat::Csv,
+            )
+            .await;
+
+            client.close().await;
+            result
+        },
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        },
+    }
+}
@@ -58,6 +58,10 @@
 
 #[cfg(feature = "axum-ws")]
 pub mod axum;
+#[cfg(feature = "tokio")]
+pub mod poll;
+#[cfg(feature = "tokio")]
+pub mod transport;
 #[cfg(feature = "axum-ws")]
 pub mod virtual_server;
 
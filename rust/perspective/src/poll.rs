@@ -0,0 +1,87 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! A `tokio` implementation of [`PollRuntime`], for driving a [`Server`]'s
+//! polls in the background via a [`PollDriver`].
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//!
+//! use perspective::poll::new_server;
+//! use perspective::server::PollDriverOptions;
+//!
+//! # async fn example() {
+//! let (server, driver) = new_server(PollDriverOptions {
+//!     min_interval: Duration::from_millis(50),
+//!     max_interval: Some(Duration::from_secs(1)),
+//! });
+//!
+//! // ...
+//!
+//! println!("Mean poll latency {:?}", driver.stats().mean_latency());
+//! # }
+//! ```
+
+use std::time::{Duration, Instant};
+
+use futures::future::BoxFuture;
+use perspective_server::{PollDriver, PollDriverOptions, PollRuntime, Server};
+
+/// A [`PollRuntime`] which runs on a `tokio` runtime.
+#[derive(Clone, Debug)]
+pub struct TokioPollRuntime(tokio::runtime::Handle);
+
+impl TokioPollRuntime {
+    /// A [`TokioPollRuntime`] for the current `tokio` runtime.
+    ///
+    /// # Panics
+    ///
+    /// When called outside of a `tokio` runtime.
+    pub fn current() -> Self {
+        Self(tokio::runtime::Handle::current())
+    }
+}
+
+impl From<tokio::runtime::Handle> for TokioPollRuntime {
+    fn from(handle: tokio::runtime::Handle) -> Self {
+        Self(handle)
+    }
+}
+
+impl PollRuntime for TokioPollRuntime {
+    fn spawn(&self, task: BoxFuture<'static, ()>) {
+        self.0.spawn(task);
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+
+    fn now(&self) -> Instant {
+        tokio::time::Instant::now().into_std()
+    }
+}
+
+/// Create a [`Server`] whose polls are run in the background on the current
+/// `tokio` runtime, and the [`PollDriver`] which runs them. The [`Server`]
+/// measures time by the runtime's clock, so `tokio::time::pause` applies to
+/// its idle timeouts too.
+///
+/// # Panics
+///
+/// When called outside of a `tokio` runtime.
+pub fn new_server(options: PollDriverOptions) -> (Server, PollDriver) {
+    let runtime = TokioPollRuntime::current();
+    let driver = PollDriver::new(runtime.clone(), options);
+    let server = Server::new(Some(driver.on_poll_request())).with_clock(move || runtime.now());
+    (server, driver)
+}
@@ -0,0 +1,489 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Transport-agnostic adapters which connect a byte [`Stream`] and [`Sink`]
+//! pair to a [`Server`] session, a [`VirtualServer`] or a [`Client`].
+//!
+//! Each item of the [`Stream`] and [`Sink`] is a single encoded message.
+//! Message-oriented transports (e.g. WebSockets) can map their binary
+//! messages directly, while raw byte streams (e.g. a Unix domain socket or
+//! stdin/stdout) can be framed with [`length_delimited`]. Empty messages are
+//! heartbeats: they are never passed to the [`Server`], but they do reset
+//! [`TransportOptions::idle_timeout`]. [`connect_client`] echoes each
+//! heartbeat it receives, so idle clients stay connected.
+
+use std::future::Future;
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::channel::mpsc::unbounded;
+use futures::future::BoxFuture;
+use futures::{FutureExt, Sink, SinkExt, Stream, StreamExt};
+use prost::bytes::Bytes;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::time::Instant;
+
+use crate::client::utils::ClientResult;
+use crate::client::virtual_server::{VirtualServer, VirtualServerHandler};
+use crate::client::{Client, ClientError, Session};
+use crate::server::{LocalSession, Server, SessionIdentity};
+
+mod connection;
+
+pub(crate) use connection::ConnectionMetrics;
+pub use connection::ConnectionStats;
+use connection::{Outgoing, QueuedConnection};
+
+/// The default [`TransportOptions::max_message_size`], and the frame size
+/// limit of [`length_delimited`].
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+
+/// A local error synonym for this module only.
+pub type TransportError = Box<dyn std::error::Error + Send + Sync>;
+
+/// What to do when a connection's outgoing queue is full, e.g. because a
+/// slow client can't keep up with a frequently updated table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Replace a queued `on_update` notification with a newer one for the
+    /// same subscription. Notifications which carry a row delta can't be
+    /// coalesced without losing rows, so if nothing can be coalesced the
+    /// connection is closed as for [`OverflowPolicy::Disconnect`].
+    #[default]
+    Coalesce,
+
+    /// Close the connection with [`CloseReason::Overflowed`].
+    Disconnect,
+}
+
+/// Limits for each connection served by [`serve_session`].
+#[derive(Clone, Debug)]
+pub struct TransportOptions {
+    /// The maximum number of outgoing messages queued for a connection
+    /// before [`TransportOptions::overflow`] applies.
+    pub max_queued_messages: usize,
+
+    /// What to do when the outgoing queue is full.
+    pub overflow: OverflowPolicy,
+
+    /// The maximum size in bytes of an incoming message. Larger messages
+    /// close the connection with [`CloseReason::MessageTooBig`].
+    pub max_message_size: usize,
+
+    /// How often to send a heartbeat (an empty message) to the client.
+    pub heartbeat_interval: Option<Duration>,
+
+    /// Close connections which have sent nothing (including heartbeats) for
+    /// this long, with [`CloseReason::IdleTimeout`].
+    pub idle_timeout: Option<Duration>,
+}
+
+impl Default for TransportOptions {
+    fn default() -> Self {
+        Self {
+            max_queued_messages: 1024,
+            overflow: OverflowPolicy::default(),
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            heartbeat_interval: Some(Duration::from_secs(30)),
+            idle_timeout: Some(Duration::from_secs(90)),
+        }
+    }
+}
+
+/// Why [`serve_session`] stopped processing messages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CloseReason {
+    /// The incoming [`Stream`] ended.
+    Disconnected,
+
+    /// An incoming message exceeded [`TransportOptions::max_message_size`].
+    MessageTooBig,
+
+    /// The outgoing queue exceeded [`TransportOptions::max_queued_messages`].
+    Overflowed,
+
+    /// Nothing was received for [`TransportOptions::idle_timeout`].
+    IdleTimeout,
+
+    /// The server is shutting down.
+    Shutdown,
+}
+
+/// Serve a new [`Server`] session over `incoming` and `outgoing` until
+/// `incoming` ends or a [`TransportOptions`] limit is exceeded, then close
+/// the session.
+///
+/// # Examples
+///
+/// Serving a session over a Unix domain socket:
+///
+/// ```rust,ignore
+/// let (stream, _) = listener.accept().await?;
+/// let (reader, writer) = stream.into_split();
+/// let (incoming, outgoing) = length_delimited(reader, writer);
+/// serve_session(&server, incoming, outgoing, &TransportOptions::default()).await?;
+/// ```
+pub async fn serve_session<St, Si>(
+    server: &Server,
+    incoming: St,
+    outgoing: Si,
+    options: &TransportOptions,
+) -> Result<CloseReason, TransportError>
+where
+    St: Stream<Item = Bytes>,
+    Si: Sink<Bytes>,
+    Si::Error: Into<TransportError>,
+{
+    let metrics = Arc::default();
+    run_session(
+        server,
+        incoming,
+        outgoing,
+        options,
+        SessionIdentity::default(),
+        metrics,
+        futures::future::pending(),
+    )
+    .await
+}
+
+/// [`serve_session`] as `identity`, with [`ConnectionMetrics`] and a
+/// `shutdown` signal.
+pub(crate) async fn run_session<St, Si>(
+    server: &Server,
+    incoming: St,
+    outgoing: Si,
+    options: &TransportOptions,
+    identity: SessionIdentity,
+    metrics: Arc<ConnectionMetrics>,
+    shutdown: impl Future<Output = ()>,
+) -> Result<CloseReason, TransportError>
+where
+    St: Stream<Item = Bytes>,
+    Si: Sink<Bytes>,
+    Si::Error: Into<TransportError>,
+{
+    let connection = QueuedConnection::new(options, metrics.clone());
+    let mut session = server
+        .new_session(connection.clone())
+        .await
+        .with_identity(identity);
+    let result = process_message_loop(
+        incoming,
+        outgoing,
+        &connection,
+        &mut session,
+        options,
+        &metrics,
+        shutdown,
+    )
+    .await;
+
+    session.close().await;
+    result
+}
+
+/// The events a connection's message loop waits on.
+enum TransportEvent {
+    Incoming(Option<Bytes>),
+    Queued,
+    Tick,
+    Shutdown,
+}
+
+/// The inner message loop handles the full-duplex stream of messages
+/// between the [`Client`] and [`Session`]. When this function returns,
+/// messages are no longer processed.
+async fn process_message_loop<St, Si>(
+    incoming: St,
+    outgoing: Si,
+    connection: &QueuedConnection,
+    session: &mut LocalSession,
+    options: &TransportOptions,
+    metrics: &ConnectionMetrics,
+    shutdown: impl Future<Output = ()>,
+) -> Result<CloseReason, TransportError>
+where
+    St: Stream<Item = Bytes>,
+    Si: Sink<Bytes>,
+    Si::Error: Into<TransportError>,
+{
+    use TransportEvent::*;
+
+    let mut incoming = std::pin::pin!(incoming);
+    let mut outgoing = std::pin::pin!(outgoing);
+    let mut shutdown = std::pin::pin!(shutdown);
+    let mut timer = options
+        .heartbeat_interval
+        .or(options.idle_timeout)
+        .map(|period| tokio::time::interval_at(Instant::now() + period, period));
+
+    let mut last_seen = Instant::now();
+    loop {
+        let event = tokio::select! {
+            msg = incoming.next() => Incoming(msg),
+            _ = connection.notified() => Queued,
+            _ = tick(&mut timer) => Tick,
+            _ = &mut shutdown => Shutdown,
+        };
+
+        match event {
+            Incoming(None) => return Ok(CloseReason::Disconnected),
+            Incoming(Some(bytes)) if bytes.is_empty() => last_seen = Instant::now(),
+            Incoming(Some(bytes)) => {
+                last_seen = Instant::now();
+                if bytes.len() > options.max_message_size {
+                    return Ok(CloseReason::MessageTooBig);
+                }
+
+                metrics.record_in(bytes.len());
+                session.handle_request(&bytes).await?;
+            },
+            Queued => loop {
+                match connection.pop() {
+                    Outgoing::Message(bytes) => {
+                        metrics.record_out(bytes.len());
+                        outgoing.send(bytes).await.map_err(Into::into)?;
+                    },
+                    Outgoing::Overflowed => return Ok(CloseReason::Overflowed),
+                    Outgoing::Empty => break,
+                }
+            },
+            Tick => {
+                if let Some(timeout) = options.idle_timeout
+                    && last_seen.elapsed() > timeout
+                {
+                    return Ok(CloseReason::IdleTimeout);
+                }
+
+                if options.heartbeat_interval.is_some() {
+                    outgoing.send(Bytes::new()).await.map_err(Into::into)?;
+                }
+            },
+            Shutdown => return Ok(CloseReason::Shutdown),
+        }
+    }
+}
+
+async fn tick(timer: &mut Option<tokio::time::Interval>) {
+    match timer {
+        Some(timer) => {
+            timer.tick().await;
+        },
+        None => futures::future::pending().await,
+    }
+}
+
+/// Serve a [`VirtualServer`] for `handler` over `incoming` and `outgoing`,
+/// until `incoming` ends.
+pub async fn serve_virtual_server<St, Si, H>(
+    handler: H,
+    incoming: St,
+    outgoing: Si,
+) -> Result<(), TransportError>
+where
+    St: Stream<Item = Bytes>,
+    Si: Sink<Bytes>,
+    Si::Error: Into<TransportError>,
+    H: VirtualServerHandler,
+{
+    let mut incoming = std::pin::pin!(incoming);
+    let mut outgoing = std::pin::pin!(outgoing);
+    let mut processor = VirtualServer::new(handler);
+    while let Some(msg) = incoming.next().await {
+        if !msg.is_empty() {
+            let resp = processor.handle_request(msg).await?;
+            outgoing.send(resp).await.map_err(Into::into)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Create a [`Client`] which sends its requests to `outgoing` and receives
+/// responses from `incoming`.
+///
+/// The returned future drives the connection and must be polled (e.g.
+/// spawned) for the [`Client`] to make progress. It resolves when
+/// `incoming` ends, after which the [`Client`]'s pending requests and
+/// `on_error` callbacks are notified of the disconnection. Heartbeats from
+/// the server are echoed back, so a [`Client`] which only receives (e.g.
+/// `on_update` callbacks) is not closed by the server's
+/// [`TransportOptions::idle_timeout`].
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let stream = tokio::net::UnixStream::connect("/tmp/perspective.sock").await?;
+/// let (reader, writer) = stream.into_split();
+/// let (incoming, outgoing) = length_delimited(reader, writer);
+/// let (client, driver) = connect_client(incoming, outgoing)?;
+/// tokio::spawn(driver);
+/// let tables = client.get_hosted_table_names().await?;
+/// ```
+pub fn connect_client<St, Si>(
+    incoming: St,
+    outgoing: Si,
+) -> ClientResult<(Client, BoxFuture<'static, Result<(), TransportError>>)>
+where
+    St: Stream<Item = Bytes> + Send + 'static,
+    Si: Sink<Bytes> + Send + 'static,
+    Si::Error: Into<TransportError>,
+{
+    let (send, mut requests) = unbounded::<Bytes>();
+    let client = Client::new_with_callback(None, move |req: Vec<u8>| {
+        let result = send.unbounded_send(req.into());
+        async move { Ok::<_, TransportError>(result?) }
+    })?;
+
+    let driver = {
+        let client = client.clone();
+        async move {
+            let mut incoming = std::pin::pin!(incoming);
+            let mut outgoing = std::pin::pin!(outgoing);
+            let result: Result<(), TransportError> = loop {
+                tokio::select! {
+                    msg = incoming.next() => match msg {
+                        Some(msg) if msg.is_empty() => {
+                            if let Err(err) = outgoing.send(msg).await {
+                                break Err(err.into());
+                            }
+                        },
+                        Some(msg) => {
+                            if let Err(err) = client.handle_response(&msg).await {
+                                break Err(err.into());
+                            }
+                        },
+                        None => break Ok(()),
+                    },
+                    Some(req) = requests.next() => {
+                        if let Err(err) = outgoing.send(req).await {
+                            break Err(err.into());
+                        }
+                    },
+                }
+            };
+
+            let message = match &result {
+                Ok(()) => "Disconnected".to_owned(),
+                Err(err) => err.to_string(),
+            };
+
+            let reconnect = None::<fn() -> futures::future::Ready<ClientResult<()>>>;
+            if let Err(err) = client
+                .handle_error(ClientError::TransportError(message), reconnect)
+                .await
+            {
+                tracing::error!("Error handling disconnect {}", err);
+            }
+
+            result
+        }
+    };
+
+    Ok((client, driver.boxed()))
+}
+
+/// Frame a raw byte stream as messages, each prefixed with its length as a
+/// big-endian `u32` (compatible with the defaults of `tokio-util`'s
+/// `LengthDelimitedCodec`).
+///
+/// The returned [`Stream`] ends when `reader` is closed or errors, or when a
+/// frame's length exceeds [`DEFAULT_MAX_MESSAGE_SIZE`] (see
+/// [`length_delimited_with_limit`]).
+pub fn length_delimited<R, W>(
+    reader: R,
+    writer: W,
+) -> (
+    impl Stream<Item = Bytes> + Send + 'static,
+    impl Sink<Bytes, Error = io::Error> + Send + 'static,
+)
+where
+    R: AsyncRead + Unpin + Send + 'static,
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    length_delimited_with_limit(reader, writer, DEFAULT_MAX_MESSAGE_SIZE)
+}
+
+/// Like [`length_delimited`], ending the returned [`Stream`] when an incoming
+/// frame's length prefix exceeds `max_frame_size`, before any of the frame is
+/// read. This should match the [`TransportOptions::max_message_size`] of the
+/// session it serves.
+pub fn length_delimited_with_limit<R, W>(
+    reader: R,
+    writer: W,
+    max_frame_size: usize,
+) -> (
+    impl Stream<Item = Bytes> + Send + 'static,
+    impl Sink<Bytes, Error = io::Error> + Send + 'static,
+)
+where
+    R: AsyncRead + Unpin + Send + 'static,
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    let incoming = futures::stream::unfold(reader, |mut reader| async move {
+        match read_frame(&mut reader, max_frame_size).await {
+            Ok(Some(frame)) => Some((frame, reader)),
+            Ok(None) => None,
+            Err(err) => {
+                tracing::error!("Transport error {}", err);
+                None
+            },
+        }
+    });
+
+    let outgoing = futures::sink::unfold(writer, |mut writer, frame: Bytes| async move {
+        let len = u32::try_from(frame.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Frame too large"))?;
+
+        writer.write_u32(len).await?;
+        writer.write_all(&frame).await?;
+        writer.flush().await?;
+        Ok::<_, io::Error>(writer)
+    });
+
+    (incoming, outgoing)
+}
+
+/// Read a single length-prefixed frame of at most `max_len` bytes, or `None`
+/// if `reader` is closed between frames.
+async fn read_frame<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    max_len: usize,
+) -> io::Result<Option<Bytes>> {
+    let len = match reader.read_u32().await {
+        Ok(len) => len,
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err),
+    };
+
+    if len as usize > max_len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Frame of {len} bytes exceeds the {max_len} byte limit"),
+        ));
+    }
+
+    // Read incrementally rather than pre-allocating `len` bytes, so a bogus
+    // length can't allocate more than was actually sent.
+    let mut frame = Vec::new();
+    (&mut *reader)
+        .take(len as u64)
+        .read_to_end(&mut frame)
+        .await?;
+    if frame.len() != len as usize {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    Ok(Some(frame.into()))
+}
@@ -0,0 +1,267 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Bounded outgoing queues and metrics for a single connection.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use axum::body::Bytes;
+use prost::Message;
+use tokio::sync::Notify;
+
+use super::{OverflowPolicy, TransportError, TransportOptions};
+use crate::proto::response::ClientResp;
+use crate::proto::{Response, ViewOnUpdateResp};
+use crate::server::SessionHandler;
+
+/// A snapshot of a connection's metrics.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConnectionStats {
+    pub messages_in: u64,
+    pub messages_out: u64,
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+
+    /// `on_update` notifications replaced by newer ones while queued.
+    pub coalesced: u64,
+
+    /// The current length of the outgoing queue.
+    pub queue_depth: usize,
+
+    /// The longest the outgoing queue has been.
+    pub max_queue_depth: usize,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ConnectionMetrics {
+    messages_in: AtomicU64,
+    messages_out: AtomicU64,
+    bytes_in: AtomicU64,
+    bytes_out: AtomicU64,
+    coalesced: AtomicU64,
+    queue_depth: AtomicUsize,
+    max_queue_depth: AtomicUsize,
+}
+
+impl ConnectionMetrics {
+    pub(crate) fn record_in(&self, len: usize) {
+        self.messages_in.fetch_add(1, Ordering::Relaxed);
+        self.bytes_in.fetch_add(len as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_out(&self, len: usize) {
+        self.messages_out.fetch_add(1, Ordering::Relaxed);
+        self.bytes_out.fetch_add(len as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn stats(&self) -> ConnectionStats {
+        ConnectionStats {
+            messages_in: self.messages_in.load(Ordering::Relaxed),
+            messages_out: self.messages_out.load(Ordering::Relaxed),
+            bytes_in: self.bytes_in.load(Ordering::Relaxed),
+            bytes_out: self.bytes_out.load(Ordering::Relaxed),
+            coalesced: self.coalesced.load(Ordering::Relaxed),
+            queue_depth: self.queue_depth.load(Ordering::Relaxed),
+            max_queue_depth: self.max_queue_depth.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// The next item of a connection's outgoing queue.
+pub(super) enum Outgoing {
+    Message(Bytes),
+    Overflowed,
+    Empty,
+}
+
+#[derive(Default)]
+struct OutgoingQueue {
+    messages: VecDeque<Bytes>,
+    overflowed: bool,
+}
+
+struct ConnectionState {
+    queue: Mutex<OutgoingQueue>,
+    notify: Notify,
+    max_queued_messages: usize,
+    overflow: OverflowPolicy,
+    metrics: Arc<ConnectionMetrics>,
+}
+
+/// A connection's bounded outgoing queue, which a [`crate::server::Server`]
+/// session sends its responses to via [`SessionHandler`].
+#[derive(Clone)]
+pub(super) struct QueuedConnection(Arc<ConnectionState>);
+
+impl QueuedConnection {
+    pub(super) fn new(options: &TransportOptions, metrics: Arc<ConnectionMetrics>) -> Self {
+        Self(Arc::new(ConnectionState {
+            queue: Mutex::default(),
+            notify: Notify::new(),
+            max_queued_messages: options.max_queued_messages,
+            overflow: options.overflow,
+            metrics,
+        }))
+    }
+
+    /// Resolves when a message has been queued (or the queue overflowed)
+    /// since the last call to [`QueuedConnection::pop`] returned
+    /// [`Outgoing::Empty`].
+    pub(super) async fn notified(&self) {
+        self.0.notify.notified().await
+    }
+
+    pub(super) fn pop(&self) -> Outgoing {
+        let mut queue = self.0.queue.lock().unwrap();
+        if queue.overflowed {
+            Outgoing::Overflowed
+        } else if let Some(bytes) = queue.messages.pop_front() {
+            let depth = queue.messages.len();
+            self.0.metrics.queue_depth.store(depth, Ordering::Relaxed);
+            Outgoing::Message(bytes)
+        } else {
+            Outgoing::Empty
+        }
+    }
+
+    fn push(&self, resp: &[u8]) {
+        let state = &self.0;
+        let mut queue = state.queue.lock().unwrap();
+        if queue.overflowed {
+            return;
+        }
+
+        if queue.messages.len() >= state.max_queued_messages {
+            if state.overflow == OverflowPolicy::Coalesce && coalesce(&mut queue.messages, resp) {
+                state.metrics.coalesced.fetch_add(1, Ordering::Relaxed);
+            } else {
+                queue.overflowed = true;
+                queue.messages.clear();
+            }
+        } else {
+            queue.messages.push_back(Bytes::copy_from_slice(resp));
+        }
+
+        let depth = queue.messages.len();
+        drop(queue);
+        state.metrics.queue_depth.store(depth, Ordering::Relaxed);
+        state
+            .metrics
+            .max_queue_depth
+            .fetch_max(depth, Ordering::Relaxed);
+
+        state.notify.notify_one();
+    }
+}
+
+/// The [`SessionHandler`] implementation provides a method for a
+/// [`crate::client::Session`] to send messages to this connection, which
+/// may (or may not) be solicited
+/// (e.g. within the async call stack of
+/// [`crate::client::Session::handle_request`]). Messages are queued rather
+/// than awaited, so a slow client can't stall the [`crate::server::Server`].
+impl SessionHandler for QueuedConnection {
+    async fn send_response<'a>(&'a mut self, resp: &'a [u8]) -> Result<(), TransportError> {
+        self.push(resp);
+        Ok(())
+    }
+}
+
+/// Replace the most recently queued `on_update` notification for the same
+/// subscription as `resp`, moving it to the back of the queue. Returns
+/// `false` if `resp` can't be coalesced.
+fn coalesce(messages: &mut VecDeque<Bytes>, resp: &[u8]) -> bool {
+    let Some(key) = on_update_key(resp) else {
+        return false;
+    };
+
+    let Some(idx) = messages
+        .iter()
+        .rposition(|queued| on_update_key(queued) == Some(key))
+    else {
+        return false;
+    };
+
+    messages.remove(idx);
+    messages.push_back(Bytes::copy_from_slice(resp));
+    true
+}
+
+/// The `msg_id` of an `on_update` notification without a row delta, which
+/// is superseded by any later notification for the same subscription.
+fn on_update_key(bytes: &[u8]) -> Option<u32> {
+    let resp = Response::decode(bytes).ok()?;
+    match resp.client_resp? {
+        ClientResp::ViewOnUpdateResp(ViewOnUpdateResp { delta: None, .. }) => Some(resp.msg_id),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn on_update(msg_id: u32, delta: Option<Vec<u8>>) -> Vec<u8> {
+        Response {
+            msg_id,
+            entity_id: "view".to_owned(),
+            client_resp: Some(ClientResp::ViewOnUpdateResp(ViewOnUpdateResp {
+                delta,
+                port_id: 0,
+            })),
+        }
+        .encode_to_vec()
+    }
+
+    fn connection(overflow: OverflowPolicy) -> QueuedConnection {
+        let options = TransportOptions {
+            max_queued_messages: 2,
+            overflow,
+            ..TransportOptions::default()
+        };
+
+        QueuedConnection::new(&options, Arc::default())
+    }
+
+    #[test]
+    fn test_coalesce_on_update_without_delta() {
+        let conn = connection(OverflowPolicy::Coalesce);
+        conn.push(&on_update(1, None));
+        conn.push(&on_update(2, None));
+        conn.push(&on_update(1, None));
+        assert!(matches!(conn.pop(), Outgoing::Message(x) if x == on_update(2, None)));
+        assert!(matches!(conn.pop(), Outgoing::Message(x) if x == on_update(1, None)));
+        assert!(matches!(conn.pop(), Outgoing::Empty));
+        assert_eq!(conn.0.metrics.stats().coalesced, 1);
+        assert_eq!(conn.0.metrics.stats().max_queue_depth, 2);
+    }
+
+    #[test]
+    fn test_overflow_with_delta() {
+        let conn = connection(OverflowPolicy::Coalesce);
+        conn.push(&on_update(1, Some(vec![0])));
+        conn.push(&on_update(1, Some(vec![1])));
+        conn.push(&on_update(1, Some(vec![2])));
+        assert!(matches!(conn.pop(), Outgoing::Overflowed));
+    }
+
+    #[test]
+    fn test_overflow_disconnect() {
+        let conn = connection(OverflowPolicy::Disconnect);
+        conn.push(&on_update(1, None));
+        conn.push(&on_update(1, None));
+        conn.push(&on_update(1, None));
+        assert!(matches!(conn.pop(), Outgoing::Overflowed));
+    }
+}
@@ -12,51 +12,25 @@
 
 use std::net::SocketAddr;
 
+use axum::body::Bytes;
 use axum::extract::connect_info::ConnectInfo;
-use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::ws::{Message, WebSocketUpgrade};
 use axum::routing::{MethodRouter, get};
-use perspective_client::virtual_server::{VirtualServer, VirtualServerHandler};
+use futures::{SinkExt, StreamExt};
+use perspective_client::virtual_server::VirtualServerHandler;
 
-/// A local error synonym for this module only.
-type PerspectiveWSError = Box<dyn std::error::Error + Send + Sync>;
+use crate::transport::serve_virtual_server;
 
 pub type PSPError = Box<dyn std::error::Error + Send + Sync>;
 
-/// The inner message loop handles the full-duplex stream of messages
-/// between the [`perspective::Client`] and [`Session`]. When this
-/// funciton returns, messages are no longer processed.
-async fn process_message_loop(
-    socket: &mut WebSocket,
-    handler: impl VirtualServerHandler,
-) -> Result<(), PerspectiveWSError> {
-    use Message::*;
-    let mut processor = VirtualServer::new(handler);
-    loop {
-        match socket.recv().await {
-            Some(Ok(Binary(msg))) => {
-                socket
-                    .send(Binary(processor.handle_request(msg).await?))
-                    .await?
-            },
-            Some(_) | None => {
-                tracing::debug!("Unexpected msg");
-                break;
-            },
-        };
-    }
-
-    Ok(())
-}
-
 /// This handler is responsible for the beginning-to-end lifecycle of a
 /// single WebSocket connection to an [`axum`] server.
 ///
 /// Messages will come in from the [`axum::extract::ws::WebSocket`] in binary
-/// form via [`Message::Binary`], where they'll be routed to
-/// [`perspective::Session::handle_request`]. The server may generate
-/// one or more responses, which it will then send back to
-/// the [`axum::extract::ws::WebSocket::send`] method via its
-/// [`SessionHandler`] impl.
+/// form via [`Message::Binary`], where they'll be routed to a
+/// [`perspective_client::virtual_server::VirtualServer`] for `handler` by
+/// [`serve_virtual_server`], which sends each response back over the
+/// [`axum::extract::ws::WebSocket`].
 pub fn custom_websocket_handler<S, T>(handler: T) -> MethodRouter<S>
 where
     T: VirtualServerHandler + Clone + Send + Sync + 'static,
@@ -66,8 +40,21 @@ where
                                             ConnectInfo(addr): ConnectInfo<SocketAddr>|
            -> axum::response::Response {
         tracing::info!("{addr} Connected.");
-        ws.on_upgrade(move |mut socket| async move {
-            if let Err(msg) = process_message_loop(&mut socket, handler).await {
+        ws.on_upgrade(move |socket| async move {
+            let (sink, stream) = socket.split();
+            let incoming = stream.map_while(|msg| match msg {
+                Ok(Message::Binary(bytes)) => Some(bytes),
+                _ => {
+                    tracing::debug!("Unexpected msg");
+                    None
+                },
+            });
+
+            let outgoing = sink.with(|bytes: Bytes| {
+                futures::future::ready(Ok::<_, axum::Error>(Message::Binary(bytes)))
+            });
+
+            if let Err(msg) = serve_virtual_server(handler, incoming, outgoing).await {
                 tracing::error!("Internal error {}", msg);
             }
 
@@ -0,0 +1,197 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#[cfg(feature = "axum-ws")]
+mod internal {
+    use std::error::Error;
+
+    use perspective_client::config::{Filter, FilterTerm, Scalar, ViewConfigUpdate};
+    use perspective_client::proto::Request;
+    use perspective_client::{
+        ClientError, JoinOptions, TableInitOptions, UnionOptions, UnnestOptions, UpdateData,
+        UpdateOptions,
+    };
+    use perspective_server::{
+        AuthorizationPolicy, LocalClient, Operation, Server, SessionIdentity,
+    };
+
+    /// Only `admin` may read `secret` or write anything, and non-admins only
+    /// see rows of `orders` in their own `region`.
+    struct TestPolicy;
+
+    impl AuthorizationPolicy for TestPolicy {
+        fn authorize(
+            &self,
+            session: &SessionIdentity,
+            operation: Operation,
+            table: &str,
+            _request: &Request,
+        ) -> Result<(), String> {
+            let is_admin = session.principal.as_deref() == Some("admin");
+            match (operation, table) {
+                _ if is_admin => Ok(()),
+                (Operation::Read, "secret") => Err("secret is admin-only".to_owned()),
+                (Operation::Write | Operation::Delete, _) => Err("read-only".to_owned()),
+                _ => Ok(()),
+            }
+        }
+
+        fn view_filters(&self, session: &SessionIdentity, table: &str) -> Vec<Filter> {
+            match (session.metadata.get("region"), table) {
+                (Some(region), "orders") => vec![Filter::new(
+                    "region",
+                    "==",
+                    FilterTerm::Scalar(Scalar::String(region.clone())),
+                )],
+                _ => vec![],
+            }
+        }
+    }
+
+    fn identity(principal: &str, region: Option<&str>) -> SessionIdentity {
+        SessionIdentity {
+            principal: Some(principal.to_owned()),
+            metadata: region
+                .map(|x| [("region".to_owned(), x.to_owned())].into())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn named(name: &str) -> TableInitOptions {
+        TableInitOptions {
+            name: Some(name.to_owned()),
+            ..TableInitOptions::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_denied_requests_return_errors() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None).with_authorization_policy(TestPolicy);
+        let admin = LocalClient::new(&server).with_identity(identity("admin", None));
+        let guest = LocalClient::new(&server).with_identity(identity("guest", None));
+        let secret = admin
+            .table(UpdateData::Csv("x\n1".to_owned()).into(), named("secret"))
+            .await?;
+
+        let public = admin
+            .table(UpdateData::Csv("x\n1".to_owned()).into(), named("public"))
+            .await?;
+
+        assert_eq!(guest.get_hosted_table_names().await?, vec!["public"]);
+        assert!(guest.open_table("secret".to_owned()).await.is_err());
+
+        let guest_public = guest.open_table("public".to_owned()).await?;
+        let result = guest_public
+            .update(UpdateData::Csv("x\n2".to_owned()), UpdateOptions::default())
+            .await;
+
+        assert!(matches!(result, Err(ClientError::PermissionDenied(_))));
+
+        // The session is still usable after a denied request.
+        assert_eq!(guest_public.size().await?, 1);
+
+        // `system_info` omits `View`s, and sessions, on unreadable tables.
+        let secret_view = secret.view(None).await?;
+        let guest_view = guest_public.view(None).await?;
+        let info = guest.system_info().await?;
+        assert!(info.views.iter().all(|x| x.table_id == "public"));
+        assert_eq!(info.sessions.len(), 1);
+        assert_eq!(info.sessions[0].num_views, 1);
+        guest_view.delete().await?;
+        secret_view.delete().await?;
+        secret.delete(Default::default()).await?;
+        public.delete(Default::default()).await?;
+        guest.close().await;
+        admin.close().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_view_filters_are_injected() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None).with_authorization_policy(TestPolicy);
+        let admin = LocalClient::new(&server).with_identity(identity("admin", None));
+        let guest = LocalClient::new(&server).with_identity(identity("guest", Some("EU")));
+        let orders = admin
+            .table(
+                UpdateData::Csv("region,qty\nEU,1\nUS,2\nEU,3".to_owned()).into(),
+                named("orders"),
+            )
+            .await?;
+
+        let guest_orders = guest.open_table("orders".to_owned()).await?;
+        let view = guest_orders
+            .view(Some(ViewConfigUpdate {
+                filter: Some(vec![Filter::new(
+                    "qty",
+                    ">",
+                    FilterTerm::Scalar(Scalar::Float(0.0)),
+                )]),
+                ..ViewConfigUpdate::default()
+            }))
+            .await?;
+
+        assert_eq!(view.num_rows().await?, 2);
+        view.delete().await?;
+        orders.delete(Default::default()).await?;
+        guest.close().await;
+        admin.close().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_view_filters_apply_to_derived_tables() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None).with_authorization_policy(TestPolicy);
+        let admin = LocalClient::new(&server).with_identity(identity("admin", None));
+        let guest = LocalClient::new(&server).with_identity(identity("guest", Some("EU")));
+        let orders = admin
+            .table(
+                UpdateData::Csv("region,qty\nEU,1\nUS,2\nEU,3".to_owned()).into(),
+                named("orders"),
+            )
+            .await?;
+
+        // An unfiltered copy of `orders` would bypass the `region` filter.
+        let result = guest
+            .union(vec!["orders".into()], UnionOptions::default())
+            .await;
+
+        assert!(matches!(result, Err(ClientError::PermissionDenied(_))));
+        let result = guest
+            .join(
+                "orders".into(),
+                "orders".into(),
+                "region",
+                JoinOptions::default(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(ClientError::PermissionDenied(_))));
+        let result = guest
+            .unnest("orders".into(), "region", UnnestOptions::default())
+            .await;
+
+        assert!(matches!(result, Err(ClientError::PermissionDenied(_))));
+        assert_eq!(admin.get_hosted_table_names().await?, vec!["orders"]);
+
+        // Sessions without row-level filters may still derive tables.
+        let union = admin
+            .union(vec!["orders".into()], UnionOptions::default())
+            .await?;
+
+        assert_eq!(union.size().await?, 3);
+        union.delete(Default::default()).await?;
+        orders.delete(Default::default()).await?;
+        guest.close().await;
+        admin.close().await;
+        Ok(())
+    }
+}
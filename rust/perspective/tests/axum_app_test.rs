@@ -0,0 +1,245 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#[cfg(feature = "axum-ws")]
+mod internal {
+    use std::error::Error;
+
+    use perspective::axum::PerspectiveApp;
+    use perspective_client::proto::Request;
+    use perspective_client::{TableInitOptions, UpdateData};
+    use perspective_server::{AuthorizationPolicy, Operation, Server, SessionIdentity};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    #[tokio::test]
+    async fn test_status_endpoint_and_graceful_shutdown() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = server.new_local_client();
+        let mut options = TableInitOptions::default();
+        options.set_name("trades");
+        client
+            .table(UpdateData::Csv("x,y\n1,a\n2,b".to_owned()).into(), options)
+            .await?;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let app = PerspectiveApp::new(server).with_status_path("/status");
+        let handle = tokio::spawn(app.serve(listener, async move {
+            let _ = stopped.await;
+        }));
+
+        let mut stream = TcpStream::connect(addr).await?;
+        stream
+            .write_all(b"GET /status HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await?;
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+        assert!(response.starts_with("HTTP/1.1 200"));
+        let body = response.split("\r\n\r\n").nth(1).unwrap_or_default();
+        let status: serde_json::Value = serde_json::from_str(body)?;
+        assert_eq!(
+            status,
+            serde_json::json!({
+                "sessions": 0,
+                "connections": [],
+                "tables": [{ "name": "trades", "size": 2, "columns": 2 }]
+            })
+        );
+
+        stop.send(()).unwrap();
+        handle.await??;
+        client.close().await;
+        Ok(())
+    }
+
+    /// Send an HTTP/1.0 request, so the response body is not chunked.
+    async fn request(
+        addr: std::net::SocketAddr,
+        method: &str,
+        path: &str,
+        accept: &str,
+        body: &str,
+    ) -> Result<(u16, String), Box<dyn Error>> {
+        let mut stream = TcpStream::connect(addr).await?;
+        let req = format!(
+            "{method} {path} HTTP/1.0\r\nAccept: {accept}\r\nContent-Type: \
+             application/json\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+
+        stream.write_all(req.as_bytes()).await?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+        let (head, body) = response.split_once("\r\n\r\n").unwrap_or_default();
+        let status = head.split(' ').nth(1).unwrap_or_default().parse()?;
+        Ok((status, body.to_owned()))
+    }
+
+    #[tokio::test]
+    async fn test_export_routes() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = server.new_local_client();
+        let mut options = TableInitOptions::default();
+        options.set_name("trades");
+        client
+            .table(UpdateData::Csv("x,y\n1,a\n2,b".to_owned()).into(), options)
+            .await?;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let app = PerspectiveApp::new(server).with_export_routes();
+        let handle = tokio::spawn(app.serve(listener, async move {
+            let _ = stopped.await;
+        }));
+
+        let (status, body) = request(addr, "GET", "/tables", "*/*", "").await?;
+        assert_eq!(status, 200);
+        assert_eq!(body, r#"["trades"]"#);
+
+        let (status, body) = request(addr, "GET", "/tables/trades/schema", "*/*", "").await?;
+        assert_eq!(status, 200);
+        let schema: serde_json::Value = serde_json::from_str(&body)?;
+        assert_eq!(schema, serde_json::json!({"x": "integer", "y": "string"}));
+
+        let query = r#"{"columns": ["x"]}"#;
+        let (status, body) = request(
+            addr,
+            "POST",
+            "/tables/trades/query",
+            "application/json",
+            query,
+        )
+        .await?;
+        assert_eq!(status, 200);
+        let rows: serde_json::Value = serde_json::from_str(&body)?;
+        assert_eq!(rows, serde_json::json!([{"x": 1}, {"x": 2}]));
+
+        let (status, body) = request(
+            addr,
+            "POST",
+            "/tables/trades/query",
+            "application/x-ndjson",
+            query,
+        )
+        .await?;
+        assert_eq!(status, 200);
+        assert_eq!(body.lines().count(), 2);
+
+        let (status, _) = request(addr, "POST", "/tables/trades/query", "image/png", query).await?;
+        assert_eq!(status, 406);
+
+        let (status, _) = request(addr, "POST", "/tables/missing/query", "*/*", query).await?;
+        assert_eq!(status, 404);
+
+        stop.send(()).unwrap();
+        handle.await??;
+        client.close().await;
+        Ok(())
+    }
+
+    /// Only `admin` may read `secret`.
+    struct AdminPolicy;
+
+    impl AuthorizationPolicy for AdminPolicy {
+        fn authorize(
+            &self,
+            session: &SessionIdentity,
+            operation: Operation,
+            table: &str,
+            _request: &Request,
+        ) -> Result<(), String> {
+            match (operation, table) {
+                (Operation::Read, "secret") if session.principal.as_deref() != Some("admin") => {
+                    Err("secret is admin-only".to_owned())
+                },
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// `GET` `path` with an `X-User` header, if `user` is set.
+    async fn get_as(
+        addr: std::net::SocketAddr,
+        path: &str,
+        user: Option<&str>,
+    ) -> Result<String, Box<dyn Error>> {
+        let mut stream = TcpStream::connect(addr).await?;
+        let user = user.map(|x| format!("X-User: {x}\r\n")).unwrap_or_default();
+        let req = format!("GET {path} HTTP/1.0\r\n{user}\r\n");
+        stream.write_all(req.as_bytes()).await?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+        let (_, body) = response.split_once("\r\n\r\n").unwrap_or_default();
+        Ok(body.to_owned())
+    }
+
+    #[tokio::test]
+    async fn test_routes_use_request_identity() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None).with_authorization_policy(AdminPolicy);
+        let client = server.new_local_client().with_identity(SessionIdentity {
+            principal: Some("admin".to_owned()),
+            ..SessionIdentity::default()
+        });
+
+        for name in ["public", "secret"] {
+            let mut options = TableInitOptions::default();
+            options.set_name(name);
+            client
+                .table(UpdateData::Csv("x\n1".to_owned()).into(), options)
+                .await?;
+        }
+
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let app = PerspectiveApp::new(server)
+            .with_export_routes()
+            .with_status_path("/status")
+            .with_identity(|parts| SessionIdentity {
+                principal: parts
+                    .headers
+                    .get("x-user")
+                    .and_then(|x| x.to_str().ok())
+                    .map(ToOwned::to_owned),
+                ..SessionIdentity::default()
+            });
+
+        let handle = tokio::spawn(app.serve(listener, async move {
+            let _ = stopped.await;
+        }));
+
+        assert_eq!(get_as(addr, "/tables", None).await?, r#"["public"]"#);
+        let tables: Vec<String> =
+            serde_json::from_str(&get_as(addr, "/tables", Some("admin")).await?)?;
+        assert_eq!(tables.len(), 2);
+
+        let status: serde_json::Value =
+            serde_json::from_str(&get_as(addr, "/status", None).await?)?;
+        assert_eq!(
+            status["tables"],
+            serde_json::json!([{ "name": "public", "size": 1, "columns": 1 }])
+        );
+
+        let status: serde_json::Value =
+            serde_json::from_str(&get_as(addr, "/status", Some("admin")).await?)?;
+        assert_eq!(status["tables"].as_array().map(Vec::len), Some(2));
+
+        stop.send(()).unwrap();
+        handle.await??;
+        client.close().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#[cfg(feature = "cli")]
+mod internal {
+    use std::error::Error;
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    /// Run the `perspective` binary with `args`, piping `stdin` to it.
+    fn perspective(args: &[&str], stdin: &str) -> Result<String, Box<dyn Error>> {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_perspective"))
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        child.stdin.take().unwrap().write_all(stdin.as_bytes())?;
+        let output = child.wait_with_output()?;
+        assert!(output.status.success());
+        Ok(String::from_utf8(output.stdout)?)
+    }
+
+    #[test]
+    fn test_convert_csv_to_json() -> Result<(), Box<dyn Error>> {
+        let json = perspective(&["convert", "--format", "json"], "x,y\n1,a\n2,b\n")?;
+        let json: serde_json::Value = serde_json::from_str(&json)?;
+        assert_eq!(
+            json,
+            serde_json::json!([{"x": 1, "y": "a"}, {"x": 2, "y": "b"}])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_with_flags() -> Result<(), Box<dyn Error>> {
+        let input =
+            "{\"x\": 1, \"y\": \"a\"}\n{\"x\": 2, \"y\": \"b\"}\n{\"x\": 3, \"y\": \"b\"}\n";
+        let columns = perspective(
+            &[
+                "query", "--format", "columns", "--column", "x", "--filter", "y", "==", "b",
+                "--sort", "x", "desc",
+            ],
+            input,
+        )?;
+
+        let columns: serde_json::Value = serde_json::from_str(&columns)?;
+        assert_eq!(columns, serde_json::json!({"x": [3, 2]}));
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#[cfg(feature = "tokio")]
+mod internal {
+    use std::collections::HashMap;
+    use std::error::Error;
+
+    use perspective_client::config::{ColumnType, ViewConfigUpdate};
+    use perspective_client::{TableData, TableInitOptions, UpdateData, ViewWindow};
+    use perspective_server::{LocalClient, Server};
+
+    #[tokio::test]
+    async fn test_logical_column_types_roundtrip() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = LocalClient::new(&server);
+        let schema = vec![
+            ("a".to_owned(), ColumnType::Int64),
+            ("c".to_owned(), ColumnType::Duration),
+            ("d".to_owned(), ColumnType::String),
+        ];
+
+        let table = client
+            .table(TableData::Schema(schema), TableInitOptions::default())
+            .await?;
+        table
+            .update(
+                UpdateData::JsonRows(
+                    r#"[{"a": 123, "c": 1500, "d": "x"},
+                        {"a": -4, "c": 250, "d": "y"}]"#
+                        .into(),
+                ),
+                Default::default(),
+            )
+            .await?;
+
+        let expected = HashMap::from([
+            ("a".to_owned(), ColumnType::Int64),
+            ("c".to_owned(), ColumnType::Duration),
+            ("d".to_owned(), ColumnType::String),
+        ]);
+
+        assert_eq!(table.schema().await?, expected);
+        let view = table.view(None).await?;
+        assert_eq!(view.schema().await?, expected);
+
+        // Arrow egress carries the int64 and duration types.
+        let arrow = view.to_arrow(ViewWindow::default()).await?;
+        let table2 = client
+            .table(UpdateData::Arrow(arrow).into(), TableInitOptions::default())
+            .await?;
+
+        assert_eq!(table2.schema().await?, expected);
+
+        let json = table2
+            .view(None)
+            .await?
+            .to_columns_string(ViewWindow::default())
+            .await?;
+
+        let columns: serde_json::Value = serde_json::from_str(&json)?;
+        assert_eq!(columns["a"], serde_json::json!([123, -4]));
+        assert_eq!(columns["c"], serde_json::json!([1500, 250]));
+
+        view.delete().await?;
+        client.close().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_logical_column_types_aggregates() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = LocalClient::new(&server);
+        let schema = vec![
+            ("c".to_owned(), ColumnType::Duration),
+            ("e".to_owned(), ColumnType::Duration),
+            ("d".to_owned(), ColumnType::String),
+        ];
+
+        let table = client
+            .table(TableData::Schema(schema), TableInitOptions::default())
+            .await?;
+
+        table
+            .update(
+                UpdateData::JsonRows(
+                    r#"[{"c": 1000, "e": 1, "d": "x"}, {"c": 500, "e": 2, "d": "x"}]"#.into(),
+                ),
+                Default::default(),
+            )
+            .await?;
+
+        let view = table
+            .view(Some(ViewConfigUpdate {
+                group_by: Some(vec!["d".to_owned()]),
+                columns: Some(vec![Some("c".to_owned()), Some("e".to_owned())]),
+                aggregates: Some(HashMap::from([
+                    ("c".to_owned(), "sum".into()),
+                    ("e".to_owned(), "count".into()),
+                ])),
+                ..ViewConfigUpdate::default()
+            }))
+            .await?;
+
+        // A sum of durations is a duration, a count of them is not.
+        let schema = view.schema().await?;
+        assert_eq!(schema["c"], ColumnType::Duration);
+        assert_eq!(schema["e"], ColumnType::Integer);
+
+        view.delete().await?;
+        client.close().await;
+        Ok(())
+    }
+}
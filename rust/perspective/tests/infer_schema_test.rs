@@ -0,0 +1,128 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#[cfg(feature = "tokio")]
+mod internal {
+    use std::collections::HashMap;
+    use std::error::Error;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use perspective_client::config::ColumnType;
+    use perspective_client::{InferSchemaOptions, TableInitOptions, UpdateData, ViewWindow};
+    use perspective_server::{LocalClient, Server, SessionLimits};
+
+    const CSV: &str = "id,date,x\n007,2024-01-01,1\n008,2024-01-02,2\n009,N/A,3\n";
+
+    #[tokio::test]
+    async fn test_infer_schema_reports_offending_values() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = LocalClient::new(&server);
+        let data = UpdateData::Csv(CSV.to_owned());
+        let inferred = client
+            .infer_schema(data, InferSchemaOptions::default())
+            .await?;
+
+        let names = inferred.schema.iter().map(|(x, _)| x.as_str());
+        assert_eq!(names.collect::<Vec<_>>(), vec!["id", "date", "x"]);
+
+        let date = &inferred.columns["date"];
+        assert_eq!(date.candidate, ColumnType::Date);
+        assert!(date.confidence < 1.0);
+        assert_eq!(date.offending_values, vec!["N/A"]);
+
+        let id = &inferred.columns["id"];
+        assert_eq!(id.candidate, ColumnType::String);
+
+        let x = &inferred.columns["x"];
+        assert_eq!(x.r#type, ColumnType::Integer);
+        assert_eq!(x.candidate, ColumnType::Integer);
+        assert_eq!(x.confidence, 1.0);
+        assert!(x.offending_values.is_empty());
+
+        let options = InferSchemaOptions {
+            column_types: Some(HashMap::from([("x".to_owned(), ColumnType::Float)])),
+            ..InferSchemaOptions::default()
+        };
+
+        let inferred = client
+            .infer_schema(UpdateData::Csv(CSV.to_owned()), options)
+            .await?;
+        assert_eq!(inferred.columns["x"].r#type, ColumnType::Float);
+        client.close().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_infer_schema_does_not_host_a_table() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None).with_session_limits(SessionLimits {
+            max_tables: Some(0),
+            ..SessionLimits::default()
+        });
+
+        let client = LocalClient::new(&server);
+        let updates = Arc::new(AtomicU32::new(0));
+        let count = updates.clone();
+        client
+            .on_hosted_tables_update(move || {
+                count.fetch_add(1, Ordering::SeqCst);
+                async {}
+            })
+            .await?;
+
+        let data = UpdateData::Csv(CSV.to_owned());
+        let inferred = client
+            .infer_schema(data, InferSchemaOptions::default())
+            .await?;
+
+        assert_eq!(inferred.schema.len(), 3);
+        assert!(client.get_hosted_table_names().await?.is_empty());
+        assert_eq!(updates.load(Ordering::SeqCst), 0);
+        client.close().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_table_column_types_and_date_formats() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = LocalClient::new(&server);
+        let options = TableInitOptions {
+            column_types: Some(HashMap::from([("id".to_owned(), ColumnType::String)])),
+            date_formats: Some(HashMap::from([("date".to_owned(), "%d/%m/%Y".to_owned())])),
+            ..TableInitOptions::default()
+        };
+
+        let data = UpdateData::Csv("id,date\n1,25/12/2024\n2,01/02/2025\n".to_owned());
+        let table = client.table(data.into(), options).await?;
+        let schema = table.schema().await?;
+        assert_eq!(schema["id"], ColumnType::String);
+        assert_eq!(schema["date"], ColumnType::Date);
+        let json = table
+            .view(None)
+            .await?
+            .to_columns_string(ViewWindow::default())
+            .await?;
+
+        assert!(json.starts_with(r#"{"id":["1","2"],"date":["#));
+
+        // Unknown `column_types` columns are an error.
+        let options = TableInitOptions {
+            column_types: Some(HashMap::from([("nope".to_owned(), ColumnType::String)])),
+            ..TableInitOptions::default()
+        };
+
+        let data = UpdateData::Csv("id\n1\n".to_owned());
+        assert!(client.table(data.into(), options).await.is_err());
+        client.close().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#[cfg(feature = "tokio")]
+mod internal {
+    use std::error::Error;
+
+    use perspective_client::config::{ColumnType, Filter, FilterTerm, Scalar, ViewConfigUpdate};
+    use perspective_client::{TableInitOptions, UnnestOptions, UpdateData, ViewWindow};
+    use perspective_server::{LocalClient, Server};
+
+    const ROWS: &str = r#"[
+        {"id": 1, "tags": ["a", "b"], "px": [1.5, 2.5]},
+        {"id": 2, "tags": ["b"], "px": []},
+        {"id": 3, "tags": null, "px": [3.5]}
+    ]"#;
+
+    fn filter(column: &str, op: &str, term: FilterTerm) -> Option<ViewConfigUpdate> {
+        Some(ViewConfigUpdate {
+            filter: Some(vec![Filter::new(column, op, term)]),
+            ..ViewConfigUpdate::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_list_columns_schema_filter_and_egress() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = LocalClient::new(&server);
+        let table = client
+            .table(
+                UpdateData::JsonRows(ROWS.into()).into(),
+                TableInitOptions::default(),
+            )
+            .await?;
+
+        let schema = table.schema().await?;
+        assert_eq!(schema["tags"], ColumnType::ListString);
+        assert_eq!(schema["px"], ColumnType::ListFloat);
+
+        let view = table
+            .view(filter(
+                "tags",
+                "contains",
+                FilterTerm::Scalar(Scalar::String("a".into())),
+            ))
+            .await?;
+
+        assert_eq!(view.num_rows().await?, 1);
+        view.delete().await?;
+
+        let view = table
+            .view(filter(
+                "tags",
+                "contains any",
+                FilterTerm::Array(vec![Scalar::String("a".into()), Scalar::String("b".into())]),
+            ))
+            .await?;
+
+        assert_eq!(view.num_rows().await?, 2);
+        view.delete().await?;
+
+        let view = table
+            .view(filter(
+                "px",
+                "contains",
+                FilterTerm::Scalar(Scalar::Float(2.5)),
+            ))
+            .await?;
+
+        assert_eq!(view.num_rows().await?, 1);
+        view.delete().await?;
+
+        // Lists are written as JSON arrays, not their stored text.
+        let view = table.view(None).await?;
+        let json = view.to_columns_string(ViewWindow::default()).await?;
+        let columns: serde_json::Value = serde_json::from_str(&json)?;
+        assert_eq!(
+            columns["tags"],
+            serde_json::json!([["a", "b"], ["b"], null])
+        );
+        assert_eq!(columns["px"], serde_json::json!([[1.5, 2.5], [], [3.5]]));
+
+        view.delete().await?;
+        client.close().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unnest_list_column() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = LocalClient::new(&server);
+        let table = client
+            .table(
+                UpdateData::JsonRows(ROWS.into()).into(),
+                TableInitOptions::default(),
+            )
+            .await?;
+
+        let tags = client
+            .unnest((&table).into(), "tags", UnnestOptions::default())
+            .await?;
+
+        // One row per element, and one null row for the null list.
+        let schema = tags.schema().await?;
+        assert_eq!(schema["tags"], ColumnType::String);
+        assert_eq!(schema["px"], ColumnType::ListFloat);
+        assert_eq!(tags.size().await?, 4);
+
+        let view = tags
+            .view(filter(
+                "tags",
+                "==",
+                FilterTerm::Scalar(Scalar::String("b".into())),
+            ))
+            .await?;
+
+        assert_eq!(view.num_rows().await?, 2);
+
+        // The unnest table is recomputed when its source is updated.
+        table
+            .update(
+                UpdateData::JsonRows(r#"[{"id": 4, "tags": ["b", "c"]}]"#.into()),
+                Default::default(),
+            )
+            .await?;
+
+        assert_eq!(tags.size().await?, 6);
+        assert_eq!(view.num_rows().await?, 3);
+        assert!(
+            client
+                .unnest((&table).into(), "id", UnnestOptions::default())
+                .await
+                .is_err()
+        );
+
+        view.delete().await?;
+        client.close().await;
+        Ok(())
+    }
+}
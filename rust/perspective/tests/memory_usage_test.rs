@@ -0,0 +1,110 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#[cfg(feature = "tokio")]
+mod internal {
+    use std::error::Error;
+
+    use perspective_client::config::ViewConfigUpdate;
+    use perspective_client::{TableInitOptions, UpdateData};
+    use perspective_server::{LocalClient, Server};
+
+    fn named(name: &str) -> TableInitOptions {
+        TableInitOptions {
+            name: Some(name.to_owned()),
+            ..TableInitOptions::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_system_info_memory_breakdown() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = LocalClient::new(&server);
+        let other = LocalClient::new(&server);
+        let csv = (0..1000)
+            .map(|x| format!("{},{}", x % 10, x))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let big = client
+            .table(UpdateData::Csv(format!("k,v\n{csv}")).into(), named("big"))
+            .await?;
+
+        let small = client
+            .table(
+                UpdateData::Csv("k,v\n1,2".to_owned()).into(),
+                named("small"),
+            )
+            .await?;
+
+        let grouped = big
+            .view(Some(ViewConfigUpdate {
+                group_by: Some(vec!["k".to_owned()]),
+                ..ViewConfigUpdate::default()
+            }))
+            .await?;
+
+        let other_big = other.open_table("big".to_owned()).await?;
+        let flat = other_big.view(None).await?;
+        let info = client.system_info().await?;
+
+        let big_usage = info.tables.iter().find(|x| x.table_id == "big").unwrap();
+        let small_usage = info.tables.iter().find(|x| x.table_id == "small").unwrap();
+        assert_eq!(big_usage.num_rows, 1000);
+        assert_eq!(big_usage.num_views, 2);
+        assert_eq!(small_usage.num_rows, 1);
+        assert_eq!(small_usage.num_views, 0);
+        assert!(big_usage.bytes > small_usage.bytes);
+
+        let grouped_usage = info
+            .views
+            .iter()
+            .find(|x| x.view_id == grouped.name)
+            .unwrap();
+
+        assert_eq!(grouped_usage.table_id, "big");
+        assert_eq!(grouped_usage.num_rows, 11);
+        assert!(grouped_usage.bytes > 0);
+
+        let flat_usage = info.views.iter().find(|x| x.view_id == flat.name).unwrap();
+        assert_eq!(flat_usage.num_rows, 1000);
+        assert_ne!(flat_usage.session_id, grouped_usage.session_id);
+
+        // Each session's views are attributed to it.
+        let current = info.sessions.iter().find(|x| x.is_current).unwrap();
+        assert_eq!(current.session_id, grouped_usage.session_id);
+        assert_eq!(current.num_views, 1);
+        assert_eq!(current.view_bytes, grouped_usage.bytes);
+
+        let other_session = info
+            .sessions
+            .iter()
+            .find(|x| x.session_id == flat_usage.session_id)
+            .unwrap();
+
+        assert!(!other_session.is_current);
+        assert_eq!(other_session.num_views, 1);
+
+        // Closed sessions and deleted views are no longer reported.
+        flat.delete().await?;
+        other.close().await;
+        let info = client.system_info().await?;
+        assert_eq!(info.views.len(), 1);
+        assert_eq!(info.sessions.len(), 1);
+
+        grouped.delete().await?;
+        small.delete(Default::default()).await?;
+        big.delete(Default::default()).await?;
+        client.close().await;
+        Ok(())
+    }
+}
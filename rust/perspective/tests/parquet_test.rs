@@ -0,0 +1,124 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#[cfg(all(feature = "parquet", feature = "tokio"))]
+mod internal {
+    use std::error::Error;
+
+    use perspective_client::{
+        ParquetCompression, ParquetWriteOptions, TableInitOptions, UpdateData, UpdateOptions,
+        ViewWindow,
+    };
+    use perspective_server::{LocalClient, Server};
+
+    #[tokio::test]
+    async fn test_parquet_roundtrip_with_projection() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = LocalClient::new(&server);
+        let csv = UpdateData::Csv("x,y,z\n1,a,true\n2,b,false\n3,c,true".to_owned());
+        let table = client
+            .table(csv.into(), TableInitOptions::default())
+            .await?;
+        let view = table.view(None).await?;
+        let options = ParquetWriteOptions {
+            compression: Some(ParquetCompression::Gzip),
+            row_group_size: Some(1),
+        };
+
+        let parquet = view.to_parquet(ViewWindow::default(), options).await?;
+        assert!(parquet.starts_with(b"PAR1"));
+
+        let data = UpdateData::Parquet {
+            data: parquet.clone(),
+            columns: Some(vec!["z".to_owned(), "x".to_owned()]),
+        };
+
+        let table2 = client
+            .table(data.into(), TableInitOptions::default())
+            .await?;
+        assert_eq!(table2.columns().await?, vec!["x", "z"]);
+        let json = table2
+            .view(None)
+            .await?
+            .to_columns_string(ViewWindow::default())
+            .await?;
+        assert_eq!(json, r#"{"x":[1,2,3],"z":[true,false,true]}"#);
+
+        // Every row group is streamed in as its own update.
+        let data = UpdateData::Parquet {
+            data: parquet,
+            columns: None,
+        };
+
+        table.update(data, UpdateOptions::default()).await?;
+        assert_eq!(table.size().await?, 6);
+        client.close().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_parquet_unknown_column() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = LocalClient::new(&server);
+        let csv = UpdateData::Csv("x\n1".to_owned());
+        let table = client
+            .table(csv.into(), TableInitOptions::default())
+            .await?;
+        let view = table.view(None).await?;
+        let parquet = view
+            .to_parquet(ViewWindow::default(), ParquetWriteOptions::default())
+            .await?;
+
+        let data = UpdateData::Parquet {
+            data: parquet,
+            columns: Some(vec!["y".to_owned()]),
+        };
+
+        assert!(
+            client
+                .table(data.into(), TableInitOptions::default())
+                .await
+                .is_err()
+        );
+        client.close().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_parquet_remove() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = LocalClient::new(&server);
+        let options = TableInitOptions {
+            index: Some("x".to_owned()),
+            ..TableInitOptions::default()
+        };
+
+        let csv = UpdateData::Csv("x,y\n1,a\n2,b\n3,c".to_owned());
+        let table = client.table(csv.into(), options).await?;
+        let view = table.view(None).await?;
+        let parquet = view
+            .to_parquet(ViewWindow::default(), ParquetWriteOptions::default())
+            .await?;
+
+        // Requests without a streaming path decode the whole file at once.
+        let data = UpdateData::Parquet {
+            data: parquet,
+            columns: Some(vec!["x".to_owned()]),
+        };
+
+        table.remove(data).await?;
+        assert_eq!(table.size().await?, 0);
+        view.delete().await?;
+        client.close().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#[cfg(feature = "tokio")]
+mod internal {
+    use std::error::Error;
+    use std::time::Duration;
+
+    use perspective::poll::new_server;
+    use perspective_client::{TableInitOptions, UpdateData, UpdateOptions};
+    use perspective_server::{LocalClient, PollDriverOptions};
+
+    #[tokio::test(start_paused = true)]
+    async fn test_poll_driver_coalesces_updates() -> Result<(), Box<dyn Error>> {
+        let (server, driver) = new_server(PollDriverOptions {
+            min_interval: Duration::from_millis(20),
+            ..PollDriverOptions::default()
+        });
+
+        let client = LocalClient::new(&server);
+        let table = client
+            .table(
+                UpdateData::Csv("x\n0".to_owned()).into(),
+                TableInitOptions::default(),
+            )
+            .await?;
+
+        for i in 1..100 {
+            table
+                .update(UpdateData::Csv(format!("x\n{i}")), UpdateOptions::default())
+                .await?;
+        }
+
+        // Time is paused, so this returns only once every poll scheduled within
+        // it has run.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(table.size().await?, 100);
+        let stats = driver.stats();
+        assert!(stats.polls > 0);
+        assert!(stats.polls < 100);
+        assert!(stats.coalesced > 0);
+        assert!(stats.max_latency >= stats.mean_latency());
+        table.delete(Default::default()).await?;
+        client.close().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#[cfg(feature = "axum-ws")]
+mod internal {
+    use std::error::Error;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    use perspective_client::proto::request::ClientReq;
+    use perspective_client::proto::{
+        MakeTableData, MakeTableReq, Request, TableSizeReq, make_table_data,
+    };
+    use perspective_client::{Session, SessionRecorder, read_recording};
+    use perspective_server::Server;
+    use prost::Message;
+
+    #[derive(Clone, Default)]
+    struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn requests() -> Vec<Request> {
+        let data = make_table_data::Data::FromCsv("x\n1\n2".to_owned());
+        vec![
+            Request {
+                msg_id: 1,
+                entity_id: "recorded".to_owned(),
+                client_req: Some(ClientReq::MakeTableReq(MakeTableReq {
+                    data: Some(MakeTableData { data: Some(data) }),
+                    options: None,
+                })),
+            },
+            Request {
+                msg_id: 2,
+                entity_id: "recorded".to_owned(),
+                client_req: Some(ClientReq::TableSizeReq(TableSizeReq {})),
+            },
+        ]
+    }
+
+    async fn record() -> Result<Vec<u8>, Box<dyn Error>> {
+        let buf = SharedBuf::default();
+        let recorder = SessionRecorder::new(buf.clone());
+        let server = Server::new(None);
+        let session = server
+            .new_session_with_callback(recorder.wrap_callback(|_| Box::pin(async { Ok(()) })))
+            .await;
+
+        let session = recorder.wrap(session);
+        for request in requests() {
+            session.handle_request(&request.encode_to_vec()).await?;
+        }
+
+        session.close().await;
+        Ok(buf.0.lock().unwrap().clone())
+    }
+
+    #[tokio::test]
+    async fn test_replay_matches_recording() -> Result<(), Box<dyn Error>> {
+        let recording = read_recording(record().await?.as_slice())?;
+        assert_eq!(recording.len(), 4);
+        let report = Server::new(None).replay(&recording).await?;
+        assert_eq!(report.requests, 2);
+        assert_eq!(report.responses, 2);
+        assert!(report.is_identical());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_replay_reports_diffs() -> Result<(), Box<dyn Error>> {
+        let recording = read_recording(record().await?.as_slice())?;
+        let server = Server::new(None);
+
+        // The recorded `MakeTableReq` fails if the table already exists.
+        server.replay(&recording).await?;
+        let report = server.replay(&recording).await?;
+        assert_eq!(report.diffs.len(), 1);
+        assert_eq!(report.diffs[0].msg_id, 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#[cfg(feature = "axum-ws")]
+mod internal {
+    use std::error::Error;
+
+    use perspective_client::config::ViewConfigUpdate;
+    use perspective_client::{TableInitOptions, UpdateData, UpdateOptions, ViewWindow};
+    use perspective_server::{LocalClient, Replica, ReplicaOptions, Server};
+
+    #[tokio::test]
+    async fn test_replica_mirrors_upstream_updates() -> Result<(), Box<dyn Error>> {
+        let upstream_server = Server::new(None);
+        let upstream = LocalClient::new(&upstream_server);
+        let options = TableInitOptions {
+            name: Some("quotes".to_owned()),
+            index: Some("sym".to_owned()),
+            ..TableInitOptions::default()
+        };
+
+        let quotes = upstream
+            .table(
+                UpdateData::Csv("sym,px\nA,1\nB,2".to_owned()).into(),
+                options,
+            )
+            .await?;
+
+        let server = Server::new(None);
+        let replica = Replica::new(&server, (*upstream).clone(), ReplicaOptions {
+            tables: vec!["quotes".to_owned()],
+        })
+        .await?;
+
+        let client = LocalClient::new(&server);
+        let local = client.open_table("quotes".to_owned()).await?;
+        assert_eq!(local.get_index(), Some("sym".to_owned()));
+        let view = local
+            .view(Some(ViewConfigUpdate {
+                columns: Some(vec![Some("px".to_owned())]),
+                ..ViewConfigUpdate::default()
+            }))
+            .await?;
+
+        quotes
+            .update(
+                UpdateData::Csv("sym,px\nB,3\nC,4".to_owned()),
+                UpdateOptions::default(),
+            )
+            .await?;
+
+        assert_eq!(
+            view.to_columns_string(ViewWindow::default()).await?,
+            r#"{"px":[1,3,4]}"#
+        );
+
+        assert_eq!(replica.stats()["quotes"].updates, 1);
+        let lag = replica.lag().await?;
+        assert_eq!(lag[0].upstream_rows, lag[0].local_rows);
+
+        // Resyncing replaces the local table's data in place.
+        replica.resync().await?;
+        assert_eq!(view.num_rows().await?, 3);
+        assert_eq!(replica.stats()["quotes"].resyncs, 1);
+
+        view.delete().await?;
+        client.close().await;
+        replica.close().await;
+        quotes.delete(Default::default()).await?;
+        upstream.close().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#[cfg(feature = "tokio")]
+mod internal {
+    use std::collections::HashMap;
+    use std::error::Error;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    use perspective::poll::new_server;
+    use perspective_client::config::ColumnType;
+    use perspective_client::{
+        RetentionOptions, TableInitOptions, UpdateData, UpdateOptions, ViewWindow,
+    };
+    use perspective_server::{LocalClient, PollDriverOptions, Server};
+
+    #[tokio::test]
+    async fn test_max_rows_drops_earliest_rows() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = LocalClient::new(&server);
+        let options = TableInitOptions {
+            index: Some("id".to_owned()),
+            retention: Some(RetentionOptions {
+                max_rows: Some(3),
+                ..RetentionOptions::default()
+            }),
+            ..TableInitOptions::default()
+        };
+
+        let data = UpdateData::Csv("id,x\n1,a\n2,b\n3,c\n4,d\n5,e".to_owned());
+        let table = client.table(data.into(), options).await?;
+        assert_eq!(table.size().await?, 3);
+
+        // Updating an existing row does not change its insertion order.
+        table
+            .update(
+                UpdateData::Csv("id,x\n3,z\n6,f".to_owned()),
+                UpdateOptions::default(),
+            )
+            .await?;
+
+        let view = table.view(None).await?;
+        let json = view.to_columns_string(ViewWindow::default()).await?;
+        assert_eq!(json, r#"{"id":[4,5,6],"x":["d","e","f"]}"#);
+        view.delete().await?;
+        table.delete(Default::default()).await?;
+        client.close().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_max_age_drops_old_rows() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = LocalClient::new(&server);
+        let options = TableInitOptions {
+            column_types: Some(HashMap::from([("t".to_owned(), ColumnType::Datetime)])),
+            retention: Some(RetentionOptions {
+                age_column: Some("t".to_owned()),
+                max_age: Some(60.0),
+                ..RetentionOptions::default()
+            }),
+            ..TableInitOptions::default()
+        };
+
+        let data = "t,x\n2000-01-01 00:00:00,1\n2100-01-01 00:00:00,2".to_owned();
+        let table = client.table(UpdateData::Csv(data).into(), options).await?;
+
+        assert_eq!(table.size().await?, 1);
+        table.delete(Default::default()).await?;
+
+        // The age column must exist and be a `"datetime"`.
+        let options = TableInitOptions {
+            retention: Some(RetentionOptions {
+                age_column: Some("x".to_owned()),
+                max_age: Some(60.0),
+                ..RetentionOptions::default()
+            }),
+            ..TableInitOptions::default()
+        };
+
+        let data = UpdateData::Csv("x\n1".to_owned());
+        assert!(client.table(data.into(), options).await.is_err());
+        client.close().await;
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_idle_tables_are_evicted() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None).with_clock(|| tokio::time::Instant::now().into_std());
+        let client = LocalClient::new(&server);
+        let retention = RetentionOptions {
+            idle_timeout: Some(0.05),
+            ..RetentionOptions::default()
+        };
+
+        let options = TableInitOptions {
+            name: Some("idle".to_owned()),
+            retention: Some(retention.clone()),
+            ..TableInitOptions::default()
+        };
+
+        let idle = client
+            .table(UpdateData::Csv("x\n1".to_owned()).into(), options)
+            .await?;
+
+        let kept = client
+            .table(
+                UpdateData::Csv("x\n1".to_owned()).into(),
+                Default::default(),
+            )
+            .await?;
+
+        let other = LocalClient::new(&server);
+        let opened = other.open_table("idle".to_owned()).await?;
+        assert_eq!(opened.get_retention(), Some(&retention));
+
+        let deleted = Arc::new(AtomicU32::new(0));
+        let count = deleted.clone();
+        idle.on_delete(Box::new(move || {
+            count.fetch_add(1, Ordering::SeqCst);
+        }))
+        .await?;
+
+        // Idle tables are evicted when the engine is next polled, here after
+        // this request.
+        kept.size().await?;
+        assert_eq!(deleted.load(Ordering::SeqCst), 0);
+        tokio::time::advance(Duration::from_millis(100)).await;
+        kept.size().await?;
+
+        let names = client.get_hosted_table_names().await?;
+        assert_eq!(names, vec![kept.get_name().to_owned()]);
+        assert_eq!(deleted.load(Ordering::SeqCst), 1);
+        assert!(idle.size().await.is_err());
+        kept.delete(Default::default()).await?;
+        other.close().await;
+        client.close().await;
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_idle_tables_are_evicted_without_traffic() -> Result<(), Box<dyn Error>> {
+        let (server, _driver) = new_server(PollDriverOptions {
+            max_interval: Some(Duration::from_millis(10)),
+            ..PollDriverOptions::default()
+        });
+
+        let client = LocalClient::new(&server);
+        let options = TableInitOptions {
+            retention: Some(RetentionOptions {
+                idle_timeout: Some(0.05),
+                ..RetentionOptions::default()
+            }),
+            ..TableInitOptions::default()
+        };
+
+        let table = client
+            .table(UpdateData::Csv("x\n1".to_owned()).into(), options)
+            .await?;
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let tx = std::sync::Mutex::new(Some(tx));
+        table
+            .on_delete(Box::new(move || {
+                if let Some(tx) = tx.lock().unwrap().take() {
+                    let _ = tx.send(());
+                }
+            }))
+            .await?;
+
+        // No further requests are made, so only the timer polls.
+        tokio::time::advance(Duration::from_millis(100)).await;
+        rx.await?;
+        assert!(client.get_hosted_table_names().await?.is_empty());
+        client.close().await;
+        Ok(())
+    }
+}
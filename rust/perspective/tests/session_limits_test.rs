@@ -0,0 +1,168 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#[cfg(feature = "axum-ws")]
+mod internal {
+    use std::error::Error;
+    use std::time::Duration;
+
+    use perspective_client::config::ViewWindow;
+    use perspective_client::{
+        ClientError, TableInitOptions, UnionOptions, UpdateData, UpdateOptions,
+    };
+    use perspective_server::{LocalClient, Server, SessionLimits};
+
+    fn csv(rows: usize) -> String {
+        std::iter::once("x".to_owned())
+            .chain((0..rows).map(|x| x.to_string()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[tokio::test]
+    async fn test_max_views_and_tables() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None).with_session_limits(SessionLimits {
+            max_views: Some(1),
+            max_tables: Some(1),
+            ..SessionLimits::default()
+        });
+
+        let client = LocalClient::new(&server);
+        let table = client
+            .table(UpdateData::Csv(csv(3)).into(), TableInitOptions::default())
+            .await?;
+
+        let result = client
+            .table(UpdateData::Csv(csv(3)).into(), TableInitOptions::default())
+            .await;
+
+        assert!(matches!(result, Err(ClientError::ResourceExhausted(_))));
+        let view = table.view(None).await?;
+        assert!(matches!(
+            table.view(None).await,
+            Err(ClientError::ResourceExhausted(_))
+        ));
+
+        // Deleting a `View` frees its slot.
+        view.delete().await?;
+        let view = table.view(None).await?;
+        let usage = client.system_info().await?.session_usage.unwrap();
+        assert_eq!(usage.num_views, 1);
+        assert_eq!(usage.num_tables, 1);
+        assert_eq!(usage.rows_ingested, 3);
+        assert_eq!(usage.rejected_requests, 2);
+        view.delete().await?;
+        table.delete(Default::default()).await?;
+        client.close().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failed_table_delete_holds_its_slot() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None).with_session_limits(SessionLimits {
+            max_tables: Some(2),
+            ..SessionLimits::default()
+        });
+
+        let client = LocalClient::new(&server);
+        let table = client
+            .table(UpdateData::Csv(csv(3)).into(), TableInitOptions::default())
+            .await?;
+
+        let union = client
+            .union(vec![(&table).into()], UnionOptions::default())
+            .await?;
+
+        // A source of a union can't be deleted.
+        assert!(table.delete(Default::default()).await.is_err());
+        let usage = client.system_info().await?.session_usage.unwrap();
+        assert_eq!(usage.num_tables, 2);
+        union.delete(Default::default()).await?;
+        table.delete(Default::default()).await?;
+        let usage = client.system_info().await?.session_usage.unwrap();
+        assert_eq!(usage.num_tables, 0);
+        client.close().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_max_rows_per_second() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None).with_session_limits(SessionLimits {
+            max_rows_per_second: Some(10),
+            ..SessionLimits::default()
+        });
+
+        let client = LocalClient::new(&server);
+        let table = client
+            .table(UpdateData::Csv(csv(8)).into(), TableInitOptions::default())
+            .await?;
+
+        let result = table
+            .update(UpdateData::Csv(csv(8)), UpdateOptions::default())
+            .await;
+
+        assert!(matches!(result, Err(ClientError::ResourceExhausted(_))));
+
+        // Arrow rows are counted from the record batch headers.
+        let view = table.view(None).await?;
+        let arrow = view.to_arrow(ViewWindow::default()).await?;
+        let result = table
+            .update(UpdateData::Arrow(arrow), UpdateOptions::default())
+            .await;
+
+        assert!(matches!(result, Err(ClientError::ResourceExhausted(_))));
+
+        // Replacing a `Table`'s rows counts them too.
+        let result = table.replace(UpdateData::Csv(csv(8))).await;
+        assert!(matches!(result, Err(ClientError::ResourceExhausted(_))));
+        assert_eq!(table.size().await?, 8);
+        view.delete().await?;
+        table.delete(Default::default()).await?;
+        client.close().await;
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_idle_views_are_evicted() -> Result<(), Box<dyn Error>> {
+        let timeout = Duration::from_millis(10);
+        let server = Server::new(None)
+            .with_clock(|| tokio::time::Instant::now().into_std())
+            .with_session_limits(SessionLimits {
+                view_idle_timeout: Some(timeout),
+                ..SessionLimits::default()
+            });
+
+        let client = LocalClient::new(&server);
+        let table = client
+            .table(UpdateData::Csv(csv(3)).into(), TableInitOptions::default())
+            .await?;
+
+        let view = table.view(None).await?;
+        assert_eq!(view.num_rows().await?, 3);
+
+        server.evict_idle_views().await?;
+        assert_eq!(view.num_rows().await?, 3);
+        tokio::time::advance(timeout * 2).await;
+        server.evict_idle_views().await?;
+        assert!(matches!(
+            view.num_rows().await,
+            Err(ClientError::ViewNotFound)
+        ));
+
+        let usage = client.system_info().await?.session_usage.unwrap();
+        assert_eq!(usage.num_views, 0);
+        assert_eq!(usage.evicted_views, 1);
+        table.delete(Default::default()).await?;
+        client.close().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,196 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#[cfg(feature = "axum-ws")]
+mod internal {
+    use std::error::Error;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    use perspective_client::{RetentionOptions, TableInitOptions, UpdateData, UpdateOptions};
+    use perspective_server::{LocalClient, Server, SnapshotOptions};
+
+    #[tokio::test]
+    async fn test_snapshot_and_update_log_are_restored() -> Result<(), Box<dyn Error>> {
+        let dir = std::env::temp_dir().join(format!("psp-snapshot-{}", std::process::id()));
+        let log = dir.join("updates.log");
+        let server = Server::new(None).with_update_log(&log);
+        let client = LocalClient::new(&server);
+        let options = TableInitOptions {
+            name: Some("prices".to_owned()),
+            index: Some("id".to_owned()),
+            ..TableInitOptions::default()
+        };
+
+        let table = client
+            .table(
+                UpdateData::Csv("id,px\n1,10\n2,20".to_owned()).into(),
+                options,
+            )
+            .await?;
+
+        // Failed requests are not logged.
+        let len = std::fs::metadata(&log)?.len();
+        let options = TableInitOptions {
+            name: Some("prices".to_owned()),
+            ..TableInitOptions::default()
+        };
+
+        let duplicate = client
+            .table(UpdateData::Csv("id,px\n4,40".to_owned()).into(), options)
+            .await;
+
+        assert!(duplicate.is_err());
+        assert_eq!(std::fs::metadata(&log)?.len(), len);
+        server.snapshot(&dir, SnapshotOptions::default()).await?;
+        table
+            .update(
+                UpdateData::Csv("id,px\n2,21\n3,30".to_owned()),
+                UpdateOptions::default(),
+            )
+            .await?;
+
+        client.close().await;
+
+        // A new process, restoring the snapshot then replaying the log.
+        let server = Server::new(None).with_update_log(&log);
+        assert_eq!(server.restore(&dir).await?, vec!["prices"]);
+        let client = LocalClient::new(&server);
+        let table = client.open_table("prices".to_owned()).await?;
+        assert_eq!(table.get_index(), Some("id".to_owned()));
+        assert_eq!(table.size().await?, 3);
+
+        // A second snapshot truncates the log.
+        server.snapshot(&dir, SnapshotOptions::default()).await?;
+        assert_eq!(std::fs::metadata(&log)?.len(), 0);
+        table.delete(Default::default()).await?;
+        client.close().await;
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_subset_snapshot_keeps_other_tables() -> Result<(), Box<dyn Error>> {
+        let dir = std::env::temp_dir().join(format!("psp-subset-{}", std::process::id()));
+        let log = dir.join("updates.log");
+        let server = Server::new(None).with_update_log(&log);
+        let client = LocalClient::new(&server);
+        for name in ["a", "b"] {
+            let options = TableInitOptions {
+                name: Some(name.to_owned()),
+                ..TableInitOptions::default()
+            };
+
+            client
+                .table(UpdateData::Csv("x\n1".to_owned()).into(), options)
+                .await?;
+        }
+
+        server.snapshot(&dir, SnapshotOptions::default()).await?;
+        for name in ["a", "b"] {
+            client
+                .open_table(name.to_owned())
+                .await?
+                .update(UpdateData::Csv("x\n2".to_owned()), UpdateOptions::default())
+                .await?;
+        }
+
+        let options = TableInitOptions {
+            name: Some("c".to_owned()),
+            ..TableInitOptions::default()
+        };
+
+        client
+            .table(UpdateData::Csv("x\n1".to_owned()).into(), options)
+            .await?;
+
+        // Only "a" is snapshotted; "b" keeps its previous snapshot and its
+        // logged update, and "c" its logged creation.
+        let options = SnapshotOptions {
+            tables: Some(vec!["a".to_owned()]),
+        };
+
+        server.snapshot(&dir, options).await?;
+        client.close().await;
+
+        let server = Server::new(None).with_update_log(&log);
+        let mut restored = server.restore(&dir).await?;
+        restored.sort();
+        assert_eq!(restored, vec!["a", "b"]);
+        let client = LocalClient::new(&server);
+        let mut names = client.get_hosted_table_names().await?;
+        names.sort();
+        assert_eq!(names, vec!["a", "b", "c"]);
+        for (name, size) in [("a", 2), ("b", 2), ("c", 1)] {
+            let table = client.open_table(name.to_owned()).await?;
+            assert_eq!(table.size().await?, size);
+            table.delete(Default::default()).await?;
+        }
+
+        client.close().await;
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_evicted_tables_are_not_restored() -> Result<(), Box<dyn Error>> {
+        let dir = std::env::temp_dir().join(format!("psp-evicted-{}", std::process::id()));
+        let log = dir.join("updates.log");
+        let server = Server::new(None)
+            .with_clock(|| tokio::time::Instant::now().into_std())
+            .with_update_log(&log);
+        let client = LocalClient::new(&server);
+        let options = TableInitOptions {
+            name: Some("idle".to_owned()),
+            retention: Some(RetentionOptions {
+                idle_timeout: Some(0.05),
+                ..RetentionOptions::default()
+            }),
+            ..TableInitOptions::default()
+        };
+
+        let idle = client
+            .table(UpdateData::Csv("x\n1".to_owned()).into(), options)
+            .await?;
+
+        let options = TableInitOptions {
+            name: Some("kept".to_owned()),
+            ..TableInitOptions::default()
+        };
+
+        let kept = client
+            .table(UpdateData::Csv("x\n1".to_owned()).into(), options)
+            .await?;
+
+        let deleted = Arc::new(AtomicBool::new(false));
+        let flag = deleted.clone();
+        idle.on_delete(Box::new(move || flag.store(true, Ordering::SeqCst)))
+            .await?;
+
+        tokio::time::advance(Duration::from_millis(100)).await;
+        kept.size().await?;
+        assert!(deleted.load(Ordering::SeqCst));
+
+        assert_eq!(client.get_hosted_table_names().await?, vec!["kept"]);
+        client.close().await;
+
+        // Replaying the log must not bring the evicted table back.
+        let server = Server::new(None).with_update_log(&log);
+        assert!(server.restore(&dir).await?.is_empty());
+        let client = LocalClient::new(&server);
+        assert_eq!(client.get_hosted_table_names().await?, vec!["kept"]);
+        client.close().await;
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
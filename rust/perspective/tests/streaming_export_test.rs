@@ -0,0 +1,225 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#[cfg(feature = "tokio")]
+mod internal {
+    use std::error::Error;
+    use std::sync::{Arc, Mutex, OnceLock};
+
+    use futures::TryStreamExt;
+    use perspective_client::proto::request::ClientReq;
+    use perspective_client::proto::response::ClientResp;
+    use perspective_client::proto::{Request, Response, ViewStreamNextReq, ViewToCsvStreamResp};
+    use perspective_client::{
+        Client, Session, TableInitOptions, UpdateData, UpdateOptions, ViewWindow,
+    };
+    use perspective_server::{LocalClient, LocalSession, Server};
+    use prost::Message;
+    use tokio::sync::RwLock;
+
+    #[tokio::test]
+    async fn test_streaming_export_chunks() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = LocalClient::new(&server);
+        let csv = (0..10).fold("x,y\n".to_owned(), |csv, x| {
+            csv + &format!("{x},{}\n", x * 2)
+        });
+        let table = client
+            .table(UpdateData::Csv(csv).into(), TableInitOptions::default())
+            .await?;
+
+        let view = table.view(None).await?;
+        let window = ViewWindow {
+            chunk_rows: Some(3),
+            ..ViewWindow::default()
+        };
+
+        // 4 chunks of at most 3 rows, the first with the schema and the last
+        // with the end-of-stream marker.
+        let chunks: Vec<_> = view
+            .to_arrow_stream(window.clone())
+            .await?
+            .try_collect()
+            .await?;
+        assert_eq!(chunks.len(), 4);
+        let arrow = chunks.concat();
+        let table2 = client
+            .table(
+                UpdateData::Arrow(arrow.into()).into(),
+                TableInitOptions::default(),
+            )
+            .await?;
+
+        assert_eq!(table2.size().await?, 10);
+        let json = table2
+            .view(None)
+            .await?
+            .to_columns_string(ViewWindow::default())
+            .await?;
+
+        assert_eq!(json, view.to_columns_string(ViewWindow::default()).await?);
+
+        // Only the first chunk has the header row.
+        let chunks: Vec<_> = view.to_csv_stream(window).await?.try_collect().await?;
+        assert_eq!(chunks.len(), 4);
+        assert!(chunks[0].contains('x'));
+        assert!(!chunks[1].contains('x'));
+        let csv = view.to_csv(ViewWindow::default()).await?;
+        assert_eq!(chunks.concat(), csv);
+        client.close().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_streaming_export_reads_chunks_on_demand() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = LocalClient::new(&server);
+        let csv = (0..10).fold("x,y\n".to_owned(), |csv, x| csv + &format!("{x},{x}\n"));
+        let options = TableInitOptions {
+            index: Some("x".to_owned()),
+            ..TableInitOptions::default()
+        };
+
+        let table = client.table(UpdateData::Csv(csv).into(), options).await?;
+        let view = table.view(None).await?;
+        let window = ViewWindow {
+            chunk_rows: Some(5),
+            ..ViewWindow::default()
+        };
+
+        let mut chunks = view.to_csv_stream(window.clone()).await?;
+        let first = chunks.try_next().await?.unwrap();
+        assert!(first.contains("\n4,4\n"));
+
+        // The second chunk is not serialized until it is requested, so it
+        // reflects this update.
+        table
+            .update(
+                UpdateData::Csv("x,y\n9,-1".to_owned()),
+                UpdateOptions::default(),
+            )
+            .await?;
+
+        let second = chunks.try_next().await?.unwrap();
+        assert!(second.contains("\n9,-1\n"));
+        assert!(chunks.try_next().await?.is_none());
+
+        // Dropping a stream early doesn't disturb the next export.
+        let mut chunks = view.to_csv_stream(window.clone()).await?;
+        chunks.try_next().await?;
+        drop(chunks);
+        let csv: Vec<_> = view.to_csv_stream(window).await?.try_collect().await?;
+        assert_eq!(csv.concat(), view.to_csv(ViewWindow::default()).await?);
+        client.close().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_streaming_export_is_released() -> Result<(), Box<dyn Error>> {
+        // A `Client` connected to a `Session` which logs the messages between
+        // them.
+        let server = Server::new(None);
+        let requests = Arc::new(Mutex::new(Vec::<Request>::new()));
+        let responses = Arc::new(Mutex::new(Vec::<Response>::new()));
+        let client = Arc::new(OnceLock::<Client>::new());
+        let session = server
+            .new_session_with_callback({
+                let client = client.clone();
+                let responses = responses.clone();
+                move |msg| {
+                    let client = client.get().unwrap().clone();
+                    responses
+                        .lock()
+                        .unwrap()
+                        .push(Response::decode(msg).unwrap());
+                    Box::pin(async move {
+                        client.handle_response(msg).await?;
+                        Ok::<_, Box<dyn Error + Send + Sync>>(())
+                    })
+                }
+            })
+            .await;
+
+        let session = Arc::new(RwLock::new(Some(session)));
+        client
+            .set(Client::new_with_callback(None, {
+                let requests = requests.clone();
+                let session = session.clone();
+                move |msg: Vec<u8>| {
+                    requests
+                        .lock()
+                        .unwrap()
+                        .push(Request::decode(msg.as_slice()).unwrap());
+                    let session = session.clone();
+                    async move {
+                        let session = session.read().await;
+                        session.as_ref().unwrap().handle_request(&msg).await?;
+                        Ok::<_, Box<dyn Error + Send + Sync>>(())
+                    }
+                }
+            })?)
+            .unwrap();
+
+        let client = client.get().unwrap();
+        let csv = (0..10).fold("x\n".to_owned(), |csv, x| csv + &format!("{x}\n"));
+        let table = client
+            .table(UpdateData::Csv(csv).into(), TableInitOptions::default())
+            .await?;
+
+        let view = table.view(None).await?;
+        let window = ViewWindow {
+            chunk_rows: Some(3),
+            ..ViewWindow::default()
+        };
+
+        let mut chunks = view.to_csv_stream(window).await?;
+        chunks.try_next().await?;
+        let msg_id = requests.lock().unwrap().last().unwrap().msg_id;
+        drop(chunks);
+
+        // The export is cancelled with the next request, and the server
+        // answers with an empty final frame once it has released it.
+        assert_eq!(table.size().await?, 10);
+        let cancelled = requests.lock().unwrap().iter().any(|x| {
+            x.msg_id == msg_id
+                && matches!(
+                    x.client_req,
+                    Some(ClientReq::ViewStreamNextReq(ViewStreamNextReq {
+                        cancel: true
+                    }))
+                )
+        });
+
+        assert!(cancelled);
+
+        let last = responses
+            .lock()
+            .unwrap()
+            .iter()
+            .rfind(|x| x.msg_id == msg_id)
+            .and_then(|x| x.client_resp.clone());
+
+        assert_eq!(
+            last,
+            Some(ClientResp::ViewToCsvStreamResp(ViewToCsvStreamResp {
+                csv: String::new(),
+                done: true,
+            }))
+        );
+
+        view.delete().await?;
+        table.delete(Default::default()).await?;
+        let session: LocalSession = session.write().await.take().unwrap();
+        session.close().await;
+        Ok(())
+    }
+}
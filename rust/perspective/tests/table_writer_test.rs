@@ -0,0 +1,195 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#[cfg(feature = "tokio")]
+mod internal {
+    use std::error::Error;
+    use std::time::Duration;
+
+    use perspective_client::{
+        TableInitOptions, TableWriter, TableWriterInput, TableWriterOptions, UpdateData, ViewWindow,
+    };
+    use perspective_server::{LocalClient, Server};
+
+    fn row(value: serde_json::Value) -> TableWriterInput {
+        TableWriterInput::Row(value.as_object().unwrap().clone())
+    }
+
+    #[tokio::test]
+    async fn test_table_writer_coalesces_indexed_rows() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = LocalClient::new(&server);
+        let options = TableInitOptions {
+            index: Some("id".to_owned()),
+            ..TableInitOptions::default()
+        };
+
+        let csv = UpdateData::Csv("id,x,y\n0,0,a".to_owned());
+        let table = client.table(csv.into(), options).await?;
+        let options = TableWriterOptions {
+            max_delay: Duration::from_secs(60),
+            ..TableWriterOptions::default()
+        };
+
+        let (writer, task) = TableWriter::new(table.clone(), options);
+        let task = tokio::spawn(task.run(tokio::time::sleep));
+        writer
+            .write(row(serde_json::json!({"id": 1, "x": 1, "y": "b"})))
+            .await?;
+        writer
+            .write(row(serde_json::json!({"id": 2, "x": 2, "y": "c"})))
+            .await?;
+        writer
+            .write(row(serde_json::json!({"id": 1, "x": 3})))
+            .await?;
+        writer.flush().await?;
+
+        let stats = writer.stats();
+        assert_eq!(stats.rows_received, 3);
+        assert_eq!(stats.rows_written, 2);
+        assert_eq!(stats.rows_coalesced, 1);
+        assert_eq!(stats.flushes, 1);
+
+        let view = table.view(None).await?;
+        let json = view.to_columns_string(ViewWindow::default()).await?;
+        assert_eq!(json, r#"{"id":[0,1,2],"x":[0,3,2],"y":["a","b","c"]}"#);
+
+        // Dropping the last `TableWriter` flushes and ends the task.
+        writer
+            .write(row(serde_json::json!({"id": 3, "x": 4})))
+            .await?;
+        drop(writer);
+        task.await??;
+        assert_eq!(table.size().await?, 4);
+        client.close().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_table_writer_does_not_coalesce_rows_without_an_index()
+    -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = LocalClient::new(&server);
+        let options = TableInitOptions {
+            index: Some("id".to_owned()),
+            ..TableInitOptions::default()
+        };
+
+        let csv = UpdateData::Csv("id,x\n0,0".to_owned());
+        let table = client.table(csv.into(), options).await?;
+        let options = TableWriterOptions {
+            max_delay: Duration::from_secs(60),
+            ..TableWriterOptions::default()
+        };
+
+        let (writer, task) = TableWriter::new(table.clone(), options);
+        let task = tokio::spawn(task.run(tokio::time::sleep));
+        writer.write(row(serde_json::json!({"x": 1}))).await?;
+        writer.write(row(serde_json::json!({"x": 2}))).await?;
+        writer
+            .write(row(serde_json::json!({"id": null, "x": 3})))
+            .await?;
+        writer
+            .write(row(serde_json::json!({"id": null, "x": 4})))
+            .await?;
+        writer.flush().await?;
+
+        let stats = writer.stats();
+        assert_eq!(stats.rows_received, 4);
+        assert_eq!(stats.rows_written, 4);
+        assert_eq!(stats.rows_coalesced, 0);
+        drop(writer);
+        task.await??;
+        client.close().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_table_writer_coalesces_indexed_arrow() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = LocalClient::new(&server);
+        let csv = UpdateData::Csv("id,x\n1,1\n2,2\n1,3".to_owned());
+        let source = client
+            .table(csv.into(), TableInitOptions::default())
+            .await?;
+        let view = source.view(None).await?;
+        let arrow = view.to_arrow(ViewWindow::default()).await?;
+        let options = TableInitOptions {
+            index: Some("id".to_owned()),
+            ..TableInitOptions::default()
+        };
+
+        let csv = UpdateData::Csv("id,x\n0,0".to_owned());
+        let table = client.table(csv.into(), options).await?;
+        let options = TableWriterOptions {
+            max_delay: Duration::from_secs(60),
+            ..TableWriterOptions::default()
+        };
+
+        let (writer, task) = TableWriter::new(table.clone(), options);
+        let task = tokio::spawn(task.run(tokio::time::sleep));
+        writer.write(TableWriterInput::Arrow(arrow.clone())).await?;
+        writer.write(TableWriterInput::Arrow(arrow)).await?;
+        writer.flush().await?;
+
+        let stats = writer.stats();
+        assert_eq!(stats.rows_received, 6);
+        assert_eq!(stats.rows_written, 2);
+        assert_eq!(stats.rows_coalesced, 4);
+
+        let view = table.view(None).await?;
+        let json = view.to_columns_string(ViewWindow::default()).await?;
+        assert_eq!(json, r#"{"id":[0,1,2],"x":[0,3,2]}"#);
+        drop(writer);
+        task.await??;
+        client.close().await;
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_table_writer_flushes_on_max_rows_and_max_delay() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = LocalClient::new(&server);
+        let csv = UpdateData::Csv("x\n0".to_owned());
+        let table = client
+            .table(csv.into(), TableInitOptions::default())
+            .await?;
+        let view = table.view(None).await?;
+        let arrow = view.to_arrow(ViewWindow::default()).await?;
+        let options = TableWriterOptions {
+            max_rows: 2,
+            max_delay: Duration::from_millis(10),
+            ..TableWriterOptions::default()
+        };
+
+        let (writer, task) = TableWriter::new(table.clone(), options);
+        let task = tokio::spawn(task.run(tokio::time::sleep));
+        let input = futures::stream::iter([
+            TableWriterInput::Arrow(arrow.clone()),
+            TableWriterInput::Arrow(arrow.clone()),
+            TableWriterInput::Arrow(arrow),
+        ]);
+
+        writer.write_all(input).await?;
+
+        // Time is paused, so this returns only once the `max_delay` flush has
+        // run.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(table.size().await?, 4);
+        assert_eq!(writer.stats().flushes, 2);
+        assert_eq!(writer.stats().pending_rows, 0);
+        drop(writer);
+        task.await??;
+        client.close().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#[cfg(feature = "tokio")]
+mod internal {
+    use std::error::Error;
+
+    use perspective_client::config::{Date, Filter, FilterTerm, Scalar, ViewConfigUpdate};
+    use perspective_client::{TableInitOptions, UpdateData};
+    use perspective_server::{LocalClient, Server};
+
+    #[tokio::test]
+    async fn test_get_config_legacy_temporal_encoding() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let client = LocalClient::new(&server);
+        let data = UpdateData::Csv("d,x\n2024-01-01,1\n2024-01-02,2".to_owned());
+        let table = client
+            .table(data.into(), TableInitOptions::default())
+            .await?;
+
+        let date = Date::from_ymd(2024, 1, 2).unwrap();
+        let view = table
+            .view(Some(ViewConfigUpdate {
+                filter: Some(vec![Filter::new(
+                    "d",
+                    "==",
+                    FilterTerm::Scalar(Scalar::Date(date)),
+                )]),
+                ..ViewConfigUpdate::default()
+            }))
+            .await?;
+
+        assert_eq!(view.num_rows().await?, 1);
+        let mut config = view.get_config().await?;
+        assert_eq!(
+            config.filter[0].term(),
+            &FilterTerm::Scalar(Scalar::Date(date))
+        );
+
+        // The language bindings' `get_config` encoding is unchanged.
+        config.downgrade_temporal_scalars();
+        assert_eq!(
+            config.filter[0].term(),
+            &FilterTerm::Scalar(Scalar::String("2024-01-02".to_owned()))
+        );
+
+        view.delete().await?;
+        table.delete(Default::default()).await?;
+        client.close().await;
+        Ok(())
+    }
+}
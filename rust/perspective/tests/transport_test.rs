@@ -0,0 +1,117 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#[cfg(feature = "tokio")]
+mod internal {
+    use std::error::Error;
+    use std::time::Duration;
+
+    use perspective::transport::{
+        CloseReason, TransportOptions, connect_client, length_delimited,
+        length_delimited_with_limit, serve_session,
+    };
+    use perspective_client::{TableInitOptions, UpdateData};
+    use perspective_server::Server;
+    use tokio::io::AsyncWriteExt;
+
+    #[tokio::test]
+    async fn test_client_and_session_over_byte_stream() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let (client_io, server_io) = tokio::io::duplex(1024);
+        let session = tokio::spawn({
+            let server = server.clone();
+            async move {
+                let (reader, writer) = tokio::io::split(server_io);
+                let (incoming, outgoing) = length_delimited(reader, writer);
+                let options = TransportOptions::default();
+                serve_session(&server, incoming, outgoing, &options)
+                    .await
+                    .map_err(|x| x.to_string())
+            }
+        });
+
+        let (reader, writer) = tokio::io::split(client_io);
+        let (incoming, outgoing) = length_delimited(reader, writer);
+        let (client, driver) = connect_client(incoming, outgoing)?;
+        let driver = tokio::spawn(driver);
+        let mut options = TableInitOptions::default();
+        options.set_name("quotes");
+        let table = client
+            .table(UpdateData::Csv("px\n1\n2\n3".to_owned()).into(), options)
+            .await?;
+
+        assert_eq!(table.size().await?, 3);
+        assert_eq!(client.get_hosted_table_names().await?, vec!["quotes"]);
+        table.delete(Default::default()).await?;
+        driver.abort();
+        drop(client);
+        assert_eq!(session.await??, CloseReason::Disconnected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_oversize_frames_are_rejected_before_reading() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let (mut client_io, server_io) = tokio::io::duplex(1024);
+        let session = tokio::spawn({
+            let server = server.clone();
+            async move {
+                let (reader, writer) = tokio::io::split(server_io);
+                let (incoming, outgoing) = length_delimited_with_limit(reader, writer, 1024);
+                let options = TransportOptions::default();
+                serve_session(&server, incoming, outgoing, &options)
+                    .await
+                    .map_err(|x| x.to_string())
+            }
+        });
+
+        // Only the length prefix is sent, so the session would wait forever
+        // if it tried to read the frame.
+        client_io.write_u32(u32::MAX).await?;
+        assert_eq!(session.await??, CloseReason::Disconnected);
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_idle_client_echoes_heartbeats() -> Result<(), Box<dyn Error>> {
+        let server = Server::new(None);
+        let (client_io, server_io) = tokio::io::duplex(1024);
+        let session = tokio::spawn({
+            let server = server.clone();
+            async move {
+                let (reader, writer) = tokio::io::split(server_io);
+                let (incoming, outgoing) = length_delimited(reader, writer);
+                let options = TransportOptions {
+                    heartbeat_interval: Some(Duration::from_millis(10)),
+                    idle_timeout: Some(Duration::from_millis(30)),
+                    ..TransportOptions::default()
+                };
+
+                serve_session(&server, incoming, outgoing, &options)
+                    .await
+                    .map_err(|x| x.to_string())
+            }
+        });
+
+        let (reader, writer) = tokio::io::split(client_io);
+        let (incoming, outgoing) = length_delimited(reader, writer);
+        let (client, driver) = connect_client(incoming, outgoing)?;
+        let driver = tokio::spawn(driver);
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert!(!session.is_finished());
+        assert!(client.get_hosted_table_names().await?.is_empty());
+        driver.abort();
+        drop(client);
+        assert_eq!(session.await??, CloseReason::Disconnected);
+        Ok(())
+    }
+}
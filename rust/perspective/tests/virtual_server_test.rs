@@ -0,0 +1,125 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#[cfg(feature = "tokio")]
+mod internal {
+    use std::error::Error;
+
+    use indexmap::IndexMap;
+    use perspective::transport::{connect_client, length_delimited, serve_virtual_server};
+    use perspective_client::config::{ViewConfig, ViewConfigUpdate};
+    use perspective_client::proto::{ColumnType, HostedTable, ViewPort};
+    use perspective_client::protocol::Capabilities;
+    use perspective_client::virtual_server::{
+        VIRTUAL_SERVER_CAPABILITIES, VirtualDataSlice, VirtualServerFuture, VirtualServerHandler,
+    };
+    use perspective_client::{ClientError, JoinOptions, JoinType, UnionOptions, UnnestOptions};
+
+    /// A handler hosting a single empty table, `t`.
+    struct EmptyHandler;
+
+    impl VirtualServerHandler for EmptyHandler {
+        type Error = std::io::Error;
+
+        fn get_hosted_tables(
+            &self,
+        ) -> VirtualServerFuture<'_, Result<Vec<HostedTable>, Self::Error>> {
+            Box::pin(async { Ok(vec!["t".into()]) })
+        }
+
+        fn table_schema(
+            &self,
+            _table_id: &str,
+        ) -> VirtualServerFuture<'_, Result<IndexMap<String, ColumnType>, Self::Error>> {
+            Box::pin(async { Ok(IndexMap::from([("x".to_owned(), ColumnType::Integer)])) })
+        }
+
+        fn table_size(&self, _table_id: &str) -> VirtualServerFuture<'_, Result<u32, Self::Error>> {
+            Box::pin(async { Ok(0) })
+        }
+
+        fn table_make_view(
+            &mut self,
+            _table_id: &str,
+            view_id: &str,
+            _config: &mut ViewConfigUpdate,
+        ) -> VirtualServerFuture<'_, Result<String, Self::Error>> {
+            let view_id = view_id.to_owned();
+            Box::pin(async move { Ok(view_id) })
+        }
+
+        fn view_delete(&self, _view_id: &str) -> VirtualServerFuture<'_, Result<(), Self::Error>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn view_get_data(
+            &self,
+            _view_id: &str,
+            config: &ViewConfig,
+            _schema: &IndexMap<String, ColumnType>,
+            _viewport: &ViewPort,
+        ) -> VirtualServerFuture<'_, Result<VirtualDataSlice, Self::Error>> {
+            let config = config.clone();
+            Box::pin(async move { Ok(VirtualDataSlice::new(config)) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_tables_are_not_implemented() -> Result<(), Box<dyn Error>> {
+        let (client_io, server_io) = tokio::io::duplex(1024);
+        let server = tokio::spawn(async move {
+            let (reader, writer) = tokio::io::split(server_io);
+            let (incoming, outgoing) = length_delimited(reader, writer);
+            serve_virtual_server(EmptyHandler, incoming, outgoing)
+                .await
+                .map_err(|x| x.to_string())
+        });
+
+        let (reader, writer) = tokio::io::split(client_io);
+        let (incoming, outgoing) = length_delimited(reader, writer);
+        let (client, driver) = connect_client(incoming, outgoing)?;
+        let driver = tokio::spawn(driver);
+        let capabilities = client.handshake().await?;
+        assert_eq!(capabilities, VIRTUAL_SERVER_CAPABILITIES);
+        assert!(!capabilities.contains(Capabilities::UNION));
+
+        let result = client
+            .union(vec!["t".into(), "t".into()], UnionOptions::default())
+            .await;
+
+        assert!(matches!(result, Err(ClientError::NotImplemented("union"))));
+        let result = client
+            .unnest("t".into(), "x", UnnestOptions::default())
+            .await;
+        assert!(matches!(result, Err(ClientError::NotImplemented("unnest"))));
+
+        let options = JoinOptions {
+            join_type: Some(JoinType::Right),
+            ..JoinOptions::default()
+        };
+
+        let result = client.join("t".into(), "t".into(), "x", options).await;
+        assert!(matches!(result, Err(ClientError::NotImplemented(_))));
+
+        // Plain joins are sent, but fail with a server error.
+        let result = client
+            .join("t".into(), "t".into(), "x", JoinOptions::default())
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(client.get_hosted_table_names().await?, vec!["t"]);
+        driver.abort();
+        drop(client);
+        server.await??;
+        Ok(())
+    }
+}